@@ -38,8 +38,33 @@ use syn::{
 /// ```
 ///
 /// The return type must be `Result<T, agent_client_protocol::Error>` where `T: Serialize`.
+///
+/// # Client helpers
+///
+/// `#[custom_methods(client = module_name)]` additionally emits a `pub mod module_name`
+/// next to the impl block with one async function per route. Each helper is named after
+/// the handler with any `dispatch_`/`on_` prefix removed, sends the request under the
+/// method name declared on the request type, and deserializes the handler's `Ok` type:
+///
+/// ```ignore
+/// let tools: GetToolsResponse = custom_client::get_tools(cx, GetToolsRequest { .. }).await?;
+/// ```
+///
+/// Helpers for handlers without a parameter take only the connection and send the request
+/// type's `Default` value.
 #[proc_macro_attribute]
-pub fn custom_methods(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn custom_methods(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut client_module: Option<syn::Ident> = None;
+    let attr_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("client") {
+            client_module = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported custom_methods argument"))
+        }
+    });
+    parse_macro_input!(attr with attr_parser);
+
     let mut impl_block = parse_macro_input!(item as ItemImpl);
 
     let mut routes: Vec<Route> = Vec::new();
@@ -209,7 +234,69 @@ pub fn custom_methods(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let schemas_item: ImplItem = syn::parse2(schemas_fn).expect("generated schemas fn must parse");
     impl_block.items.push(schemas_item);
 
-    TokenStream::from(quote! { #impl_block })
+    let client = client_module.map(|module| client_helpers(&module, &routes));
+
+    TokenStream::from(quote! {
+        #impl_block
+        #client
+    })
+}
+
+/// Generate the typed client helper module requested via `client = ...`.
+fn client_helpers(module: &syn::Ident, routes: &[Route]) -> syn::ItemMod {
+    let helpers = routes.iter().map(|route| {
+        let req_type = &route.request_type;
+        let helper_ident = client_fn_ident(&route.fn_ident);
+        let response_type = match &route.ok_type {
+            Some(ok_ty) => quote! { #ok_ty },
+            None => quote! { serde_json::Value },
+        };
+
+        let (param, request_expr) = match &route.param_type {
+            Some(pt) => (quote! { , req: #pt }, quote! { req }),
+            None => (quote! {}, quote! { <#req_type as Default>::default() }),
+        };
+
+        quote! {
+            pub async fn #helper_ident(
+                cx: &agent_client_protocol::ConnectionTo<agent_client_protocol::Agent>
+                #param
+            ) -> Result<#response_type, agent_client_protocol::Error> {
+                let req = #request_expr;
+                let method = agent_client_protocol::JsonRpcMessage::method(
+                    &<#req_type as Default>::default(),
+                )
+                .to_string();
+                let params = serde_json::to_value(&req)
+                    .map_err(|e| agent_client_protocol::Error::invalid_params().data(e.to_string()))?;
+                let msg = agent_client_protocol::UntypedMessage::new(&method, params)
+                    .map_err(|e| agent_client_protocol::Error::internal_error().data(e.to_string()))?;
+                let value = cx.send_request(msg).block_task().await?;
+                serde_json::from_value(value)
+                    .map_err(|e| agent_client_protocol::Error::internal_error().data(e.to_string()))
+            }
+        }
+    });
+
+    let client_mod = quote! {
+        pub mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#helpers)*
+        }
+    };
+    syn::parse2(client_mod).expect("generated client module must parse")
+}
+
+/// Name a client helper after its handler, dropping the `dispatch_`/`on_` prefix.
+fn client_fn_ident(fn_ident: &syn::Ident) -> syn::Ident {
+    let name = fn_ident.to_string();
+    let trimmed = name
+        .strip_prefix("dispatch_")
+        .or_else(|| name.strip_prefix("on_"))
+        .unwrap_or(&name);
+    syn::Ident::new(trimmed, fn_ident.span())
 }
 
 struct Route {
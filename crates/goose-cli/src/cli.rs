@@ -2044,7 +2044,7 @@ async fn handle_local_models_command(command: LocalModelsCommand) -> Result<()>
     match command {
         LocalModelsCommand::Search { query, limit } => {
             println!("Searching HuggingFace for '{}'...", query);
-            let results = hf_models::search_local_models(&query, limit).await?;
+            let results = hf_models::search_local_models(&query, limit, 0).await?;
 
             if results.is_empty() {
                 println!("No compatible local models found.");
@@ -2057,10 +2057,17 @@ async fn handle_local_models_command(command: LocalModelsCommand) -> Result<()>
                     model.model_name, model.author, model.downloads
                 );
                 for variant in &model.variants {
-                    let size = if variant.size_bytes > 0 {
+                    let size_bytes = variant.total_size_bytes.unwrap_or(variant.size_bytes);
+                    let size = if size_bytes > 0 {
+                        let projector = if variant.mmproj_filename.is_some() {
+                            " incl. vision projector"
+                        } else {
+                            ""
+                        };
                         format!(
-                            "{:.1}GB",
-                            variant.size_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+                            "{:.1}GB{}",
+                            size_bytes as f64 / (1024.0 * 1024.0 * 1024.0),
+                            projector
                         )
                     } else {
                         "unknown".to_string()
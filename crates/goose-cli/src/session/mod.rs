@@ -1225,7 +1225,7 @@ impl CliSession {
 
         let mut progress_bars = output::McpSpinners::new();
         let cancel_token_clone = cancel_token.clone();
-        let mut markdown_buffer = streaming_buffer::MarkdownBuffer::new();
+        let mut markdown_buffer = streaming_buffer::MarkdownBuffer::from_env();
         let mut prompted_credits_urls: HashSet<String> = HashSet::new();
         let mut thinking_header_shown = false;
         let run_started = Instant::now();
@@ -2584,6 +2584,10 @@ mod tests {
                 serde_json::json!(["output-128k-2025-02-19"]),
             )])),
             reasoning: Some(false),
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let switched =
@@ -499,6 +499,10 @@ fn render_thinking_streaming(
 }
 
 fn render_tool_request(req: &ToolRequest, theme: Theme, debug: bool) {
+    // The complete request follows with its arguments; render that one.
+    if req.is_partial() {
+        return;
+    }
     match &req.tool_call {
         Ok(call) => match call.name.to_string().as_str() {
             name if is_shell_tool_name(name) => render_shell_request(call, debug),
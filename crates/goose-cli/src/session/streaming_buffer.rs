@@ -62,6 +62,15 @@ fn truncated_show_lines() -> usize {
     *VALUE
 }
 
+fn flush_on_paragraphs() -> bool {
+    static VALUE: LazyLock<bool> = LazyLock::new(|| {
+        std::env::var("GOOSE_CLI_FLUSH_PARAGRAPHS")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    });
+    *VALUE
+}
+
 fn truncate_code_blocks(content: &str) -> String {
     let Some(max_lines) = max_code_block_lines() else {
         return content.to_string();
@@ -190,6 +199,17 @@ fn save_to_temp_file(content: &str) -> Option<String> {
     Some(path.display().to_string())
 }
 
+/// Length of the line break at the start of `s`: 1 for `\n`, 2 for `\r\n`, 0 otherwise.
+fn line_break_len(s: &str) -> usize {
+    if s.starts_with('\n') {
+        1
+    } else if s.starts_with("\r\n") {
+        2
+    } else {
+        0
+    }
+}
+
 /// Regex that tokenizes markdown inline elements.
 /// Order matters: longer/more-specific patterns first.
 static INLINE_TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -221,9 +241,17 @@ static INLINE_TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| {
 /// Accumulates chunks and returns content that is safe to render,
 /// holding back any incomplete markdown constructs. Large code blocks
 /// are automatically truncated with full content saved to a temp file.
+///
+/// A completed paragraph (text followed by a blank line) is always released
+/// once no construct is open across it. In paragraph mode the buffer releases
+/// *only* whole paragraphs, so prose renders a block at a time.
 #[derive(Default)]
 pub struct MarkdownBuffer {
     buffer: String,
+    paragraph_flush: bool,
+    /// The last couple of lines already released, so a line at the start of
+    /// the buffer can tell whether it follows a blank line.
+    released_tail: String,
 }
 
 /// Tracks the current parsing state for markdown constructs.
@@ -232,6 +260,7 @@ struct ParseState {
     in_code_block: bool,
     code_fence_char: char,
     code_fence_len: usize,
+    in_indented_code: bool,
     in_table: bool,
     pending_heading: bool,
     in_inline_code: bool,
@@ -248,6 +277,7 @@ impl ParseState {
     /// Returns true if no markdown constructs are currently open.
     fn is_clean(&self) -> bool {
         !self.in_code_block
+            && !self.in_indented_code
             && !self.in_table
             && !self.pending_heading
             && !self.in_inline_code
@@ -271,6 +301,18 @@ impl MarkdownBuffer {
         Self::default()
     }
 
+    /// Create a buffer that honours `GOOSE_CLI_FLUSH_PARAGRAPHS`.
+    pub fn from_env() -> Self {
+        Self::new().with_paragraph_flush(flush_on_paragraphs())
+    }
+
+    /// Only release content at paragraph boundaries, holding partial paragraphs
+    /// until their blank line arrives or the stream is flushed.
+    pub fn with_paragraph_flush(mut self, enabled: bool) -> Self {
+        self.paragraph_flush = enabled;
+        self
+    }
+
     /// Add a chunk of markdown text to the buffer.
     ///
     /// Returns any content that is safe to render, or None if the buffer
@@ -287,6 +329,7 @@ impl MarkdownBuffer {
             // - The regex tokenizer operates on &str which guarantees UTF-8
             let to_render = self.buffer[..safe_end].to_string();
             self.buffer = self.buffer[safe_end..].to_string();
+            self.remember_released(&to_render);
             Some(truncate_code_blocks(&to_render))
         } else {
             None
@@ -298,13 +341,25 @@ impl MarkdownBuffer {
     /// Call this at the end of a stream to get any buffered content,
     /// even if markdown constructs are unclosed.
     pub fn flush(&mut self) -> String {
+        self.released_tail.clear();
         std::mem::take(&mut self.buffer)
     }
 
-    /// Find the last byte position where the parse state is "clean".
+    /// Keep the tail of released content, enough to see the line before the
+    /// next one to start.
+    fn remember_released(&mut self, released: &str) {
+        self.released_tail.push_str(released);
+        if let Some((cut, _)) = self.released_tail.rmatch_indices('\n').nth(2) {
+            self.released_tail.drain(..=cut);
+        }
+    }
+
+    /// Find the last byte position where the parse state is "clean", or the last
+    /// clean paragraph boundary in paragraph mode.
     fn find_safe_end(&self) -> usize {
         let mut state = ParseState::default();
         let mut last_safe: usize = 0;
+        let mut last_paragraph_end: usize = 0;
         let bytes = self.buffer.as_bytes();
         let len = bytes.len();
         let mut pos: usize = 0;
@@ -313,13 +368,25 @@ impl MarkdownBuffer {
             let at_line_start = pos == 0 || bytes[pos - 1] == b'\n';
 
             if at_line_start {
+                let was_indented_code = state.in_indented_code;
                 if let Some(new_pos) = self.process_line_start(&mut state, pos) {
                     pos = new_pos;
                     if state.is_clean() {
                         last_safe = pos;
+                        if self.ends_paragraph(pos) {
+                            last_paragraph_end = pos;
+                        }
                     }
                     continue;
                 }
+                // The line that ends an indented code block releases the block
+                // before any of its own markdown is considered.
+                if was_indented_code && state.is_clean() {
+                    last_safe = pos;
+                    if self.ends_paragraph(pos) {
+                        last_paragraph_end = pos;
+                    }
+                }
             }
 
             if state.in_code_block {
@@ -342,7 +409,10 @@ impl MarkdownBuffer {
 
                 self.process_inline_token(&mut state, token);
 
-                if state.is_clean() {
+                // A backtick run at the end of the buffer can still grow with the next
+                // chunk, and a longer run would no longer close the open code span.
+                let run_may_grow = token.starts_with('`') && token_end == len;
+                if state.is_clean() && !run_may_grow {
                     last_safe = token_end;
                 }
             }
@@ -351,13 +421,46 @@ impl MarkdownBuffer {
                 state.pending_heading = false;
                 if state.is_clean() {
                     last_safe = line_end;
+                    if self.ends_paragraph(line_end) {
+                        last_paragraph_end = line_end;
+                    }
                 }
             }
 
             pos = line_end;
         }
 
-        last_safe
+        if self.paragraph_flush {
+            last_paragraph_end
+        } else {
+            last_safe
+        }
+    }
+
+    /// Whether the buffer up to `end` finishes with a blank line.
+    fn ends_paragraph(&self, end: usize) -> bool {
+        self.buffer
+            .get(..end)
+            .and_then(|text| text.strip_suffix('\n'))
+            .map(|text| text.strip_suffix('\r').unwrap_or(text))
+            .is_some_and(|text| text.ends_with('\n'))
+    }
+
+    /// Whether a line starting at `pos` follows a blank line or opens the stream,
+    /// looking back into released content when the line starts the buffer.
+    fn follows_blank_line(&self, pos: usize) -> bool {
+        let text = format!("{}{}", self.released_tail, &self.buffer[..pos]);
+        if text.is_empty() {
+            return true;
+        }
+        let Some(before) = text.strip_suffix('\n') else {
+            return false;
+        };
+        let before = before.strip_suffix('\r').unwrap_or(before);
+        before
+            .rsplit('\n')
+            .next()
+            .is_some_and(|line| line.trim().is_empty())
     }
 
     /// Process block-level constructs at the start of a line.
@@ -370,6 +473,12 @@ impl MarkdownBuffer {
             state.pending_heading = false;
         }
 
+        if !state.in_code_block {
+            if let Some(line_len) = self.check_indented_code(remaining, state, pos) {
+                return Some(pos + line_len);
+            }
+        }
+
         if let Some(fence_result) = self.check_code_fence(remaining, state) {
             return Some(pos + fence_result);
         }
@@ -384,7 +493,7 @@ impl MarkdownBuffer {
                 let after_hashes = &remaining[hashes..];
                 if after_hashes.is_empty()
                     || after_hashes.starts_with(' ')
-                    || after_hashes.starts_with('\n')
+                    || line_break_len(after_hashes) > 0
                 {
                     state.pending_heading = true;
                     return None;
@@ -397,9 +506,9 @@ impl MarkdownBuffer {
             return None;
         }
 
-        if (remaining.starts_with('\n') || remaining.is_empty()) && state.in_table {
+        if (line_break_len(remaining) > 0 || remaining.is_empty()) && state.in_table {
             state.in_table = false;
-            return Some(pos + 1);
+            return Some(pos + line_break_len(remaining).max(1));
         }
 
         if state.in_table && !remaining.starts_with('|') {
@@ -409,12 +518,50 @@ impl MarkdownBuffer {
         None
     }
 
+    /// Check for an indented code block and update state accordingly.
+    ///
+    /// A line indented by four spaces or a tab opens a block when it follows a
+    /// blank line; indented and blank lines continue it, and any other line ends
+    /// it. Returns the length of a code line so its content is never tokenized.
+    fn check_indented_code(&self, line: &str, state: &mut ParseState, pos: usize) -> Option<usize> {
+        let line_len = line.find('\n').map_or(line.len(), |i| i + 1);
+        let content = &line[..line_len];
+        let indented = content.starts_with("    ") || content.starts_with('\t');
+        let blank = content.trim().is_empty();
+
+        if state.in_indented_code {
+            if indented || blank {
+                return Some(line_len);
+            }
+            state.in_indented_code = false;
+            return None;
+        }
+
+        if indented && !blank && self.follows_blank_line(pos) {
+            state.in_indented_code = true;
+            return Some(line_len);
+        }
+
+        None
+    }
+
     /// Check for a code fence and update state accordingly.
     ///
     /// Returns the position after the fence line if found, None otherwise.
     fn check_code_fence(&self, line: &str, state: &mut ParseState) -> Option<usize> {
         let trimmed = line.trim_start();
 
+        // A fence indented four spaces after a blank line opens an indented
+        // code block instead; leave it for that line's own check.
+        let indent = &line[..line.len() - trimmed.len()];
+        if !state.in_code_block {
+            if let Some((_, fence_indent)) = indent.rsplit_once('\n') {
+                if fence_indent.starts_with("    ") || fence_indent.starts_with('\t') {
+                    return None;
+                }
+            }
+        }
+
         let fence_char = trimmed.chars().next()?;
         if fence_char != '`' && fence_char != '~' {
             return None;
@@ -431,7 +578,7 @@ impl MarkdownBuffer {
             if fence_char == state.code_fence_char
                 && fence_len >= state.code_fence_len
                 && (after_fence.is_empty()
-                    || after_fence.starts_with('\n')
+                    || line_break_len(after_fence) > 0
                     || after_fence.trim().is_empty())
             {
                 state.in_code_block = false;
@@ -567,6 +714,21 @@ mod tests {
         &["Use the ", "`println!` macro."]
         ; "inline code split"
     )]
+    #[test_case(
+        &["Use `a", "*b*`"],
+        &["Use ", "`a*b*`"]
+        ; "inline code with asterisks split after opening backtick"
+    )]
+    #[test_case(
+        &["Use `a*", "b*` here"],
+        &["Use ", "`a*b*` here"]
+        ; "inline code split between asterisks"
+    )]
+    #[test_case(
+        &["`a*b`", "`"],
+        &["`a*b``"]
+        ; "trailing backtick run held until it cannot grow"
+    )]
     #[test_case(
         &["Check [the docs](https://doc", "s.rs) for more."],
         &["Check ", "[the docs](https://docs.rs) for more."]
@@ -576,6 +738,42 @@ mod tests {
         assert_eq!(stream(chunks), expected);
     }
 
+    #[test]
+    fn completed_paragraph_flushes_despite_trailing_partial_token() {
+        let mut buf = MarkdownBuffer::new();
+        assert_eq!(
+            buf.push("The first paragraph ends here.\n\nThen **bo"),
+            Some("The first paragraph ends here.\n\nThen ".to_string())
+        );
+        assert_eq!(buf.flush(), "**bo");
+    }
+
+    #[test]
+    fn paragraph_mode_flushes_at_paragraph_boundaries_before_stream_end() {
+        let mut buf = MarkdownBuffer::new().with_paragraph_flush(true);
+        assert_eq!(buf.push("A long explanation"), None);
+        assert_eq!(buf.push(" that keeps going.\nStill the"), None);
+        assert_eq!(
+            buf.push(" same paragraph.\n\nSecond"),
+            Some("A long explanation that keeps going.\nStill the same paragraph.\n\n".to_string())
+        );
+        assert_eq!(
+            buf.push(" one.\r\n\r\nThird"),
+            Some("Second one.\r\n\r\n".to_string())
+        );
+        assert_eq!(buf.flush(), "Third");
+    }
+
+    #[test]
+    fn paragraph_mode_holds_paragraph_with_open_construct() {
+        let mut buf = MarkdownBuffer::new().with_paragraph_flush(true);
+        assert_eq!(buf.push("Some **bold\n\nstill"), None);
+        assert_eq!(
+            buf.push(" bold** text.\n\n"),
+            Some("Some **bold\n\nstill bold** text.\n\n".to_string())
+        );
+    }
+
     // ===========================================
     // Code blocks (most important for bat rendering)
     // ===========================================
@@ -605,10 +803,44 @@ mod tests {
         &["```\ncode"]
         ; "unclosed code block flushes at end"
     )]
+    #[test_case(
+        &["```rust\r\nfn main() {}\r\n```\r\nAfter", " the block\r\n"],
+        &["```rust\r\nfn main() {}\r\n```\r\nAfter", " the block\r\n"]
+        ; "crlf code block closes before trailing text"
+    )]
+    #[test_case(
+        &["Intro\r\n", "```sh\r\necho hi\r\n```\r\nDone", " here\r\n", "more"],
+        &["Intro\r\n", "```sh\r\necho hi\r\n```\r\nDone", " here\r\n", "more"]
+        ; "crlf text after code block streams incrementally"
+    )]
     fn test_code_blocks(chunks: &[&str], expected: &[&str]) {
         assert_eq!(stream(chunks), expected);
     }
 
+    #[test_case(
+        &["Example:\n\n", "    let x = *ptr * 2;\n", "    let y = **z;\n", "\nDone **now**.\n"],
+        &["Example:\n\n", "    let x = *ptr * 2;\n    let y = **z;\n\nDone **now**.\n"]
+        ; "indented code with asterisks held until a non-indented line"
+    )]
+    #[test_case(
+        &["\tfoo_bar(*args)\n", "\n", "Next _one_"],
+        &["\tfoo_bar(*args)\n\nNext _one_"]
+        ; "tab indented code at stream start"
+    )]
+    #[test_case(
+        &["Intro\n\n    ```\n", "    code\nAfter\n"],
+        &["Intro\n\n", "    ```\n    code\nAfter\n"]
+        ; "indented fence is code not a fence"
+    )]
+    #[test_case(
+        &["Some text\n", "    with *emphasis", "* here\n"],
+        &["Some text\n", "    with ", "*emphasis* here\n"]
+        ; "indented paragraph continuation is not code"
+    )]
+    fn test_indented_code_blocks(chunks: &[&str], expected: &[&str]) {
+        assert_eq!(stream(chunks), expected);
+    }
+
     // ===========================================
     // Headings
     // ===========================================
@@ -3,8 +3,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::io::AsyncWriteExt;
-use tracing::info;
+use tracing::{info, warn};
 
 fn partial_path_for(destination: &Path) -> PathBuf {
     destination.with_extension(
@@ -68,11 +69,61 @@ pub struct DownloadProgress {
     pub eta_seconds: Option<u64>,
     /// Error message if failed
     pub error: Option<String>,
+    /// Combined on-disk size of the downloaded files, set once the download completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_size_bytes: Option<u64>,
     /// Whether the background download task has exited
     #[serde(skip)]
     pub task_exited: bool,
 }
 
+impl DownloadProgress {
+    /// Mark the download completed, recording the size that actually landed on disk.
+    pub fn mark_completed(&mut self, final_size_bytes: u64) {
+        self.status = DownloadStatus::Completed;
+        self.progress_percent = 100.0;
+        self.final_size_bytes = Some(final_size_bytes);
+    }
+}
+
+/// What is already on disk at a download destination.
+#[derive(Debug, PartialEq)]
+enum ExistingFile {
+    Missing,
+    /// Matches the size the server reports, or the server reported none.
+    Complete(u64),
+    /// Some other file with the same name; the download replaces it.
+    SizeMismatch {
+        on_disk: u64,
+        expected: u64,
+    },
+}
+
+/// `expected_bytes` is the server-reported size, 0 when unknown.
+fn check_existing_file(destination: &Path, expected_bytes: u64) -> ExistingFile {
+    let Ok(meta) = std::fs::metadata(destination) else {
+        return ExistingFile::Missing;
+    };
+    let on_disk = meta.len();
+    if expected_bytes == 0 || on_disk == expected_bytes {
+        ExistingFile::Complete(on_disk)
+    } else {
+        ExistingFile::SizeMismatch {
+            on_disk,
+            expected: expected_bytes,
+        }
+    }
+}
+
+/// Total size of the files at `paths`, following symlinks. Missing files count as zero.
+pub fn size_on_disk(paths: &[PathBuf]) -> u64 {
+    paths
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum DownloadStatus {
@@ -84,6 +135,118 @@ pub enum DownloadStatus {
 
 type DownloadMap = Arc<Mutex<HashMap<String, DownloadProgress>>>;
 
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    pub bearer_token: Option<String>,
+    /// Cap on transfer speed across all files of the download, in bytes per second.
+    pub max_bytes_per_second: Option<u64>,
+    /// Fetch each large file as this many concurrent byte-range segments when the
+    /// server supports `Range`. 0 or 1 downloads over a single connection.
+    pub segments: usize,
+}
+
+/// Files smaller than this are not worth splitting across connections.
+const MIN_SEGMENT_BYTES: u64 = 64 * 1024 * 1024;
+
+type SharedThrottle = Arc<Mutex<Throttle>>;
+
+/// Split `total_bytes` into at most `segments` contiguous inclusive byte ranges, each
+/// at least `min_segment_bytes` long unless the whole file is smaller.
+fn plan_segments(total_bytes: u64, segments: usize, min_segment_bytes: u64) -> Vec<(u64, u64)> {
+    if total_bytes == 0 {
+        return Vec::new();
+    }
+    let max_by_size = (total_bytes / min_segment_bytes.max(1)).max(1);
+    let count = (segments.max(1) as u64).min(max_by_size);
+    let base = total_bytes / count;
+    let remainder = total_bytes % count;
+    let mut start = 0;
+    (0..count)
+        .map(|index| {
+            let len = base + u64::from(index < remainder);
+            let range = (start, start + len - 1);
+            start += len;
+            range
+        })
+        .collect()
+}
+
+fn segment_path_for(destination: &Path, index: usize) -> PathBuf {
+    let partial = partial_path_for(destination);
+    partial.with_extension(format!("{index}.part"))
+}
+
+/// Concatenate downloaded segments, in order, into `partial_path` and remove them.
+async fn reassemble_segments(
+    segment_paths: &[PathBuf],
+    partial_path: &Path,
+    expected_bytes: u64,
+) -> Result<()> {
+    let mut output = tokio::fs::File::create(partial_path).await?;
+    let mut written = 0;
+    for segment in segment_paths {
+        let mut input = tokio::fs::File::open(segment).await?;
+        written += tokio::io::copy(&mut input, &mut output).await?;
+    }
+    output.flush().await?;
+    if written != expected_bytes {
+        anyhow::bail!(
+            "Reassembled download is {} bytes, expected {}",
+            written,
+            expected_bytes
+        );
+    }
+    for segment in segment_paths {
+        let _ = tokio::fs::remove_file(segment).await;
+    }
+    Ok(())
+}
+
+/// A segment request came back without partial content, so the server ignores `Range`.
+#[derive(Debug)]
+struct RangeNotSupported;
+
+impl std::fmt::Display for RangeNotSupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "server does not support range requests")
+    }
+}
+
+impl std::error::Error for RangeNotSupported {}
+
+/// Token bucket allowing up to one second of burst at the configured rate.
+struct Throttle {
+    bytes_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Throttle {
+    fn new(bytes_per_second: u64, now: Instant) -> Self {
+        let bytes_per_second = bytes_per_second.max(1) as f64;
+        Self {
+            bytes_per_second,
+            tokens: bytes_per_second,
+            last_refill: now,
+        }
+    }
+
+    /// Spend `bytes` and return how long the caller must wait to stay under the rate.
+    fn consume(&mut self, bytes: u64, now: Instant) -> Duration {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.bytes_per_second).min(self.bytes_per_second);
+        self.last_refill = now;
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.bytes_per_second)
+        }
+    }
+}
+
 pub struct DownloadManager {
     downloads: DownloadMap,
 }
@@ -216,6 +379,45 @@ impl DownloadManager {
         total_size_hint: u64,
         bearer_token: Option<String>,
         on_complete: Option<Box<dyn FnOnce() + Send + 'static>>,
+    ) -> Result<()> {
+        self.download_model_sharded_with_options(
+            model_id,
+            files,
+            total_size_hint,
+            DownloadOptions {
+                bearer_token,
+                ..Default::default()
+            },
+            on_complete,
+        )
+        .await
+    }
+
+    pub async fn download_model_with_options(
+        &self,
+        model_id: String,
+        url: String,
+        destination: PathBuf,
+        options: DownloadOptions,
+        on_complete: Option<Box<dyn FnOnce() + Send + 'static>>,
+    ) -> Result<()> {
+        self.download_model_sharded_with_options(
+            model_id,
+            vec![(url, destination)],
+            0,
+            options,
+            on_complete,
+        )
+        .await
+    }
+
+    pub async fn download_model_sharded_with_options(
+        &self,
+        model_id: String,
+        files: Vec<(String, PathBuf)>,
+        total_size_hint: u64,
+        options: DownloadOptions,
+        on_complete: Option<Box<dyn FnOnce() + Send + 'static>>,
     ) -> Result<()> {
         info!(model_id = %model_id, file_count = files.len(), "Starting model download");
         {
@@ -246,6 +448,7 @@ impl DownloadManager {
                     speed_bps: None,
                     eta_seconds: None,
                     error: None,
+                    final_size_bytes: None,
                     task_exited: false,
                 },
             );
@@ -262,52 +465,168 @@ impl DownloadManager {
 
         let downloads = self.downloads.clone();
         let model_id_clone = model_id.clone();
-        let files_for_cleanup: Vec<PathBuf> = files.iter().map(|(_, d)| d.clone()).collect();
 
         tokio::spawn(async move {
-            let result = Self::download_files_sequentially(
-                &files,
-                &downloads,
-                &model_id_clone,
-                bearer_token.as_deref(),
-            )
-            .await;
-
-            match result {
-                Ok(_) => {
-                    info!(model_id = %model_id_clone, "Download completed successfully");
-                    if let Ok(mut downloads) = downloads.lock() {
-                        if let Some(progress) = downloads.get_mut(&model_id_clone) {
-                            progress.status = DownloadStatus::Completed;
-                            progress.progress_percent = 100.0;
-                            progress.task_exited = true;
-                        }
-                    }
+            if Self::run_download(&files, &downloads, &model_id_clone, &options)
+                .await
+                .is_ok()
+            {
+                if let Some(callback) = on_complete {
+                    callback();
+                }
+            }
+        });
 
-                    if let Some(callback) = on_complete {
-                        callback();
-                    }
+        Ok(())
+    }
+
+    /// Download `files` under the progress entry `model_id` and wait for the transfer to
+    /// finish, for callers already running on their own task. The entry may have been
+    /// reserved beforehand; a cancelled one is left alone.
+    pub async fn download_model_files(
+        &self,
+        model_id: &str,
+        files: Vec<(String, PathBuf)>,
+        total_size_hint: u64,
+        options: DownloadOptions,
+    ) -> Result<()> {
+        info!(model_id = %model_id, file_count = files.len(), "Starting model download");
+        {
+            let mut downloads = self
+                .downloads
+                .lock()
+                .map_err(|_| anyhow::anyhow!("Failed to acquire lock"))?;
+            let progress =
+                downloads
+                    .entry(model_id.to_string())
+                    .or_insert_with(|| DownloadProgress {
+                        model_id: model_id.to_string(),
+                        status: DownloadStatus::Downloading,
+                        bytes_downloaded: 0,
+                        total_bytes: 0,
+                        progress_percent: 0.0,
+                        speed_bps: None,
+                        eta_seconds: None,
+                        error: None,
+                        final_size_bytes: None,
+                        task_exited: false,
+                    });
+            if progress.status == DownloadStatus::Cancelled {
+                progress.task_exited = true;
+                anyhow::bail!("Download cancelled");
+            }
+            progress.status = DownloadStatus::Downloading;
+            progress.total_bytes = total_size_hint;
+            progress.error = None;
+            progress.final_size_bytes = None;
+            progress.task_exited = false;
+        }
+
+        for (_, dest) in &files {
+            if let Some(parent) = dest.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to create directory: {}", e))?;
+            }
+        }
+
+        Self::run_download(&files, &self.downloads, model_id, &options).await
+    }
+
+    /// Mark `model_id` completed without a transfer when every destination already
+    /// holds a file of its expected size. Files of another size are reported and left
+    /// for the download to replace. A file of unknown size (0) can't be verified by name
+    /// alone, so the download always runs and decides whether to fetch it.
+    pub fn reuse_existing_files(&self, model_id: &str, files: &[(PathBuf, u64)]) -> bool {
+        if files.iter().any(|(_, expected_bytes)| *expected_bytes == 0) {
+            return false;
+        }
+        let mut on_disk_total = 0;
+        let mut all_complete = true;
+        for (destination, expected_bytes) in files {
+            match check_existing_file(destination, *expected_bytes) {
+                ExistingFile::Complete(on_disk) => on_disk_total += on_disk,
+                ExistingFile::SizeMismatch { on_disk, expected } => {
+                    warn!(
+                        model_id = %model_id,
+                        path = %destination.display(),
+                        on_disk,
+                        expected,
+                        "Existing file does not match the download size, overwriting"
+                    );
+                    all_complete = false;
                 }
-                Err(e) => {
-                    for dest in &files_for_cleanup {
-                        let partial = partial_path_for(dest);
-                        let _ = tokio::fs::remove_file(&partial).await;
+                ExistingFile::Missing => all_complete = false,
+            }
+        }
+        if !all_complete {
+            return false;
+        }
+
+        info!(model_id = %model_id, "Model files already on disk, skipping download");
+        if let Ok(mut downloads) = self.downloads.lock() {
+            let progress =
+                downloads
+                    .entry(model_id.to_string())
+                    .or_insert_with(|| DownloadProgress {
+                        model_id: model_id.to_string(),
+                        status: DownloadStatus::Completed,
+                        bytes_downloaded: 0,
+                        total_bytes: 0,
+                        progress_percent: 0.0,
+                        speed_bps: None,
+                        eta_seconds: None,
+                        error: None,
+                        final_size_bytes: None,
+                        task_exited: false,
+                    });
+            progress.bytes_downloaded = on_disk_total;
+            progress.total_bytes = on_disk_total;
+            progress.mark_completed(on_disk_total);
+            progress.task_exited = true;
+        }
+        true
+    }
+
+    /// Transfer `files` and record the outcome in `model_id`'s progress entry.
+    async fn run_download(
+        files: &[(String, PathBuf)],
+        downloads: &DownloadMap,
+        model_id: &str,
+        options: &DownloadOptions,
+    ) -> Result<()> {
+        let destinations: Vec<PathBuf> = files.iter().map(|(_, d)| d.clone()).collect();
+        let result = Self::download_files_sequentially(files, downloads, model_id, options).await;
+
+        match &result {
+            Ok(_) => {
+                info!(model_id = %model_id, "Download completed successfully");
+                let final_size = size_on_disk(&destinations);
+                if let Ok(mut downloads) = downloads.lock() {
+                    if let Some(progress) = downloads.get_mut(model_id) {
+                        progress.mark_completed(final_size);
+                        progress.task_exited = true;
                     }
+                }
+            }
+            Err(e) => {
+                for dest in &destinations {
+                    let partial = partial_path_for(dest);
+                    let _ = tokio::fs::remove_file(&partial).await;
+                }
 
-                    if let Ok(mut downloads) = downloads.lock() {
-                        if let Some(progress) = downloads.get_mut(&model_id_clone) {
-                            if progress.status != DownloadStatus::Cancelled {
-                                progress.status = DownloadStatus::Failed;
-                            }
-                            progress.error = Some(e.to_string());
-                            progress.task_exited = true;
+                if let Ok(mut downloads) = downloads.lock() {
+                    if let Some(progress) = downloads.get_mut(model_id) {
+                        if progress.status != DownloadStatus::Cancelled {
+                            progress.status = DownloadStatus::Failed;
                         }
+                        progress.error = Some(e.to_string());
+                        progress.task_exited = true;
                     }
                 }
             }
-        });
-
-        Ok(())
+        }
+        result
     }
 
     const MAX_RETRIES: u32 = 10;
@@ -346,8 +665,12 @@ impl DownloadManager {
         files: &[(String, PathBuf)],
         downloads: &DownloadMap,
         model_id: &str,
-        bearer_token: Option<&str>,
+        options: &DownloadOptions,
     ) -> Result<(), anyhow::Error> {
+        let bearer_token = options.bearer_token.as_deref();
+        let throttle: Option<SharedThrottle> = options
+            .max_bytes_per_second
+            .map(|rate| Arc::new(Mutex::new(Throttle::new(rate, Instant::now()))));
         let client = reqwest::Client::builder()
             .connect_timeout(std::time::Duration::from_secs(30))
             .read_timeout(std::time::Duration::from_secs(120))
@@ -357,6 +680,7 @@ impl DownloadManager {
         // every file returned a size; partial results would underestimate.
         let mut total: u64 = 0;
         let mut all_resolved = true;
+        let mut remote_sizes = Vec::with_capacity(files.len());
         for (url, _) in files {
             let size = Self::apply_bearer_token(client.head(url), bearer_token)
                 .send()
@@ -368,6 +692,7 @@ impl DownloadManager {
                 all_resolved = false;
             }
             total += size;
+            remote_sizes.push(size);
         }
         if all_resolved && total > 0 {
             if let Ok(mut dl) = downloads.lock() {
@@ -380,30 +705,66 @@ impl DownloadManager {
         let start_time = std::time::Instant::now();
         let mut cumulative_bytes: u64 = 0;
         // Account for already-downloaded shards
-        for (_, dest) in files {
-            let partial = partial_path_for(dest);
-            if dest.exists() {
-                if let Ok(meta) = tokio::fs::metadata(dest).await {
-                    cumulative_bytes += meta.len();
+        let mut complete = Vec::with_capacity(files.len());
+        for ((_, dest), &expected) in files.iter().zip(&remote_sizes) {
+            match check_existing_file(dest, expected) {
+                ExistingFile::Complete(len) => {
+                    cumulative_bytes += len;
+                    complete.push(true);
+                    continue;
                 }
-            } else if partial.exists() {
-                if let Ok(meta) = tokio::fs::metadata(&partial).await {
-                    cumulative_bytes += meta.len();
+                ExistingFile::SizeMismatch { on_disk, expected } => {
+                    warn!(
+                        model_id = %model_id,
+                        path = %dest.display(),
+                        on_disk,
+                        expected,
+                        "Existing file does not match the download size, overwriting"
+                    );
                 }
+                ExistingFile::Missing => {}
+            }
+            complete.push(false);
+            if let Ok(meta) = tokio::fs::metadata(partial_path_for(dest)).await {
+                cumulative_bytes += meta.len();
             }
         }
         let bytes_at_start = cumulative_bytes;
 
-        for (url, destination) in files {
+        for ((url, destination), already_complete) in files.iter().zip(complete) {
             if Self::is_cancelled(downloads, model_id) {
                 anyhow::bail!("Download cancelled");
             }
 
-            // Skip already-completed shards
-            if destination.exists() {
+            // Skip files already on disk at the expected size
+            if already_complete {
                 continue;
             }
 
+            if options.segments > 1 && !partial_path_for(destination).exists() {
+                match Self::download_one_file_segmented(
+                    &client,
+                    url,
+                    destination,
+                    downloads,
+                    model_id,
+                    &mut cumulative_bytes,
+                    start_time,
+                    bytes_at_start,
+                    options,
+                    throttle.as_ref(),
+                )
+                .await
+                {
+                    Ok(true) => continue,
+                    Ok(false) => {}
+                    Err(e) if e.is::<RangeNotSupported>() => {
+                        info!(model_id = %model_id, "Server ignored segment range, downloading over a single connection");
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+
             Self::download_one_file(
                 &client,
                 url,
@@ -414,6 +775,7 @@ impl DownloadManager {
                 start_time,
                 bytes_at_start,
                 bearer_token,
+                throttle.as_ref(),
             )
             .await?;
         }
@@ -432,6 +794,7 @@ impl DownloadManager {
         start_time: std::time::Instant,
         bytes_at_start: u64,
         bearer_token: Option<&str>,
+        throttle: Option<&SharedThrottle>,
     ) -> Result<(), anyhow::Error> {
         let partial_path = partial_path_for(destination);
         let mut retries = 0u32;
@@ -584,42 +947,14 @@ impl DownloadManager {
                         file_bytes += chunk_len;
                         *cumulative_bytes += chunk_len;
 
-                        let elapsed = start_time.elapsed().as_secs_f64();
-                        let bytes_this_session = cumulative_bytes.saturating_sub(bytes_at_start);
-                        let speed_bps = if elapsed > 0.0 {
-                            Some((bytes_this_session as f64 / elapsed) as u64)
-                        } else {
-                            None
-                        };
-
-                        let current_total = if let Ok(dl) = downloads.lock() {
-                            dl.get(model_id).map(|p| p.total_bytes).unwrap_or(0)
-                        } else {
-                            0
-                        };
-
-                        let eta_seconds = if let Some(speed) = speed_bps {
-                            if speed > 0 && current_total > 0 {
-                                Some(current_total.saturating_sub(*cumulative_bytes) / speed)
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        };
-
-                        if let Ok(mut dl) = downloads.lock() {
-                            if let Some(progress) = dl.get_mut(model_id) {
-                                progress.bytes_downloaded = *cumulative_bytes;
-                                progress.progress_percent = if current_total > 0 {
-                                    (*cumulative_bytes as f64 / current_total as f64 * 100.0) as f32
-                                } else {
-                                    0.0
-                                };
-                                progress.speed_bps = speed_bps;
-                                progress.eta_seconds = eta_seconds;
-                            }
-                        }
+                        Self::publish_progress(
+                            downloads,
+                            model_id,
+                            *cumulative_bytes,
+                            start_time,
+                            bytes_at_start,
+                        );
+                        Self::throttle_transfer(throttle, chunk_len, downloads, model_id).await?;
                     }
                     Ok(None) => break,
                     Err(e) => {
@@ -657,6 +992,253 @@ impl DownloadManager {
         Ok(())
     }
 
+    fn publish_progress(
+        downloads: &DownloadMap,
+        model_id: &str,
+        cumulative_bytes: u64,
+        start_time: Instant,
+        bytes_at_start: u64,
+    ) {
+        let elapsed = start_time.elapsed().as_secs_f64();
+        let bytes_this_session = cumulative_bytes.saturating_sub(bytes_at_start);
+        let speed_bps = if elapsed > 0.0 {
+            Some((bytes_this_session as f64 / elapsed) as u64)
+        } else {
+            None
+        };
+
+        if let Ok(mut dl) = downloads.lock() {
+            if let Some(progress) = dl.get_mut(model_id) {
+                let current_total = progress.total_bytes;
+                progress.bytes_downloaded = cumulative_bytes;
+                progress.progress_percent = if current_total > 0 {
+                    (cumulative_bytes as f64 / current_total as f64 * 100.0) as f32
+                } else {
+                    0.0
+                };
+                progress.speed_bps = speed_bps;
+                progress.eta_seconds = speed_bps
+                    .filter(|speed| *speed > 0 && current_total > 0)
+                    .map(|speed| current_total.saturating_sub(cumulative_bytes) / speed);
+            }
+        }
+    }
+
+    async fn throttle_transfer(
+        throttle: Option<&SharedThrottle>,
+        bytes: u64,
+        downloads: &DownloadMap,
+        model_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        let Some(throttle) = throttle else {
+            return Ok(());
+        };
+        let wait = throttle
+            .lock()
+            .map(|mut throttle| throttle.consume(bytes, Instant::now()))
+            .unwrap_or_default();
+        if !wait.is_zero() {
+            Self::cancellable_sleep(wait, downloads, model_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Fetch a file as concurrent byte-range segments, then stitch them together.
+    /// Returns `Ok(false)` without downloading anything when the file is too small to
+    /// split or the server does not advertise range support.
+    #[allow(clippy::too_many_arguments)]
+    async fn download_one_file_segmented(
+        client: &reqwest::Client,
+        url: &str,
+        destination: &Path,
+        downloads: &DownloadMap,
+        model_id: &str,
+        cumulative_bytes: &mut u64,
+        start_time: Instant,
+        bytes_at_start: u64,
+        options: &DownloadOptions,
+        throttle: Option<&SharedThrottle>,
+    ) -> Result<bool, anyhow::Error> {
+        let bearer_token = options.bearer_token.as_deref();
+        let Ok(head) = Self::apply_bearer_token(client.head(url), bearer_token)
+            .send()
+            .await
+        else {
+            return Ok(false);
+        };
+        let accepts_ranges = head
+            .headers()
+            .get("accept-ranges")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("bytes"));
+        let file_total = head.content_length().unwrap_or(0);
+        let ranges = plan_segments(file_total, options.segments, MIN_SEGMENT_BYTES);
+        if !accepts_ranges || ranges.len() < 2 {
+            return Ok(false);
+        }
+
+        info!(model_id = %model_id, segments = ranges.len(), file_total, "Downloading file in segments");
+        let segment_paths: Vec<PathBuf> = (0..ranges.len())
+            .map(|index| segment_path_for(destination, index))
+            .collect();
+        let transferred = Arc::new(std::sync::atomic::AtomicU64::new(*cumulative_bytes));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (range, path) in ranges.iter().zip(&segment_paths) {
+            tasks.spawn(Self::download_segment(
+                client.clone(),
+                url.to_string(),
+                options.bearer_token.clone(),
+                *range,
+                path.clone(),
+                downloads.clone(),
+                model_id.to_string(),
+                transferred.clone(),
+                start_time,
+                bytes_at_start,
+                throttle.cloned(),
+            ));
+        }
+
+        let mut result = Ok(());
+        while let Some(joined) = tasks.join_next().await {
+            let segment_result = joined.map_err(anyhow::Error::from).and_then(|r| r);
+            if let Err(e) = segment_result {
+                if result.is_ok() {
+                    tasks.abort_all();
+                    result = Err(e);
+                }
+            }
+        }
+        let result = match result {
+            Ok(()) => {
+                reassemble_segments(&segment_paths, &partial_path_for(destination), file_total)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            for path in &segment_paths {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+            return Err(e);
+        }
+
+        *cumulative_bytes += file_total;
+        tokio::fs::rename(partial_path_for(destination), destination).await?;
+        Ok(true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn download_segment(
+        client: reqwest::Client,
+        url: String,
+        bearer_token: Option<String>,
+        (range_start, range_end): (u64, u64),
+        path: PathBuf,
+        downloads: DownloadMap,
+        model_id: String,
+        transferred: Arc<std::sync::atomic::AtomicU64>,
+        start_time: Instant,
+        bytes_at_start: u64,
+        throttle: Option<SharedThrottle>,
+    ) -> Result<(), anyhow::Error> {
+        use std::sync::atomic::Ordering;
+
+        let segment_len = range_end - range_start + 1;
+        let mut written = 0u64;
+        let mut retries = 0u32;
+        let mut file = tokio::fs::File::create(&path).await?;
+
+        while written < segment_len {
+            if Self::is_cancelled(&downloads, &model_id) {
+                anyhow::bail!("Download cancelled");
+            }
+
+            let request = Self::apply_bearer_token(client.get(&url), bearer_token.as_deref())
+                .header(
+                    "Range",
+                    format!("bytes={}-{}", range_start + written, range_end),
+                );
+            let mut interrupted = None;
+            match request.send().await {
+                Ok(response) if response.status() == reqwest::StatusCode::PARTIAL_CONTENT => {
+                    let mut response = response;
+                    loop {
+                        match response.chunk().await {
+                            Ok(Some(chunk)) => {
+                                if Self::is_cancelled(&downloads, &model_id) {
+                                    anyhow::bail!("Download cancelled");
+                                }
+                                file.write_all(&chunk).await?;
+                                let chunk_len = chunk.len() as u64;
+                                written += chunk_len;
+                                let total =
+                                    transferred.fetch_add(chunk_len, Ordering::Relaxed) + chunk_len;
+                                Self::publish_progress(
+                                    &downloads,
+                                    &model_id,
+                                    total,
+                                    start_time,
+                                    bytes_at_start,
+                                );
+                                Self::throttle_transfer(
+                                    throttle.as_ref(),
+                                    chunk_len,
+                                    &downloads,
+                                    &model_id,
+                                )
+                                .await?;
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                interrupted = Some(e.to_string());
+                                break;
+                            }
+                        }
+                    }
+                    if interrupted.is_none() && written < segment_len {
+                        interrupted = Some("segment ended early".to_string());
+                    }
+                }
+                Ok(response) if response.status().is_success() => {
+                    return Err(RangeNotSupported.into());
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let is_transient = status.is_server_error()
+                        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                        || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                    if !is_transient {
+                        anyhow::bail!("Failed to download segment: HTTP {}", status);
+                    }
+                    interrupted = Some(format!("HTTP {}", status));
+                }
+                Err(e) => interrupted = Some(e.to_string()),
+            }
+
+            if let Some(reason) = interrupted {
+                if retries >= Self::MAX_RETRIES {
+                    anyhow::bail!(
+                        "Segment download failed after {} retries: {}",
+                        retries,
+                        reason
+                    );
+                }
+                retries += 1;
+                let delay = std::cmp::min(
+                    Self::RETRY_BASE_DELAY * 2u32.saturating_pow(retries - 1),
+                    Self::RETRY_MAX_DELAY,
+                );
+                info!(model_id = %model_id, retry = retries, error = %reason, "Retrying download segment");
+                Self::cancellable_sleep(delay, &downloads, &model_id).await?;
+            }
+        }
+
+        file.flush().await?;
+        Ok(())
+    }
+
     pub fn clear_completed(&self, model_id: &str) {
         if let Ok(mut downloads) = self.downloads.lock() {
             if let Some(progress) = downloads.get(model_id) {
@@ -688,3 +1270,170 @@ static DOWNLOAD_MANAGER: once_cell::sync::Lazy<DownloadManager> =
 pub fn get_download_manager() -> &'static DownloadManager {
     &DOWNLOAD_MANAGER
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_segments_splits_evenly_with_remainder_up_front() {
+        assert_eq!(plan_segments(10, 3, 1), vec![(0, 3), (4, 6), (7, 9)]);
+    }
+
+    #[test]
+    fn plan_segments_respects_minimum_segment_size() {
+        assert_eq!(plan_segments(250, 8, 100), vec![(0, 124), (125, 249)]);
+        assert_eq!(plan_segments(50, 8, 100), vec![(0, 49)]);
+        assert!(plan_segments(0, 4, 100).is_empty());
+    }
+
+    #[test]
+    fn completed_progress_carries_final_size_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let shards = [dir.path().join("a.gguf"), dir.path().join("b.gguf")];
+        std::fs::write(&shards[0], vec![0u8; 300]).unwrap();
+        std::fs::write(&shards[1], vec![0u8; 200]).unwrap();
+        let mut progress = DownloadProgress {
+            model_id: "model".to_string(),
+            status: DownloadStatus::Downloading,
+            bytes_downloaded: 450,
+            total_bytes: 450,
+            progress_percent: 99.0,
+            speed_bps: None,
+            eta_seconds: None,
+            error: None,
+            final_size_bytes: None,
+            task_exited: false,
+        };
+
+        progress.mark_completed(size_on_disk(&shards));
+
+        assert_eq!(progress.status, DownloadStatus::Completed);
+        assert_eq!(progress.progress_percent, 100.0);
+        assert_eq!(progress.final_size_bytes, Some(500));
+    }
+
+    #[tokio::test]
+    async fn reassemble_segments_concatenates_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("model.gguf");
+        let segments: Vec<PathBuf> = ["abc", "def", "g"]
+            .iter()
+            .enumerate()
+            .map(|(index, contents)| {
+                let path = segment_path_for(&destination, index);
+                std::fs::write(&path, contents).unwrap();
+                path
+            })
+            .collect();
+        let partial = partial_path_for(&destination);
+
+        reassemble_segments(&segments, &partial, 7).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&partial).unwrap(), "abcdefg");
+        assert!(segments.iter().all(|path| !path.exists()));
+    }
+
+    #[tokio::test]
+    async fn reassemble_segments_rejects_short_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("model.gguf");
+        let segment = segment_path_for(&destination, 0);
+        std::fs::write(&segment, "abc").unwrap();
+
+        let err = reassemble_segments(&[segment], &partial_path_for(&destination), 4)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("expected 4"));
+    }
+
+    #[test]
+    fn existing_file_at_expected_size_is_complete() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("model.gguf");
+        assert_eq!(
+            check_existing_file(&destination, 100),
+            ExistingFile::Missing
+        );
+
+        std::fs::write(&destination, vec![0u8; 100]).unwrap();
+        assert_eq!(
+            check_existing_file(&destination, 100),
+            ExistingFile::Complete(100)
+        );
+        // Without a size from the server, the file on disk is trusted.
+        assert_eq!(
+            check_existing_file(&destination, 0),
+            ExistingFile::Complete(100)
+        );
+    }
+
+    #[test]
+    fn existing_file_with_different_size_is_a_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("model.gguf");
+        std::fs::write(&destination, vec![0u8; 40]).unwrap();
+
+        assert_eq!(
+            check_existing_file(&destination, 100),
+            ExistingFile::SizeMismatch {
+                on_disk: 40,
+                expected: 100
+            }
+        );
+    }
+
+    #[test]
+    fn reuse_existing_files_completes_only_when_every_file_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("model-00001-of-00002.gguf");
+        let second = dir.path().join("model-00002-of-00002.gguf");
+        std::fs::write(&first, vec![0u8; 100]).unwrap();
+        std::fs::write(&second, vec![0u8; 40]).unwrap();
+        let manager = DownloadManager::new();
+
+        let files = vec![(first.clone(), 100), (second.clone(), 50)];
+        assert!(!manager.reuse_existing_files("m-model", &files));
+        assert!(manager.get_progress("m-model").is_none());
+
+        let files = vec![(first, 100), (second, 40)];
+        assert!(manager.reuse_existing_files("m-model", &files));
+        let progress = manager.get_progress("m-model").unwrap();
+        assert_eq!(progress.status, DownloadStatus::Completed);
+        assert_eq!(progress.final_size_bytes, Some(140));
+        assert!(progress.task_exited);
+    }
+
+    #[test]
+    fn reuse_existing_files_never_trusts_an_unknown_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("model.gguf");
+        std::fs::write(&destination, vec![0u8; 40]).unwrap();
+        let manager = DownloadManager::new();
+
+        assert!(!manager.reuse_existing_files("m-model", &[(destination, 0)]));
+        assert!(manager.get_progress("m-model").is_none());
+    }
+
+    #[test]
+    fn throttle_caps_transfer_rate() {
+        let start = Instant::now();
+        let mut now = start;
+        let mut throttle = Throttle::new(200, now);
+        for _ in 0..10 {
+            now += throttle.consume(100, now);
+        }
+        // The first second's worth is burst; the remaining 800 bytes take 4s at 200 B/s.
+        let elapsed = now.duration_since(start).as_secs_f64();
+        assert!((elapsed - 4.0).abs() < 1e-6, "elapsed {elapsed}");
+    }
+
+    #[test]
+    fn throttle_does_not_bank_more_than_one_second_of_idle_time() {
+        let start = Instant::now();
+        let mut throttle = Throttle::new(100, start);
+        let later = start + Duration::from_secs(60);
+        assert_eq!(throttle.consume(100, later), Duration::ZERO);
+        assert_eq!(throttle.consume(50, later), Duration::from_millis(500));
+    }
+}
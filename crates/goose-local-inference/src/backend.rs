@@ -46,5 +46,42 @@ pub(super) trait LocalInferenceBackend: Send + Sync {
         request: LocalGenerationRequest<'_>,
     ) -> Result<(), ProviderError>;
 
+    /// Tokenize the prompt a request would render to, using an already loaded model.
+    fn count_prompt_tokens(
+        &self,
+        _loaded: &mut dyn BackendLoadedModel,
+        _model_id: &str,
+        _system: &str,
+        _messages: &[Message],
+        _tools: &[Tool],
+        _settings: &ModelSettings,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Context window a request would get on an already loaded model, after the configured
+    /// size, the model's training context and estimated free memory are applied.
+    fn context_cap(
+        &self,
+        _loaded: &mut dyn BackendLoadedModel,
+        _context_limit: usize,
+        _settings: &ModelSettings,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Embed each text with an already loaded embedding model, one vector per text.
+    fn embed(
+        &self,
+        _loaded: &mut dyn BackendLoadedModel,
+        _texts: &[String],
+        _settings: &ModelSettings,
+    ) -> Result<Vec<Vec<f32>>, ProviderError> {
+        Err(ProviderError::NotImplemented(format!(
+            "Local inference backend '{}' does not support embeddings",
+            self.id()
+        )))
+    }
+
     fn available_memory_bytes(&self) -> u64;
 }
@@ -4,11 +4,15 @@ use hf_hub::progress::{DownloadEvent, FileStatus, ProgressEvent, ProgressHandler
 use hf_hub::repository::{ModelInfo, RepoSibling};
 use hf_hub::{HFClient, HFRepository, RepoTypeModel};
 
-use super::local_model_registry::{get_registry, model_id_from_repo, LocalModelStorage, ShardFile};
+use super::local_model_registry::{
+    get_registry, mmproj_local_path, model_id_from_repo, LocalModelStorage, ShardFile,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
+use crate::download_manager::{get_download_manager, size_on_disk};
 use crate::huggingface_auth;
+use crate::paths::Paths;
 
 const HF_API_BASE: &str = "https://huggingface.co/api/models";
 const HF_DOWNLOAD_BASE: &str = "https://huggingface.co";
@@ -50,6 +54,12 @@ pub struct HfModelVariant {
     pub supported: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unsupported_reason: Option<String>,
+    /// Vision projector downloaded alongside the model, when the repo ships one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmproj_filename: Option<String>,
+    /// Size of the model plus its companion files; only set when there are any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_size_bytes: Option<u64>,
 }
 
 fn default_supported() -> bool {
@@ -76,14 +86,26 @@ pub struct HfQuantVariant {
     pub quality_rank: u8,
     #[serde(default)]
     pub sharded: bool,
+    /// Multimodal projector a download of this variant also fetches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmproj: Option<HfGgufFile>,
 }
 
 impl HfQuantVariant {
+    /// Bytes a download of this variant fetches, including its projector.
+    pub fn download_size_bytes(&self) -> u64 {
+        self.size_bytes + self.mmproj.as_ref().map_or(0, |mmproj| mmproj.size_bytes)
+    }
+
     pub fn to_model_variant(&self, repo_id: &str) -> HfModelVariant {
         let model_id = model_id_from_repo(repo_id, &self.quantization);
         HfModelVariant {
             variant_id: self.quantization.clone(),
-            label: self.quantization.clone(),
+            label: if is_imatrix_quant(&self.filename) {
+                format!("{} (imatrix)", self.quantization)
+            } else {
+                self.quantization.clone()
+            },
             backend_id: LLAMACPP_BACKEND_ID.to_string(),
             format: GGUF_FORMAT.to_string(),
             model_id: model_id.clone(),
@@ -96,6 +118,8 @@ impl HfQuantVariant {
             sharded: self.sharded,
             supported: true,
             unsupported_reason: None,
+            mmproj_filename: self.mmproj.as_ref().map(|mmproj| mmproj.filename.clone()),
+            total_size_bytes: self.mmproj.is_some().then(|| self.download_size_bytes()),
         }
     }
 }
@@ -278,10 +302,36 @@ pub fn parse_quantization_from_filename(filename: &str) -> String {
     parse_quantization(filename)
 }
 
+/// Community tags marking an importance-matrix quant, e.g. "Model.i1-Q4_K_M.gguf".
+const IMATRIX_TAGS: &[&str] = &["i1", "imatrix", "imat"];
+
+fn gguf_stem(filename: &str) -> &str {
+    let basename = filename.rsplit('/').next().unwrap_or(filename);
+    basename.trim_end_matches(".gguf")
+}
+
+fn is_imatrix_tag(component: &str) -> bool {
+    IMATRIX_TAGS
+        .iter()
+        .any(|tag| component.eq_ignore_ascii_case(tag))
+}
+
+fn is_imatrix_quant(filename: &str) -> bool {
+    gguf_stem(filename).split(['-', '.']).any(is_imatrix_tag)
+}
+
+/// Drop imatrix tags wherever they sit so the quant is left as the last component.
+fn strip_imatrix_tags(stem: &str) -> String {
+    let kept: String = stem
+        .split_inclusive(['-', '.'])
+        .filter(|part| !is_imatrix_tag(part.trim_end_matches(['-', '.'])))
+        .collect();
+    kept.trim_end_matches(['-', '.']).to_string()
+}
+
 fn parse_quantization(filename: &str) -> String {
     // Strip directory prefix (e.g. "Q5_K_M/Model-Q5_K_M-00001-of-00002.gguf")
-    let basename = filename.rsplit('/').next().unwrap_or(filename);
-    let stem = basename.trim_end_matches(".gguf");
+    let stem = gguf_stem(filename);
 
     // Strip shard suffix like "-00001-of-00004"
     let stem = if let Some(pos) = stem.rfind("-of-") {
@@ -291,6 +341,7 @@ fn parse_quantization(filename: &str) -> String {
     } else {
         stem
     };
+    let stem = strip_imatrix_tags(stem);
 
     // The quantization tag is typically the last hyphen-separated component
     // that looks like a quant identifier (starts with Q, IQ, F, BF, TQ, MXFP, etc.)
@@ -478,8 +529,8 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
 
     let stem = model_stem_from_repo(repo_id);
 
-    let gguf_files: Vec<_> = files
-        .into_iter()
+    let gguf_files: Vec<&HfApiSibling> = files
+        .iter()
         .filter(|s| {
             s.rfilename.ends_with(".gguf")
                 && is_model_file(&s.rfilename, &stem)
@@ -491,7 +542,7 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
     let mut single_files: Vec<&HfApiSibling> = Vec::new();
     let mut shard_groups: HashMap<String, Vec<&HfApiSibling>> = HashMap::new();
 
-    for file in &gguf_files {
+    for file in gguf_files {
         if is_shard_file(&file.rfilename) {
             let quant = parse_quantization(&file.rfilename);
             shard_groups.entry(quant).or_default().push(file);
@@ -509,6 +560,7 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
         seen_quants.insert(quant.clone());
         let info = quant_info(&quant);
         let download_url = build_download_url(repo_id, &s.rfilename);
+        let mmproj = select_best_mmproj(repo_id, &files, &s.rfilename, &quant);
         variants.push(HfQuantVariant {
             quantization: quant,
             size_bytes: s.size.unwrap_or(0),
@@ -517,6 +569,7 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
             description: info.description,
             quality_rank: info.quality_rank,
             sharded: false,
+            mmproj,
         });
     }
 
@@ -530,6 +583,7 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
         let info = quant_info(&quant);
         let first_filename = &shards[0].rfilename;
         let download_url = build_download_url(repo_id, first_filename);
+        let mmproj = select_best_mmproj(repo_id, &files, first_filename, &quant);
         variants.push(HfQuantVariant {
             quantization: quant,
             size_bytes: total_size,
@@ -538,6 +592,7 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
             description: info.description,
             quality_rank: info.quality_rank,
             sharded: true,
+            mmproj,
         });
     }
 
@@ -550,18 +605,25 @@ fn group_into_variants(repo_id: &str, files: Vec<HfApiSibling>) -> Vec<HfQuantVa
     variants
 }
 
-pub async fn search_local_models(query: &str, limit: usize) -> Result<Vec<HfModelInfo>> {
+/// Search for local models, skipping the first `offset` GGUF search results. Exact-name
+/// matches and MLX repos are only added to the first page.
+pub async fn search_local_models(
+    query: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<HfModelInfo>> {
     let mut results = Vec::new();
 
-    if looks_like_repo_id(query) {
-        if let Some(model) = get_local_model_info_for_repo(query).await? {
-            results.push(model);
-        }
-    } else if let Some(model) = get_exact_name_local_model_info(query).await? {
-        results.push(model);
+    if offset == 0 {
+        let exact_match = if looks_like_repo_id(query) {
+            get_local_model_info_for_repo(query).await?
+        } else {
+            get_exact_name_local_model_info(query).await?
+        };
+        results.extend(exact_match);
     }
 
-    let mut gguf_results = search_gguf_models(query, limit).await?;
+    let mut gguf_results = search_gguf_models(query, limit, offset).await?;
     for model in &mut gguf_results {
         let gguf_variants = get_repo_gguf_variants(&model.repo_id)
             .await
@@ -573,7 +635,9 @@ pub async fn search_local_models(query: &str, limit: usize) -> Result<Vec<HfMode
     }
 
     results.extend(gguf_results);
-    append_optional_mlx_results(&mut results, search_mlx_models(query, limit).await, query);
+    if offset == 0 {
+        append_optional_mlx_results(&mut results, search_mlx_models(query, limit).await, query);
+    }
     dedupe_models(&mut results);
     results.sort_by(|a, b| {
         model_search_rank(query, a)
@@ -599,13 +663,25 @@ fn append_optional_mlx_results(
     }
 }
 
-pub async fn search_gguf_models(query: &str, limit: usize) -> Result<Vec<HfModelInfo>> {
-    let client = reqwest::Client::new();
-    let token = optional_hf_token(huggingface_auth::resolve_token_async()).await;
-    let url = format!(
+fn gguf_search_url(query: &str, limit: usize, offset: usize) -> String {
+    let mut url = format!(
         "{}?search={}&filter=gguf&sort=downloads&direction=-1&limit={}",
         HF_API_BASE, query, limit
     );
+    if offset > 0 {
+        url.push_str(&format!("&skip={offset}"));
+    }
+    url
+}
+
+pub async fn search_gguf_models(
+    query: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<HfModelInfo>> {
+    let client = reqwest::Client::new();
+    let token = optional_hf_token(huggingface_auth::resolve_token_async()).await;
+    let url = gguf_search_url(query, limit, offset);
 
     let response = apply_hf_auth(client.get(&url), token.as_deref())
         .header("User-Agent", "goose-ai-agent")
@@ -618,7 +694,13 @@ pub async fn search_gguf_models(query: &str, limit: usize) -> Result<Vec<HfModel
 
     let models: Vec<HfApiModel> = response.json().await?;
 
-    let results = models
+    Ok(gguf_models_from_search(models))
+}
+
+/// The search API can list the same repo more than once, so duplicates are merged
+/// and the result re-sorted by downloads.
+fn gguf_models_from_search(models: Vec<HfApiModel>) -> Vec<HfModelInfo> {
+    let mut results: Vec<HfModelInfo> = models
         .into_iter()
         .filter_map(|m| {
             let repo_id = m.id?;
@@ -661,7 +743,9 @@ pub async fn search_gguf_models(query: &str, limit: usize) -> Result<Vec<HfModel
         })
         .collect();
 
-    Ok(results)
+    dedupe_models(&mut results);
+    results.sort_by_key(|model| std::cmp::Reverse(model.downloads));
+    results
 }
 
 /// Fetch GGUF files for a repo and return them grouped by quantization.
@@ -676,11 +760,11 @@ pub async fn get_repo_gguf_variants(repo_id: &str) -> Result<Vec<HfQuantVariant>
         .await?;
 
     if !response.status().is_success() {
-        bail!(
-            "HuggingFace API returned status {} for repo {}",
-            response.status(),
-            repo_id
-        );
+        return Err(HfRepoStatusError {
+            repo_id: repo_id.to_string(),
+            status: response.status(),
+        }
+        .into());
     }
 
     let model: HfApiModel = response.json().await?;
@@ -742,7 +826,11 @@ pub fn parse_model_spec(spec: &str) -> Result<(String, String)> {
         )
     })?;
 
-    if !repo_id.contains('/') {
+    let well_formed_repo = repo_id
+        .split_once('/')
+        .is_some_and(|(owner, name)| !owner.is_empty() && !name.is_empty() && !name.contains('/'))
+        && !repo_id.contains(char::is_whitespace);
+    if !well_formed_repo {
         bail!("Invalid repo_id '{}': expected format 'user/repo'", repo_id);
     }
 
@@ -756,6 +844,109 @@ pub fn parse_model_spec(spec: &str) -> Result<(String, String)> {
     Ok((repo_id.to_string(), quant.to_string()))
 }
 
+/// Outcome of checking a `user/repo:quantization` spec before committing to a download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelSpecValidation {
+    Valid {
+        repo_id: String,
+        quantization: String,
+        size_bytes: u64,
+    },
+    InvalidFormat {
+        message: String,
+    },
+    RepoNotFound {
+        repo_id: String,
+        message: String,
+    },
+    QuantizationNotFound {
+        repo_id: String,
+        quantization: String,
+        available: Vec<String>,
+    },
+}
+
+/// A repo lookup the HuggingFace API answered with a non-success status.
+#[derive(Debug)]
+pub struct HfRepoStatusError {
+    pub repo_id: String,
+    pub status: reqwest::StatusCode,
+}
+
+impl std::fmt::Display for HfRepoStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HuggingFace API returned status {} for repo {}",
+            self.status, self.repo_id
+        )
+    }
+}
+
+impl std::error::Error for HfRepoStatusError {}
+
+/// Parse a spec and confirm its repo and quantization exist, without downloading.
+/// Lookups that fail for any reason other than a missing repo (auth, rate limits,
+/// network) are returned as errors rather than reported as a missing repo.
+pub async fn validate_model_spec(spec: &str) -> Result<ModelSpecValidation> {
+    let (repo_id, quantization) = match parse_model_spec(spec.trim()) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return Ok(ModelSpecValidation::InvalidFormat {
+                message: error.to_string(),
+            })
+        }
+    };
+    let lookup = get_repo_gguf_variants(&repo_id).await;
+    spec_lookup_outcome(repo_id, quantization, lookup)
+}
+
+fn spec_lookup_outcome(
+    repo_id: String,
+    quantization: String,
+    lookup: Result<Vec<HfQuantVariant>>,
+) -> Result<ModelSpecValidation> {
+    match lookup {
+        Ok(variants) => Ok(match_spec_quantization(repo_id, quantization, &variants)),
+        Err(error)
+            if error
+                .downcast_ref::<HfRepoStatusError>()
+                .is_some_and(|error| error.status == reqwest::StatusCode::NOT_FOUND) =>
+        {
+            Ok(ModelSpecValidation::RepoNotFound {
+                repo_id,
+                message: error.to_string(),
+            })
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn match_spec_quantization(
+    repo_id: String,
+    quantization: String,
+    variants: &[HfQuantVariant],
+) -> ModelSpecValidation {
+    match variants
+        .iter()
+        .find(|variant| variant.quantization.eq_ignore_ascii_case(&quantization))
+    {
+        Some(variant) => ModelSpecValidation::Valid {
+            repo_id,
+            quantization: variant.quantization.clone(),
+            size_bytes: variant.size_bytes,
+        },
+        None => ModelSpecValidation::QuantizationNotFound {
+            repo_id,
+            quantization,
+            available: variants
+                .iter()
+                .map(|variant| variant.quantization.clone())
+                .collect(),
+        },
+    }
+}
+
 /// Resolve a model spec to all GGUF files for that quantization (handles shards).
 pub async fn resolve_model_spec_full(spec: &str) -> Result<(String, ResolvedModel)> {
     let (repo_id, quant) = parse_model_spec(spec)?;
@@ -909,14 +1100,50 @@ pub async fn resolve_model_spec(spec: &str) -> Result<(String, HfGgufFile)> {
     Ok((repo_id, resolved.files.into_iter().next().unwrap()))
 }
 
+/// Attention dimensions that determine how much KV cache each context token needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KvCacheShape {
+    pub n_layer: u64,
+    pub n_head_kv: u64,
+    pub k_per_head: u64,
+    pub v_per_head: u64,
+}
+
+impl KvCacheShape {
+    /// K and V across all KV heads and layers, at 2 bytes (f16) per element.
+    pub fn bytes_per_token(&self) -> u64 {
+        (self.k_per_head + self.v_per_head) * self.n_head_kv * self.n_layer * 2
+    }
+
+    /// Context tokens whose KV cache fits in `available_bytes`.
+    pub fn max_context_for_memory(&self, available_bytes: u64) -> Option<usize> {
+        let bytes_per_token = self.bytes_per_token();
+        if bytes_per_token == 0 {
+            return None;
+        }
+        // Reserve memory for computation scratch buffers (attention, etc.) and other overhead.
+        // The compute buffer can be 40-50% of the KV cache size for large models, so we
+        // conservatively use only half the available memory for the KV cache.
+        let usable = (available_bytes as f64 * 0.5) as u64;
+        Some((usable / bytes_per_token) as usize)
+    }
+}
+
 /// Recommend which quantization variant to use based on available memory.
+///
+/// With a `target_context` of `(tokens, shape)`, the KV cache for that many tokens is
+/// set aside first so the recommended weights still leave room for the context.
 pub fn recommend_variant(
     variants: &[HfQuantVariant],
     available_memory_bytes: u64,
+    target_context: Option<(u64, KvCacheShape)>,
 ) -> Option<usize> {
     // We need ~10-20% overhead beyond model size for inference context.
     // Pick the highest-quality variant that fits.
-    let usable = (available_memory_bytes as f64 * 0.85) as u64;
+    let kv_cache_bytes = target_context.map_or(0, |(tokens, shape)| {
+        tokens.saturating_mul(shape.bytes_per_token())
+    });
+    let usable = ((available_memory_bytes as f64 * 0.85) as u64).saturating_sub(kv_cache_bytes);
 
     let mut best: Option<usize> = None;
     for (i, v) in variants.iter().enumerate() {
@@ -944,6 +1171,53 @@ mod tests {
         assert_eq!(parse_quantization("random-name.gguf"), "unknown");
     }
 
+    #[test]
+    fn test_gguf_download_plan_targets_goose_models_dir() {
+        let file = |filename: &str| HfGgufFile {
+            filename: filename.to_string(),
+            size_bytes: 10,
+            quantization: "Q4_K_M".to_string(),
+            download_url: format!(
+                "https://huggingface.co/someone/model-GGUF/resolve/main/{filename}"
+            ),
+        };
+        let resolved = ResolvedModel {
+            files: vec![
+                file("Q4_K_M/model-00001-of-00002.gguf"),
+                file("Q4_K_M/model-00002-of-00002.gguf"),
+            ],
+            total_size: 20,
+            mmproj: Some(file("mmproj-F16.gguf")),
+        };
+
+        let (transfers, paths, mmproj_path) = gguf_download_plan("someone/model-GGUF", &resolved);
+
+        let models_dir = Paths::in_data_dir("models");
+        assert_eq!(
+            paths,
+            vec![
+                models_dir.join("Q4_K_M/model-00001-of-00002.gguf"),
+                models_dir.join("Q4_K_M/model-00002-of-00002.gguf"),
+            ]
+        );
+        assert_eq!(
+            mmproj_path,
+            Some(mmproj_local_path("someone/model-GGUF", "mmproj-F16.gguf"))
+        );
+        assert_eq!(transfers.len(), 3);
+        assert_eq!(
+            transfers[0],
+            (resolved.files[0].download_url.clone(), paths[0].clone())
+        );
+        assert_eq!(
+            transfers[2],
+            (
+                resolved.mmproj.as_ref().unwrap().download_url.clone(),
+                mmproj_path.unwrap()
+            )
+        );
+    }
+
     #[test]
     fn test_hf_authorization_header() {
         assert_eq!(
@@ -971,6 +1245,43 @@ mod tests {
         assert_eq!(parse_quantization("Model-UD-IQ1_M.gguf"), "IQ1_M");
     }
 
+    #[test]
+    fn test_parse_quantization_imatrix_tags() {
+        assert_eq!(parse_quantization("model-i1-Q4_K_M.gguf"), "Q4_K_M");
+        assert_eq!(parse_quantization("Model.i1-IQ3_XS.gguf"), "IQ3_XS");
+        assert_eq!(parse_quantization("Model-Q5_K_S-imat.gguf"), "Q5_K_S");
+        assert_eq!(parse_quantization("Model-Q6_K.imatrix.gguf"), "Q6_K");
+        assert_eq!(
+            parse_quantization("Q8_0/Model.i1-Q8_0-00001-of-00002.gguf"),
+            "Q8_0"
+        );
+
+        assert!(is_imatrix_quant("Model.i1-Q4_K_M.gguf"));
+        assert!(!is_imatrix_quant("Model-Q4_K_M.gguf"));
+    }
+
+    #[test]
+    fn test_imatrix_variant_label_notes_provenance() {
+        let variant = |filename: &str| HfQuantVariant {
+            quantization: parse_quantization(filename),
+            size_bytes: 1,
+            filename: filename.to_string(),
+            download_url: String::new(),
+            description: "",
+            quality_rank: 0,
+            sharded: false,
+            mmproj: None,
+        };
+
+        let imatrix =
+            variant("Model.i1-Q4_K_M.gguf").to_model_variant("mradermacher/Model-i1-GGUF");
+        assert_eq!(imatrix.label, "Q4_K_M (imatrix)");
+        assert_eq!(imatrix.variant_id, "Q4_K_M");
+
+        let plain = variant("Model-Q4_K_M.gguf").to_model_variant("bartowski/Model-GGUF");
+        assert_eq!(plain.label, "Q4_K_M");
+    }
+
     #[test]
     fn test_is_shard_file() {
         assert!(is_shard_file("Q5_K_M/Model-Q5_K_M-00001-of-00002.gguf"));
@@ -991,6 +1302,98 @@ mod tests {
         assert!(parse_model_spec("no-colon").is_err());
         assert!(parse_model_spec("noslash:Q4_K_M").is_err());
         assert!(parse_model_spec("owner/repo:").is_err());
+        assert!(parse_model_spec("owner/:Q4_K_M").is_err());
+        assert!(parse_model_spec("/repo:Q4_K_M").is_err());
+        assert!(parse_model_spec("owner/my repo:Q4_K_M").is_err());
+    }
+
+    fn spec_test_variant(quantization: &str, size_bytes: u64) -> HfQuantVariant {
+        HfQuantVariant {
+            quantization: quantization.to_string(),
+            size_bytes,
+            filename: format!("model-{quantization}.gguf"),
+            download_url: String::new(),
+            description: "",
+            quality_rank: 0,
+            sharded: false,
+            mmproj: None,
+        }
+    }
+
+    #[test]
+    fn test_match_spec_quantization_valid() {
+        let variants = vec![
+            spec_test_variant("Q4_K_M", 4_000),
+            spec_test_variant("Q8_0", 8_000),
+        ];
+        assert_eq!(
+            match_spec_quantization("owner/repo".into(), "q8_0".into(), &variants),
+            ModelSpecValidation::Valid {
+                repo_id: "owner/repo".into(),
+                quantization: "Q8_0".into(),
+                size_bytes: 8_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_spec_quantization_lists_alternatives() {
+        let variants = vec![
+            spec_test_variant("Q4_K_M", 4_000),
+            spec_test_variant("Q8_0", 8_000),
+        ];
+        assert_eq!(
+            match_spec_quantization("owner/repo".into(), "Q5_K_M".into(), &variants),
+            ModelSpecValidation::QuantizationNotFound {
+                repo_id: "owner/repo".into(),
+                quantization: "Q5_K_M".into(),
+                available: vec!["Q4_K_M".into(), "Q8_0".into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_model_spec_rejects_bad_format_offline() {
+        assert!(matches!(
+            validate_model_spec("not a spec").await.unwrap(),
+            ModelSpecValidation::InvalidFormat { .. }
+        ));
+    }
+
+    #[test]
+    fn test_spec_lookup_only_reports_missing_repo_for_not_found() {
+        let status_error = |status| -> Result<Vec<HfQuantVariant>> {
+            Err(HfRepoStatusError {
+                repo_id: "owner/repo".into(),
+                status,
+            }
+            .into())
+        };
+
+        assert!(matches!(
+            spec_lookup_outcome(
+                "owner/repo".into(),
+                "Q4_K_M".into(),
+                status_error(reqwest::StatusCode::NOT_FOUND),
+            ),
+            Ok(ModelSpecValidation::RepoNotFound { .. })
+        ));
+        for status in [
+            reqwest::StatusCode::UNAUTHORIZED,
+            reqwest::StatusCode::FORBIDDEN,
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            let error =
+                spec_lookup_outcome("owner/repo".into(), "Q4_K_M".into(), status_error(status))
+                    .unwrap_err();
+            assert!(error.to_string().contains(status.as_str()));
+        }
+        assert!(spec_lookup_outcome(
+            "owner/repo".into(),
+            "Q4_K_M".into(),
+            Err(anyhow::anyhow!("connection refused")),
+        )
+        .is_err());
     }
 
     #[test]
@@ -1011,6 +1414,8 @@ mod tests {
             sharded: false,
             supported: true,
             unsupported_reason: None,
+            mmproj_filename: None,
+            total_size_bytes: None,
         };
         let mlx_variant = HfModelVariant {
             variant_id: MLX_VARIANT_ID.to_string(),
@@ -1027,6 +1432,8 @@ mod tests {
             sharded: true,
             supported: true,
             unsupported_reason: None,
+            mmproj_filename: None,
+            total_size_bytes: None,
         };
         let mut models = vec![
             HfModelInfo {
@@ -1068,6 +1475,57 @@ mod tests {
             .any(|variant| variant.backend_id == MLX_BACKEND_ID));
     }
 
+    #[test]
+    fn test_gguf_models_from_search_merges_duplicate_repos() {
+        let models: Vec<HfApiModel> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "org/popular-GGUF",
+                "downloads": 900,
+                "siblings": [{ "rfilename": "popular-Q4_K_M.gguf", "size": 4 }]
+            },
+            {
+                "id": "org/other-GGUF",
+                "downloads": 500,
+                "siblings": []
+            },
+            {
+                "id": "org/popular-GGUF",
+                "downloads": 1200,
+                "siblings": [
+                    { "rfilename": "popular-Q4_K_M.gguf", "size": 4 },
+                    { "rfilename": "popular-Q8_0.gguf", "size": 8 }
+                ]
+            }
+        ]))
+        .unwrap();
+
+        let results = gguf_models_from_search(models);
+
+        let repo_ids: Vec<&str> = results.iter().map(|m| m.repo_id.as_str()).collect();
+        assert_eq!(repo_ids, vec!["org/popular-GGUF", "org/other-GGUF"]);
+        assert_eq!(results[0].downloads, 1200);
+        let filenames: Vec<&str> = results[0]
+            .gguf_files
+            .iter()
+            .map(|f| f.filename.as_str())
+            .collect();
+        assert_eq!(filenames, vec!["popular-Q4_K_M.gguf", "popular-Q8_0.gguf"]);
+    }
+
+    #[test]
+    fn gguf_search_url_pages_with_skip() {
+        assert_eq!(
+            gguf_search_url("qwen", 20, 0),
+            format!("{HF_API_BASE}?search=qwen&filter=gguf&sort=downloads&direction=-1&limit=20")
+        );
+        assert_eq!(
+            gguf_search_url("qwen", 20, 40),
+            format!(
+                "{HF_API_BASE}?search=qwen&filter=gguf&sort=downloads&direction=-1&limit=20&skip=40"
+            )
+        );
+    }
+
     fn sibling(filename: &str) -> RepoSibling {
         RepoSibling {
             rfilename: filename.to_string(),
@@ -1227,9 +1685,8 @@ mod tests {
         assert_eq!(best_download_count(Some(0), Some(0)), None);
     }
 
-    #[test]
-    fn test_recommend_variant() {
-        let variants = vec![
+    fn recommend_test_variants() -> Vec<HfQuantVariant> {
+        vec![
             HfQuantVariant {
                 quantization: "Q2_K".into(),
                 size_bytes: 2_000_000_000,
@@ -1238,6 +1695,7 @@ mod tests {
                 description: "Small",
                 quality_rank: 24,
                 sharded: false,
+                mmproj: None,
             },
             HfQuantVariant {
                 quantization: "Q4_K_M".into(),
@@ -1247,6 +1705,7 @@ mod tests {
                 description: "Medium",
                 quality_rank: 45,
                 sharded: false,
+                mmproj: None,
             },
             HfQuantVariant {
                 quantization: "Q8_0".into(),
@@ -1256,12 +1715,44 @@ mod tests {
                 description: "Large",
                 quality_rank: 80,
                 sharded: false,
+                mmproj: None,
             },
-        ];
+        ]
+    }
 
-        assert_eq!(recommend_variant(&variants, 5_000_000_000), Some(1));
-        assert_eq!(recommend_variant(&variants, 10_000_000_000), Some(2));
-        assert_eq!(recommend_variant(&variants, 1_000_000_000), None);
+    #[test]
+    fn test_recommend_variant() {
+        let variants = recommend_test_variants();
+        assert_eq!(recommend_variant(&variants, 5_000_000_000, None), Some(1));
+        assert_eq!(recommend_variant(&variants, 10_000_000_000, None), Some(2));
+        assert_eq!(recommend_variant(&variants, 1_000_000_000, None), None);
+    }
+
+    #[test]
+    fn test_recommend_variant_leaves_room_for_kv_cache() {
+        let variants = recommend_test_variants();
+        // Llama-3-8B-like attention: 128 KiB of KV cache per token.
+        let shape = KvCacheShape {
+            n_layer: 32,
+            n_head_kv: 8,
+            k_per_head: 128,
+            v_per_head: 128,
+        };
+        assert_eq!(shape.bytes_per_token(), 131_072);
+
+        let memory = 10_000_000_000;
+        assert_eq!(
+            recommend_variant(&variants, memory, Some((2048, shape))),
+            Some(2)
+        );
+        assert_eq!(
+            recommend_variant(&variants, memory, Some((32_768, shape))),
+            Some(1)
+        );
+        assert_eq!(
+            recommend_variant(&variants, memory, Some((131_072, shape))),
+            None
+        );
     }
 
     #[test]
@@ -1291,6 +1782,7 @@ mod tests {
             speed_bps: None,
             eta_seconds: None,
             error: None,
+            final_size_bytes: None,
             task_exited: false,
         });
 
@@ -1338,6 +1830,47 @@ mod tests {
         assert_eq!(variants[0].quantization, "Q4_K_M");
     }
 
+    #[test]
+    fn test_group_into_variants_attaches_mmproj_companion() {
+        let files = vec![
+            HfApiSibling {
+                rfilename: "gemma-3-27b-it-Q4_K_M.gguf".into(),
+                size: Some(4_000_000_000),
+            },
+            HfApiSibling {
+                rfilename: "mmproj-BF16.gguf".into(),
+                size: Some(800_000_000),
+            },
+        ];
+        let variants = group_into_variants("unsloth/gemma-3-27b-it-GGUF", files);
+        let mmproj = variants[0].mmproj.as_ref().unwrap();
+        assert_eq!(mmproj.filename, "mmproj-BF16.gguf");
+        assert_eq!(variants[0].download_size_bytes(), 4_800_000_000);
+
+        let model_variant = variants[0].to_model_variant("unsloth/gemma-3-27b-it-GGUF");
+        assert_eq!(model_variant.size_bytes, 4_000_000_000);
+        assert_eq!(
+            model_variant.mmproj_filename.as_deref(),
+            Some("mmproj-BF16.gguf")
+        );
+        assert_eq!(model_variant.total_size_bytes, Some(4_800_000_000));
+    }
+
+    #[test]
+    fn test_group_into_variants_without_mmproj_reports_model_size_only() {
+        let files = vec![HfApiSibling {
+            rfilename: "Model-Q4_K_M.gguf".into(),
+            size: Some(4_000_000_000),
+        }];
+        let variants = group_into_variants("someone/Model-GGUF", files);
+        assert!(variants[0].mmproj.is_none());
+        assert_eq!(variants[0].download_size_bytes(), 4_000_000_000);
+
+        let model_variant = variants[0].to_model_variant("someone/Model-GGUF");
+        assert_eq!(model_variant.mmproj_filename, None);
+        assert_eq!(model_variant.total_size_bytes, None);
+    }
+
     #[test]
     fn test_group_into_variants_includes_shard_only_quants() {
         let files = vec![
@@ -1752,6 +2285,8 @@ fn mlx_variants_from_model_info(
             && cfg!(target_os = "macos")
             && cfg!(feature = "mlx"),
         unsupported_reason: mlx_unsupported_reason(mlx_config),
+        mmproj_filename: None,
+        total_size_bytes: None,
     }]
 }
 
@@ -2007,24 +2542,54 @@ fn snapshot_root_for_file(
 async fn resolve_gguf_model(repo_id: &str, quantization: &str) -> Result<ResolvedLocalModel> {
     let spec = format!("{}:{}", repo_id, quantization);
     let (_repo, resolved) = resolve_model_spec_full(&spec).await?;
-    let (local_paths, mmproj_path) =
-        download_gguf_to_hf_cache(repo_id, quantization, &resolved).await?;
+    let (local_paths, mmproj_path) = download_gguf_model(repo_id, quantization, &resolved).await?;
     Ok(ResolvedLocalModel::Gguf {
         repo_id: repo_id.to_string(),
         quantization: quantization.to_string(),
         resolved,
         local_paths,
         mmproj_path,
-        storage: LocalModelStorage::HuggingFaceCache,
+        storage: LocalModelStorage::GooseManaged,
     })
 }
 
-async fn download_gguf_to_hf_cache(
+/// Where each file of a resolved GGUF model lands in Goose's models directory, paired
+/// with the URL it is fetched from. The projector, if any, comes last.
+fn gguf_download_plan(
+    repo_id: &str,
+    resolved: &ResolvedModel,
+) -> (
+    Vec<(String, std::path::PathBuf)>,
+    Vec<std::path::PathBuf>,
+    Option<std::path::PathBuf>,
+) {
+    let paths: Vec<std::path::PathBuf> = resolved
+        .files
+        .iter()
+        .map(|file| Paths::in_data_dir("models").join(&file.filename))
+        .collect();
+    let mmproj_path = resolved
+        .mmproj
+        .as_ref()
+        .map(|mmproj| mmproj_local_path(repo_id, &mmproj.filename));
+    let transfers = resolved
+        .files
+        .iter()
+        .zip(&paths)
+        .chain(resolved.mmproj.iter().zip(&mmproj_path))
+        .map(|(file, path)| (file.download_url.clone(), path.clone()))
+        .collect();
+    (transfers, paths, mmproj_path)
+}
+
+/// GGUF files go through the download manager, so model downloads honour the
+/// configured rate limit and segment count like every other local-inference transfer.
+/// Files already on disk at the size the Hub reports are reused as they are.
+async fn download_gguf_model(
     repo_id: &str,
     quantization: &str,
     resolved: &ResolvedModel,
 ) -> Result<(Vec<std::path::PathBuf>, Option<std::path::PathBuf>)> {
-    let (owner, name) = split_repo_id(repo_id)?;
     let model_id = model_id_from_repo(repo_id, quantization);
     let total_size = resolved
         .files
@@ -2032,52 +2597,26 @@ async fn download_gguf_to_hf_cache(
         .chain(resolved.mmproj.iter())
         .map(|file| file.size_bytes)
         .sum();
-    let progress = HfDownloadProgress::new(model_id, total_size);
-    progress.init();
-    let client = hf_client().await?;
-    let repo = client.model(owner.to_string(), name.to_string());
-    let mut paths = Vec::with_capacity(resolved.files.len());
-    for file in &resolved.files {
-        let path = match repo
-            .download_file()
-            .filename(file.filename.clone())
-            .progress(progress.clone())
-            .send()
-            .await
-            .map_err(anyhow::Error::from)
-        {
-            Ok(path) => path,
-            Err(error) => {
-                progress.fail(&error);
-                return Err(error);
-            }
-        };
-        progress.finish_file(file.size_bytes);
-        paths.push(path);
+    let (transfers, paths, mmproj_path) = gguf_download_plan(repo_id, resolved);
+    let progress_id = format!("{}-model", model_id);
+    let expected: Vec<(std::path::PathBuf, u64)> = transfers
+        .iter()
+        .zip(resolved.files.iter().chain(resolved.mmproj.iter()))
+        .map(|((_, path), file)| (path.clone(), file.size_bytes))
+        .collect();
+    if get_download_manager().reuse_existing_files(&progress_id, &expected) {
+        return Ok((paths, mmproj_path));
     }
 
-    let mmproj_path = if let Some(mmproj) = &resolved.mmproj {
-        let path = match repo
-            .download_file()
-            .filename(mmproj.filename.clone())
-            .progress(progress.clone())
-            .send()
-            .await
-            .map_err(anyhow::Error::from)
-        {
-            Ok(path) => path,
-            Err(error) => {
-                progress.fail(&error);
-                return Err(error);
-            }
-        };
-        progress.finish_file(mmproj.size_bytes);
-        Some(path)
-    } else {
-        None
-    };
-
-    progress.complete();
+    let token = optional_hf_token(huggingface_auth::resolve_token_async()).await;
+    get_download_manager()
+        .download_model_files(
+            &progress_id,
+            transfers,
+            total_size,
+            crate::management::download_options(token),
+        )
+        .await?;
     Ok((paths, mmproj_path))
 }
 
@@ -2138,6 +2677,7 @@ async fn resolve_mlx_model(repo_id: &str, variant_id: &str) -> Result<ResolvedLo
     let progress = HfDownloadProgress::new(repo_id.to_string(), total_size);
     progress.init();
     let mut snapshot_path = None;
+    let mut paths = Vec::with_capacity(filenames.len());
     for filename in filenames {
         let file_size = siblings
             .iter()
@@ -2162,8 +2702,9 @@ async fn resolve_mlx_model(repo_id: &str, variant_id: &str) -> Result<ResolvedLo
             snapshot_path = snapshot_root_for_file(&path, &filename);
         }
         progress.finish_file(file_size);
+        paths.push(path);
     }
-    progress.complete();
+    progress.complete(size_on_disk(&paths));
     let snapshot_path = snapshot_path
         .ok_or_else(|| anyhow::anyhow!("MLX model {} has no downloadable files", repo_id))?;
     let total_size = if total_size > 0 {
@@ -2217,6 +2758,7 @@ impl HfDownloadProgress {
                     progress.speed_bps = None;
                     progress.eta_seconds = None;
                     progress.error = None;
+                    progress.final_size_bytes = None;
                     progress.task_exited = false;
                 }
             });
@@ -2230,6 +2772,7 @@ impl HfDownloadProgress {
                 speed_bps: None,
                 eta_seconds: None,
                 error: None,
+                final_size_bytes: None,
                 task_exited: false,
             });
         }
@@ -2243,13 +2786,12 @@ impl HfDownloadProgress {
             })
     }
 
-    fn complete(&self) {
+    fn complete(&self, final_size_bytes: u64) {
         crate::download_manager::get_download_manager().update_progress(
             &format!("{}-model", self.model_id),
             |progress| {
                 if progress.status != crate::download_manager::DownloadStatus::Cancelled {
-                    progress.status = crate::download_manager::DownloadStatus::Completed;
-                    progress.progress_percent = 100.0;
+                    progress.mark_completed(final_size_bytes);
                 }
                 progress.task_exited = true;
             },
@@ -2393,6 +2935,7 @@ pub fn register_resolved_model(resolved: ResolvedLocalModel, source: &str) -> Re
             let settings = super::local_model_registry::default_settings_for_model(&model_id);
             super::local_model_registry::LocalModelEntry {
                 id: model_id.clone(),
+                embedding_capable: super::local_model_registry::is_embedding_model_repo(&repo_id),
                 repo_id,
                 filename: first_file.filename.clone(),
                 quantization: variant_id,
@@ -2425,6 +2968,7 @@ pub fn register_resolved_model(resolved: ResolvedLocalModel, source: &str) -> Re
             settings.backend_id = Some(backend_id.clone());
             super::local_model_registry::LocalModelEntry {
                 id: model_id.clone(),
+                embedding_capable: false,
                 repo_id,
                 filename: variant_id.clone(),
                 quantization: variant_id,
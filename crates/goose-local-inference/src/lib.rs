@@ -23,7 +23,10 @@ use anyhow::Result;
 use async_stream::try_stream;
 use async_trait::async_trait;
 use backend::{BackendLoadedModel, LocalInferenceBackend};
-use goose_provider_types::base::{MessageStream, Provider, ProviderDescriptor, ProviderMetadata};
+use futures::StreamExt;
+use goose_provider_types::base::{
+    EmbeddingCapable, MessageStream, Provider, ProviderDescriptor, ProviderMetadata,
+};
 use goose_provider_types::conversation::message::{
     Message, MessageContent, SystemNotificationType,
 };
@@ -40,6 +43,7 @@ use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex as StdMutex, Weak};
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Notify};
 use uuid::Uuid;
 
@@ -48,6 +52,7 @@ type ModelSlotHandle = Arc<ModelSlot>;
 struct ModelSlot {
     state: Mutex<ModelSlotState>,
     notify: Notify,
+    last_used: StdMutex<Instant>,
 }
 
 enum ModelSlotState {
@@ -61,8 +66,149 @@ impl ModelSlot {
         Self {
             state: Mutex::new(ModelSlotState::Empty),
             notify: Notify::new(),
+            last_used: StdMutex::new(Instant::now()),
         }
     }
+
+    fn touch(&self) {
+        *self.last_used.lock().expect("last_used lock poisoned") = Instant::now();
+    }
+
+    fn last_used(&self) -> Instant {
+        *self.last_used.lock().expect("last_used lock poisoned")
+    }
+}
+
+struct EvictionCandidate {
+    size_bytes: u64,
+    last_used: Instant,
+    pinned: bool,
+}
+
+/// Pick the least recently used candidates to unload until `required_bytes` fits,
+/// returning their indices in eviction order. Pinned models are never picked.
+fn select_lru_evictions(
+    candidates: &[EvictionCandidate],
+    available_bytes: u64,
+    required_bytes: u64,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..candidates.len())
+        .filter(|&index| !candidates[index].pinned)
+        .collect();
+    order.sort_by_key(|&index| candidates[index].last_used);
+
+    let mut free_bytes = available_bytes;
+    let mut evictions = Vec::new();
+    for index in order {
+        if free_bytes >= required_bytes {
+            break;
+        }
+        free_bytes = free_bytes.saturating_add(candidates[index].size_bytes);
+        evictions.push(index);
+    }
+    evictions
+}
+
+/// Whether pinned models keep `required_bytes` from fitting even after `evictions` are
+/// unloaded. An unknown size (`u64::MAX`) never fits, so the load is attempted anyway.
+fn pinned_models_block_load(
+    candidates: &[EvictionCandidate],
+    evictions: &[usize],
+    available_bytes: u64,
+    required_bytes: u64,
+) -> bool {
+    let freed_bytes = evictions
+        .iter()
+        .map(|&index| candidates[index].size_bytes)
+        .fold(available_bytes, u64::saturating_add);
+    required_bytes != u64::MAX
+        && candidates.iter().any(|candidate| candidate.pinned)
+        && freed_bytes < required_bytes
+}
+
+/// Indices of models whose last use is at least `ttl` before `now`.
+fn select_idle_evictions(last_used: &[Instant], now: Instant, ttl: Duration) -> Vec<usize> {
+    last_used
+        .iter()
+        .enumerate()
+        .filter(|(_, last_used)| now.saturating_duration_since(**last_used) >= ttl)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+const MODEL_IDLE_TTL_KEY: &str = "GOOSE_LOCAL_MODEL_IDLE_TTL_SECS";
+const DEFAULT_MODEL_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a loaded model may sit unused before it is unloaded; `None` when disabled.
+fn model_idle_ttl() -> Option<Duration> {
+    let secs = config_resolver::string_param(MODEL_IDLE_TTL_KEY)
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    match secs {
+        Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs)),
+        None => Some(DEFAULT_MODEL_IDLE_TTL),
+    }
+}
+
+async fn unload_idle_models_periodically(runtime: Weak<InferenceRuntime>) {
+    let mut interval = tokio::time::interval(IDLE_CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        let Some(runtime) = runtime.upgrade() else {
+            return;
+        };
+        if let Some(ttl) = model_idle_ttl() {
+            runtime.unload_idle_models(Instant::now(), ttl);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MemoryPressure {
+    available_bytes: u64,
+    idle_loaded_bytes: u64,
+    busy_slots: usize,
+}
+
+/// Admit a cold load once it fits after evicting idle models. While other models are
+/// busy, queue rather than load over them: evicting a model mid-session only makes that
+/// session reload it next turn, and the two end up unloading each other. With nothing
+/// busy there is no one to wait for, so the load proceeds and succeeds or fails alone.
+fn admit_cold_load(pressure: &MemoryPressure, required_bytes: u64) -> bool {
+    pressure.busy_slots == 0
+        || pressure
+            .available_bytes
+            .saturating_add(pressure.idle_loaded_bytes)
+            >= required_bytes
+}
+
+fn estimated_model_bytes(model_id: &str) -> Option<u64> {
+    let (weights, mmproj) = registered_model_bytes(model_id)?;
+    let bytes = weights + mmproj;
+    (bytes > 0).then_some(bytes)
+}
+
+/// Registered size of a model's weights across all shards, and of its projector.
+pub(crate) fn registered_model_bytes(model_id: &str) -> Option<(u64, u64)> {
+    let registry = local_model_registry::get_registry().lock().ok()?;
+    let entry = registry.get_model(model_id)?;
+    Some((entry.file_size(), entry.mmproj_size_bytes))
+}
+
+fn is_model_pinned(model_id: &str) -> bool {
+    local_model_registry::get_registry()
+        .lock()
+        .ok()
+        .and_then(|registry| {
+            registry
+                .get_model_settings(model_id)
+                .map(|settings| settings.pinned)
+        })
+        .unwrap_or(false)
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -86,9 +232,24 @@ impl ModelCacheKey {
     }
 }
 
+/// A model resident in memory, as reported by [`loaded_models`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadedModelInfo {
+    pub model_id: String,
+    pub backend_id: &'static str,
+    /// Estimated from the model files on disk; 0 when the registry doesn't know the size.
+    pub size_bytes: u64,
+    /// A generation is running on the model, so evicting it has to wait.
+    pub in_use: bool,
+}
+
+const CAPACITY_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct InferenceRuntime {
     models: StdMutex<HashMap<ModelCacheKey, ModelSlotHandle>>,
     cold_load_lock: Mutex<()>,
+    /// Signalled whenever a generation finishes, so queued cold loads can re-check memory.
+    capacity_changed: Notify,
     backends: HashMap<&'static str, Arc<dyn LocalInferenceBackend>>,
 }
 
@@ -113,13 +274,21 @@ impl InferenceRuntime {
         let mut backends = HashMap::new();
         backends.insert(LLAMACPP_BACKEND_ID, llamacpp_backend);
         backends.insert(MLX_BACKEND_ID, mlx_backend);
-        let runtime = Arc::new(Self {
+        let runtime = Arc::new(Self::with_backends(backends));
+        *guard = Arc::downgrade(&runtime);
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(unload_idle_models_periodically(Arc::downgrade(&runtime)));
+        }
+        Ok(runtime)
+    }
+
+    fn with_backends(backends: HashMap<&'static str, Arc<dyn LocalInferenceBackend>>) -> Self {
+        Self {
             models: StdMutex::new(HashMap::new()),
             cold_load_lock: Mutex::new(()),
+            capacity_changed: Notify::new(),
             backends,
-        });
-        *guard = Arc::downgrade(&runtime);
-        Ok(runtime)
+        }
     }
 
     fn default_backend(&self) -> &dyn LocalInferenceBackend {
@@ -157,13 +326,240 @@ impl InferenceRuntime {
         map.get(key).cloned()
     }
 
-    fn other_model_slots(&self, keep_key: &ModelCacheKey) -> Vec<ModelSlotHandle> {
+    /// Load a model into its slot without evicting others. Used for small auxiliary
+    /// models (embeddings) that should coexist with the chat model.
+    async fn ensure_loaded_alongside(
+        &self,
+        slot: &ModelSlot,
+        backend: &Arc<dyn LocalInferenceBackend>,
+        model_id: &str,
+        resolved: &ResolvedModelPaths,
+    ) -> Result<(), ProviderError> {
+        loop {
+            let mut state = slot.state.lock().await;
+            match &*state {
+                ModelSlotState::Loaded(_) => return Ok(()),
+                ModelSlotState::Loading => {
+                    let notified = slot.notify.notified();
+                    drop(state);
+                    notified.await;
+                }
+                ModelSlotState::Empty => {
+                    *state = ModelSlotState::Loading;
+                    drop(state);
+
+                    let backend = backend.clone();
+                    let model_id = model_id.to_string();
+                    let resolved = resolved.clone();
+                    let loaded = match tokio::task::spawn_blocking(move || {
+                        backend.load_model(&model_id, &resolved, &resolved.settings)
+                    })
+                    .await
+                    {
+                        Ok(result) => result,
+                        Err(err) => Err(ProviderError::ExecutionError(err.to_string())),
+                    };
+
+                    let mut state = slot.state.lock().await;
+                    let result = match loaded {
+                        Ok(loaded) => {
+                            *state = ModelSlotState::Loaded(loaded);
+                            Ok(())
+                        }
+                        Err(err) => {
+                            *state = ModelSlotState::Empty;
+                            Err(err)
+                        }
+                    };
+                    slot.notify.notify_waiters();
+                    return result;
+                }
+            }
+        }
+    }
+
+    fn other_model_slots(&self, keep_key: &ModelCacheKey) -> Vec<(ModelCacheKey, ModelSlotHandle)> {
         let map = self.models.lock().expect("model cache lock poisoned");
         map.iter()
             .filter(|(key, _)| *key != keep_key)
-            .map(|(_, slot)| slot.clone())
+            .map(|(key, slot)| (key.clone(), slot.clone()))
             .collect()
     }
+
+    /// Slots holding a model. A slot that can't be locked is generating, which only
+    /// happens with its model loaded.
+    fn loaded_models(&self) -> Vec<LoadedModelInfo> {
+        let slots = {
+            let map = self.models.lock().expect("model cache lock poisoned");
+            map.iter()
+                .map(|(key, slot)| (key.clone(), slot.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut loaded = Vec::new();
+        for (key, slot) in slots {
+            let in_use = match slot.state.try_lock().as_deref() {
+                Ok(ModelSlotState::Loaded(_)) => false,
+                Ok(ModelSlotState::Empty | ModelSlotState::Loading) => continue,
+                Err(_) => true,
+            };
+            loaded.push(LoadedModelInfo {
+                size_bytes: estimated_model_bytes(&key.model_id).unwrap_or(0),
+                model_id: key.model_id,
+                backend_id: key.backend_id,
+                in_use,
+            });
+        }
+        loaded
+    }
+
+    fn memory_pressure(&self, keep_key: &ModelCacheKey, available_bytes: u64) -> MemoryPressure {
+        let mut pressure = MemoryPressure {
+            available_bytes,
+            ..Default::default()
+        };
+        for (key, slot) in self.other_model_slots(keep_key) {
+            match slot.state.try_lock().as_deref() {
+                // A pinned model won't be unloaded, so it frees nothing.
+                Ok(ModelSlotState::Loaded(_)) if is_model_pinned(&key.model_id) => {}
+                Ok(ModelSlotState::Loaded(_)) => {
+                    pressure.idle_loaded_bytes = pressure
+                        .idle_loaded_bytes
+                        .saturating_add(estimated_model_bytes(&key.model_id).unwrap_or(0));
+                }
+                Ok(ModelSlotState::Empty) => {}
+                Ok(ModelSlotState::Loading) | Err(_) => pressure.busy_slots += 1,
+            }
+        }
+        pressure
+    }
+
+    /// Wait until `required_bytes` can be freed without unloading a model that another
+    /// session is using, then take the cold-load lock. The lock is only held once the load
+    /// is admitted, so a queued request never blocks other cold loads. Re-polls
+    /// periodically in case a slot was only briefly locked, and gives up with `None` once
+    /// `is_cancelled` reports that nobody is waiting for the model any more.
+    async fn acquire_cold_load(
+        &self,
+        keep_key: &ModelCacheKey,
+        backend: &dyn LocalInferenceBackend,
+        required_bytes: u64,
+        mut on_queued: impl FnMut(),
+        is_cancelled: impl Fn() -> bool,
+    ) -> Option<tokio::sync::MutexGuard<'_, ()>> {
+        let mut queued = false;
+        loop {
+            if is_cancelled() {
+                return None;
+            }
+            let capacity_changed = self.capacity_changed.notified();
+            let pressure = self.memory_pressure(keep_key, backend.available_memory_bytes());
+            if admit_cold_load(&pressure, required_bytes) {
+                let Ok(guard) =
+                    tokio::time::timeout(CAPACITY_RECHECK_INTERVAL, self.cold_load_lock.lock())
+                        .await
+                else {
+                    continue;
+                };
+                // Another cold load may have taken the room while this one waited for the lock.
+                let pressure = self.memory_pressure(keep_key, backend.available_memory_bytes());
+                if admit_cold_load(&pressure, required_bytes) {
+                    return Some(guard);
+                }
+                continue;
+            }
+            if !queued {
+                queued = true;
+                on_queued();
+            }
+            let _ = tokio::time::timeout(CAPACITY_RECHECK_INTERVAL, capacity_changed).await;
+        }
+    }
+
+    /// Unload least recently used models until `required_bytes` fits. Slots that are
+    /// busy generating or loading are skipped instead of waited on. Fails without
+    /// unloading anything when pinned models leave too little room.
+    fn evict_lru_for(
+        &self,
+        keep_key: &ModelCacheKey,
+        available_bytes: u64,
+        required_bytes: u64,
+    ) -> Result<(), ProviderError> {
+        let slots = self.other_model_slots(keep_key);
+        let mut guards = Vec::new();
+        let mut candidates = Vec::new();
+        for (key, slot) in &slots {
+            let Ok(state) = slot.state.try_lock() else {
+                continue;
+            };
+            if matches!(*state, ModelSlotState::Loaded(_)) {
+                candidates.push(EvictionCandidate {
+                    size_bytes: estimated_model_bytes(&key.model_id).unwrap_or(0),
+                    last_used: slot.last_used(),
+                    pinned: is_model_pinned(&key.model_id),
+                });
+                guards.push((key, state));
+            }
+        }
+
+        let evictions = select_lru_evictions(&candidates, available_bytes, required_bytes);
+        if pinned_models_block_load(&candidates, &evictions, available_bytes, required_bytes) {
+            let pinned = guards
+                .iter()
+                .zip(&candidates)
+                .filter(|(_, candidate)| candidate.pinned)
+                .map(|((key, _), _)| key.model_id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(ProviderError::ExecutionError(format!(
+                "Not enough memory to load {}: pinned model(s) {} stay resident. \
+                 Unpin or evict one to make room.",
+                keep_key.model_id, pinned
+            )));
+        }
+
+        for index in evictions {
+            let (key, state) = &mut guards[index];
+            tracing::info!(model = %key.model_id, "Unloading least recently used model to free memory");
+            **state = ModelSlotState::Empty;
+        }
+        Ok(())
+    }
+
+    /// Unload models unused for `ttl`. A generating slot holds its state lock, so
+    /// `try_lock` skips it and a model is never unloaded mid-generation.
+    fn unload_idle_models(&self, now: Instant, ttl: Duration) {
+        let slots = {
+            let map = self.models.lock().expect("model cache lock poisoned");
+            map.iter()
+                .map(|(key, slot)| (key.clone(), slot.clone()))
+                .collect::<Vec<_>>()
+        };
+        let mut guards = Vec::new();
+        let mut last_used = Vec::new();
+        for (key, slot) in &slots {
+            let Ok(state) = slot.state.try_lock() else {
+                continue;
+            };
+            if matches!(*state, ModelSlotState::Loaded(_)) && !is_model_pinned(&key.model_id) {
+                last_used.push(slot.last_used());
+                guards.push((key, slot, state));
+            }
+        }
+
+        let mut unloaded = false;
+        for index in select_idle_evictions(&last_used, now, ttl) {
+            let (key, slot, state) = &mut guards[index];
+            tracing::info!(model = %key.model_id, idle_secs = ttl.as_secs(), "Unloading idle local model");
+            **state = ModelSlotState::Empty;
+            slot.notify.notify_waiters();
+            unloaded = true;
+        }
+        drop(guards);
+        if unloaded {
+            self.capacity_changed.notify_waiters();
+        }
+    }
 }
 
 pub async fn is_model_loaded(model_name: &str) -> Result<bool, ProviderError> {
@@ -189,27 +585,17 @@ pub async fn is_model_loaded(model_name: &str) -> Result<bool, ProviderError> {
 }
 
 pub async fn loaded_model_ids() -> Result<HashSet<String>, ProviderError> {
-    let Some(runtime) = current_runtime() else {
-        return Ok(HashSet::new());
-    };
-    let slots = {
-        let map = runtime.models.lock().expect("model cache lock poisoned");
-        map.iter()
-            .map(|(key, slot)| (key.model_id.clone(), slot.clone()))
-            .collect::<Vec<_>>()
-    };
+    Ok(loaded_models()
+        .into_iter()
+        .map(|model| model.model_id)
+        .collect())
+}
 
-    let mut loaded = HashSet::new();
-    for (model_id, slot) in slots {
-        if let Ok(state) = slot.state.try_lock() {
-            if matches!(*state, ModelSlotState::Loaded(_)) {
-                loaded.insert(model_id);
-            }
-        } else {
-            loaded.insert(model_id);
-        }
-    }
-    Ok(loaded)
+/// Models currently held in memory by the running inference runtime, if any.
+pub fn loaded_models() -> Vec<LoadedModelInfo> {
+    current_runtime()
+        .map(|runtime| runtime.loaded_models())
+        .unwrap_or_default()
 }
 
 pub async fn evict_model(model_name: &str) -> Result<bool, ProviderError> {
@@ -249,6 +635,7 @@ pub(crate) struct ResolvedModelPaths {
     pub mmproj_path: Option<PathBuf>,
     pub backend_id: Option<String>,
     pub draft_model_path: Option<PathBuf>,
+    pub embedding_capable: bool,
 }
 
 fn resolve_model_local_path(model_id: &str) -> Option<PathBuf> {
@@ -270,9 +657,14 @@ fn resolve_model_path(model_id: &str) -> Option<ResolvedModelPaths> {
             let ctx = entry.settings.context_size.unwrap_or(0) as usize;
             let mut settings = entry.settings.clone();
             let defaults = default_settings_for_model(model_id);
-            settings.vision_capable = defaults.vision_capable;
-            settings.mmproj_size_bytes = entry.mmproj_size_bytes;
-            let mmproj_path = entry.mmproj_path.as_ref().filter(|p| p.exists()).cloned();
+            settings.vision_capable = defaults.vision_capable || settings.mmproj_path.is_some();
+            if settings.mmproj_path.is_none() {
+                settings.mmproj_size_bytes = entry.mmproj_size_bytes;
+            }
+            let mmproj_path = entry
+                .effective_mmproj_path()
+                .filter(|p| p.exists())
+                .map(PathBuf::from);
             let backend_id = entry
                 .backend_id
                 .clone()
@@ -294,6 +686,7 @@ fn resolve_model_path(model_id: &str) -> Option<ResolvedModelPaths> {
                 mmproj_path,
                 backend_id,
                 draft_model_path,
+                embedding_capable: entry.embedding_capable,
             });
         }
     }
@@ -301,38 +694,152 @@ fn resolve_model_path(model_id: &str) -> Option<ResolvedModelPaths> {
     None
 }
 
+/// Explain why a registered model's file is missing, distinguishing a download still in
+/// flight from one that never started.
+fn model_file_missing_error(
+    model_id: &str,
+    status: &local_model_registry::ModelDownloadStatus,
+) -> ProviderError {
+    match status {
+        local_model_registry::ModelDownloadStatus::Downloading {
+            progress_percent, ..
+        } => ProviderError::ExecutionError(format!(
+            "Model {} is still downloading ({:.0}% complete). Please wait for the download to finish.",
+            model_id, progress_percent
+        )),
+        _ => ProviderError::ExecutionError(format!(
+            "Model not downloaded: {}. Please download it from Settings > Local Inference.",
+            model_id
+        )),
+    }
+}
+
+pub(crate) fn missing_model_error(model_id: &str) -> ProviderError {
+    let status = local_model_registry::get_registry()
+        .lock()
+        .ok()
+        .and_then(|registry| {
+            registry
+                .get_model(model_id)
+                .map(|entry| entry.download_status())
+        })
+        .unwrap_or(local_model_registry::ModelDownloadStatus::NotDownloaded);
+    model_file_missing_error(model_id, &status)
+}
+
+fn check_model_downloaded(model_id: &str) -> Result<(), ProviderError> {
+    let resolved = resolve_model_path(model_id)
+        .ok_or_else(|| ProviderError::ExecutionError(format!("Model not found: {}", model_id)))?;
+    if !resolved.model_path.exists() {
+        return Err(ProviderError::ExecutionError(format!(
+            "Model file for {} is missing: {}",
+            model_id,
+            resolved.model_path.display()
+        )));
+    }
+    Ok(())
+}
+
 pub fn available_inference_memory_bytes(runtime: &InferenceRuntime) -> u64 {
     runtime.default_backend().available_memory_bytes()
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalModelRecommendation {
+    pub model_id: String,
+    pub available_memory_bytes: u64,
+    pub model_size_bytes: Option<u64>,
+    pub reason: String,
+}
+
 pub fn recommend_local_model(runtime: &InferenceRuntime) -> String {
-    use local_model_registry::{get_registry, is_featured_model, FEATURED_MODELS};
+    recommend_local_model_with_reason(runtime).model_id
+}
 
-    let available_memory = available_inference_memory_bytes(runtime);
+pub fn recommend_local_model_with_reason(runtime: &InferenceRuntime) -> LocalModelRecommendation {
+    choose_recommended_model(
+        &featured_model_sizes(),
+        available_inference_memory_bytes(runtime),
+    )
+}
 
-    if let Ok(registry) = get_registry().lock() {
-        let mut models: Vec<_> = registry
-            .list_models()
-            .iter()
-            .filter(|m| is_featured_model(&m.id) && m.size_bytes > 0)
-            .collect();
-        models.sort_by_key(|model| std::cmp::Reverse(model.size_bytes));
-
-        // Return largest that fits in available memory
-        for model in &models {
-            if available_memory >= model.size_bytes {
-                return model.id.clone();
-            }
-        }
+fn featured_model_sizes() -> Vec<(String, u64)> {
+    use local_model_registry::{get_registry, is_featured_model};
 
-        // If nothing fits, return smallest
-        if let Some(smallest) = models.last() {
-            return smallest.id.clone();
-        }
+    get_registry()
+        .lock()
+        .map(|registry| {
+            registry
+                .list_models()
+                .iter()
+                .filter(|m| is_featured_model(&m.id) && m.size_bytes > 0)
+                .map(|m| (m.id.clone(), m.size_bytes))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Default to the largest downloaded featured model that fits in `available_memory`
+/// (the smallest one when none fits), keeping the small fixed default on a fresh install.
+fn default_model_for(downloaded_featured: &[(String, u64)], available_memory: u64) -> String {
+    if downloaded_featured.is_empty() {
+        return DEFAULT_MODEL.to_string();
+    }
+    choose_recommended_model(downloaded_featured, available_memory).model_id
+}
+
+fn choose_recommended_model(
+    featured: &[(String, u64)],
+    available_memory: u64,
+) -> LocalModelRecommendation {
+    use local_model_registry::FEATURED_MODELS;
+
+    let mut models: Vec<&(String, u64)> = featured.iter().collect();
+    models.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    let recommendation =
+        |(model_id, size): &(String, u64), reason: String| LocalModelRecommendation {
+            model_id: model_id.clone(),
+            available_memory_bytes: available_memory,
+            model_size_bytes: Some(*size),
+            reason,
+        };
+
+    if let Some(model) = models
+        .iter()
+        .copied()
+        .find(|(_, size)| available_memory >= *size)
+    {
+        return recommendation(
+            model,
+            format!(
+                "largest featured model that fits in {}",
+                format_gigabytes(available_memory)
+            ),
+        );
     }
 
-    // Fallback to first featured model
-    FEATURED_MODELS[0].spec.to_string()
+    if let Some(smallest) = models.last().copied() {
+        return recommendation(
+            smallest,
+            format!(
+                "no featured model fits in {}; smallest featured model needs {}",
+                format_gigabytes(available_memory),
+                format_gigabytes(smallest.1)
+            ),
+        );
+    }
+
+    LocalModelRecommendation {
+        model_id: FEATURED_MODELS[0].spec.to_string(),
+        available_memory_bytes: available_memory,
+        model_size_bytes: None,
+        reason: "model sizes are unknown; defaulting to the first featured model".to_string(),
+    }
+}
+
+fn format_gigabytes(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
 }
 
 fn build_openai_messages_json(
@@ -374,6 +881,16 @@ fn build_openai_text_messages_json(
     serde_json::to_string(&arr).unwrap_or_else(|_| "[]".to_string())
 }
 
+/// Plain-text prompt for `raw_completion` models: the system prompt and each message's
+/// text, joined by blank lines, with no role markup.
+fn build_raw_completion_prompt(system: &str, messages: &[Message]) -> String {
+    std::iter::once(system.to_string())
+        .chain(messages.iter().map(extract_text_content))
+        .filter(|text| !text.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 fn convert_text_media_markers(messages: &mut [Value], marker: &str) {
     if marker.is_empty() {
         return;
@@ -559,7 +1076,7 @@ fn finalize_usage(
     path_label: &str,
     prompt_token_count: usize,
     output_token_count: i32,
-    extra_log_fields: Option<(&str, &str)>,
+    extra_log_fields: &[(&str, serde_json::Value)],
 ) -> ProviderUsage {
     let input_tokens = prompt_token_count as i32;
     let total_tokens = input_tokens + output_token_count;
@@ -573,8 +1090,8 @@ fn finalize_usage(
         "prompt_tokens": input_tokens,
         "output_tokens": output_token_count,
     });
-    if let Some((key, value)) = extra_log_fields {
-        log_json[key] = serde_json::json!(value);
+    for (key, value) in extra_log_fields {
+        log_json[*key] = value.clone();
     }
     let _ = log.write(&log_json, Some(&usage));
     ProviderUsage::new(model_name, usage)
@@ -603,14 +1120,20 @@ impl ProviderDescriptor for LocalInferenceProvider {
     where
         Self: Sized,
     {
-        use crate::local_model_registry::{get_registry, FEATURED_MODELS};
+        use crate::local_model_registry::{get_registry, is_featured_model, FEATURED_MODELS};
 
         let mut known_models: Vec<&str> = FEATURED_MODELS.iter().map(|m| m.spec).collect();
 
         // Add any registry models not already in the featured list
         let mut dynamic_models = Vec::new();
+        let mut downloaded_featured = Vec::new();
+        let mut vision = false;
         if let Ok(registry) = get_registry().lock() {
             for entry in registry.list_models() {
+                vision |= entry.has_vision();
+                if is_featured_model(&entry.id) && entry.is_downloaded() {
+                    downloaded_featured.push((entry.id.clone(), entry.file_size()));
+                }
                 if !known_models.contains(&entry.id.as_str()) {
                     dynamic_models.push(entry.id.clone());
                 }
@@ -619,15 +1142,24 @@ impl ProviderDescriptor for LocalInferenceProvider {
         let dynamic_refs: Vec<&str> = dynamic_models.iter().map(|s| s.as_str()).collect();
         known_models.extend(dynamic_refs);
 
+        // Listing providers must not start the inference runtime; the backend's memory
+        // figure only needs the device list.
+        let default_model =
+            default_model_for(&downloaded_featured, llamacpp::available_memory_bytes());
+
         ProviderMetadata::new(
             PROVIDER_NAME,
             "Local Inference",
             "Local inference using quantized GGUF models (llama.cpp)",
-            DEFAULT_MODEL,
+            &default_model,
             known_models,
             "https://github.com/utilityai/llama-cpp-rs",
             vec![],
         )
+        .with_embeddings()
+        .with_vision(vision)
+        // Models must be downloaded before they can run, so arbitrary names are never valid.
+        .without_unlisted_models()
     }
 }
 
@@ -651,12 +1183,67 @@ impl Provider for LocalInferenceProvider {
         Ok(all_models)
     }
 
+    /// Counts with the model's own tokenizer and chat template. Only available once the
+    /// model is loaded, so callers never trigger a cold load just to count.
+    async fn count_tokens(
+        &self,
+        model_config: &ModelConfig,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Option<usize> {
+        let resolved = resolve_model_path(&model_config.model_name)?;
+        let backend = self.runtime.backend_for_model(&resolved).ok()?;
+        let key = ModelCacheKey::new(
+            backend.id(),
+            model_config.model_name.clone(),
+            resolved.settings.chat_template.clone(),
+        );
+        let slot = self.runtime.model_slot(&key)?;
+        let mut state = slot.state.lock().await;
+        let ModelSlotState::Loaded(loaded) = &mut *state else {
+            return None;
+        };
+        backend.count_prompt_tokens(
+            loaded.as_mut(),
+            &model_config.model_name,
+            system,
+            messages,
+            tools,
+            &resolved.settings,
+        )
+    }
+
+    async fn health_check(&self, model_config: &ModelConfig) -> Result<(), ProviderError> {
+        check_model_downloaded(&model_config.model_name)
+    }
+
+    fn supports_tools(&self, model_config: &ModelConfig) -> bool {
+        resolve_model_path(&model_config.model_name)
+            .is_some_and(|resolved| resolved.settings.supports_native_tools())
+    }
+
     async fn stream(
         &self,
         model_config: &ModelConfig,
         system: &str,
         messages: &[Message],
         tools: &[Tool],
+    ) -> Result<MessageStream, ProviderError> {
+        self.stream_with_log(model_config, system, messages, tools, None)
+    }
+}
+
+impl LocalInferenceProvider {
+    /// `capture` replaces the installed request logger for this request, so callers can read
+    /// back what the backend logged (applied prompt, raw generated text).
+    fn stream_with_log(
+        &self,
+        model_config: &ModelConfig,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+        capture: Option<Box<dyn RequestLogHandle>>,
     ) -> Result<MessageStream, ProviderError> {
         let resolved = resolve_model_path(&model_config.model_name).ok_or_else(|| {
             ProviderError::ExecutionError(format!("Model not found: {}", model_config.model_name))
@@ -717,12 +1304,16 @@ impl Provider for LocalInferenceProvider {
         let (tx, mut rx) = tokio::sync::mpsc::channel::<
             Result<(Option<Message>, Option<ProviderUsage>), ProviderError>,
         >(32);
-        let mut log = start_log(model_config, &log_payload)?;
+        let mut log = match capture {
+            Some(capture) => Some(capture),
+            None => start_log(model_config, &log_payload)?,
+        };
 
         tokio::spawn(async move {
             let mut model_load_ms = None;
+            model_slot.touch();
 
-            // Ensure model is loaded — unload any other models first to free memory.
+            // Ensure model is loaded, unloading other models only when it doesn't fit beside them.
             loop {
                 let state = model_slot.state.lock().await;
                 match &*state {
@@ -735,7 +1326,29 @@ impl Provider for LocalInferenceProvider {
                     ModelSlotState::Empty => {
                         drop(state);
 
-                        let cold_load_guard = runtime.cold_load_lock.lock().await;
+                        // Unknown sizes fall back to unloading everything, as the
+                        // model may not fit alongside anything else.
+                        let required_bytes = estimated_model_bytes(&model_name).unwrap_or(u64::MAX);
+                        let Some(cold_load_guard) = runtime
+                            .acquire_cold_load(
+                                &cache_key,
+                                backend.as_ref(),
+                                required_bytes,
+                                || {
+                                    let queued_message = Message::assistant().with_system_notification(
+                                        SystemNotificationType::ProgressMessage,
+                                        format!(
+                                            "Waiting for another local model to finish before loading {model_name}..."
+                                        ),
+                                    );
+                                    let _ = tx.try_send(Ok((Some(queued_message), None)));
+                                },
+                                || tx.is_closed(),
+                            )
+                            .await
+                        else {
+                            return;
+                        };
                         let mut state = model_slot.state.lock().await;
                         match &*state {
                             ModelSlotState::Loaded(_) => break,
@@ -762,13 +1375,16 @@ impl Provider for LocalInferenceProvider {
                             return;
                         }
 
-                        let other_model_slots = runtime.other_model_slots(&cache_key);
-                        for slot in other_model_slots {
-                            let mut other = slot.state.lock().await;
-                            if matches!(*other, ModelSlotState::Loaded(_)) {
-                                tracing::info!("Unloading previous model to free memory");
-                                *other = ModelSlotState::Empty;
-                            }
+                        let available_bytes = backend.available_memory_bytes();
+                        if let Err(err) =
+                            runtime.evict_lru_for(&cache_key, available_bytes, required_bytes)
+                        {
+                            let mut state = model_slot.state.lock().await;
+                            *state = ModelSlotState::Empty;
+                            model_slot.notify.notify_waiters();
+                            let _ = log.error(&err);
+                            let _ = tx.send(Err(err)).await;
+                            return;
                         }
 
                         let model_id = model_name.clone();
@@ -832,7 +1448,7 @@ impl Provider for LocalInferenceProvider {
                 }
             }
 
-            tokio::task::spawn_blocking(move || {
+            let generation = tokio::task::spawn_blocking(move || {
                 // Macro to log errors before sending them through the channel
                 macro_rules! send_err {
                     ($err:expr) => {{
@@ -849,6 +1465,7 @@ impl Provider for LocalInferenceProvider {
                 }
 
                 let mut model_guard = model_arc.state.blocking_lock();
+                model_arc.touch();
                 let loaded = match &mut *model_guard {
                     ModelSlotState::Loaded(loaded) => loaded.as_mut(),
                     ModelSlotState::Empty | ModelSlotState::Loading => {
@@ -878,6 +1495,7 @@ impl Provider for LocalInferenceProvider {
                 };
 
                 let result = backend.generate(loaded, request);
+                model_arc.touch();
 
                 if let Err(err) = result {
                     let msg = match &err {
@@ -889,6 +1507,8 @@ impl Provider for LocalInferenceProvider {
                     let _ = tx.blocking_send(Err(err));
                 }
             });
+            let _ = generation.await;
+            runtime.capacity_changed.notify_waiters();
         });
 
         Ok(Box::pin(try_stream! {
@@ -901,9 +1521,610 @@ impl Provider for LocalInferenceProvider {
     }
 }
 
+/// Result of running a prompt once through a local model, for debugging templates and
+/// tool-call parsing.
+pub struct DebugGeneration {
+    pub applied_prompt: Option<String>,
+    pub raw_text: String,
+    pub messages: Vec<Message>,
+}
+
+#[derive(Clone, Default)]
+struct CapturedLog(Arc<StdMutex<Vec<String>>>);
+
+impl RequestLogHandle for CapturedLog {
+    fn write(&mut self, s: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0
+            .lock()
+            .expect("captured log lock poisoned")
+            .push(s.to_string());
+        Ok(())
+    }
+}
+
+impl CapturedLog {
+    fn last_data_field(&self, key: &str) -> Option<String> {
+        let lines = self.0.lock().expect("captured log lock poisoned");
+        lines.iter().rev().find_map(|line| {
+            let value: Value = serde_json::from_str(line).ok()?;
+            value.get("data")?.get(key)?.as_str().map(str::to_string)
+        })
+    }
+}
+
+/// Run a single generation to completion and return the raw model output next to the
+/// messages the backend parsed out of it.
+pub async fn debug_generate(
+    model_config: &ModelConfig,
+    system: &str,
+    messages: &[Message],
+    tools: &[Tool],
+) -> Result<DebugGeneration, ProviderError> {
+    let runtime = InferenceRuntime::get_or_init().map_err(|error| {
+        ProviderError::ExecutionError(format!("Failed to initialize local inference: {error}"))
+    })?;
+    let provider = LocalInferenceProvider {
+        runtime,
+        name: PROVIDER_NAME.to_string(),
+    };
+    let capture = CapturedLog::default();
+    let mut stream = provider.stream_with_log(
+        model_config,
+        system,
+        messages,
+        tools,
+        Some(Box::new(capture.clone())),
+    )?;
+
+    let mut parsed = Vec::new();
+    while let Some(item) = stream.next().await {
+        let (message, _usage) = item?;
+        let Some(message) = message else {
+            continue;
+        };
+        if message
+            .content
+            .iter()
+            .any(|content| matches!(content, MessageContent::SystemNotification(_)))
+        {
+            continue;
+        }
+        parsed.push(message);
+    }
+
+    Ok(DebugGeneration {
+        applied_prompt: capture.last_data_field("applied_prompt"),
+        raw_text: capture
+            .last_data_field("generated_text")
+            .unwrap_or_default(),
+        messages: parsed,
+    })
+}
+
+/// One item of a local model's message stream, as `stream` yielded it.
+pub struct StreamTestEvent {
+    pub message: Option<Message>,
+    pub usage: Option<ProviderUsage>,
+    /// Time from the start of the request until the stream yielded this item.
+    pub elapsed: std::time::Duration,
+}
+
+/// Drive a prompt through the provider's streaming path and hand each item to `on_event` as
+/// the stream yields it, so callers can check chunking, tool-call parsing and the final usage.
+/// Returning `false` from `on_event` stops the run; dropping the stream cancels generation.
+pub async fn stream_test(
+    model_config: &ModelConfig,
+    system: &str,
+    messages: &[Message],
+    tools: &[Tool],
+    mut on_event: impl FnMut(StreamTestEvent) -> bool,
+) -> Result<(), ProviderError> {
+    let runtime = InferenceRuntime::get_or_init().map_err(|error| {
+        ProviderError::ExecutionError(format!("Failed to initialize local inference: {error}"))
+    })?;
+    let provider = LocalInferenceProvider {
+        runtime,
+        name: PROVIDER_NAME.to_string(),
+    };
+    let started = std::time::Instant::now();
+    let mut stream = provider
+        .stream(model_config, system, messages, tools)
+        .await?;
+
+    while let Some(item) = stream.next().await {
+        let (message, usage) = item?;
+        let message = message.filter(|message| {
+            !message
+                .content
+                .iter()
+                .any(|content| matches!(content, MessageContent::SystemNotification(_)))
+        });
+        if message.is_none() && usage.is_none() {
+            continue;
+        }
+        let event = StreamTestEvent {
+            message,
+            usage,
+            elapsed: started.elapsed(),
+        };
+        if !on_event(event) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Prompt size of a request as a loaded model would render it, next to the context window
+/// the request would get.
+pub struct PromptTokenCount {
+    pub token_count: usize,
+    pub context_limit: usize,
+}
+
+/// Count a request's prompt with the model's own chat template and tokenizer. The model
+/// must already be loaded; counting never triggers a cold load.
+pub async fn count_prompt_tokens(
+    model_config: &ModelConfig,
+    system: &str,
+    messages: &[Message],
+    tools: &[Tool],
+) -> Result<PromptTokenCount, ProviderError> {
+    let runtime = InferenceRuntime::get_or_init().map_err(|error| {
+        ProviderError::ExecutionError(format!("Failed to initialize local inference: {error}"))
+    })?;
+    let model_name = &model_config.model_name;
+    let resolved = resolve_model_path(model_name)
+        .ok_or_else(|| ProviderError::ExecutionError(format!("Model not found: {model_name}")))?;
+    let backend = runtime.backend_for_model(&resolved)?;
+    let key = ModelCacheKey::new(
+        backend.id(),
+        model_name.clone(),
+        resolved.settings.chat_template.clone(),
+    );
+    let not_loaded = || ProviderError::ExecutionError(format!("Model {model_name} is not loaded"));
+    let slot = runtime.model_slot(&key).ok_or_else(not_loaded)?;
+    let mut state = slot.state.lock().await;
+    let ModelSlotState::Loaded(loaded) = &mut *state else {
+        return Err(not_loaded());
+    };
+
+    let token_count = backend
+        .count_prompt_tokens(
+            loaded.as_mut(),
+            model_name,
+            system,
+            messages,
+            tools,
+            &resolved.settings,
+        )
+        .ok_or_else(|| {
+            ProviderError::ExecutionError(format!(
+                "Failed to render and tokenize the prompt for {model_name}"
+            ))
+        })?;
+    let context_limit = backend
+        .context_cap(loaded.as_mut(), resolved.context_limit, &resolved.settings)
+        .unwrap_or(resolved.context_limit);
+    Ok(PromptTokenCount {
+        token_count,
+        context_limit,
+    })
+}
+
+#[async_trait]
+impl EmbeddingCapable for LocalInferenceProvider {
+    async fn create_embeddings(
+        &self,
+        model_name: &str,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, ProviderError> {
+        let resolved = resolve_model_path(model_name).ok_or_else(|| {
+            ProviderError::ExecutionError(format!("Model not found: {}", model_name))
+        })?;
+        if !resolved.embedding_capable {
+            return Err(ProviderError::ExecutionError(format!(
+                "Model {} is not marked as an embedding model",
+                model_name
+            )));
+        }
+        let backend = self.runtime.backend_for_model(&resolved)?;
+        let key = ModelCacheKey::new(
+            backend.id(),
+            model_name,
+            resolved.settings.chat_template.clone(),
+        );
+        let slot = self.runtime.get_or_create_model_slot(key);
+        self.runtime
+            .ensure_loaded_alongside(&slot, &backend, model_name, &resolved)
+            .await?;
+
+        tokio::task::spawn_blocking(move || {
+            let mut state = slot.state.blocking_lock();
+            let ModelSlotState::Loaded(loaded) = &mut *state else {
+                return Err(ProviderError::ExecutionError(
+                    "Model not loaded".to_string(),
+                ));
+            };
+            backend.embed(loaded.as_mut(), &texts, &resolved.settings)
+        })
+        .await
+        .map_err(|e| ProviderError::ExecutionError(e.to_string()))?
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    fn candidate(size_bytes: u64, age_secs: u64, now: Instant) -> EvictionCandidate {
+        EvictionCandidate {
+            size_bytes,
+            last_used: now - Duration::from_secs(age_secs),
+            pinned: false,
+        }
+    }
+
+    fn pinned_candidate(size_bytes: u64, age_secs: u64, now: Instant) -> EvictionCandidate {
+        EvictionCandidate {
+            pinned: true,
+            ..candidate(size_bytes, age_secs, now)
+        }
+    }
+
+    #[test]
+    fn lru_eviction_stops_once_free_memory_covers_the_model() {
+        let now = Instant::now();
+        let candidates = vec![
+            candidate(2_000, 5, now),
+            candidate(5_000, 30, now),
+            candidate(3_000, 10, now),
+        ];
+        // The oldest model alone frees enough, so the newer ones stay loaded.
+        assert_eq!(select_lru_evictions(&candidates, 1_000, 6_000), vec![1]);
+        assert_eq!(select_lru_evictions(&candidates, 1_000, 6_001), vec![1, 2]);
+    }
+
+    #[test]
+    fn lru_eviction_skips_when_model_already_fits() {
+        let now = Instant::now();
+        let candidates = vec![candidate(4_000, 10, now), candidate(2_000, 5, now)];
+        assert!(select_lru_evictions(&candidates, 8_000, 6_000).is_empty());
+    }
+
+    #[test]
+    fn lru_eviction_unloads_oldest_first_until_it_fits() {
+        let now = Instant::now();
+        let candidates = vec![
+            candidate(3_000, 5, now),
+            candidate(2_000, 30, now),
+            candidate(4_000, 10, now),
+        ];
+        assert_eq!(select_lru_evictions(&candidates, 1_000, 6_000), vec![1, 2]);
+    }
+
+    #[test]
+    fn lru_eviction_unloads_everything_when_required_size_unknown() {
+        let now = Instant::now();
+        let candidates = vec![candidate(3_000, 5, now), candidate(2_000, 30, now)];
+        assert_eq!(
+            select_lru_evictions(&candidates, 1_000, u64::MAX),
+            vec![1, 0]
+        );
+    }
+
+    #[test]
+    fn lru_eviction_never_unloads_pinned_models() {
+        let now = Instant::now();
+        let candidates = vec![
+            pinned_candidate(3_000, 60, now),
+            candidate(2_000, 30, now),
+            candidate(4_000, 10, now),
+        ];
+        assert_eq!(select_lru_evictions(&candidates, 1_000, 6_000), vec![1, 2]);
+        assert_eq!(
+            select_lru_evictions(&candidates, 1_000, u64::MAX),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn pinned_models_fail_a_load_that_cannot_fit_around_them() {
+        let now = Instant::now();
+        let candidates = vec![pinned_candidate(6_000, 60, now), candidate(2_000, 30, now)];
+
+        let evictions = select_lru_evictions(&candidates, 1_000, 4_000);
+        assert_eq!(evictions, vec![1]);
+        assert!(pinned_models_block_load(
+            &candidates,
+            &evictions,
+            1_000,
+            4_000
+        ));
+
+        let evictions = select_lru_evictions(&candidates, 1_000, 3_000);
+        assert!(!pinned_models_block_load(
+            &candidates,
+            &evictions,
+            1_000,
+            3_000
+        ));
+
+        // Unknown sizes still attempt the load rather than failing up front.
+        let evictions = select_lru_evictions(&candidates, 1_000, u64::MAX);
+        assert!(!pinned_models_block_load(
+            &candidates,
+            &evictions,
+            1_000,
+            u64::MAX
+        ));
+
+        let unpinned = vec![candidate(6_000, 60, now), candidate(2_000, 30, now)];
+        assert!(!pinned_models_block_load(&unpinned, &[0, 1], 1_000, 64_000));
+    }
+
+    #[test]
+    fn idle_eviction_selects_models_past_ttl() {
+        let now = Instant::now();
+        let last_used = vec![
+            now - Duration::from_secs(5 * 60),
+            now - Duration::from_secs(45 * 60),
+            now - Duration::from_secs(30 * 60),
+        ];
+        assert_eq!(
+            select_idle_evictions(&last_used, now, Duration::from_secs(30 * 60)),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn idle_eviction_keeps_models_used_after_check_started() {
+        let now = Instant::now();
+        let last_used = vec![now + Duration::from_secs(1), now];
+        assert!(select_idle_evictions(&last_used, now, Duration::from_secs(60)).is_empty());
+    }
+
+    fn pressure(available_bytes: u64, idle_loaded_bytes: u64, busy_slots: usize) -> MemoryPressure {
+        MemoryPressure {
+            available_bytes,
+            idle_loaded_bytes,
+            busy_slots,
+        }
+    }
+
+    #[test]
+    fn admission_allows_load_that_fits_beside_busy_models() {
+        assert!(admit_cold_load(&pressure(8_000, 0, 1), 6_000));
+        assert!(admit_cold_load(&pressure(2_000, 4_000, 1), 6_000));
+    }
+
+    #[test]
+    fn admission_queues_when_only_busy_models_could_make_room() {
+        assert!(!admit_cold_load(&pressure(2_000, 3_000, 1), 6_000));
+        assert!(!admit_cold_load(&pressure(2_000, 0, 2), u64::MAX));
+    }
+
+    #[test]
+    fn admission_never_waits_when_nothing_is_busy() {
+        assert!(admit_cold_load(&pressure(0, 0, 0), 6_000));
+        assert!(admit_cold_load(&pressure(1_000, 2_000, 0), u64::MAX));
+    }
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    fn featured_sizes() -> Vec<(String, u64)> {
+        vec![
+            ("small".to_string(), GB),
+            ("large".to_string(), 9 * GB),
+            ("medium".to_string(), 4 * GB),
+        ]
+    }
+
+    #[test]
+    fn recommendation_picks_largest_model_that_fits() {
+        let recommendation = choose_recommended_model(&featured_sizes(), 10 * GB + GB / 5);
+        assert_eq!(recommendation.model_id, "large");
+        assert_eq!(recommendation.model_size_bytes, Some(9 * GB));
+        assert_eq!(
+            recommendation.reason,
+            "largest featured model that fits in 10.2 GB"
+        );
+    }
+
+    #[test]
+    fn recommendation_falls_back_to_smallest_when_nothing_fits() {
+        let recommendation = choose_recommended_model(&featured_sizes(), GB / 2);
+        assert_eq!(recommendation.model_id, "small");
+        assert_eq!(
+            recommendation.reason,
+            "no featured model fits in 0.5 GB; smallest featured model needs 1.0 GB"
+        );
+    }
+
+    #[test]
+    fn recommendation_without_known_sizes_uses_first_featured_model() {
+        let recommendation = choose_recommended_model(&[], 16 * GB);
+        assert_eq!(
+            recommendation.model_id,
+            local_model_registry::FEATURED_MODELS[0].spec
+        );
+        assert_eq!(recommendation.model_size_bytes, None);
+        assert_eq!(recommendation.available_memory_bytes, 16 * GB);
+    }
+
+    struct FixedMemoryBackend(u64);
+
+    impl LocalInferenceBackend for FixedMemoryBackend {
+        fn id(&self) -> &'static str {
+            LLAMACPP_BACKEND_ID
+        }
+
+        fn load_model(
+            &self,
+            _model_id: &str,
+            _resolved: &ResolvedModelPaths,
+            _settings: &local_model_registry::ModelSettings,
+        ) -> Result<Box<dyn BackendLoadedModel>, ProviderError> {
+            unimplemented!("not loaded in tests")
+        }
+
+        fn generate(
+            &self,
+            _loaded: &mut dyn BackendLoadedModel,
+            _request: backend::LocalGenerationRequest<'_>,
+        ) -> Result<(), ProviderError> {
+            unimplemented!("not generated in tests")
+        }
+
+        fn available_memory_bytes(&self) -> u64 {
+            self.0
+        }
+    }
+
+    fn runtime_with_memory(bytes: u64) -> InferenceRuntime {
+        let backend: Arc<dyn LocalInferenceBackend> = Arc::new(FixedMemoryBackend(bytes));
+        InferenceRuntime::with_backends(HashMap::from([(LLAMACPP_BACKEND_ID, backend)]))
+    }
+
+    struct StubLoadedModel;
+
+    impl BackendLoadedModel for StubLoadedModel {
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_queued_load_lets_another_load_proceed() {
+        let runtime = Arc::new(runtime_with_memory(2 * GB));
+        let backend = FixedMemoryBackend(2 * GB);
+        let key = |name: &str| {
+            ModelCacheKey::new(
+                LLAMACPP_BACKEND_ID,
+                format!("goose-test/{name}-GGUF:Q4_K_M"),
+                ChatTemplate::default(),
+            )
+        };
+        // A generating model holds its slot lock, so a load that doesn't fit beside it queues.
+        let busy = runtime.get_or_create_model_slot(key("busy"));
+        *busy.state.try_lock().unwrap() = ModelSlotState::Loaded(Box::new(StubLoadedModel));
+        let _generating = busy.state.try_lock().unwrap();
+
+        let queued = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let waiting = {
+            let runtime = runtime.clone();
+            let queued = queued.clone();
+            let cancelled = cancelled.clone();
+            tokio::spawn(async move {
+                runtime
+                    .acquire_cold_load(
+                        &key("large"),
+                        &FixedMemoryBackend(2 * GB),
+                        8 * GB,
+                        || queued.store(true, std::sync::atomic::Ordering::SeqCst),
+                        || cancelled.load(std::sync::atomic::Ordering::SeqCst),
+                    )
+                    .await
+                    .is_some()
+            })
+        };
+        while !queued.load(std::sync::atomic::Ordering::SeqCst) {
+            tokio::task::yield_now().await;
+        }
+
+        let guard = tokio::time::timeout(
+            Duration::from_secs(5),
+            runtime.acquire_cold_load(&key("small"), &backend, GB, || {}, || false),
+        )
+        .await
+        .expect("a queued load must not hold the cold-load lock");
+        assert!(guard.is_some());
+        drop(guard);
+
+        cancelled.store(true, std::sync::atomic::Ordering::SeqCst);
+        let admitted = tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("a cancelled load stops waiting")
+            .unwrap();
+        assert!(!admitted);
+    }
+
+    #[test]
+    fn loaded_models_reports_resident_slots() {
+        let runtime = runtime_with_memory(16 * GB);
+        assert!(runtime.loaded_models().is_empty());
+
+        let loaded_key = ModelCacheKey::new(
+            LLAMACPP_BACKEND_ID,
+            "goose-test/loaded-GGUF:Q4_K_M",
+            ChatTemplate::default(),
+        );
+        let empty_key = ModelCacheKey::new(
+            LLAMACPP_BACKEND_ID,
+            "goose-test/empty-GGUF:Q4_K_M",
+            ChatTemplate::default(),
+        );
+        let slot = runtime.get_or_create_model_slot(loaded_key);
+        *slot.state.try_lock().unwrap() = ModelSlotState::Loaded(Box::new(StubLoadedModel));
+        runtime.get_or_create_model_slot(empty_key);
+
+        let expected = LoadedModelInfo {
+            model_id: "goose-test/loaded-GGUF:Q4_K_M".to_string(),
+            backend_id: LLAMACPP_BACKEND_ID,
+            size_bytes: 0,
+            in_use: false,
+        };
+        assert_eq!(runtime.loaded_models(), vec![expected.clone()]);
+
+        let _generating = slot.state.try_lock().unwrap();
+        assert_eq!(
+            runtime.loaded_models(),
+            vec![LoadedModelInfo {
+                in_use: true,
+                ..expected
+            }]
+        );
+    }
+
+    #[test]
+    fn default_model_is_largest_downloaded_model_that_fits() {
+        assert_eq!(default_model_for(&featured_sizes(), 16 * GB), "large");
+        // The largest download doesn't fit, so the next one down is the default.
+        assert_eq!(default_model_for(&featured_sizes(), 6 * GB), "medium");
+        assert_eq!(default_model_for(&featured_sizes(), GB / 2), "small");
+        assert_eq!(
+            default_model_for(&[("medium".to_string(), 4 * GB)], 16 * GB),
+            "medium"
+        );
+        assert_eq!(default_model_for(&[], 16 * GB), DEFAULT_MODEL);
+    }
+
+    #[test]
+    fn missing_model_error_reports_download_in_progress() {
+        let downloading = local_model_registry::ModelDownloadStatus::Downloading {
+            progress_percent: 42.4,
+            bytes_downloaded: 424,
+            total_bytes: 1000,
+            speed_bps: 10,
+        };
+        let message = model_file_missing_error("org/repo:Q4_K_M", &downloading).to_string();
+        assert!(message.contains("still downloading (42% complete)"));
+        assert!(!message.contains("not downloaded"));
+
+        let message = model_file_missing_error(
+            "org/repo:Q4_K_M",
+            &local_model_registry::ModelDownloadStatus::NotDownloaded,
+        )
+        .to_string();
+        assert!(message.contains("Model not downloaded: org/repo:Q4_K_M"));
+    }
+
+    #[test]
+    fn health_check_rejects_unknown_model() {
+        let err = check_model_downloaded("goose-test/not-a-model-GGUF:Q4_K_M").unwrap_err();
+        assert!(err.to_string().contains("Model not found"));
+    }
 
     #[test]
     fn converts_marker_in_string_content_to_media_marker_part() {
@@ -947,4 +2168,22 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn raw_completion_prompt_has_no_chat_markup() {
+        let messages = vec![
+            Message::user().with_text("Once upon a time"),
+            Message::assistant().with_text(""),
+            Message::assistant().with_text("there was a goose"),
+        ];
+
+        assert_eq!(
+            build_raw_completion_prompt("A story.", &messages),
+            "A story.\n\nOnce upon a time\n\nthere was a goose"
+        );
+        assert_eq!(
+            build_raw_completion_prompt("", &messages[..1]),
+            "Once upon a time"
+        );
+    }
 }
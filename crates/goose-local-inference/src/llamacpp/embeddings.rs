@@ -0,0 +1,82 @@
+use goose_provider_types::errors::ProviderError;
+use llama_cpp_2::llama_batch::LlamaBatch;
+use llama_cpp_2::model::AddBos;
+
+use super::inference_engine::{build_context_params, LoadedModel};
+use super::LlamaCppBackend;
+use crate::local_model_registry::ModelSettings;
+
+/// Embedding inputs are short; avoid allocating for the full trained window of models
+/// that advertise very long contexts.
+const MAX_EMBEDDING_CONTEXT: u32 = 8192;
+
+/// Run the model in embedding mode and return one L2-normalized vector per text.
+///
+/// Pooling of the last-layer hidden states follows the GGUF's `pooling_type` metadata
+/// (mean for nomic-embed, CLS for bge, ...).
+pub(super) fn embed_texts(
+    loaded: &LoadedModel,
+    backend: &LlamaCppBackend,
+    texts: &[String],
+    settings: &ModelSettings,
+) -> Result<Vec<Vec<f32>>, ProviderError> {
+    let n_ctx = settings
+        .context_size
+        .unwrap_or_else(|| loaded.model.n_ctx_train())
+        .clamp(1, MAX_EMBEDDING_CONTEXT);
+    // Non-causal encoders must see a whole sequence in a single micro-batch.
+    let ctx_params = build_context_params(n_ctx, settings)
+        .with_n_batch(n_ctx)
+        .with_n_ubatch(n_ctx)
+        .with_embeddings(true);
+    let mut ctx = loaded
+        .model
+        .new_context(backend.llama_backend(), ctx_params)
+        .map_err(|e| ProviderError::ExecutionError(format!("Failed to create context: {}", e)))?;
+
+    let mut batch = LlamaBatch::new(n_ctx as usize, 1);
+    let mut embeddings = Vec::with_capacity(texts.len());
+    for text in texts {
+        let mut tokens = loaded
+            .model
+            .str_to_token(text, AddBos::Always)
+            .map_err(|e| ProviderError::ExecutionError(format!("Failed to tokenize: {}", e)))?;
+        tokens.truncate(n_ctx as usize);
+
+        batch.clear();
+        batch
+            .add_sequence(&tokens, 0, false)
+            .map_err(|e| ProviderError::ExecutionError(format!("Failed to create batch: {}", e)))?;
+        ctx.clear_kv_cache();
+        ctx.decode(&mut batch).map_err(|e| {
+            ProviderError::ExecutionError(format!("Embedding decode failed: {}", e))
+        })?;
+
+        let embedding = ctx.embeddings_seq_ith(0).map_err(|e| {
+            ProviderError::ExecutionError(format!("Model did not produce pooled embeddings: {}", e))
+        })?;
+        embeddings.push(normalize(embedding));
+    }
+
+    Ok(embeddings)
+}
+
+fn normalize(embedding: &[f32]) -> Vec<f32> {
+    let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return embedding.to_vec();
+    }
+    embedding.iter().map(|v| v / norm).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_produces_unit_vectors() {
+        let normalized = normalize(&[3.0, 4.0]);
+        assert_eq!(normalized, vec![0.6, 0.8]);
+        assert_eq!(normalize(&[0.0, 0.0]), vec![0.0, 0.0]);
+    }
+}
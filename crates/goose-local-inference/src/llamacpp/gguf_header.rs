@@ -0,0 +1,311 @@
+//! Minimal GGUF header reader, used to describe models llama.cpp refuses to load and to
+//! size models before they are loaded.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::hf_models::KvCacheShape;
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+const ARCHITECTURE_KEY: &str = "general.architecture";
+const CONTEXT_LENGTH_SUFFIX: &str = "context_length";
+/// Tokenizer vocabularies follow the model hyperparameters and can hold hundreds of
+/// thousands of entries, so the scan stops there.
+const TOKENIZER_KEY_PREFIX: &str = "tokenizer.";
+const GGUF_TYPE_U32: u32 = 4;
+const GGUF_TYPE_STRING: u32 = 8;
+const GGUF_TYPE_ARRAY: u32 = 9;
+const GGUF_TYPE_U64: u32 = 10;
+/// Converters write `general.architecture` first and the hyperparameters right after the
+/// `general.*` keys; bound the scan so a corrupt header never makes us walk the whole file.
+const MAX_KEYS_SCANNED: u64 = 64;
+const MAX_STRING_LEN: u64 = 1 << 20;
+
+#[derive(Debug, Default)]
+struct HeaderMetadata {
+    architecture: Option<String>,
+    integers: HashMap<String, u64>,
+}
+
+impl HeaderMetadata {
+    /// Look up a nonzero `{architecture}.{suffix}` hyperparameter.
+    fn arch_integer(&self, suffix: &str) -> Option<u64> {
+        let arch = self.architecture.as_deref()?;
+        self.integers
+            .get(&format!("{arch}.{suffix}"))
+            .copied()
+            .filter(|value| *value > 0)
+    }
+}
+
+/// Read `general.architecture` from a GGUF file without loading it through llama.cpp.
+pub(super) fn read_architecture(path: &Path) -> Option<String> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+    read_architecture_from(&mut reader)
+}
+
+/// Read the attention dimensions of a GGUF file without loading it through llama.cpp.
+pub(crate) fn read_kv_cache_shape(path: &Path) -> Option<KvCacheShape> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+    read_kv_cache_shape_from(&mut reader)
+}
+
+/// Read the context length a GGUF model was trained with (`{arch}.context_length`).
+pub(crate) fn read_context_length(path: &Path) -> Option<u64> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+    read_context_length_from(&mut reader)
+}
+
+fn read_architecture_from(reader: &mut impl Read) -> Option<String> {
+    read_metadata_from(reader, |metadata| metadata.architecture.is_some())?.architecture
+}
+
+fn read_kv_cache_shape_from(reader: &mut impl Read) -> Option<KvCacheShape> {
+    let metadata = read_metadata_from(reader, |_| false)?;
+    let get = |suffix: &str| metadata.arch_integer(suffix);
+
+    let n_layer = get("block_count")?;
+    let n_head = get("attention.head_count")?;
+    let head_dim = get("embedding_length")? / n_head;
+    // Per-layer `head_count_kv` arrays are skipped; assuming full heads overestimates
+    // the cache rather than underestimating it.
+    Some(KvCacheShape {
+        n_layer,
+        n_head_kv: get("attention.head_count_kv").unwrap_or(n_head),
+        k_per_head: get("attention.key_length").unwrap_or(head_dim),
+        v_per_head: get("attention.value_length").unwrap_or(head_dim),
+    })
+}
+
+fn read_context_length_from(reader: &mut impl Read) -> Option<u64> {
+    read_metadata_from(reader, |metadata| {
+        metadata.arch_integer(CONTEXT_LENGTH_SUFFIX).is_some()
+    })?
+    .arch_integer(CONTEXT_LENGTH_SUFFIX)
+}
+
+fn read_metadata_from(
+    reader: &mut impl Read,
+    done: impl Fn(&HeaderMetadata) -> bool,
+) -> Option<HeaderMetadata> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic).ok()?;
+    if &magic != GGUF_MAGIC {
+        return None;
+    }
+    // Version 1 used 32-bit counts and predates every model llama.cpp still loads.
+    if read_u32(reader)? < 2 {
+        return None;
+    }
+    let _tensor_count = read_u64(reader)?;
+    let kv_count = read_u64(reader)?;
+
+    let mut metadata = HeaderMetadata::default();
+    for _ in 0..kv_count.min(MAX_KEYS_SCANNED) {
+        if done(&metadata) {
+            break;
+        }
+        let key = read_string(reader)?;
+        if key.starts_with(TOKENIZER_KEY_PREFIX) {
+            break;
+        }
+        match read_u32(reader)? {
+            GGUF_TYPE_STRING if key == ARCHITECTURE_KEY => {
+                metadata.architecture = Some(read_string(reader)?);
+            }
+            GGUF_TYPE_U32 => {
+                metadata.integers.insert(key, read_u32(reader)? as u64);
+            }
+            GGUF_TYPE_U64 => {
+                metadata.integers.insert(key, read_u64(reader)?);
+            }
+            value_type => skip_value(reader, value_type)?,
+        }
+    }
+    Some(metadata)
+}
+
+fn skip_value(reader: &mut impl Read, value_type: u32) -> Option<()> {
+    match value_type {
+        GGUF_TYPE_STRING => {
+            read_string(reader)?;
+        }
+        GGUF_TYPE_ARRAY => {
+            let item_type = read_u32(reader)?;
+            let count = read_u64(reader)?;
+            match scalar_size(item_type) {
+                Some(size) => skip_bytes(reader, size.checked_mul(count)?)?,
+                None => {
+                    for _ in 0..count {
+                        skip_value(reader, item_type)?;
+                    }
+                }
+            }
+        }
+        other => skip_bytes(reader, scalar_size(other)?)?,
+    }
+    Some(())
+}
+
+fn scalar_size(value_type: u32) -> Option<u64> {
+    match value_type {
+        0 | 1 | 7 => Some(1),
+        2 | 3 => Some(2),
+        4..=6 => Some(4),
+        10..=12 => Some(8),
+        _ => None,
+    }
+}
+
+fn skip_bytes(reader: &mut impl Read, len: u64) -> Option<()> {
+    let skipped = std::io::copy(&mut reader.take(len), &mut std::io::sink()).ok()?;
+    (skipped == len).then_some(())
+}
+
+fn read_u32(reader: &mut impl Read) -> Option<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf).ok()?;
+    Some(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> Option<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf).ok()?;
+    Some(u64::from_le_bytes(buf))
+}
+
+fn read_string(reader: &mut impl Read) -> Option<String> {
+    let len = read_u64(reader)?;
+    if len > MAX_STRING_LEN {
+        return None;
+    }
+    let mut buf = vec![0u8; len as usize];
+    reader.read_exact(&mut buf).ok()?;
+    String::from_utf8(buf).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_string(buf: &mut Vec<u8>, value: &str) {
+        buf.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        buf.extend_from_slice(value.as_bytes());
+    }
+
+    fn header(kvs: impl FnOnce(&mut Vec<u8>), kv_count: u64) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(GGUF_MAGIC);
+        buf.extend_from_slice(&3u32.to_le_bytes());
+        buf.extend_from_slice(&0u64.to_le_bytes());
+        buf.extend_from_slice(&kv_count.to_le_bytes());
+        kvs(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn reads_architecture_after_other_keys() {
+        let bytes = header(
+            |buf| {
+                push_string(buf, "general.alignment");
+                buf.extend_from_slice(&4u32.to_le_bytes());
+                buf.extend_from_slice(&32u32.to_le_bytes());
+
+                push_string(buf, "general.tags");
+                buf.extend_from_slice(&GGUF_TYPE_ARRAY.to_le_bytes());
+                buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+                buf.extend_from_slice(&2u64.to_le_bytes());
+                push_string(buf, "text-generation");
+                push_string(buf, "chat");
+
+                push_string(buf, ARCHITECTURE_KEY);
+                buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+                push_string(buf, "futurenet");
+            },
+            3,
+        );
+
+        assert_eq!(
+            read_architecture_from(&mut bytes.as_slice()).as_deref(),
+            Some("futurenet")
+        );
+    }
+
+    #[test]
+    fn rejects_non_gguf_and_truncated_files() {
+        assert_eq!(
+            read_architecture_from(&mut b"GGML\x03\0\0\0".as_slice()),
+            None
+        );
+
+        let bytes = header(|buf| push_string(buf, ARCHITECTURE_KEY), 1);
+        assert_eq!(read_architecture_from(&mut bytes.as_slice()), None);
+    }
+
+    #[test]
+    fn reads_kv_cache_shape_from_hyperparameters() {
+        let push_u32 = |buf: &mut Vec<u8>, key: &str, value: u32| {
+            push_string(buf, key);
+            buf.extend_from_slice(&GGUF_TYPE_U32.to_le_bytes());
+            buf.extend_from_slice(&value.to_le_bytes());
+        };
+        let bytes = header(
+            |buf| {
+                push_string(buf, ARCHITECTURE_KEY);
+                buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+                push_string(buf, "llama");
+                push_u32(buf, "llama.block_count", 32);
+                push_u32(buf, "llama.embedding_length", 4096);
+                push_u32(buf, "llama.attention.head_count", 32);
+                push_u32(buf, "llama.attention.head_count_kv", 8);
+                push_u32(buf, "tokenizer.ggml.bos_token_id", 1);
+            },
+            6,
+        );
+
+        assert_eq!(
+            read_kv_cache_shape_from(&mut bytes.as_slice()),
+            Some(KvCacheShape {
+                n_layer: 32,
+                n_head_kv: 8,
+                k_per_head: 128,
+                v_per_head: 128,
+            })
+        );
+    }
+
+    #[test]
+    fn reads_context_length_for_the_architecture() {
+        let bytes = header(
+            |buf| {
+                push_string(buf, ARCHITECTURE_KEY);
+                buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+                push_string(buf, "qwen2");
+                push_string(buf, "llama.context_length");
+                buf.extend_from_slice(&GGUF_TYPE_U32.to_le_bytes());
+                buf.extend_from_slice(&4096u32.to_le_bytes());
+                push_string(buf, "qwen2.context_length");
+                buf.extend_from_slice(&GGUF_TYPE_U64.to_le_bytes());
+                buf.extend_from_slice(&32768u64.to_le_bytes());
+            },
+            3,
+        );
+
+        assert_eq!(read_context_length_from(&mut bytes.as_slice()), Some(32768));
+    }
+
+    #[test]
+    fn kv_cache_shape_needs_layer_and_head_counts() {
+        let bytes = header(
+            |buf| {
+                push_string(buf, ARCHITECTURE_KEY);
+                buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+                push_string(buf, "llama");
+            },
+            1,
+        );
+        assert_eq!(read_kv_cache_shape_from(&mut bytes.as_slice()), None);
+    }
+}
@@ -1,8 +1,9 @@
 //! Tool call emulation for models without native tool-calling support.
 //!
 //! The model is prompted to emit shell commands as `$ command` on a new line and
-//! code blocks as `` ```execute `` fenced blocks. A streaming parser detects these
-//! patterns and converts them into tool-call messages.
+//! code blocks as `` ```execute `` fenced blocks. Multi-line shell commands can be
+//! wrapped between `$$$` lines, which are captured verbatim as a single command. A
+//! streaming parser detects these patterns and converts them into tool-call messages.
 //!
 //! # Known false-positive scenarios
 //!
@@ -13,6 +14,11 @@
 //!   a shell command. Mid-sentence `$` (e.g. "costs $50") is safe — only `\n$` or
 //!   `$` at the very start of output triggers command detection.
 //!
+//! - **Top-level JSON objects in JSON mode.** With `emulator_json_mode` enabled, a
+//!   line starting with `{` is buffered until its braces balance. Objects shaped like
+//!   `{"tool": ..., "args": ...}` become tool calls; anything else is passed through as
+//!   text.
+//!
 //! - **`` ```execute `` in explanatory code fences.** If the model uses this exact
 //!   fence tag in prose, the content will be executed. Standard `` ```js `` or
 //!   `` ```python `` fences are not affected.
@@ -23,20 +29,35 @@
 use goose_provider_types::conversation::message::{Message, MessageContent};
 use goose_provider_types::errors::ProviderError;
 use rmcp::model::{CallToolRequestParams, Tool};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::borrow::Cow;
 use uuid::Uuid;
 
+use super::super::local_model_registry::DEFAULT_EMULATOR_MAX_CODE_CHARS;
+use super::super::tool_parsing::{canonical_tool_name, parse_lenient_json};
 use super::super::{finalize_usage, thinking_output::ThinkingOutputFilter, StreamSender};
 use super::inference_engine::{
     generation_loop, prepare_generation, GenerationContext, StopSuffixTrimmer, TokenAction,
+    UsageProgress,
 };
 
 const SHELL_TOOL: &str = "developer__shell";
 const CODE_EXECUTION_TOOL: &str = "code_execution__execute_typescript";
 
 const HOLD_BACK_CODE_MODE: usize = " ```execute_typescript\n".len();
+#[cfg(not(windows))]
 const HOLD_BACK_SHELL_ONLY: usize = "\n$".len();
+#[cfg(windows)]
+const HOLD_BACK_SHELL_ONLY: usize = "\nPS>".len();
+const CODE_FENCE: &str = "```";
+const EXECUTE_FENCE_INFO: &str = "execute_typescript";
+const COMMAND_FENCE: &str = "$$$";
+/// Line prefixes that start a shell command. Models prompted for PowerShell write commands
+/// the way its console echoes them. A bare `>` is not one: it opens a markdown blockquote.
+#[cfg(not(windows))]
+const COMMAND_PREFIXES: &[&str] = &["$"];
+#[cfg(windows)]
+const COMMAND_PREFIXES: &[&str] = &["$", "PS>"];
 
 pub(super) fn load_tiny_model_prompt() -> String {
     use std::env;
@@ -70,7 +91,11 @@ pub(super) fn load_tiny_model_prompt() -> String {
     })
 }
 
-pub(super) fn build_emulator_tool_description(tools: &[Tool], code_mode_enabled: bool) -> String {
+pub(super) fn build_emulator_tool_description(
+    tools: &[Tool],
+    code_mode_enabled: bool,
+    json_mode_enabled: bool,
+) -> String {
     let mut tool_desc = String::new();
 
     if code_mode_enabled {
@@ -123,6 +148,25 @@ pub(super) fn build_emulator_tool_description(tools: &[Tool], code_mode_enabled:
                 tool_desc.push_str(&format!("- {namespace}.{camel_name}(): {desc}\n"));
             }
         }
+    } else if json_mode_enabled {
+        tool_desc.push_str("\n\n# Tools\n\n");
+        tool_desc.push_str(
+            "To call a tool, write a single JSON object on its own line and nothing else \
+             on that line:\n\n",
+        );
+        tool_desc.push_str(
+            "{\"tool\": \"developer__shell\", \"args\": {\"command\": \"ls -1 /tmp\"}}\n\n",
+        );
+        tool_desc.push_str("You have access to the following tools:\n\n");
+        for tool in tools {
+            let desc = tool
+                .description
+                .as_ref()
+                .map(|d| d.as_ref())
+                .unwrap_or("No description");
+            let schema = serde_json::to_string(&tool.input_schema).unwrap_or_default();
+            tool_desc.push_str(&format!("- {}: {}\n  args: {}\n", tool.name, desc, schema));
+        }
     } else {
         tool_desc.push_str("\n\n# Tools\n\nYou have access to the following tools:\n\n");
         for tool in tools {
@@ -133,6 +177,10 @@ pub(super) fn build_emulator_tool_description(tools: &[Tool], code_mode_enabled:
                 .unwrap_or("No description");
             tool_desc.push_str(&format!("- {}: {}\n", tool.name, desc));
         }
+        tool_desc.push_str(
+            "\nFor a shell command that spans several lines, put it between two lines \
+             containing only $$$:\n\n$$$\nfor f in *.txt; do\n  wc -l \"$f\"\ndone\n$$$\n",
+        );
     }
 
     tool_desc
@@ -142,18 +190,30 @@ enum EmulatorAction {
     Text(String),
     ShellCommand(String),
     ExecuteCode(String),
+    ToolCall {
+        name: String,
+        arguments: serde_json::Map<String, Value>,
+    },
 }
 
 enum ParserState {
     Normal,
     InCommand,
     InExecuteBlock,
+    InJsonObject,
 }
 
 struct StreamingEmulatorParser {
     buffer: String,
     state: ParserState,
     code_mode_enabled: bool,
+    json_mode_enabled: bool,
+    /// Inside a prose ``` fence, where `$ ...` lines are examples rather than commands.
+    in_code_fence: bool,
+    /// Whitespace-only text held until real text follows, so it never becomes a blank bubble.
+    pending_whitespace: String,
+    /// Longest unterminated execute block `flush` still runs.
+    max_flush_code_chars: usize,
 }
 
 impl StreamingEmulatorParser {
@@ -162,9 +222,23 @@ impl StreamingEmulatorParser {
             buffer: String::new(),
             state: ParserState::Normal,
             code_mode_enabled,
+            json_mode_enabled: false,
+            in_code_fence: false,
+            pending_whitespace: String::new(),
+            max_flush_code_chars: DEFAULT_EMULATOR_MAX_CODE_CHARS,
         }
     }
 
+    fn with_json_mode(mut self, enabled: bool) -> Self {
+        self.json_mode_enabled = enabled;
+        self
+    }
+
+    fn with_max_flush_code_chars(mut self, max_chars: usize) -> Self {
+        self.max_flush_code_chars = max_chars;
+        self
+    }
+
     fn process_chunk(&mut self, chunk: &str) -> Vec<EmulatorAction> {
         self.buffer.push_str(chunk);
         let mut results = Vec::new();
@@ -172,8 +246,18 @@ impl StreamingEmulatorParser {
         loop {
             match self.state {
                 ParserState::InCommand => {
-                    if let Some((command_line, rest)) = self.buffer.split_once('\n') {
-                        if let Some(command) = command_line.strip_prefix('$') {
+                    if let Some(body) = fenced_command_body(&self.buffer) {
+                        let Some((command, rest)) = split_fenced_command(body) else {
+                            break;
+                        };
+                        let command = command.to_string();
+                        self.buffer = rest.to_string();
+                        self.state = ParserState::Normal;
+                        if !command.trim().is_empty() {
+                            results.push(EmulatorAction::ShellCommand(command));
+                        }
+                    } else if let Some((command_line, rest)) = self.buffer.split_once('\n') {
+                        if let Some(command) = strip_command_prefix(command_line) {
                             let command = command.trim();
                             if !command.is_empty() {
                                 results.push(EmulatorAction::ShellCommand(command.to_string()));
@@ -204,6 +288,21 @@ impl StreamingEmulatorParser {
                         break;
                     }
                 }
+                ParserState::InJsonObject => {
+                    if let Some(end) = json_object_end(&self.buffer) {
+                        let object: String = self.buffer.drain(..end).collect();
+                        let action = json_object_action(object);
+                        if matches!(action, EmulatorAction::ToolCall { .. })
+                            && self.buffer.starts_with('\n')
+                        {
+                            self.buffer.remove(0);
+                        }
+                        results.push(action);
+                        self.state = ParserState::Normal;
+                    } else {
+                        break;
+                    }
+                }
                 ParserState::Normal => {
                     // Check for ```execute block (code mode)
                     if self.code_mode_enabled {
@@ -229,15 +328,76 @@ impl StreamingEmulatorParser {
                         }
                     }
 
+                    if self.in_code_fence {
+                        if let Some(idx) = line_fence_start(&self.buffer) {
+                            let text: String =
+                                self.buffer.drain(..idx + CODE_FENCE.len()).collect();
+                            results.push(EmulatorAction::Text(text));
+                            self.in_code_fence = false;
+                            continue;
+                        }
+                        let text: String = self
+                            .buffer
+                            .drain(..partial_fence_start(&self.buffer))
+                            .collect();
+                        if !text.is_empty() {
+                            results.push(EmulatorAction::Text(text));
+                        }
+                        break;
+                    }
+
+                    let fence_start = line_fence_start(&self.buffer).filter(|fence_idx| {
+                        let dollar_idx = command_line_start(&self.buffer);
+                        let json_idx = self
+                            .json_mode_enabled
+                            .then(|| self.buffer.find("\n{"))
+                            .flatten();
+                        [dollar_idx, json_idx]
+                            .into_iter()
+                            .flatten()
+                            .all(|idx| *fence_idx < idx)
+                    });
+                    if let Some(idx) = fence_start {
+                        let info = self.buffer.get(idx + CODE_FENCE.len()..).unwrap_or("");
+                        if self.code_mode_enabled && EXECUTE_FENCE_INFO.starts_with(info) {
+                            // May still turn into an execute block once more text arrives.
+                            break;
+                        }
+                        let text: String = self.buffer.drain(..idx + CODE_FENCE.len()).collect();
+                        results.push(EmulatorAction::Text(text));
+                        self.in_code_fence = true;
+                        continue;
+                    }
+
+                    if self.json_mode_enabled {
+                        let json_start = self.buffer.find("\n{").filter(|json_idx| {
+                            command_line_start(&self.buffer)
+                                .is_none_or(|dollar_idx| *json_idx < dollar_idx)
+                        });
+                        if let Some(idx) = json_start {
+                            let text: String = self.buffer.drain(..=idx).collect();
+                            if !text.trim().is_empty() {
+                                results.push(EmulatorAction::Text(text));
+                            }
+                            self.state = ParserState::InJsonObject;
+                            continue;
+                        }
+                        if self.buffer.starts_with('{') && self.buffer.len() == chunk.len() {
+                            self.state = ParserState::InJsonObject;
+                            continue;
+                        }
+                    }
+
                     // Check for $ command
-                    if let Some((before_dollar, from_dollar)) = self.buffer.split_once("\n$") {
-                        let text = format!("{}\n", before_dollar);
+                    if let Some(idx) = command_line_start(&self.buffer) {
+                        let text: String = self.buffer.drain(..=idx).collect();
                         if !text.trim().is_empty() {
                             results.push(EmulatorAction::Text(text));
                         }
-                        self.buffer = format!("${}", from_dollar);
                         self.state = ParserState::InCommand;
-                    } else if self.buffer.starts_with('$') && self.buffer.len() == chunk.len() {
+                    } else if strip_command_prefix(&self.buffer).is_some()
+                        && self.buffer.len() == chunk.len()
+                    {
                         self.state = ParserState::InCommand;
                     } else {
                         let hold_back = if self.code_mode_enabled {
@@ -262,7 +422,7 @@ impl StreamingEmulatorParser {
             }
         }
 
-        results
+        self.suppress_blank_text(results)
     }
 
     fn flush(&mut self) -> Vec<EmulatorAction> {
@@ -270,9 +430,16 @@ impl StreamingEmulatorParser {
 
         if !self.buffer.is_empty() {
             match self.state {
+                ParserState::InCommand if fenced_command_body(&self.buffer).is_some() => {
+                    // Generation stopped before the closing `$$$`; run what was written.
+                    let command = fenced_command_body(&self.buffer).unwrap_or("").trim_end();
+                    if !command.trim().is_empty() {
+                        results.push(EmulatorAction::ShellCommand(command.to_string()));
+                    }
+                }
                 ParserState::InCommand => {
                     let command_line = self.buffer.trim();
-                    if let Some(command) = command_line.strip_prefix('$') {
+                    if let Some(command) = strip_command_prefix(command_line) {
                         let command = command.trim();
                         if !command.is_empty() {
                             results.push(EmulatorAction::ShellCommand(command.to_string()));
@@ -282,11 +449,26 @@ impl StreamingEmulatorParser {
                     }
                 }
                 ParserState::InExecuteBlock => {
+                    // A runaway generation must not be executed whole.
                     let code = self.buffer.trim();
-                    if !code.is_empty() {
+                    let code_chars = code.chars().count();
+                    if code_chars > self.max_flush_code_chars {
+                        tracing::warn!(
+                            code_chars,
+                            max_chars = self.max_flush_code_chars,
+                            "Unterminated execute block is too long to run, emitting it as text"
+                        );
+                        results.push(EmulatorAction::Text(format!(
+                            "{CODE_FENCE}{EXECUTE_FENCE_INFO}\n{}",
+                            self.buffer
+                        )));
+                    } else if !code.is_empty() {
                         results.push(EmulatorAction::ExecuteCode(code.to_string()));
                     }
                 }
+                ParserState::InJsonObject => {
+                    results.push(json_object_action(std::mem::take(&mut self.buffer)));
+                }
                 ParserState::Normal => {
                     results.push(EmulatorAction::Text(self.buffer.clone()));
                 }
@@ -294,14 +476,158 @@ impl StreamingEmulatorParser {
             self.buffer.clear();
             self.state = ParserState::Normal;
         }
+        self.in_code_fence = false;
 
+        let results = self.suppress_blank_text(results);
+        self.pending_whitespace.clear();
         results
     }
+
+    /// Drop whitespace-only `Text` actions, carrying their content into the next non-blank
+    /// text so paragraph breaks between chunks survive. Whitespace before a tool call or at
+    /// the end of output is discarded.
+    fn suppress_blank_text(&mut self, actions: Vec<EmulatorAction>) -> Vec<EmulatorAction> {
+        let mut kept = Vec::with_capacity(actions.len());
+        for action in actions {
+            match action {
+                EmulatorAction::Text(text) if text.trim().is_empty() => {
+                    self.pending_whitespace.push_str(&text);
+                }
+                EmulatorAction::Text(text) => {
+                    let mut joined = std::mem::take(&mut self.pending_whitespace);
+                    joined.push_str(&text);
+                    kept.push(EmulatorAction::Text(joined));
+                }
+                other => {
+                    self.pending_whitespace.clear();
+                    kept.push(other);
+                }
+            }
+        }
+        kept
+    }
+}
+
+fn strip_command_prefix(line: &str) -> Option<&str> {
+    COMMAND_PREFIXES
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+}
+
+/// Byte offset of the newline before the first line that starts a shell command.
+fn command_line_start(buffer: &str) -> Option<usize> {
+    COMMAND_PREFIXES
+        .iter()
+        .filter_map(|prefix| buffer.find(&format!("\n{prefix}")))
+        .min()
+}
+
+/// The text after an opening `$$$` line, if the pending command is fenced.
+fn fenced_command_body(buffer: &str) -> Option<&str> {
+    buffer.strip_prefix(COMMAND_FENCE)?.strip_prefix('\n')
+}
+
+/// Splits a fenced command body at its closing `$$$` line into the command and the
+/// text following the fence.
+fn split_fenced_command(body: &str) -> Option<(&str, &str)> {
+    let (command, rest) = if let Some(rest) = body.strip_prefix(COMMAND_FENCE) {
+        ("", rest)
+    } else {
+        let end = body.find(&format!("\n{COMMAND_FENCE}"))?;
+        (body.get(..end)?, body.get(end + 1 + COMMAND_FENCE.len()..)?)
+    };
+    Some((command, rest.strip_prefix('\n').unwrap_or(rest)))
+}
+
+/// Byte offset of the first ``` that opens a line.
+fn line_fence_start(buffer: &str) -> Option<usize> {
+    buffer
+        .match_indices(CODE_FENCE)
+        .map(|(idx, _)| idx)
+        .find(|&idx| {
+            idx == 0
+                || buffer
+                    .get(..idx)
+                    .is_some_and(|before| before.ends_with('\n'))
+        })
+}
+
+/// Where a trailing partial closing fence starts, so it is held back until complete.
+fn partial_fence_start(buffer: &str) -> usize {
+    buffer
+        .rfind('\n')
+        .filter(|&idx| {
+            buffer
+                .get(idx + 1..)
+                .is_some_and(|tail| CODE_FENCE.starts_with(tail))
+        })
+        .unwrap_or(buffer.len())
+}
+
+/// Byte length of the leading JSON object once its braces balance, ignoring braces
+/// inside string literals.
+fn json_object_end(buffer: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, ch) in buffer.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(idx + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn json_object_action(object: String) -> EmulatorAction {
+    match json_tool_call(object.trim()) {
+        Some((name, arguments)) => EmulatorAction::ToolCall { name, arguments },
+        None => EmulatorAction::Text(object),
+    }
+}
+
+/// Accepts the `{"tool", "args"}` shape the JSON-mode prompt asks for, plus the
+/// `{"name", "arguments"}` shape models trained on native tool calls tend to emit.
+/// Both the object and a string-encoded `arguments` are parsed leniently.
+fn json_tool_call(text: &str) -> Option<(String, serde_json::Map<String, Value>)> {
+    let value: Value = parse_lenient_json(text).ok()?;
+    let name = value
+        .get("tool")
+        .or_else(|| value.get("name"))?
+        .as_str()
+        .filter(|name| !name.is_empty())?;
+    let arguments = match value.get("args").or_else(|| value.get("arguments")) {
+        None | Some(Value::Null) => serde_json::Map::new(),
+        Some(Value::Object(arguments)) => arguments.clone(),
+        Some(Value::String(encoded)) => match parse_lenient_json(encoded).ok()? {
+            Value::Object(arguments) => arguments,
+            _ => return None,
+        },
+        Some(_) => return None,
+    };
+    Some((name.to_string(), arguments))
 }
 
 fn send_emulator_action(
     action: &EmulatorAction,
     message_id: &str,
+    tools: &[Tool],
     tx: &StreamSender,
 ) -> Result<bool, ()> {
     match action {
@@ -347,6 +673,20 @@ fn send_emulator_action(
                 .map_err(|_| ())?;
             Ok(true)
         }
+        EmulatorAction::ToolCall { name, arguments } => {
+            let tool_id = Uuid::new_v4().to_string();
+            let name = canonical_tool_name(name, tools).to_string();
+            let tool_call =
+                CallToolRequestParams::new(Cow::Owned(name)).with_arguments(arguments.clone());
+            let mut message = Message::assistant();
+            message
+                .content
+                .push(MessageContent::tool_request(tool_id, Ok(tool_call)));
+            message.id = Some(message_id.to_string());
+            tx.blocking_send(Ok((Some(message), None)))
+                .map_err(|_| ())?;
+            Ok(true)
+        }
     }
 }
 
@@ -360,33 +700,41 @@ pub(super) fn generate_with_emulated_tools(
     let mut llama_ctx = prepared.llama_ctx;
     let prompt_token_count = prepared.prompt_token_count;
     let effective_ctx = prepared.effective_ctx;
+    let n_keep = prepared.n_keep;
 
     let message_id = ctx.message_id;
+    let tools = ctx.tools;
     let tx = ctx.tx;
-    let mut emulator_parser = StreamingEmulatorParser::new(code_mode_enabled);
+    let mut emulator_parser = StreamingEmulatorParser::new(code_mode_enabled)
+        .with_json_mode(ctx.settings.emulator_json_mode)
+        .with_max_flush_code_chars(ctx.settings.max_emulated_code_chars());
     let mut output_filter = ThinkingOutputFilter::new(
         ctx.settings.enable_thinking,
         &template_result.generation_prompt,
     );
-    let mut stop_trimmer = StopSuffixTrimmer::new(&template_result.additional_stops);
+    let mut stop_trimmer =
+        StopSuffixTrimmer::for_generation(&template_result.additional_stops, ctx.settings);
     let mut generated_text = String::new();
     let mut tool_call_emitted = false;
     let mut send_failed = false;
     let mut stop_string_emitted = false;
 
-    let output_token_count = generation_loop(
+    let usage_progress = UsageProgress::for_settings(ctx.settings, ctx.tx, prompt_token_count);
+    let generation = generation_loop(
         &ctx.loaded.model,
         &mut llama_ctx,
         ctx.settings,
         prompt_token_count,
         effective_ctx,
+        n_keep,
+        usage_progress.as_ref(),
         |piece| {
             generated_text.push_str(piece);
             let filtered = output_filter.push_text(piece);
             let (content, stop_seen) = stop_trimmer.push(&filtered.content);
             let actions = emulator_parser.process_chunk(&content);
             for action in actions {
-                match send_emulator_action(&action, message_id, tx) {
+                match send_emulator_action(&action, message_id, tools, tx) {
                     Ok(is_tool) => {
                         if is_tool {
                             tool_call_emitted = true;
@@ -400,12 +748,7 @@ pub(super) fn generate_with_emulated_tools(
             }
             if tool_call_emitted {
                 Ok(TokenAction::Stop)
-            } else if stop_seen
-                || template_result
-                    .additional_stops
-                    .iter()
-                    .any(|stop| generated_text.ends_with(stop))
-            {
+            } else if stop_seen || stop_trimmer.ends_with_stop(&generated_text) {
                 stop_string_emitted = true;
                 Ok(TokenAction::Stop)
             } else {
@@ -433,7 +776,7 @@ pub(super) fn generate_with_emulated_tools(
                 content
             };
             for action in emulator_parser.process_chunk(&content) {
-                if send_emulator_action(&action, message_id, tx).is_err() {
+                if send_emulator_action(&action, message_id, tools, tx).is_err() {
                     send_failed = true;
                     break;
                 }
@@ -443,20 +786,23 @@ pub(super) fn generate_with_emulated_tools(
 
     if !send_failed {
         for action in emulator_parser.flush() {
-            if send_emulator_action(&action, message_id, tx).is_err() {
+            if send_emulator_action(&action, message_id, tools, tx).is_err() {
                 break;
             }
         }
     }
 
-    let provider_usage = finalize_usage(
+    generation.send_truncation_notice(ctx.tx);
+
+    let mut provider_usage = finalize_usage(
         ctx.log,
         std::mem::take(&mut ctx.model_name),
         "emulator",
         prompt_token_count,
-        output_token_count,
-        None,
+        generation.output_token_count,
+        &generation.log_fields(&generated_text),
     );
+    generation.attach_logprobs(&mut provider_usage);
     let _ = ctx.tx.blocking_send(Ok((None, Some(provider_usage))));
     Ok(())
 }
@@ -480,6 +826,26 @@ mod tests {
         parse_chunks(&[input], code_mode)
     }
 
+    fn parse_json_chunks(chunks: &[&str]) -> Vec<EmulatorAction> {
+        let mut parser = StreamingEmulatorParser::new(false).with_json_mode(true);
+        let mut actions = Vec::new();
+        for chunk in chunks {
+            actions.extend(parser.process_chunk(chunk));
+        }
+        actions.extend(parser.flush());
+        actions
+    }
+
+    fn assert_tool_call(action: &EmulatorAction, expected_name: &str, expected_args: Value) {
+        match action {
+            EmulatorAction::ToolCall { name, arguments } => {
+                assert_eq!(name, expected_name);
+                assert_eq!(Value::Object(arguments.clone()), expected_args);
+            }
+            other => panic!("expected ToolCall, got {:?}", action_label(other)),
+        }
+    }
+
     fn trim_chunks(chunks: &[&str], stops: &[String]) -> (String, bool) {
         let mut trimmer = StopSuffixTrimmer::new(stops);
         let mut output = String::new();
@@ -554,6 +920,7 @@ mod tests {
             EmulatorAction::Text(_) => "Text",
             EmulatorAction::ShellCommand(_) => "ShellCommand",
             EmulatorAction::ExecuteCode(_) => "ExecuteCode",
+            EmulatorAction::ToolCall { .. } => "ToolCall",
         }
     }
 
@@ -589,6 +956,31 @@ mod tests {
         assert_eq!(all_text.trim(), "Hello, world!");
     }
 
+    #[test]
+    fn whitespace_only_text_is_suppressed() {
+        let actions = parse_chunks(
+            &["Done.", "\n\n", "Next   step:", "\n", "$ ls\n", "\n\n"],
+            false,
+        );
+        for action in &actions {
+            if let EmulatorAction::Text(t) = action {
+                assert!(!t.trim().is_empty(), "whitespace-only text emitted: {t:?}");
+            }
+        }
+        let all_text: String = actions
+            .iter()
+            .filter_map(|a| match a {
+                EmulatorAction::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(
+            all_text.contains("Done.\n\nNext   step:"),
+            "got {all_text:?}"
+        );
+        assert_shell(&actions[actions.len() - 1], "ls");
+    }
+
     #[test]
     fn single_shell_command() {
         let actions = parse_all("$ ls -la\n", false);
@@ -669,6 +1061,40 @@ mod tests {
         assert_shell(shells[0], "ls -la");
     }
 
+    #[cfg(windows)]
+    #[test]
+    fn windows_prompt_prefixes_trigger_shell_commands() {
+        let actions = parse_chunks(&["Here:\n$ dir\n", "Then:\nPS> Get-ChildItem\n"], false);
+        let shells: Vec<_> = actions
+            .iter()
+            .filter(|a| matches!(a, EmulatorAction::ShellCommand(_)))
+            .collect();
+        assert_eq!(shells.len(), 2);
+        assert_shell(shells[0], "dir");
+        assert_shell(shells[1], "Get-ChildItem");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_prompt_prefix_at_start_of_output() {
+        let actions = parse_all("PS> whoami\n", false);
+        assert_eq!(actions.len(), 1);
+        assert_shell(&actions[0], "whoami");
+    }
+
+    #[test]
+    fn markdown_blockquote_stays_text() {
+        let actions = parse_chunks(&["As the docs put it:\n", "> dir lists files\n"], false);
+        let all_text: String = actions
+            .iter()
+            .map(|a| match a {
+                EmulatorAction::Text(t) => t.as_str(),
+                _ => panic!("expected only Text actions"),
+            })
+            .collect();
+        assert_eq!(all_text.trim(), "As the docs put it:\n> dir lists files");
+    }
+
     #[test]
     fn execute_fence_split_across_chunks() {
         let actions = parse_chunks(
@@ -709,6 +1135,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dollar_lines_inside_prose_code_fence_are_not_commands() {
+        let input = "Run this yourself:\n```bash\n$ ls\n```\nThen:\n$ pwd\n";
+        for actions in [
+            parse_all(input, false),
+            parse_chunks(
+                &input.split_inclusive(['\n', '`']).collect::<Vec<_>>(),
+                true,
+            ),
+        ] {
+            let shells: Vec<_> = actions
+                .iter()
+                .filter(|a| matches!(a, EmulatorAction::ShellCommand(_)))
+                .collect();
+            assert_eq!(shells.len(), 1);
+            assert_shell(shells[0], "pwd");
+
+            let text: String = actions
+                .iter()
+                .filter_map(|a| match a {
+                    EmulatorAction::Text(t) => Some(t.as_str()),
+                    _ => None,
+                })
+                .collect();
+            assert!(
+                text.contains("```bash\n$ ls\n```"),
+                "fence text lost: {text:?}"
+            );
+        }
+    }
+
     #[test]
     fn empty_command_ignored() {
         let actions = parse_all("$\n", false);
@@ -772,6 +1229,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fenced_command_captured_verbatim() {
+        let input = "Looping:\n$$$\nfor f in *.txt; do\n  wc -l \"$f\"\ndone\n$$$\nDone.";
+        let actions = parse_all(input, false);
+        let shells: Vec<_> = actions
+            .iter()
+            .filter(|a| matches!(a, EmulatorAction::ShellCommand(_)))
+            .collect();
+        assert_eq!(shells.len(), 1);
+        assert_shell(shells[0], "for f in *.txt; do\n  wc -l \"$f\"\ndone");
+        let text: String = actions
+            .iter()
+            .filter_map(|a| match a {
+                EmulatorAction::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Looping:\nDone.");
+    }
+
+    #[test]
+    fn fenced_command_split_across_chunks() {
+        let chunks = [
+            "$$",
+            "$\nfor f in *; do\n  echo $f\ndone\n$",
+            "$$\n",
+            "$ ls\n",
+        ];
+        let actions = parse_chunks(&chunks, false);
+        assert_eq!(actions.len(), 2);
+        assert_shell(&actions[0], "for f in *; do\n  echo $f\ndone");
+        assert_shell(&actions[1], "ls");
+    }
+
+    #[test]
+    fn fenced_command_token_by_token() {
+        let input = "Running it\n$$$\ncat <<EOF\n$HOME\nEOF\n$$$\n";
+        let chars: Vec<String> = input.chars().map(|c| c.to_string()).collect();
+        let chunks: Vec<&str> = chars.iter().map(|s| s.as_str()).collect();
+        let shells: Vec<_> = parse_chunks(&chunks, false)
+            .into_iter()
+            .filter(|a| matches!(a, EmulatorAction::ShellCommand(_)))
+            .collect();
+        assert_eq!(shells.len(), 1);
+        assert_shell(&shells[0], "cat <<EOF\n$HOME\nEOF");
+    }
+
+    #[test]
+    fn unclosed_fenced_command_flushed() {
+        let actions = parse_all("$$$\necho one\necho two\n", false);
+        assert_eq!(actions.len(), 1);
+        assert_shell(&actions[0], "echo one\necho two");
+    }
+
     #[test]
     fn unclosed_execute_block_flushed() {
         // Model stops generating mid-block
@@ -784,4 +1295,76 @@ mod tests {
         assert_eq!(executes.len(), 1);
         assert_execute(executes[0], "let x = 1;");
     }
+
+    #[test]
+    fn oversized_unclosed_execute_block_is_not_run() {
+        let code = "let x = 1;\n".repeat(10);
+        let mut parser = StreamingEmulatorParser::new(true).with_max_flush_code_chars(50);
+        let mut actions = parser.process_chunk(&format!("```execute_typescript\n{code}"));
+        actions.extend(parser.flush());
+
+        assert_eq!(actions.len(), 1);
+        assert_text(&actions[0], &format!("```execute_typescript\n{code}"));
+
+        // A closed block of the same size is still run.
+        let mut parser = StreamingEmulatorParser::new(true).with_max_flush_code_chars(50);
+        let mut actions = parser.process_chunk(&format!("```execute_typescript\n{code}```\n"));
+        actions.extend(parser.flush());
+        assert!(matches!(actions[..], [EmulatorAction::ExecuteCode(_)]));
+    }
+
+    #[test]
+    fn json_mode_line_is_tool_call() {
+        let actions = parse_json_chunks(&[
+            "Let me check.\n",
+            "{\"tool\": \"developer__shell\", \"args\": {\"command\": \"echo {hi}\"}}\n",
+        ]);
+        assert_eq!(actions.len(), 2);
+        assert_text(&actions[0], "Let me check.");
+        assert_tool_call(
+            &actions[1],
+            "developer__shell",
+            json!({"command": "echo {hi}"}),
+        );
+    }
+
+    #[test]
+    fn json_mode_object_split_across_chunks() {
+        let input = "{\"name\": \"todo__write\", \"arguments\": {\"items\": [\"a\", \"b\"]}}";
+        let chunks: Vec<String> = input.chars().map(|c| c.to_string()).collect();
+        let chunk_refs: Vec<&str> = chunks.iter().map(|s| s.as_str()).collect();
+        let actions = parse_json_chunks(&chunk_refs);
+        assert_eq!(actions.len(), 1);
+        assert_tool_call(&actions[0], "todo__write", json!({"items": ["a", "b"]}));
+    }
+
+    #[test]
+    fn json_mode_accepts_near_json_tool_calls() {
+        let actions = parse_json_chunks(&[
+            "{\"tool\": \"developer__shell\", \"args\": {\"command\": \"ls\",},}\n",
+            "{'name': 'todo__write', 'arguments': \"{'items': ['a', 'b',]}\"}\n",
+        ]);
+        assert_eq!(actions.len(), 2);
+        assert_tool_call(&actions[0], "developer__shell", json!({"command": "ls"}));
+        assert_tool_call(&actions[1], "todo__write", json!({"items": ["a", "b"]}));
+    }
+
+    #[test]
+    fn json_mode_non_tool_object_stays_text() {
+        let actions = parse_json_chunks(&["Config:\n{\"debug\": true}\nDone."]);
+        let text: String = actions
+            .iter()
+            .map(|a| match a {
+                EmulatorAction::Text(t) => t.as_str(),
+                other => panic!("expected Text, got {:?}", action_label(other)),
+            })
+            .collect();
+        assert_eq!(text, "Config:\n{\"debug\": true}\nDone.");
+    }
+
+    #[test]
+    fn json_object_ignored_without_json_mode() {
+        let actions = parse_all("{\"tool\": \"developer__shell\", \"args\": {}}", false);
+        assert!(actions.iter().all(|a| matches!(a, EmulatorAction::Text(_))));
+    }
 }
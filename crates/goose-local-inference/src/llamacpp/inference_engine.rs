@@ -1,6 +1,9 @@
 use crate::backend::LocalInferenceBackend;
+use crate::hf_models::KvCacheShape;
 use crate::local_model_registry::ModelSettings;
 use crate::multimodal::ExtractedImage;
+use goose_provider_types::conversation::message::{Message, SystemNotificationType};
+use goose_provider_types::conversation::token_usage::{ProviderUsage, TokenLogprob};
 use goose_provider_types::errors::ProviderError;
 use goose_provider_types::request_log::{LoggerHandleExt, RequestLogHandle};
 use llama_cpp_2::context::params::LlamaContextParams;
@@ -9,7 +12,10 @@ use llama_cpp_2::model::{AddBos, ChatTemplateResult, LlamaChatTemplate, LlamaMod
 use llama_cpp_2::mtmd::{MtmdBitmap, MtmdContext, MtmdInputText};
 use llama_cpp_2::openai::OpenAIChatTemplateParams;
 use llama_cpp_2::sampling::LlamaSampler;
+use llama_cpp_2::token::LlamaToken;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroU32;
+use std::sync::Mutex;
 
 use super::super::StreamSender;
 use super::LlamaCppBackend;
@@ -25,6 +31,7 @@ pub(super) struct GenerationContext<'a> {
     pub tx: &'a StreamSender,
     pub log: &'a mut Option<Box<dyn RequestLogHandle>>,
     pub images: &'a [ExtractedImage],
+    pub tools: &'a [rmcp::model::Tool],
 }
 
 pub(super) struct LoadedModel {
@@ -32,6 +39,96 @@ pub(super) struct LoadedModel {
     pub templates: LoadedChatTemplates,
     /// Multimodal context for vision models. None for text-only models.
     pub mtmd_ctx: Option<MtmdContext>,
+    pub system_tokens: SystemPromptTokenCache<LlamaToken>,
+}
+
+/// Tokens for the rendered system block of the most recent prompt. A session sends the
+/// same system prompt every turn, so the block is tokenized once and only the messages
+/// after it are tokenized per request.
+pub(super) struct SystemPromptTokenCache<T> {
+    entry: Mutex<Option<CachedSystemPrompt<T>>>,
+}
+
+struct CachedSystemPrompt<T> {
+    key: u64,
+    prefix: String,
+    tokens: Vec<T>,
+}
+
+impl<T> Default for SystemPromptTokenCache<T> {
+    fn default() -> Self {
+        Self {
+            entry: Mutex::new(None),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> SystemPromptTokenCache<T> {
+    /// Tokenize `prompt` as its system block followed by everything after it.
+    ///
+    /// On a miss the whole prompt is tokenized and `render_prefix` renders the system
+    /// block on its own. The block's tokens are only cached when they match the leading
+    /// tokens of the whole prompt, so a block whose last token would merge with the text
+    /// after it is never cached. Cached tokens are reused while the prompt still starts
+    /// with that exact rendering. `tokenize` is told whether its text starts the prompt,
+    /// which decides BOS.
+    pub(super) fn tokenize<E>(
+        &self,
+        key: u64,
+        prompt: &str,
+        render_prefix: impl FnOnce() -> Option<String>,
+        mut tokenize: impl FnMut(&str, bool) -> Result<Vec<T>, E>,
+    ) -> Result<Vec<T>, E> {
+        let mut entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let cached = entry
+            .as_ref()
+            .filter(|cached| cached.key == key && prompt.starts_with(&cached.prefix));
+        if let Some(cached) = cached {
+            let mut tokens = cached.tokens.clone();
+            tokens.extend(tokenize(
+                prompt.get(cached.prefix.len()..).unwrap_or_default(),
+                false,
+            )?);
+            return Ok(tokens);
+        }
+
+        let tokens = tokenize(prompt, true)?;
+        if let Some(prefix) =
+            render_prefix().filter(|prefix| !prefix.is_empty() && prompt.starts_with(prefix))
+        {
+            let prefix_tokens = tokenize(&prefix, true)?;
+            if tokens.starts_with(&prefix_tokens) {
+                *entry = Some(CachedSystemPrompt {
+                    key,
+                    prefix,
+                    tokens: prefix_tokens,
+                });
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Token count of the cached system block, if it was cached under `key`.
+    pub(super) fn prefix_len(&self, key: u64) -> Option<usize> {
+        let entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        entry
+            .as_ref()
+            .filter(|cached| cached.key == key)
+            .map(|cached| cached.tokens.len())
+    }
+}
+
+/// Identifies a rendered system block by everything fed to the template for it.
+fn system_prompt_key(system_json: &str, tools_json: Option<&str>, enable_thinking: bool) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (system_json, tools_json, enable_thinking).hash(&mut hasher);
+    hasher.finish()
 }
 
 pub(super) struct LoadedChatTemplates {
@@ -45,6 +142,8 @@ pub(super) struct PreparedGeneration<'model> {
     pub llama_ctx: llama_cpp_2::context::LlamaContext<'model>,
     pub prompt_token_count: usize,
     pub effective_ctx: usize,
+    /// Tokens kept at the front of the KV cache when context shifting is enabled.
+    pub n_keep: Option<usize>,
 }
 
 pub(super) struct StopSuffixTrimmer {
@@ -64,6 +163,25 @@ impl StopSuffixTrimmer {
         }
     }
 
+    /// Trimmer for one generation: the template's stops plus the user's `stop_sequences`.
+    pub(super) fn for_generation(template_stops: &[String], settings: &ModelSettings) -> Self {
+        let mut stops = template_stops.to_vec();
+        stops.extend(
+            settings
+                .stop_sequences
+                .iter()
+                .filter(|stop| !template_stops.contains(stop))
+                .cloned(),
+        );
+        Self::new(&stops)
+    }
+
+    /// Whether the accumulated output ends with any stop, including ones the trimmer
+    /// never saw because they were consumed as markup before reaching it.
+    pub(super) fn ends_with_stop(&self, text: &str) -> bool {
+        self.stops.iter().any(|stop| text.ends_with(stop.as_str()))
+    }
+
     pub(super) fn push(&mut self, chunk: &str) -> (String, bool) {
         if self.stops.is_empty() {
             return (chunk.to_string(), false);
@@ -123,11 +241,6 @@ pub(super) fn estimate_max_context_for_memory(
     }
     let available = raw_available.saturating_sub(mmproj_overhead_bytes);
 
-    // Reserve memory for computation scratch buffers (attention, etc.) and other overhead.
-    // The compute buffer can be 40-50% of the KV cache size for large models, so we
-    // conservatively use only half the available memory for the KV cache.
-    let usable = (available as f64 * 0.5) as u64;
-
     let n_layer = model.n_layer() as u64;
     let n_head_kv = model.n_head_kv() as u64;
     let n_head = model.n_head() as u64;
@@ -154,14 +267,31 @@ pub(super) fn estimate_max_context_for_memory(
         .and_then(|v| v.parse::<u64>().ok())
         .unwrap_or(head_dim);
 
-    // Total KV dimensions across all KV heads, times n_layer, times 2 bytes (f16) per element
-    let bytes_per_token = (k_per_head + v_per_head) * n_head_kv * n_layer * 2;
+    KvCacheShape {
+        n_layer,
+        n_head_kv,
+        k_per_head,
+        v_per_head,
+    }
+    .max_context_for_memory(available)
+}
 
-    if bytes_per_token == 0 {
+/// The memory-derived context ceiling, unless the model's settings opt out of it.
+pub(super) fn memory_context_ceiling(
+    settings: &ModelSettings,
+    estimate: impl FnOnce() -> Option<usize>,
+) -> Option<usize> {
+    if settings.ignore_memory_context_cap {
+        static WARNED: std::sync::Once = std::sync::Once::new();
+        WARNED.call_once(|| {
+            tracing::warn!(
+                "ignore_memory_context_cap is set: context is not capped to estimated free memory, \
+                 and an oversized KV cache can crash the process"
+            );
+        });
         return None;
     }
-
-    Some((usable / bytes_per_token) as usize)
+    estimate()
 }
 
 pub(super) fn context_cap(
@@ -193,6 +323,24 @@ pub(super) fn context_cap(
     }
 }
 
+/// An explicit `context_size` bypasses the memory cap in [`context_cap`], so reject one that
+/// can't fit before allocating: a KV cache that exceeds memory aborts the whole process.
+pub(super) fn check_configured_context_fits(
+    settings: &crate::local_model_registry::ModelSettings,
+    memory_max_ctx: Option<usize>,
+) -> Result<(), ProviderError> {
+    match (settings.context_size, memory_max_ctx) {
+        (Some(ctx_size), Some(mem_max)) if ctx_size as usize > mem_max => {
+            Err(ProviderError::ContextLengthExceeded(format!(
+                "Configured context size ({ctx_size} tokens) exceeds estimated memory capacity \
+                 ({mem_max} tokens). Lower the context size in this model's settings, or clear \
+                 it to size the context automatically."
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub(super) fn effective_context_size(
     prompt_token_count: usize,
     settings: &crate::local_model_registry::ModelSettings,
@@ -213,6 +361,88 @@ pub(super) fn effective_context_size(
     limit
 }
 
+/// Smallest window that must remain after the kept prefix for a context shift to make
+/// progress; below this the model would spend the whole window re-shifting.
+const MIN_CONTEXT_SHIFT_WINDOW: usize = 512;
+
+/// Tokens to keep when shifting: the rendered system prompt, or `None` if it leaves too
+/// little room for the shifted conversation.
+pub(super) fn context_shift_n_keep(
+    system_prompt_tokens: usize,
+    prompt_token_count: usize,
+    effective_ctx: usize,
+) -> Option<usize> {
+    let n_keep = system_prompt_tokens.min(prompt_token_count);
+    (effective_ctx.saturating_sub(n_keep) >= MIN_CONTEXT_SHIFT_WINDOW).then_some(n_keep)
+}
+
+/// Tokens to drop after the kept prefix, matching llama.cpp's default of half the
+/// shiftable region.
+pub(super) fn context_shift_discard(n_past: usize, n_keep: usize) -> usize {
+    n_past.saturating_sub(n_keep) / 2
+}
+
+/// Prompt to prefill when it doesn't fit the window: the kept prefix plus the most recent
+/// half of the shiftable region, like llama.cpp's server truncation. `None` if it fits.
+pub(super) fn trim_prompt_for_context_shift<T: Copy>(
+    tokens: &[T],
+    n_keep: usize,
+    effective_ctx: usize,
+) -> Option<Vec<T>> {
+    if tokens.len() < effective_ctx {
+        return None;
+    }
+    let tail = effective_ctx.saturating_sub(n_keep) / 2;
+    let mut trimmed = tokens[..n_keep].to_vec();
+    trimmed.extend_from_slice(&tokens[tokens.len() - tail..]);
+    Some(trimmed)
+}
+
+/// GGUF keys of the models whose KV cache llama.cpp can't shift (M-RoPE position
+/// sections, sliding-window attention, recurrent state); shifting those asserts.
+const UNSHIFTABLE_ARCH_KEYS: &[&str] = &[
+    "rope.dimension_sections",
+    "attention.sliding_window",
+    "ssm.conv_kernel",
+    "wkv.head_size",
+];
+
+/// llama-cpp-2 doesn't expose `llama_memory_can_shift`, so read the same signals from the
+/// model's metadata.
+fn supports_context_shift(model: &LlamaModel) -> bool {
+    let Ok(arch) = model.meta_val_str("general.architecture") else {
+        return false;
+    };
+    UNSHIFTABLE_ARCH_KEYS
+        .iter()
+        .all(|key| model.meta_val_str(&format!("{arch}.{key}")).is_err())
+}
+
+fn shift_context(
+    ctx: &mut llama_cpp_2::context::LlamaContext<'_>,
+    n_keep: usize,
+    n_past: usize,
+) -> Result<usize, ProviderError> {
+    let discard = context_shift_discard(n_past, n_keep);
+    let shift_error = |e: &dyn std::fmt::Display| {
+        ProviderError::ExecutionError(format!("Context shift failed: {e}"))
+    };
+    ctx.clear_kv_cache_seq(
+        Some(0),
+        Some(n_keep as u32),
+        Some((n_keep + discard) as u32),
+    )
+    .map_err(|e| shift_error(&e))?;
+    ctx.kv_cache_seq_add(
+        0,
+        Some((n_keep + discard) as u32),
+        Some(n_past as u32),
+        -(discard as i32),
+    )
+    .map_err(|e| shift_error(&e))?;
+    Ok(n_past - discard)
+}
+
 pub(super) fn build_context_params(
     ctx_size: u32,
     settings: &crate::local_model_registry::ModelSettings,
@@ -234,27 +464,78 @@ pub(super) fn build_context_params(
     params
 }
 
-pub(super) fn build_sampler(settings: &crate::local_model_registry::ModelSettings) -> LlamaSampler {
+/// One stage of the sampler chain, kept separate from `LlamaSampler` so the chain
+/// `build_sampler` assembles can be inspected.
+#[derive(Debug, Clone, PartialEq)]
+enum SamplerStage {
+    Penalties {
+        last_n: i32,
+        repeat: f32,
+        frequency: f32,
+        presence: f32,
+    },
+    Greedy,
+    TopK(i32),
+    TopP {
+        p: f32,
+        min_keep: usize,
+    },
+    MinP {
+        p: f32,
+        min_keep: usize,
+    },
+    Temp(f32),
+    Dist(u32),
+    MirostatV2 {
+        seed: u32,
+        tau: f32,
+        eta: f32,
+    },
+}
+
+impl SamplerStage {
+    fn into_sampler(self) -> LlamaSampler {
+        match self {
+            SamplerStage::Penalties {
+                last_n,
+                repeat,
+                frequency,
+                presence,
+            } => LlamaSampler::penalties(last_n, repeat, frequency, presence),
+            SamplerStage::Greedy => LlamaSampler::greedy(),
+            SamplerStage::TopK(k) => LlamaSampler::top_k(k),
+            SamplerStage::TopP { p, min_keep } => LlamaSampler::top_p(p, min_keep),
+            SamplerStage::MinP { p, min_keep } => LlamaSampler::min_p(p, min_keep),
+            SamplerStage::Temp(t) => LlamaSampler::temp(t),
+            SamplerStage::Dist(seed) => LlamaSampler::dist(seed),
+            SamplerStage::MirostatV2 { seed, tau, eta } => {
+                LlamaSampler::mirostat_v2(seed, tau, eta)
+            }
+        }
+    }
+}
+
+fn sampler_stages(settings: &ModelSettings) -> Vec<SamplerStage> {
     use crate::local_model_registry::SamplingConfig;
 
     let has_penalties = settings.repeat_penalty != 1.0
         || settings.frequency_penalty != 0.0
         || settings.presence_penalty != 0.0;
 
-    let mut samplers: Vec<LlamaSampler> = Vec::new();
+    let mut stages = Vec::new();
 
     if has_penalties {
-        samplers.push(LlamaSampler::penalties(
-            settings.repeat_last_n,
-            settings.repeat_penalty,
-            settings.frequency_penalty,
-            settings.presence_penalty,
-        ));
+        stages.push(SamplerStage::Penalties {
+            last_n: settings.repeat_last_n,
+            repeat: settings.repeat_penalty,
+            frequency: settings.frequency_penalty,
+            presence: settings.presence_penalty,
+        });
     }
 
     match &settings.sampling {
         SamplingConfig::Greedy => {
-            samplers.push(LlamaSampler::greedy());
+            stages.push(SamplerStage::Greedy);
         }
         SamplingConfig::Temperature {
             temperature,
@@ -262,18 +543,38 @@ pub(super) fn build_sampler(settings: &crate::local_model_registry::ModelSetting
             top_p,
             min_p,
             seed,
+            min_keep,
         } => {
-            samplers.push(LlamaSampler::top_k(*top_k));
-            samplers.push(LlamaSampler::top_p(*top_p, 1));
-            samplers.push(LlamaSampler::min_p(*min_p, 1));
-            samplers.push(LlamaSampler::temp(*temperature));
-            samplers.push(LlamaSampler::dist(seed.unwrap_or(0)));
+            stages.push(SamplerStage::TopK(*top_k));
+            stages.push(SamplerStage::TopP {
+                p: *top_p,
+                min_keep: *min_keep,
+            });
+            stages.push(SamplerStage::MinP {
+                p: *min_p,
+                min_keep: *min_keep,
+            });
+            stages.push(SamplerStage::Temp(*temperature));
+            stages.push(SamplerStage::Dist(seed.unwrap_or(0)));
         }
         SamplingConfig::MirostatV2 { tau, eta, seed } => {
-            samplers.push(LlamaSampler::mirostat_v2(seed.unwrap_or(0), *tau, *eta));
+            stages.push(SamplerStage::MirostatV2 {
+                seed: seed.unwrap_or(0),
+                tau: *tau,
+                eta: *eta,
+            });
         }
     }
 
+    stages
+}
+
+pub(super) fn build_sampler(settings: &ModelSettings) -> LlamaSampler {
+    let mut samplers: Vec<LlamaSampler> = sampler_stages(settings)
+        .into_iter()
+        .map(SamplerStage::into_sampler)
+        .collect();
+
     if samplers.len() == 1 {
         samplers.pop().unwrap()
     } else {
@@ -296,7 +597,10 @@ pub(super) fn validate_and_compute_context(
     } else {
         0
     };
-    let memory_max_ctx = estimate_max_context_for_memory(&loaded.model, backend, mmproj_overhead);
+    let memory_max_ctx = memory_context_ceiling(settings, || {
+        estimate_max_context_for_memory(&loaded.model, backend, mmproj_overhead)
+    });
+    check_configured_context_fits(settings, memory_max_ctx)?;
     let effective_ctx = effective_context_size(
         prompt_token_count,
         settings,
@@ -323,6 +627,30 @@ pub(super) fn validate_and_compute_context(
     Ok((prompt_token_count, effective_ctx))
 }
 
+/// Prompts shorter than this prefill quickly enough that progress isn't worth reporting.
+const PREFILL_PROGRESS_MIN_TOKENS: usize = 4096;
+
+/// Reports `(tokens_processed, total_tokens)` after each prefill chunk.
+pub(super) type PrefillProgress<'a> = &'a mut dyn FnMut(usize, usize);
+
+/// Feed `tokens` to `decode` in `n_batch`-sized chunks, reporting progress after each one.
+fn prefill_in_batches<T, E>(
+    tokens: &[T],
+    n_batch: usize,
+    mut decode: impl FnMut(&[T]) -> Result<(), E>,
+    mut on_progress: Option<PrefillProgress<'_>>,
+) -> Result<(), E> {
+    let mut processed = 0;
+    for chunk in tokens.chunks(n_batch.max(1)) {
+        decode(chunk)?;
+        processed += chunk.len();
+        if let Some(report) = on_progress.as_mut() {
+            report(processed, tokens.len());
+        }
+    }
+    Ok(())
+}
+
 /// Create a llama context and prefill (decode) all prompt tokens.
 pub(super) fn create_and_prefill_context<'model>(
     loaded: &'model LoadedModel,
@@ -330,6 +658,7 @@ pub(super) fn create_and_prefill_context<'model>(
     tokens: &[llama_cpp_2::token::LlamaToken],
     effective_ctx: usize,
     settings: &crate::local_model_registry::ModelSettings,
+    on_progress: Option<PrefillProgress<'_>>,
 ) -> Result<llama_cpp_2::context::LlamaContext<'model>, ProviderError> {
     let ctx_params = build_context_params(effective_ctx as u32, settings);
     let mut ctx = loaded
@@ -338,12 +667,18 @@ pub(super) fn create_and_prefill_context<'model>(
         .map_err(|e| ProviderError::ExecutionError(format!("Failed to create context: {}", e)))?;
 
     let n_batch = ctx.n_batch() as usize;
-    for chunk in tokens.chunks(n_batch) {
-        let mut batch = LlamaBatch::get_one(chunk)
-            .map_err(|e| ProviderError::ExecutionError(format!("Failed to create batch: {}", e)))?;
-        ctx.decode(&mut batch)
-            .map_err(|e| ProviderError::ExecutionError(format!("Prefill decode failed: {}", e)))?;
-    }
+    prefill_in_batches(
+        tokens,
+        n_batch,
+        |chunk| {
+            let mut batch = LlamaBatch::get_one(chunk).map_err(|e| {
+                ProviderError::ExecutionError(format!("Failed to create batch: {}", e))
+            })?;
+            ctx.decode(&mut batch)
+                .map_err(|e| ProviderError::ExecutionError(format!("Prefill decode failed: {}", e)))
+        },
+        on_progress,
+    )?;
 
     Ok(ctx)
 }
@@ -391,7 +726,10 @@ pub(super) fn create_and_prefill_multimodal<'model>(
 
     let n_ctx_train = loaded.model.n_ctx_train() as usize;
     let mmproj_overhead = settings.mmproj_size_bytes;
-    let memory_max_ctx = estimate_max_context_for_memory(&loaded.model, backend, mmproj_overhead);
+    let memory_max_ctx = memory_context_ceiling(settings, || {
+        estimate_max_context_for_memory(&loaded.model, backend, mmproj_overhead)
+    });
+    check_configured_context_fits(settings, memory_max_ctx)?;
     let effective_ctx = effective_context_size(
         prompt_token_count,
         settings,
@@ -422,15 +760,36 @@ pub(super) fn create_and_prefill_multimodal<'model>(
     Ok((llama_ctx, prompt_token_count, effective_ctx))
 }
 
+/// Prepend BOS only when the tokenizer asks for one and the rendered prompt doesn't
+/// already start with it. Most chat templates emit BOS themselves, and doubling it
+/// degrades output as much as omitting it.
+pub(super) fn bos_policy(prompt: &str, bos_text: Option<&str>, add_bos_token: bool) -> AddBos {
+    match bos_text {
+        Some(bos) if add_bos_token && !bos.is_empty() && !prompt.starts_with(bos) => AddBos::Always,
+        _ => AddBos::Never,
+    }
+}
+
+pub(super) fn prompt_bos_policy(model: &LlamaModel, prompt: &str) -> AddBos {
+    let add_bos_token = model
+        .meta_val_str("tokenizer.ggml.add_bos_token")
+        .is_ok_and(|value| value == "true");
+    let mut decoder = encoding_rs::UTF_8.new_decoder();
+    let bos_text = model
+        .token_to_piece(model.token_bos(), &mut decoder, true, None)
+        .ok();
+    bos_policy(prompt, bos_text.as_deref(), add_bos_token)
+}
+
 pub(super) fn prepare_generation<'model>(
     ctx: &mut GenerationContext<'model>,
     oai_messages_json: &str,
     full_tools_json: Option<&str>,
     compact_tools_json: Option<&str>,
 ) -> Result<PreparedGeneration<'model>, ProviderError> {
-    let apply_template = |tools: Option<&str>| {
+    let apply_template = |messages_json: &str, tools: Option<&str>, add_generation_prompt: bool| {
         let params = OpenAIChatTemplateParams {
-            messages_json: oai_messages_json,
+            messages_json,
             tools_json: tools,
             tool_choice: None,
             json_schema: None,
@@ -441,7 +800,7 @@ pub(super) fn prepare_generation<'model>(
                 None
             },
             chat_template_kwargs: None,
-            add_generation_prompt: true,
+            add_generation_prompt,
             use_jinja: true,
             parallel_tool_calls: false,
             enable_thinking: ctx.settings.enable_thinking,
@@ -461,24 +820,32 @@ pub(super) fn prepare_generation<'model>(
     } else {
         0
     };
-    let memory_max_ctx =
-        estimate_max_context_for_memory(&ctx.loaded.model, ctx.backend, mmproj_overhead);
+    let memory_max_ctx = memory_context_ceiling(ctx.settings, || {
+        estimate_max_context_for_memory(&ctx.loaded.model, ctx.backend, mmproj_overhead)
+    });
     let cap = context_cap(ctx.settings, ctx.context_limit, n_ctx_train, memory_max_ctx);
     let token_budget = cap.saturating_sub(min_generation_headroom);
     let estimated_image_tokens = ctx.images.len() * ctx.settings.image_token_estimate;
 
-    let template_result = match apply_template(full_tools_json) {
+    let (template_result, applied_tools_json) = match apply_template(
+        oai_messages_json,
+        full_tools_json,
+        true,
+    ) {
         Ok(r) => {
             let token_count = ctx
                 .loaded
                 .model
-                .str_to_token(&r.prompt, AddBos::Never)
+                .str_to_token(&r.prompt, prompt_bos_policy(&ctx.loaded.model, &r.prompt))
                 .map(|t| t.len())
                 .unwrap_or(0);
             if token_count + estimated_image_tokens > token_budget {
-                apply_template(compact_tools_json).unwrap_or(r)
+                match apply_template(oai_messages_json, compact_tools_json, true) {
+                    Ok(compact) => (compact, compact_tools_json),
+                    Err(_) => (r, full_tools_json),
+                }
             } else {
-                r
+                (r, full_tools_json)
             }
         }
         Err(e) => {
@@ -486,8 +853,8 @@ pub(super) fn prepare_generation<'model>(
                 error = %e,
                 "Failed to apply llama.cpp OpenAI-compatible chat template"
             );
-            match apply_template(compact_tools_json) {
-                Ok(r) => r,
+            match apply_template(oai_messages_json, compact_tools_json, true) {
+                Ok(r) => (r, compact_tools_json),
                 Err(compact_err) => {
                     return Err(ProviderError::ExecutionError(format!(
                         "Failed to apply chat template with llama.cpp's Jinja renderer. This usually means the selected built-in template name does not exist, the embedded or custom template is invalid, or the template is incompatible with the current message shape. Select a valid llama.cpp built-in template name, configure a custom inline Jinja template, or use a GGUF with valid tokenizer.chat_template metadata. Full tools error: {e}; compact tools error: {compact_err}"
@@ -502,21 +869,80 @@ pub(super) fn prepare_generation<'model>(
         None,
     );
 
-    let (llama_ctx, prompt_token_count, effective_ctx) = if !ctx.images.is_empty() {
-        create_and_prefill_multimodal(
+    let system_json = system_prompt_json(oai_messages_json);
+    let system_key = system_json
+        .as_deref()
+        .map(|json| system_prompt_key(json, applied_tools_json, ctx.settings.enable_thinking));
+
+    // Images are evaluated as opaque chunks, so only text prompts can be shifted.
+    let (llama_ctx, prompt_token_count, effective_ctx, n_keep) = if !ctx.images.is_empty() {
+        let (lctx, ptc, ectx) = create_and_prefill_multimodal(
             ctx.loaded,
             ctx.backend,
             &template_result.prompt,
             ctx.images,
             ctx.context_limit,
             ctx.settings,
-        )?
+        )?;
+        (lctx, ptc, ectx, None)
     } else {
-        let tokens = ctx
-            .loaded
-            .model
-            .str_to_token(&template_result.prompt, AddBos::Never)
-            .map_err(|e| ProviderError::ExecutionError(e.to_string()))?;
+        let bos_at_start = matches!(
+            prompt_bos_policy(&ctx.loaded.model, &template_result.prompt),
+            AddBos::Always
+        );
+        let model = &ctx.loaded.model;
+        let tokenize = |text: &str, starts_prompt: bool| {
+            let add_bos = if starts_prompt && bos_at_start {
+                AddBos::Always
+            } else {
+                AddBos::Never
+            };
+            model.str_to_token(text, add_bos)
+        };
+        let tokens = match (system_json.as_deref(), system_key) {
+            (Some(system_json), Some(key)) => ctx.loaded.system_tokens.tokenize(
+                key,
+                &template_result.prompt,
+                || {
+                    apply_template(system_json, applied_tools_json, false)
+                        .ok()
+                        .map(|rendered| rendered.prompt)
+                },
+                tokenize,
+            ),
+            _ => tokenize(&template_result.prompt, true),
+        }
+        .map_err(|e| ProviderError::ExecutionError(e.to_string()))?;
+        let n_keep = if ctx.settings.context_shift && supports_context_shift(&ctx.loaded.model) {
+            system_key
+                .and_then(|key| ctx.loaded.system_tokens.prefix_len(key))
+                .or_else(|| {
+                    let rendered =
+                        apply_template(system_json.as_deref()?, applied_tools_json, false).ok()?;
+                    model
+                        .str_to_token(&rendered.prompt, prompt_bos_policy(model, &rendered.prompt))
+                        .ok()
+                        .map(|tokens| tokens.len())
+                })
+                .and_then(|system_tokens| context_shift_n_keep(system_tokens, tokens.len(), cap))
+        } else {
+            None
+        };
+        // The window is `cap`; a prompt that already overflows it is shifted before prefill
+        // rather than rejected.
+        let tokens =
+            match n_keep.and_then(|n_keep| trim_prompt_for_context_shift(&tokens, n_keep, cap)) {
+                Some(trimmed) => {
+                    tracing::warn!(
+                        prompt_tokens = tokens.len(),
+                        kept_tokens = trimmed.len(),
+                        context = cap,
+                        "Prompt exceeds the context window; dropping its oldest tokens"
+                    );
+                    trimmed
+                }
+                None => tokens,
+            };
         let (ptc, ectx) = validate_and_compute_context(
             ctx.loaded,
             ctx.backend,
@@ -524,9 +950,26 @@ pub(super) fn prepare_generation<'model>(
             ctx.context_limit,
             ctx.settings,
         )?;
-        let lctx =
-            create_and_prefill_context(ctx.loaded, ctx.backend, &tokens, ectx, ctx.settings)?;
-        (lctx, ptc, ectx)
+        let tx = ctx.tx;
+        let mut report_progress = |processed: usize, total: usize| {
+            let message = Message::assistant().with_system_notification(
+                SystemNotificationType::ProgressMessage,
+                format!("Reading context... {processed}/{total} tokens"),
+            );
+            // Progress is best-effort; never stall the prefill on a full channel.
+            let _ = tx.try_send(Ok((Some(message), None)));
+        };
+        let on_progress = (tokens.len() >= PREFILL_PROGRESS_MIN_TOKENS)
+            .then_some(&mut report_progress as PrefillProgress<'_>);
+        let lctx = create_and_prefill_context(
+            ctx.loaded,
+            ctx.backend,
+            &tokens,
+            ectx,
+            ctx.settings,
+            on_progress,
+        )?;
+        (lctx, ptc, ectx, n_keep)
     };
 
     Ok(PreparedGeneration {
@@ -534,66 +977,316 @@ pub(super) fn prepare_generation<'model>(
         llama_ctx,
         prompt_token_count,
         effective_ctx,
+        n_keep,
     })
 }
 
+/// The leading system message on its own, rendered through the same template to measure
+/// the prefix a context shift must keep.
+fn system_prompt_json(oai_messages_json: &str) -> Option<String> {
+    let messages: Vec<serde_json::Value> = serde_json::from_str(oai_messages_json).ok()?;
+    let system = messages
+        .first()
+        .filter(|message| message.get("role").and_then(|role| role.as_str()) == Some("system"))?;
+    serde_json::to_string(&[system]).ok()
+}
+
 /// Action to take after processing a generated token piece.
 pub(super) enum TokenAction {
     Continue,
     Stop,
 }
 
+/// Records the chosen token's log-probability from the raw logits; only built when
+/// `return_logprobs` is set, since the softmax over the vocabulary runs every token.
+pub(super) struct LogprobCollector {
+    top_k: usize,
+    entries: Vec<TokenLogprob>,
+}
+
+impl LogprobCollector {
+    pub(super) fn for_settings(settings: &ModelSettings) -> Option<Self> {
+        settings.return_logprobs.then(|| Self {
+            top_k: settings.top_logprobs,
+            entries: Vec::new(),
+        })
+    }
+
+    pub(super) fn record(&mut self, logits: &[f32], token: i32, text: &str) {
+        let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let log_sum = logits.iter().map(|l| (l - max).exp()).sum::<f32>().ln() + max;
+        let logprob_of = |id: usize| logits.get(id).map_or(f32::NEG_INFINITY, |l| l - log_sum);
+
+        let mut top_logprobs = Vec::new();
+        let top_k = self.top_k.min(logits.len());
+        if top_k > 0 {
+            // Partition out the top k rather than sorting the whole vocabulary every token.
+            let by_logit_desc = |a: &usize, b: &usize| logits[*b].total_cmp(&logits[*a]);
+            let mut ranked: Vec<usize> = (0..logits.len()).collect();
+            ranked.select_nth_unstable_by(top_k - 1, by_logit_desc);
+            ranked.truncate(top_k);
+            ranked.sort_unstable_by(by_logit_desc);
+            top_logprobs = ranked
+                .into_iter()
+                .map(|id| (id as i32, logprob_of(id)))
+                .collect();
+        }
+
+        self.entries.push(TokenLogprob {
+            token,
+            text: text.to_string(),
+            logprob: logprob_of(token as usize),
+            top_logprobs,
+        });
+    }
+}
+
+pub(super) struct GenerationOutput {
+    pub output_token_count: i32,
+    pub logprobs: Option<Vec<TokenLogprob>>,
+    /// The loop ran out of its output budget instead of reaching an end-of-generation token.
+    pub truncated: bool,
+}
+
+impl GenerationOutput {
+    /// Fields added to the request log next to the usage.
+    pub(super) fn log_fields(
+        &self,
+        generated_text: &str,
+    ) -> Vec<(&'static str, serde_json::Value)> {
+        let mut fields = vec![("generated_text", serde_json::json!(generated_text))];
+        if let Some(logprobs) = &self.logprobs {
+            fields.push(("logprobs", serde_json::json!(logprobs)));
+        }
+        if self.truncated {
+            fields.push(("truncated", serde_json::json!(true)));
+        }
+        fields
+    }
+
+    /// Hand the collected logprobs to the caller on the usage stats.
+    pub(super) fn attach_logprobs(self, usage: &mut ProviderUsage) {
+        if let Some(logprobs) = self.logprobs {
+            usage.stats.get_or_insert_with(Default::default).logprobs = Some(logprobs);
+        }
+    }
+
+    /// Trailing notice for a reply cut off by the output budget, which otherwise just
+    /// stops mid-sentence.
+    pub(super) fn truncation_notice(&self) -> Option<Message> {
+        self.truncated.then(|| {
+            Message::assistant().with_system_notification(
+                SystemNotificationType::InlineMessage,
+                format!(
+                    "Output truncated after {} tokens: the reply hit its output limit. \
+                     Shorten the conversation or raise max_output_tokens for a complete reply.",
+                    self.output_token_count
+                ),
+            )
+        })
+    }
+
+    /// Send the truncation notice, if any, after the reply it belongs to.
+    pub(super) fn send_truncation_notice(&self, tx: &StreamSender) {
+        if let Some(notice) = self.truncation_notice() {
+            let _ = tx.blocking_send(Ok((Some(notice), None)));
+        }
+    }
+}
+
+/// Streams a live output-token count as a progress notification every
+/// `usage_update_interval` tokens. The updates carry no `ProviderUsage`, so the totals
+/// from `finalize_usage` at the end are never double counted.
+pub(super) struct UsageProgress<'a> {
+    tx: &'a StreamSender,
+    interval: usize,
+    prompt_token_count: usize,
+}
+
+impl<'a> UsageProgress<'a> {
+    pub(super) fn for_settings(
+        settings: &ModelSettings,
+        tx: &'a StreamSender,
+        prompt_token_count: usize,
+    ) -> Option<Self> {
+        settings
+            .usage_update_interval
+            .filter(|interval| *interval > 0)
+            .map(|interval| Self {
+                tx,
+                interval,
+                prompt_token_count,
+            })
+    }
+
+    fn record(&self, output_token_count: usize) {
+        if output_token_count % self.interval != 0 {
+            return;
+        }
+        let message = Message::assistant().with_system_notification_with_data(
+            SystemNotificationType::ProgressMessage,
+            format!("Generating... {output_token_count} tokens"),
+            serde_json::json!({
+                "input_tokens": self.prompt_token_count,
+                "output_tokens": output_token_count,
+            }),
+        );
+        // Best-effort like prefill progress; never stall generation on a full channel.
+        let _ = self.tx.try_send(Ok((Some(message), None)));
+    }
+}
+
+fn is_extra_eog_token(settings: &crate::local_model_registry::ModelSettings, token: i32) -> bool {
+    u32::try_from(token).is_ok_and(|id| settings.extra_eog_token_ids.contains(&id))
+}
+
+/// The model side of the generation loop: sampling, detokenizing and decoding each token.
+trait TokenSource {
+    fn sample(&mut self) -> LlamaToken;
+    fn is_eog(&self, token: LlamaToken) -> bool;
+    fn piece(&mut self, token: LlamaToken) -> Result<String, ProviderError>;
+    fn logits(&self) -> &[f32];
+    fn shift(&mut self, n_keep: usize, n_past: usize) -> Result<usize, ProviderError>;
+    fn decode(&mut self, token: LlamaToken) -> Result<(), ProviderError>;
+}
+
+struct LlamaTokenSource<'a, 'model> {
+    model: &'a LlamaModel,
+    ctx: &'a mut llama_cpp_2::context::LlamaContext<'model>,
+    sampler: LlamaSampler,
+    decoder: encoding_rs::Decoder,
+}
+
+impl TokenSource for LlamaTokenSource<'_, '_> {
+    fn sample(&mut self) -> LlamaToken {
+        let token = self.sampler.sample(self.ctx, -1);
+        self.sampler.accept(token);
+        token
+    }
+
+    fn is_eog(&self, token: LlamaToken) -> bool {
+        self.model.is_eog_token(token)
+    }
+
+    fn piece(&mut self, token: LlamaToken) -> Result<String, ProviderError> {
+        self.model
+            .token_to_piece(token, &mut self.decoder, true, None)
+            .map_err(|e| ProviderError::ExecutionError(format!("Failed to decode token: {}", e)))
+    }
+
+    fn logits(&self) -> &[f32] {
+        self.ctx.get_logits()
+    }
+
+    fn shift(&mut self, n_keep: usize, n_past: usize) -> Result<usize, ProviderError> {
+        shift_context(self.ctx, n_keep, n_past)
+    }
+
+    fn decode(&mut self, token: LlamaToken) -> Result<(), ProviderError> {
+        let next_tokens = [token];
+        let mut next_batch = LlamaBatch::get_one(&next_tokens)
+            .map_err(|e| ProviderError::ExecutionError(format!("Failed to create batch: {}", e)))?;
+        self.ctx
+            .decode(&mut next_batch)
+            .map_err(|e| ProviderError::ExecutionError(format!("Decode failed: {}", e)))
+    }
+}
+
 /// Run the autoregressive generation loop. Calls `on_piece` for each non-empty
 /// token piece. The callback returns `TokenAction::Stop` to break early.
-/// Returns the total number of generated tokens, or `ContextLengthExceeded`
-/// if the model exhausted the available context window.
+/// Returns the total number of generated tokens (plus per-token logprobs when
+/// `return_logprobs` is set), or `ContextLengthExceeded` if the model exhausted
+/// the available context window. Stopping at `max_output_tokens` instead marks the
+/// output as `truncated`.
+///
+/// With `n_keep` set, a full context is shifted instead: the oldest tokens after the
+/// first `n_keep` are dropped and generation continues. `usage_progress` reports the
+/// running token count as it grows.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn generation_loop(
     model: &LlamaModel,
     ctx: &mut llama_cpp_2::context::LlamaContext<'_>,
     settings: &crate::local_model_registry::ModelSettings,
     prompt_token_count: usize,
     effective_ctx: usize,
+    n_keep: Option<usize>,
+    usage_progress: Option<&UsageProgress<'_>>,
+    on_piece: impl FnMut(&str) -> Result<TokenAction, ProviderError>,
+) -> Result<GenerationOutput, ProviderError> {
+    let mut source = LlamaTokenSource {
+        model,
+        ctx,
+        sampler: build_sampler(settings),
+        decoder: encoding_rs::UTF_8.new_decoder(),
+    };
+    run_generation(
+        &mut source,
+        settings,
+        prompt_token_count,
+        effective_ctx,
+        n_keep,
+        usage_progress,
+        on_piece,
+    )
+}
+
+fn run_generation(
+    source: &mut impl TokenSource,
+    settings: &crate::local_model_registry::ModelSettings,
+    prompt_token_count: usize,
+    effective_ctx: usize,
+    n_keep: Option<usize>,
+    usage_progress: Option<&UsageProgress<'_>>,
     mut on_piece: impl FnMut(&str) -> Result<TokenAction, ProviderError>,
-) -> Result<i32, ProviderError> {
-    let mut sampler = build_sampler(settings);
+) -> Result<GenerationOutput, ProviderError> {
+    let mut logprobs = LogprobCollector::for_settings(settings);
     let context_headroom = effective_ctx.saturating_sub(prompt_token_count);
-    let max_output = if let Some(max) = settings.max_output_tokens {
-        context_headroom.min(max)
-    } else {
-        context_headroom
+    let max_output = match (n_keep, settings.max_output_tokens) {
+        (Some(_), Some(max)) => max,
+        // Shifting never runs out of room, so bound runaway output to one more window.
+        (Some(_), None) => effective_ctx,
+        (None, Some(max)) => context_headroom.min(max),
+        (None, None) => context_headroom,
     };
-    let hit_context_limit = settings
-        .max_output_tokens
-        .is_none_or(|max| context_headroom <= max);
-    let mut decoder = encoding_rs::UTF_8.new_decoder();
+    let hit_context_limit = n_keep.is_none()
+        && settings
+            .max_output_tokens
+            .is_none_or(|max| context_headroom <= max);
+    let mut n_past = prompt_token_count;
     let mut output_token_count: i32 = 0;
     let mut exhausted_loop = true;
 
     for _ in 0..max_output {
-        let token = sampler.sample(ctx, -1);
-        sampler.accept(token);
+        let token = source.sample();
 
-        if model.is_eog_token(token) {
+        if source.is_eog(token) || is_extra_eog_token(settings, token.0) {
             exhausted_loop = false;
             break;
         }
 
         output_token_count += 1;
+        if let Some(progress) = usage_progress {
+            progress.record(output_token_count as usize);
+        }
 
-        let piece = model
-            .token_to_piece(token, &mut decoder, true, None)
-            .map_err(|e| ProviderError::ExecutionError(format!("Failed to decode token: {}", e)))?;
+        let piece = source.piece(token)?;
+        if let Some(logprobs) = logprobs.as_mut() {
+            logprobs.record(source.logits(), token.0, &piece);
+        }
 
         if !piece.is_empty() && matches!(on_piece(&piece)?, TokenAction::Stop) {
             exhausted_loop = false;
             break;
         }
 
-        let next_tokens = [token];
-        let mut next_batch = LlamaBatch::get_one(&next_tokens)
-            .map_err(|e| ProviderError::ExecutionError(format!("Failed to create batch: {}", e)))?;
-        ctx.decode(&mut next_batch)
-            .map_err(|e| ProviderError::ExecutionError(format!("Decode failed: {}", e)))?;
+        if let Some(n_keep) = n_keep {
+            if n_past >= effective_ctx {
+                n_past = source.shift(n_keep, n_past)?;
+            }
+        }
+
+        source.decode(token)?;
+        n_past += 1;
     }
 
     if exhausted_loop && hit_context_limit {
@@ -606,13 +1299,380 @@ pub(super) fn generation_loop(
         )));
     }
 
-    Ok(output_token_count)
+    Ok(GenerationOutput {
+        output_token_count,
+        logprobs: logprobs.map(|collector| collector.entries),
+        truncated: exhausted_loop,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::local_model_registry::ModelSettings;
+    use crate::local_model_registry::{ModelSettings, SamplingConfig};
+
+    #[test]
+    fn system_prompt_cache_reuses_tokens_for_a_repeated_system_prompt() {
+        let cache = SystemPromptTokenCache::default();
+        let tokenize_words = |text: &str, starts_prompt: bool| {
+            let mut tokens: Vec<String> = starts_prompt
+                .then(|| "<s>".to_string())
+                .into_iter()
+                .collect();
+            tokens.extend(text.split_inclusive(' ').map(str::to_string));
+            Ok::<_, ()>(tokens)
+        };
+        let system = "<system>be brief </system> ";
+        let first = format!("{system}<user>hi </user> ");
+        let second = format!("{system}<user>hi </user> <assistant>hello </assistant> ");
+
+        let mut renders = 0;
+        let tokens = cache
+            .tokenize(
+                1,
+                &first,
+                || {
+                    renders += 1;
+                    Some(system.to_string())
+                },
+                tokenize_words,
+            )
+            .unwrap();
+        assert_eq!(tokens, tokenize_words(&first, true).unwrap());
+
+        let mut prefix_tokenized = false;
+        let tokens = cache
+            .tokenize(
+                1,
+                &second,
+                || {
+                    renders += 1;
+                    Some(system.to_string())
+                },
+                |text: &str, starts_prompt: bool| {
+                    prefix_tokenized |= starts_prompt;
+                    tokenize_words(text, starts_prompt)
+                },
+            )
+            .unwrap();
+        assert_eq!(tokens, tokenize_words(&second, true).unwrap());
+        assert_eq!(renders, 1);
+        assert!(!prefix_tokenized);
+        assert_eq!(cache.prefix_len(1), Some(4));
+        assert_eq!(cache.prefix_len(2), None);
+    }
+
+    #[test]
+    fn system_prompt_cache_retokenizes_when_the_prefix_no_longer_matches() {
+        let cache = SystemPromptTokenCache::default();
+        let tokenize_chars =
+            |text: &str, _starts_prompt: bool| Ok::<_, ()>(text.chars().collect::<Vec<_>>());
+
+        cache
+            .tokenize(1, "AB-rest", || Some("AB-".to_string()), tokenize_chars)
+            .unwrap();
+        let tokens = cache
+            .tokenize(1, "XY-rest", || None, tokenize_chars)
+            .unwrap();
+        assert_eq!(tokens.iter().collect::<String>(), "XY-rest");
+
+        let tokens = cache
+            .tokenize(1, "XY-more", || Some("XY-".to_string()), tokenize_chars)
+            .unwrap();
+        assert_eq!(tokens.iter().collect::<String>(), "XY-more");
+        assert_eq!(cache.prefix_len(1), Some(3));
+    }
+
+    #[test]
+    fn system_prompt_cache_skips_a_prefix_that_merges_across_the_boundary() {
+        let bpe = tiktoken_rs::o200k_base().unwrap();
+        let tokenize =
+            |text: &str, _starts_prompt: bool| Ok::<_, ()>(bpe.encode_with_special_tokens(text));
+        let cache = SystemPromptTokenCache::default();
+
+        let prompt = "You are a helpful assistant. Say hello world";
+        let tokens = cache
+            .tokenize(
+                1,
+                prompt,
+                || Some("You are a helpful assistant. Say hello wor".to_string()),
+                tokenize,
+            )
+            .unwrap();
+        assert_eq!(tokens, bpe.encode_with_special_tokens(prompt));
+        assert_eq!(cache.prefix_len(1), None);
+
+        let system = "You are a helpful assistant.<|endoftext|>";
+        let first = format!("{system}Say hello world");
+        let second = format!("{system}Say hello world, then goodbye");
+        for prompt in [&first, &second] {
+            let tokens = cache
+                .tokenize(1, prompt, || Some(system.to_string()), tokenize)
+                .unwrap();
+            assert_eq!(tokens, bpe.encode_with_special_tokens(prompt));
+        }
+        assert_eq!(
+            cache.prefix_len(1),
+            Some(bpe.encode_with_special_tokens(system).len())
+        );
+    }
+
+    #[test]
+    fn sampler_stages_apply_configured_min_keep() {
+        let settings = ModelSettings {
+            sampling: SamplingConfig::Temperature {
+                temperature: 0.7,
+                top_k: 40,
+                top_p: 0.9,
+                min_p: 0.2,
+                seed: Some(3),
+                min_keep: 8,
+            },
+            ..ModelSettings::default()
+        };
+        let stages = sampler_stages(&settings);
+        assert!(stages.contains(&SamplerStage::TopP {
+            p: 0.9,
+            min_keep: 8
+        }));
+        assert!(stages.contains(&SamplerStage::MinP {
+            p: 0.2,
+            min_keep: 8
+        }));
+
+        let defaults = sampler_stages(&ModelSettings::default());
+        assert!(defaults
+            .iter()
+            .any(|stage| matches!(stage, SamplerStage::MinP { min_keep: 1, .. })));
+    }
+
+    #[test]
+    fn prefill_reports_progress_per_batch() {
+        let tokens: Vec<u32> = (0..1000).collect();
+        let mut decoded = 0;
+        let mut reports = Vec::new();
+        let mut on_progress = |processed: usize, total: usize| reports.push((processed, total));
+
+        prefill_in_batches(
+            &tokens,
+            256,
+            |chunk| {
+                decoded += chunk.len();
+                Ok::<_, ()>(())
+            },
+            Some(&mut on_progress),
+        )
+        .unwrap();
+
+        assert_eq!(decoded, 1000);
+        assert_eq!(
+            reports,
+            [(256, 1000), (512, 1000), (768, 1000), (1000, 1000)]
+        );
+    }
+
+    #[test]
+    fn prefill_stops_reporting_after_decode_error() {
+        let tokens = [0u32; 10];
+        let mut reports = 0;
+        let mut on_progress = |_: usize, _: usize| reports += 1;
+
+        let result = prefill_in_batches(
+            &tokens,
+            4,
+            |chunk| {
+                if chunk.len() < 4 {
+                    Err("short")
+                } else {
+                    Ok(())
+                }
+            },
+            Some(&mut on_progress),
+        );
+
+        assert_eq!(result, Err("short"));
+        assert_eq!(reports, 2);
+    }
+
+    #[test]
+    fn bos_added_when_tokenizer_expects_it_and_prompt_lacks_it() {
+        assert!(matches!(
+            bos_policy("[INST] hi [/INST]", Some("<s>"), true),
+            AddBos::Always
+        ));
+    }
+
+    #[test]
+    fn bos_not_doubled_when_template_already_emits_it() {
+        assert!(matches!(
+            bos_policy("<s>[INST] hi [/INST]", Some("<s>"), true),
+            AddBos::Never
+        ));
+    }
+
+    #[test]
+    fn bos_skipped_when_tokenizer_does_not_add_it() {
+        assert!(matches!(
+            bos_policy("<|im_start|>user\nhi", Some("<|endoftext|>"), false),
+            AddBos::Never
+        ));
+        assert!(matches!(bos_policy("hi", None, true), AddBos::Never));
+        assert!(matches!(bos_policy("hi", Some(""), true), AddBos::Never));
+    }
+
+    fn stop_at(trimmer: &mut StopSuffixTrimmer, chunks: &[&str]) -> (String, bool) {
+        let mut output = String::new();
+        for chunk in chunks {
+            let (content, stop_seen) = trimmer.push(chunk);
+            output.push_str(&content);
+            if stop_seen {
+                return (output, true);
+            }
+        }
+        output.push_str(&trimmer.finish());
+        (output, false)
+    }
+
+    /// Replays a fixed token sequence, rendering each token as its id.
+    struct ScriptedTokens {
+        tokens: std::vec::IntoIter<i32>,
+        eog: i32,
+        decoded: Vec<i32>,
+    }
+
+    impl TokenSource for ScriptedTokens {
+        fn sample(&mut self) -> LlamaToken {
+            LlamaToken::new(self.tokens.next().unwrap_or(self.eog))
+        }
+
+        fn is_eog(&self, token: LlamaToken) -> bool {
+            token.0 == self.eog
+        }
+
+        fn piece(&mut self, token: LlamaToken) -> Result<String, ProviderError> {
+            Ok(format!("{} ", token.0))
+        }
+
+        fn logits(&self) -> &[f32] {
+            &[]
+        }
+
+        fn shift(&mut self, _n_keep: usize, n_past: usize) -> Result<usize, ProviderError> {
+            Ok(n_past)
+        }
+
+        fn decode(&mut self, token: LlamaToken) -> Result<(), ProviderError> {
+            self.decoded.push(token.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn configured_extra_eog_token_halts_generation() {
+        let generate = |settings: &ModelSettings| {
+            let mut source = ScriptedTokens {
+                tokens: vec![9707, 11, 151645, 1879].into_iter(),
+                eog: 2,
+                decoded: Vec::new(),
+            };
+            let mut text = String::new();
+            let output = run_generation(&mut source, settings, 10, 100, None, None, |piece| {
+                text.push_str(piece);
+                Ok(TokenAction::Continue)
+            })
+            .unwrap();
+            (text, output, source.decoded)
+        };
+
+        let settings = ModelSettings {
+            extra_eog_token_ids: vec![151645],
+            ..ModelSettings::default()
+        };
+        let (text, output, decoded) = generate(&settings);
+        assert_eq!(text, "9707 11 ");
+        assert_eq!(output.output_token_count, 2);
+        assert!(!output.truncated);
+        assert_eq!(decoded, vec![9707, 11]);
+
+        let (text, output, _) = generate(&ModelSettings::default());
+        assert_eq!(text, "9707 11 151645 1879 ");
+        assert_eq!(output.output_token_count, 4);
+        assert!(!is_extra_eog_token(&settings, -1));
+    }
+
+    #[test]
+    fn output_limit_truncates_generation_and_sends_notice() {
+        let mut source = ScriptedTokens {
+            tokens: vec![1, 2, 3, 4, 5].into_iter(),
+            eog: 0,
+            decoded: Vec::new(),
+        };
+        let settings = ModelSettings {
+            max_output_tokens: Some(3),
+            ..ModelSettings::default()
+        };
+        let mut text = String::new();
+        let output = run_generation(&mut source, &settings, 10, 100, None, None, |piece| {
+            text.push_str(piece);
+            Ok(TokenAction::Continue)
+        })
+        .unwrap();
+        assert_eq!(text, "1 2 3 ");
+        assert_eq!(output.output_token_count, 3);
+        assert!(output.truncated);
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        output.send_truncation_notice(&tx);
+        let (Some(notice), None) = rx.try_recv().unwrap().unwrap() else {
+            panic!("expected a notice message without usage");
+        };
+        assert!(notice.content.iter().any(|content| matches!(
+            content,
+            goose_provider_types::conversation::message::MessageContent::SystemNotification(n)
+                if n.msg.starts_with("Output truncated after 3 tokens")
+        )));
+    }
+
+    #[test]
+    fn stop_trimmer_combines_template_and_user_stops() {
+        let settings = ModelSettings {
+            stop_sequences: vec!["\nUser:".to_string(), "<|im_end|>".to_string()],
+            ..ModelSettings::default()
+        };
+        let template_stops = ["<|im_end|>".to_string()];
+
+        let mut trimmer = StopSuffixTrimmer::for_generation(&template_stops, &settings);
+        assert_eq!(trimmer.stops.len(), 2);
+        assert_eq!(
+            stop_at(&mut trimmer, &["Sure", "\nUs", "er:", " next"]),
+            ("Sure".to_string(), true)
+        );
+
+        let mut trimmer = StopSuffixTrimmer::for_generation(&template_stops, &settings);
+        assert_eq!(
+            stop_at(&mut trimmer, &["Done", "\n", "<|im", "_end|>"]),
+            ("Done\n".to_string(), true)
+        );
+
+        let mut trimmer = StopSuffixTrimmer::for_generation(&template_stops, &settings);
+        assert_eq!(
+            stop_at(&mut trimmer, &["No", " stop", "\nUs"]),
+            ("No stop\nUs".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn stop_trimmer_checks_accumulated_text_against_all_stops() {
+        let settings = ModelSettings {
+            stop_sequences: vec!["###".to_string()],
+            ..ModelSettings::default()
+        };
+        let trimmer = StopSuffixTrimmer::for_generation(&["</s>".to_string()], &settings);
+
+        assert!(trimmer.ends_with_stop("answer</s>"));
+        assert!(trimmer.ends_with_stop("answer\n###"));
+        assert!(!trimmer.ends_with_stop("answer ## "));
+    }
 
     fn default_settings() -> ModelSettings {
         ModelSettings::default()
@@ -685,4 +1745,167 @@ mod tests {
             2048
         );
     }
+
+    #[test]
+    fn test_memory_cap_bypassed_when_ignored() {
+        let settings = ModelSettings {
+            ignore_memory_context_cap: true,
+            ..default_settings()
+        };
+        let memory_max_ctx = memory_context_ceiling(&settings, || Some(2048));
+        assert_eq!(memory_max_ctx, None);
+        assert_eq!(
+            effective_context_size(100, &settings, 0, 8192, memory_max_ctx),
+            8192
+        );
+
+        let memory_max_ctx = memory_context_ceiling(&default_settings(), || Some(2048));
+        assert_eq!(
+            effective_context_size(100, &default_settings(), 0, 8192, memory_max_ctx),
+            2048
+        );
+
+        let settings = ModelSettings {
+            context_size: Some(131_072),
+            ..settings
+        };
+        let memory_max_ctx = memory_context_ceiling(&settings, || Some(2048));
+        assert!(check_configured_context_fits(&settings, memory_max_ctx).is_ok());
+        assert_eq!(context_cap(&settings, 0, 8192, memory_max_ctx), 131_072);
+    }
+
+    #[test]
+    fn test_logprobs_collected_only_when_enabled() {
+        assert!(LogprobCollector::for_settings(&default_settings()).is_none());
+
+        let settings = ModelSettings {
+            return_logprobs: true,
+            top_logprobs: 2,
+            ..default_settings()
+        };
+        let mut collector = LogprobCollector::for_settings(&settings).unwrap();
+        collector.record(&[0.0, 2.0_f32.ln(), 0.0], 1, "hi");
+
+        let entry = &collector.entries[0];
+        assert_eq!((entry.token, entry.text.as_str()), (1, "hi"));
+        assert!((entry.logprob - 0.5_f32.ln()).abs() < 1e-5);
+        assert_eq!(entry.top_logprobs.len(), 2);
+        assert_eq!(entry.top_logprobs[0].0, 1);
+        assert!((entry.top_logprobs[1].1 - 0.25_f32.ln()).abs() < 1e-5);
+
+        let generation = GenerationOutput {
+            output_token_count: 1,
+            logprobs: Some(collector.entries.clone()),
+            truncated: false,
+        };
+        let mut usage = ProviderUsage::new("model".to_string(), Default::default());
+        generation.attach_logprobs(&mut usage);
+        assert_eq!(usage.stats.unwrap().logprobs, Some(collector.entries));
+    }
+
+    #[test]
+    fn test_truncation_notice_fires_when_output_limit_reached() {
+        let mut generation = GenerationOutput {
+            output_token_count: 512,
+            logprobs: None,
+            truncated: false,
+        };
+        assert!(generation.truncation_notice().is_none());
+        assert!(!generation
+            .log_fields("partial")
+            .iter()
+            .any(|(key, _)| *key == "truncated"));
+
+        generation.truncated = true;
+        let notice = generation.truncation_notice().unwrap();
+        assert!(notice.content.iter().any(|content| matches!(
+            content,
+            goose_provider_types::conversation::message::MessageContent::SystemNotification(n)
+                if n.notification_type == SystemNotificationType::InlineMessage
+                    && n.msg.starts_with("Output truncated after 512 tokens")
+        )));
+        assert!(generation
+            .log_fields("partial")
+            .contains(&("truncated", serde_json::json!(true))));
+    }
+
+    #[test]
+    fn test_usage_progress_reports_at_configured_interval() {
+        use goose_provider_types::conversation::message::MessageContent;
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        assert!(UsageProgress::for_settings(&default_settings(), &tx, 100).is_none());
+
+        let mut settings = default_settings();
+        settings.usage_update_interval = Some(4);
+        let progress = UsageProgress::for_settings(&settings, &tx, 100).unwrap();
+        for count in 1..=10 {
+            progress.record(count);
+        }
+
+        let mut reported = Vec::new();
+        while let Ok(Ok((Some(message), usage))) = rx.try_recv() {
+            assert!(usage.is_none());
+            for content in &message.content {
+                if let MessageContent::SystemNotification(n) = content {
+                    assert_eq!(n.notification_type, SystemNotificationType::ProgressMessage);
+                    let data = n.data.as_ref().unwrap();
+                    assert_eq!(data["input_tokens"], 100);
+                    reported.push(data["output_tokens"].as_u64().unwrap());
+                }
+            }
+        }
+        assert_eq!(reported, vec![4, 8]);
+    }
+
+    #[test]
+    fn test_configured_context_larger_than_memory_is_rejected() {
+        let mut settings = default_settings();
+        settings.context_size = Some(131_072);
+        assert!(matches!(
+            check_configured_context_fits(&settings, Some(16_384)),
+            Err(ProviderError::ContextLengthExceeded(_))
+        ));
+
+        settings.context_size = Some(8192);
+        assert!(check_configured_context_fits(&settings, Some(16_384)).is_ok());
+        assert!(check_configured_context_fits(&settings, None).is_ok());
+        assert!(check_configured_context_fits(&default_settings(), Some(1024)).is_ok());
+    }
+
+    #[test]
+    fn test_context_shift_keeps_system_prompt_tokens() {
+        assert_eq!(context_shift_n_keep(300, 2000, 4096), Some(300));
+    }
+
+    #[test]
+    fn test_context_shift_n_keep_never_exceeds_prompt() {
+        assert_eq!(context_shift_n_keep(300, 120, 4096), Some(120));
+    }
+
+    #[test]
+    fn test_context_shift_disabled_when_system_prompt_fills_context() {
+        assert_eq!(context_shift_n_keep(3800, 3900, 4096), None);
+        assert_eq!(context_shift_n_keep(3584, 3900, 4096), Some(3584));
+    }
+
+    #[test]
+    fn test_overflowing_prompt_keeps_prefix_and_recent_tokens() {
+        let tokens: Vec<usize> = (0..5000).collect();
+        let trimmed = trim_prompt_for_context_shift(&tokens, 300, 4096).unwrap();
+
+        assert_eq!(trimmed.len(), 300 + 1898);
+        assert_eq!(&trimmed[..300], &tokens[..300]);
+        assert_eq!(trimmed[300..], tokens[5000 - 1898..]);
+        assert_eq!(
+            trim_prompt_for_context_shift(&tokens[..4095], 300, 4096),
+            None
+        );
+    }
+
+    #[test]
+    fn test_context_shift_discards_half_of_shiftable_region() {
+        assert_eq!(context_shift_discard(4096, 300), 1898);
+        assert_eq!(context_shift_discard(300, 300), 0);
+    }
 }
@@ -1,14 +1,16 @@
 use goose_provider_types::conversation::message::{Message, MessageContent};
 use goose_provider_types::errors::ProviderError;
-use rmcp::model::CallToolRequestParams;
+use rmcp::model::{CallToolRequestParams, Tool};
 use serde_json::Value;
 use std::borrow::Cow;
 use uuid::Uuid;
 
-use super::super::finalize_usage;
 use super::super::thinking_output::ThinkingOutputFilter;
+use super::super::tool_parsing::{canonical_tool_name, parse_lenient_json};
+use super::super::{finalize_usage, StreamSender};
 use super::inference_engine::{
     generation_loop, prepare_generation, GenerationContext, StopSuffixTrimmer, TokenAction,
+    UsageProgress,
 };
 
 pub(super) fn generate_with_native_tools(
@@ -22,11 +24,13 @@ pub(super) fn generate_with_native_tools(
     let mut llama_ctx = prepared.llama_ctx;
     let prompt_token_count = prepared.prompt_token_count;
     let effective_ctx = prepared.effective_ctx;
+    let n_keep = prepared.n_keep;
 
     let message_id = ctx.message_id;
     let tx = ctx.tx;
     let mut generated_text = String::new();
-    let mut stop_trimmer = StopSuffixTrimmer::new(&template_result.additional_stops);
+    let mut stop_trimmer =
+        StopSuffixTrimmer::for_generation(&template_result.additional_stops, ctx.settings);
     let mut stop_string_emitted = false;
 
     // Initialize streaming parser — handles thinking tokens, tool calls, etc.
@@ -48,14 +52,18 @@ pub(super) fn generate_with_native_tools(
     let mut output_filter = ThinkingOutputFilter::new(
         ctx.settings.enable_thinking,
         &template_result.generation_prompt,
-    );
+    )
+    .streaming_inline_thinking();
 
-    let output_token_count = generation_loop(
+    let usage_progress = UsageProgress::for_settings(ctx.settings, ctx.tx, prompt_token_count);
+    let generation = generation_loop(
         &ctx.loaded.model,
         &mut llama_ctx,
         ctx.settings,
         prompt_token_count,
         effective_ctx,
+        n_keep,
+        usage_progress.as_ref(),
         |piece| {
             generated_text.push_str(piece);
             let mut stop_seen = false;
@@ -83,6 +91,9 @@ pub(super) fn generate_with_native_tools(
                             if let Some(content) = delta.get("content").and_then(|v| v.as_str()) {
                                 if !content.is_empty() {
                                     let filtered = output_filter.push_text(content);
+                                    if send_thinking(&filtered.thinking, message_id, tx).is_err() {
+                                        return Ok(TokenAction::Stop);
+                                    }
                                     let (content, seen) = stop_trimmer.push(&filtered.content);
                                     stop_seen |= seen;
                                     if !content.is_empty() {
@@ -108,6 +119,9 @@ pub(super) fn generate_with_native_tools(
                 Err(e) => {
                     tracing::warn!("Streaming parser error: {}", e);
                     let filtered = output_filter.push_text(piece);
+                    if send_thinking(&filtered.thinking, message_id, tx).is_err() {
+                        return Ok(TokenAction::Stop);
+                    }
                     let (content, seen) = stop_trimmer.push(&filtered.content);
                     stop_seen |= seen;
                     if !content.is_empty() {
@@ -120,12 +134,7 @@ pub(super) fn generate_with_native_tools(
                 }
             }
 
-            let should_stop = stop_seen
-                || template_result
-                    .additional_stops
-                    .iter()
-                    .any(|stop| generated_text.ends_with(stop));
-            if should_stop {
+            if stop_seen || stop_trimmer.ends_with_stop(&generated_text) {
                 stop_string_emitted = true;
                 Ok(TokenAction::Stop)
             } else {
@@ -148,6 +157,7 @@ pub(super) fn generate_with_native_tools(
                 if let Some(content) = delta.get("content").and_then(|v| v.as_str()) {
                     if !content.is_empty() {
                         let filtered = output_filter.push_text(content);
+                        let _ = send_thinking(&filtered.thinking, message_id, tx);
                         let (content, stop_seen) = stop_trimmer.push(&filtered.content);
                         stop_string_emitted |= stop_seen;
                         if !content.is_empty() {
@@ -167,11 +177,7 @@ pub(super) fn generate_with_native_tools(
     }
 
     let filtered = output_filter.finish();
-    if !filtered.thinking.is_empty() {
-        let mut msg = Message::assistant().with_thinking(&filtered.thinking, "");
-        msg.id = Some(message_id.to_string());
-        let _ = tx.blocking_send(Ok((Some(msg), None)));
-    }
+    let _ = send_thinking(&filtered.thinking, message_id, tx);
     let content = if stop_string_emitted {
         String::new()
     } else {
@@ -194,7 +200,7 @@ pub(super) fn generate_with_native_tools(
     //   1. Extract thinking and attach it to per-tool-request messages
     //   2. Enable merge_split_tool_call_messages to reconstruct the standard
     //      OpenAI format (one assistant msg with N tool_calls, then N tool results)
-    let tool_call_contents = extract_oai_tool_call_contents(&accumulated_tool_calls);
+    let tool_call_contents = extract_oai_tool_call_contents(&accumulated_tool_calls, ctx.tools);
     if !tool_call_contents.is_empty() {
         let mut contents: Vec<MessageContent> = Vec::new();
         if !output_filter.accumulated_thinking().is_empty() {
@@ -213,24 +219,36 @@ pub(super) fn generate_with_native_tools(
         let _ = tx.blocking_send(Ok((Some(msg), None)));
     }
 
-    let provider_usage = finalize_usage(
+    generation.send_truncation_notice(ctx.tx);
+
+    let mut provider_usage = finalize_usage(
         ctx.log,
         std::mem::take(&mut ctx.model_name),
         "native",
         prompt_token_count,
-        output_token_count,
-        Some(("generated_text", &generated_text)),
+        generation.output_token_count,
+        &generation.log_fields(&generated_text),
     );
+    generation.attach_logprobs(&mut provider_usage);
     let _ = ctx.tx.blocking_send(Ok((None, Some(provider_usage))));
     Ok(())
 }
 
+fn send_thinking(thinking: &str, message_id: &str, tx: &StreamSender) -> Result<(), ()> {
+    if thinking.is_empty() {
+        return Ok(());
+    }
+    let mut msg = Message::assistant().with_thinking(thinking, "");
+    msg.id = Some(message_id.to_string());
+    tx.blocking_send(Ok((Some(msg), None))).map_err(|_| ())
+}
+
 /// Merge OpenAI streaming deltas by `index` into `MessageContent` items.
 ///
 /// Returns one `ToolRequest` content per distinct tool call index. The caller
 /// is responsible for combining these into a single `Message` (together with
 /// any accumulated thinking content).
-fn extract_oai_tool_call_contents(deltas: &[Value]) -> Vec<MessageContent> {
+fn extract_oai_tool_call_contents(deltas: &[Value], tools: &[Tool]) -> Vec<MessageContent> {
     let mut merged: std::collections::BTreeMap<u64, (String, String, String)> =
         std::collections::BTreeMap::new();
 
@@ -273,12 +291,13 @@ fn extract_oai_tool_call_contents(deltas: &[Value]) -> Vec<MessageContent> {
             let arguments: Option<serde_json::Map<String, Value>> = if args_str.is_empty() {
                 None
             } else {
-                match serde_json::from_str(&args_str) {
+                match parse_lenient_json(&args_str) {
                     Ok(args) => Some(args),
                     Err(_) => return None,
                 }
             };
 
+            let name = canonical_tool_name(&name, tools).to_string();
             let tool_call = match arguments {
                 Some(args) => CallToolRequestParams::new(Cow::Owned(name)).with_arguments(args),
                 None => CallToolRequestParams::new(Cow::Owned(name)),
@@ -323,7 +342,7 @@ mod tests {
             json!({"index": 0, "function": {"arguments": "{\"command\":"}}),
             json!({"index": 0, "function": {"arguments": " \"ls\"}"}}),
         ];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert_eq!(contents.len(), 1);
         assert_eq!(get_content_tool_call_name(&contents[0]), "developer__shell");
         let args = get_content_tool_call_args(&contents[0]).unwrap();
@@ -336,7 +355,7 @@ mod tests {
             json!({"index": 0, "id": "call_1", "function": {"name": "developer__shell", "arguments": "{\"command\": \"ls\"}"}}),
             json!({"index": 1, "id": "call_2", "function": {"name": "developer__shell", "arguments": "{\"command\": \"pwd\"}"}}),
         ];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert_eq!(contents.len(), 2);
         let args0 = get_content_tool_call_args(&contents[0]).unwrap();
         let args1 = get_content_tool_call_args(&contents[1]).unwrap();
@@ -353,7 +372,7 @@ mod tests {
             json!({"index": 0, "function": {"arguments": " \"timeout\":"}}),
             json!({"index": 0, "function": {"arguments": " 30}"}}),
         ];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert_eq!(contents.len(), 1);
         let args = get_content_tool_call_args(&contents[0]).unwrap();
         assert_eq!(args.get("command").unwrap(), "ls -la");
@@ -363,20 +382,20 @@ mod tests {
     #[test]
     fn test_empty_name_skipped() {
         let deltas = vec![json!({"index": 0, "function": {"name": "", "arguments": "{}"}})];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert!(contents.is_empty());
     }
 
     #[test]
     fn test_no_deltas() {
-        let contents = extract_oai_tool_call_contents(&[]);
+        let contents = extract_oai_tool_call_contents(&[], &[]);
         assert!(contents.is_empty());
     }
 
     #[test]
     fn test_tool_call_without_arguments() {
         let deltas = vec![json!({"index": 0, "id": "call_1", "function": {"name": "some_tool"}})];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert_eq!(contents.len(), 1);
         assert_eq!(get_content_tool_call_name(&contents[0]), "some_tool");
         assert!(get_content_tool_call_args(&contents[0]).is_none());
@@ -388,15 +407,32 @@ mod tests {
             json!({"index": 0, "id": "call_1", "function": {"name": "developer__shell", "arguments": ""}}),
             json!({"index": 0, "function": {"arguments": "{\"command\": \"rm -rf"}}),
         ];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert!(contents.is_empty());
     }
 
+    #[test]
+    fn test_dotted_name_mapped_to_known_tool() {
+        let tools = vec![Tool::new(
+            "developer__shell",
+            "Run a shell command",
+            rmcp::object!({"type": "object"}),
+        )];
+        let deltas = vec![
+            json!({"index": 0, "id": "call_1", "function": {"name": "developer.shell", "arguments": "{}"}}),
+            json!({"index": 1, "id": "call_2", "function": {"name": "memory.remember", "arguments": "{}"}}),
+        ];
+        let contents = extract_oai_tool_call_contents(&deltas, &tools);
+        assert_eq!(contents.len(), 2);
+        assert_eq!(get_content_tool_call_name(&contents[0]), "developer__shell");
+        assert_eq!(get_content_tool_call_name(&contents[1]), "memory.remember");
+    }
+
     #[test]
     fn test_generates_id_when_missing() {
         let deltas =
             vec![json!({"index": 0, "function": {"name": "some_tool", "arguments": "{}"}})];
-        let contents = extract_oai_tool_call_contents(&deltas);
+        let contents = extract_oai_tool_call_contents(&deltas, &[]);
         assert_eq!(contents.len(), 1);
         assert_eq!(get_content_tool_call_name(&contents[0]), "some_tool");
         match &contents[0] {
@@ -0,0 +1,95 @@
+use goose_provider_types::conversation::message::Message;
+use goose_provider_types::errors::ProviderError;
+use goose_provider_types::request_log::LoggerHandleExt;
+
+use super::super::backend::LocalGenerationRequest;
+use super::super::{build_raw_completion_prompt, finalize_usage};
+use super::inference_engine::{
+    create_and_prefill_context, generation_loop, prompt_bos_policy, validate_and_compute_context,
+    LoadedModel, StopSuffixTrimmer, TokenAction, UsageProgress,
+};
+use super::LlamaCppBackend;
+
+/// Generate from the concatenated message text without applying any chat template,
+/// streaming the output as plain text up to the first `stop_sequences` match. Tool calls
+/// are never parsed.
+pub(super) fn generate_raw_completion(
+    loaded: &LoadedModel,
+    backend: &LlamaCppBackend,
+    request: LocalGenerationRequest<'_>,
+) -> Result<(), ProviderError> {
+    let settings = request
+        .settings
+        .with_temperature_override(request.temperature);
+    let prompt = build_raw_completion_prompt(request.system, request.messages);
+    let _ = request
+        .log
+        .write(&serde_json::json!({"applied_prompt": &prompt}), None);
+
+    let tokens = loaded
+        .model
+        .str_to_token(&prompt, prompt_bos_policy(&loaded.model, &prompt))
+        .map_err(|e| ProviderError::ExecutionError(e.to_string()))?;
+    let (prompt_token_count, effective_ctx) = validate_and_compute_context(
+        loaded,
+        backend,
+        tokens.len(),
+        request.context_limit,
+        &settings,
+    )?;
+    let mut llama_ctx =
+        create_and_prefill_context(loaded, backend, &tokens, effective_ctx, &settings, None)?;
+
+    let message_id = request.message_id;
+    let tx = request.tx;
+    let mut generated_text = String::new();
+    let mut stop_trimmer = StopSuffixTrimmer::for_generation(&[], &settings);
+    let mut stop_string_emitted = false;
+    let send_text = |text: String| {
+        let mut msg = Message::assistant().with_text(text);
+        msg.id = Some(message_id.to_string());
+        tx.blocking_send(Ok((Some(msg), None))).is_ok()
+    };
+    let usage_progress = UsageProgress::for_settings(&settings, tx, prompt_token_count);
+    let generation = generation_loop(
+        &loaded.model,
+        &mut llama_ctx,
+        &settings,
+        prompt_token_count,
+        effective_ctx,
+        None,
+        usage_progress.as_ref(),
+        |piece| {
+            generated_text.push_str(piece);
+            let (content, stop_seen) = stop_trimmer.push(piece);
+            if !content.is_empty() && !send_text(content) {
+                return Ok(TokenAction::Stop);
+            }
+            if stop_seen {
+                stop_string_emitted = true;
+                return Ok(TokenAction::Stop);
+            }
+            Ok(TokenAction::Continue)
+        },
+    )?;
+    if !stop_string_emitted {
+        let rest = stop_trimmer.finish();
+        if !rest.is_empty() {
+            send_text(rest);
+        }
+    }
+
+    generation.send_truncation_notice(tx);
+
+    let mut provider_usage = finalize_usage(
+        request.log,
+        request.model_name,
+        "raw",
+        prompt_token_count,
+        generation.output_token_count,
+        &generation.log_fields(&generated_text),
+    );
+    generation.attach_logprobs(&mut provider_usage);
+    let _ = tx.blocking_send(Ok((None, Some(provider_usage))));
+    Ok(())
+}
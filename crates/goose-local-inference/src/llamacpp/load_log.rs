@@ -0,0 +1,104 @@
+//! llama-cpp-2 reports a failed model load as an opaque null result; llama.cpp only logs the
+//! reason. This hooks its log callback to keep the architecture rejection around, forwarding
+//! every line to the logger that was installed before.
+
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::sync::Mutex;
+
+use llama_cpp_sys_2::{ggml_log_callback, ggml_log_level};
+
+/// What llama.cpp throws (and then logs) for a `general.architecture` it doesn't know.
+pub(super) const UNKNOWN_ARCHITECTURE_ERROR: &str = "unknown model architecture";
+
+struct ChainedLogger {
+    callback: ggml_log_callback,
+    // The previous logger's state pointer, kept as an address so the static is `Send`.
+    user_data: usize,
+}
+
+static CHAINED_LOGGER: Mutex<Option<ChainedLogger>> = Mutex::new(None);
+/// Passed as our callback's state, so an already installed hook can be recognized.
+static HOOK_MARKER: u8 = 0;
+static ARCHITECTURE_REJECTION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Install the capturing callback in front of the current llama.cpp logger. Safe to call
+/// again after the logger was replaced, e.g. when the backend is re-initialized.
+pub(super) fn capture_load_failures() {
+    let mut callback: ggml_log_callback = None;
+    let mut user_data: *mut c_void = std::ptr::null_mut();
+    unsafe { llama_cpp_sys_2::llama_log_get(&mut callback, &mut user_data) };
+    let marker = std::ptr::addr_of!(HOOK_MARKER) as *mut c_void;
+    if user_data == marker {
+        return;
+    }
+
+    *CHAINED_LOGGER.lock().expect("llama log lock poisoned") = Some(ChainedLogger {
+        callback,
+        user_data: user_data as usize,
+    });
+    unsafe { llama_cpp_sys_2::llama_log_set(Some(record_load_log), marker) };
+}
+
+unsafe extern "C" fn record_load_log(
+    level: ggml_log_level,
+    text: *const c_char,
+    _user_data: *mut c_void,
+) {
+    if !text.is_null() {
+        note_load_log_line(&unsafe { CStr::from_ptr(text) }.to_string_lossy());
+    }
+
+    let forward = CHAINED_LOGGER
+        .lock()
+        .ok()
+        .and_then(|logger| logger.as_ref().map(|l| (l.callback, l.user_data)));
+    if let Some((Some(callback), user_data)) = forward {
+        unsafe { callback(level, text, user_data as *mut c_void) };
+    }
+}
+
+fn note_load_log_line(line: &str) {
+    if line.contains(UNKNOWN_ARCHITECTURE_ERROR) {
+        if let Ok(mut rejection) = ARCHITECTURE_REJECTION.lock() {
+            *rejection = Some(line.trim().to_string());
+        }
+    }
+}
+
+/// Take the architecture rejection llama.cpp logged for a failed load. With the file's
+/// architecture known, only a rejection naming it counts, so concurrent loads don't
+/// pick up each other's failures.
+pub(super) fn take_architecture_rejection(architecture: Option<&str>) -> Option<String> {
+    let mut rejection = ARCHITECTURE_REJECTION.lock().ok()?;
+    let matches = match (rejection.as_deref(), architecture) {
+        (Some(line), Some(arch)) => line.contains(&format!("'{arch}'")),
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    if matches {
+        rejection.take()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_rejection_for_the_failed_architecture() {
+        note_load_log_line("llama_model_load: loading tensors\n");
+        note_load_log_line(
+            "llama_model_load: error loading model: unknown model architecture: 'futurenet'\n",
+        );
+
+        assert_eq!(take_architecture_rejection(Some("llama")), None);
+        assert_eq!(
+            take_architecture_rejection(Some("futurenet")).as_deref(),
+            Some("llama_model_load: error loading model: unknown model architecture: 'futurenet'")
+        );
+        assert_eq!(take_architecture_rejection(Some("futurenet")), None);
+    }
+}
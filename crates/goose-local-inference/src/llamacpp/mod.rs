@@ -1,10 +1,16 @@
+mod embeddings;
+mod gguf_header;
 mod inference_emulated_tools;
 mod inference_engine;
 mod inference_native_tools;
+mod inference_raw;
+mod load_log;
+
+pub(crate) use self::gguf_header::{read_context_length, read_kv_cache_shape};
 
 use std::any::Any;
 use std::ffi::CStr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use llama_cpp_2::llama_backend::LlamaBackend;
@@ -18,18 +24,25 @@ use self::inference_emulated_tools::{
 };
 use self::inference_engine::{GenerationContext, LoadedChatTemplates, LoadedModel};
 use self::inference_native_tools::generate_with_native_tools;
+use self::inference_raw::generate_raw_completion;
 use crate::backend::{BackendLoadedModel, LocalGenerationRequest, LocalInferenceBackend};
 use crate::local_model_registry::{ChatTemplate, ModelSettings, ToolCallingMode};
 use crate::multimodal::ExtractedImage;
 use crate::tool_parsing::compact_tools_json;
 use crate::{build_openai_messages_json, build_openai_text_messages_json, ResolvedModelPaths};
+use goose_provider_types::conversation::message::Message;
 use goose_provider_types::errors::ProviderError;
 use goose_provider_types::formats::openai::format_tools;
+use rmcp::model::Tool;
 
 pub(super) const LLAMACPP_BACKEND_ID: &str = "llamacpp";
 
 const CODE_EXECUTION_TOOL: &str = "code_execution__execute_typescript";
 
+/// Share of free accelerator memory the weights may take when picking an offload
+/// count, leaving the rest for the KV cache and compute buffers.
+const AUTO_OFFLOAD_WEIGHTS_SHARE: f64 = 0.85;
+
 pub(super) fn builtin_chat_template_names() -> Vec<String> {
     let count = unsafe { llama_cpp_sys_2::llama_chat_builtin_templates(std::ptr::null_mut(), 0) };
     if count <= 0 {
@@ -208,6 +221,34 @@ fn missing_chat_template_error(
     ))
 }
 
+fn model_load_error(
+    model_id: &str,
+    model_path: &Path,
+    error: &str,
+    architecture: Option<&str>,
+) -> ProviderError {
+    let lower = error.to_ascii_lowercase();
+    let architecture_rejected = lower.contains(load_log::UNKNOWN_ARCHITECTURE_ERROR)
+        || lower.contains("unsupported model architecture");
+
+    match architecture {
+        Some(arch) if architecture_rejected => {
+            ProviderError::ExecutionError(format!(
+                "Model {model_id} uses the '{arch}' architecture, which isn't supported by the bundled llama.cpp; \
+                 try updating goose or choosing another model. ({error})"
+            ))
+        }
+        None if architecture_rejected => ProviderError::ExecutionError(format!(
+            "Model {model_id} uses an architecture that isn't supported by the bundled llama.cpp; \
+             try updating goose or choosing another model. ({error})"
+        )),
+        _ => ProviderError::ExecutionError(format!(
+            "Failed to load model {model_id} from {}: {error}",
+            model_path.display()
+        )),
+    }
+}
+
 fn load_chat_templates(
     model: &LlamaModel,
     settings: &ModelSettings,
@@ -363,6 +404,7 @@ impl LlamaCppBackend {
         };
 
         llama_cpp_2::send_logs_to_tracing(LogOptions::default());
+        load_log::capture_load_failures();
         log_inference_backend_devices();
 
         Ok(Self { backend })
@@ -372,6 +414,16 @@ impl LlamaCppBackend {
         &self.backend
     }
 
+    /// The inference memory layers can be offloaded into; none when llama.cpp only
+    /// sees the CPU, where the reported figure is system RAM.
+    fn offload_memory_bytes(&self) -> u64 {
+        if has_accelerator_device() {
+            self.available_memory_bytes()
+        } else {
+            0
+        }
+    }
+
     fn init_mtmd_context(
         model: &LlamaModel,
         mmproj_path: &Option<PathBuf>,
@@ -421,10 +473,7 @@ impl LocalInferenceBackend for LlamaCppBackend {
         let model_path = &resolved.model_path;
 
         if !model_path.exists() {
-            return Err(ProviderError::ExecutionError(format!(
-                "Model not downloaded: {}. Please download it from Settings > Local Inference.",
-                model_id
-            )));
+            return Err(crate::missing_model_error(model_id));
         }
 
         tracing::info!(
@@ -435,14 +484,24 @@ impl LocalInferenceBackend for LlamaCppBackend {
         );
 
         let mut params = LlamaModelParams::default();
-        if let Some(n_gpu_layers) = settings.n_gpu_layers {
+        let n_gpu_layers = settings
+            .n_gpu_layers
+            .or_else(|| auto_gpu_layers_for(model_id, model_path, self.offload_memory_bytes()));
+        if let Some(n_gpu_layers) = n_gpu_layers {
             params = params.with_n_gpu_layers(n_gpu_layers);
         }
         if settings.use_mlock {
             params = params.with_use_mlock(true);
         }
-        let model = LlamaModel::load_from_file(&self.backend, model_path, &params)
-            .map_err(|e| ProviderError::ExecutionError(e.to_string()))?;
+        let model =
+            LlamaModel::load_from_file(&self.backend, model_path, &params).map_err(|e| {
+                let architecture = gguf_header::read_architecture(model_path);
+                let error = match load_log::take_architecture_rejection(architecture.as_deref()) {
+                    Some(rejection) => format!("{e}: {rejection}"),
+                    None => e.to_string(),
+                };
+                model_load_error(model_id, model_path, &error, architecture.as_deref())
+            })?;
 
         let templates = load_chat_templates(&model, settings)?;
 
@@ -458,6 +517,7 @@ impl LocalInferenceBackend for LlamaCppBackend {
             model,
             templates,
             mtmd_ctx,
+            system_tokens: Default::default(),
         }))
     }
 
@@ -473,9 +533,14 @@ impl LocalInferenceBackend for LlamaCppBackend {
                 ProviderError::ExecutionError("Loaded model backend mismatch".to_string())
             })?;
 
-        let has_vision = request.resolved_model.mmproj_path.is_some();
+        if request.settings.raw_completion {
+            return generate_raw_completion(loaded, self, request);
+        }
+
+        let use_vision = request.resolved_model.mmproj_path.is_some()
+            && super::multimodal::messages_contain_images(request.messages);
         let marker = llama_cpp_2::mtmd::mtmd_default_marker();
-        let (images, vision_messages): (Vec<ExtractedImage>, Option<Vec<_>>) = if has_vision {
+        let (images, vision_messages): (Vec<ExtractedImage>, Option<Vec<_>>) = if use_vision {
             let (imgs, msgs) =
                 super::multimodal::extract_images_from_messages(request.messages, marker);
             (imgs, Some(msgs))
@@ -537,7 +602,11 @@ impl LocalInferenceBackend for LlamaCppBackend {
         );
         let use_emulator = !native_tool_calling && !request.tools.is_empty();
         let system_prompt = if use_emulator {
-            let tool_desc = build_emulator_tool_description(request.tools, code_mode_enabled);
+            let tool_desc = build_emulator_tool_description(
+                request.tools,
+                code_mode_enabled,
+                request.settings.emulator_json_mode,
+            );
             format!("{}{}", load_tiny_model_prompt(), tool_desc)
         } else {
             request.system.to_string()
@@ -573,17 +642,21 @@ impl LocalInferenceBackend for LlamaCppBackend {
             !request.tools.is_empty(),
         )?;
 
+        let settings = request
+            .settings
+            .with_temperature_override(request.temperature);
         let mut gen_ctx = GenerationContext {
             loaded,
             backend: self,
             template,
-            settings: request.settings,
+            settings: &settings,
             context_limit: request.context_limit,
             model_name: request.model_name,
             message_id: request.message_id,
             tx: request.tx,
             log: request.log,
             images: &images,
+            tools: request.tools,
         };
 
         if use_emulator {
@@ -598,26 +671,135 @@ impl LocalInferenceBackend for LlamaCppBackend {
         }
     }
 
+    fn count_prompt_tokens(
+        &self,
+        loaded: &mut dyn BackendLoadedModel,
+        model_id: &str,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+        settings: &ModelSettings,
+    ) -> Option<usize> {
+        let loaded = loaded.as_any_mut().downcast_mut::<LoadedModel>()?;
+        if settings.raw_completion {
+            let prompt = crate::build_raw_completion_prompt(system, messages);
+            return loaded
+                .model
+                .str_to_token(
+                    &prompt,
+                    inference_engine::prompt_bos_policy(&loaded.model, &prompt),
+                )
+                .ok()
+                .map(|tokens| tokens.len());
+        }
+        let has_tools = !tools.is_empty();
+        let tools_json = if has_tools {
+            format_tools(tools)
+                .ok()
+                .and_then(|spec| serde_json::to_string(&spec).ok())
+        } else {
+            None
+        };
+        let template = select_generation_template(
+            model_id,
+            &loaded.model,
+            &loaded.templates,
+            has_tools,
+            has_tools,
+        )
+        .ok()?;
+        let messages_json = build_openai_messages_json(system, messages, None);
+        let params = OpenAIChatTemplateParams {
+            messages_json: &messages_json,
+            tools_json: tools_json.as_deref(),
+            tool_choice: None,
+            json_schema: None,
+            grammar: None,
+            reasoning_format: if settings.enable_thinking {
+                Some("auto")
+            } else {
+                None
+            },
+            chat_template_kwargs: None,
+            add_generation_prompt: true,
+            use_jinja: true,
+            parallel_tool_calls: false,
+            enable_thinking: settings.enable_thinking,
+            add_bos: false,
+            add_eos: false,
+            parse_tool_calls: true,
+        };
+        let result = loaded
+            .model
+            .apply_chat_template_oaicompat(template, &params)
+            .ok()?;
+        loaded
+            .model
+            .str_to_token(
+                &result.prompt,
+                inference_engine::prompt_bos_policy(&loaded.model, &result.prompt),
+            )
+            .ok()
+            .map(|tokens| tokens.len())
+    }
+
+    fn context_cap(
+        &self,
+        loaded: &mut dyn BackendLoadedModel,
+        context_limit: usize,
+        settings: &ModelSettings,
+    ) -> Option<usize> {
+        let loaded = loaded.as_any_mut().downcast_mut::<LoadedModel>()?;
+        let mmproj_overhead = if loaded.mtmd_ctx.is_some() {
+            settings.mmproj_size_bytes
+        } else {
+            0
+        };
+        let memory_max_ctx = inference_engine::memory_context_ceiling(settings, || {
+            inference_engine::estimate_max_context_for_memory(&loaded.model, self, mmproj_overhead)
+        });
+        Some(inference_engine::context_cap(
+            settings,
+            context_limit,
+            loaded.model.n_ctx_train() as usize,
+            memory_max_ctx,
+        ))
+    }
+
+    fn embed(
+        &self,
+        loaded: &mut dyn BackendLoadedModel,
+        texts: &[String],
+        settings: &ModelSettings,
+    ) -> Result<Vec<Vec<f32>>, ProviderError> {
+        let loaded = loaded
+            .as_any_mut()
+            .downcast_mut::<LoadedModel>()
+            .ok_or_else(|| {
+                ProviderError::ExecutionError("Loaded model backend mismatch".to_string())
+            })?;
+        embeddings::embed_texts(loaded, self, texts, settings)
+    }
+
     fn available_memory_bytes(&self) -> u64 {
-        let devices = list_llama_ggml_backend_devices();
+        available_memory_bytes()
+    }
+}
+
+/// Free accelerator memory, or free system memory when llama.cpp only sees the CPU.
+/// Only lists devices, so it works before the backend is initialized.
+pub(crate) fn available_memory_bytes() -> u64 {
+    let accel_memory = accelerator_memory_bytes();
 
-        let accel_memory = devices
+    if accel_memory > 0 {
+        accel_memory
+    } else {
+        list_llama_ggml_backend_devices()
             .iter()
-            .filter(|d| is_accelerator_device(d.device_type))
+            .filter(|d| d.device_type == LlamaBackendDeviceType::Cpu)
             .map(|d| d.memory_free as u64)
             .max()
-            .unwrap_or(0);
-
-        if accel_memory > 0 {
-            accel_memory
-        } else {
-            devices
-                .iter()
-                .filter(|d| d.device_type == LlamaBackendDeviceType::Cpu)
-                .map(|d| d.memory_free as u64)
-                .max()
-                .unwrap_or(0)
-        }
+            .unwrap_or(0)
     }
 }
 
@@ -636,6 +818,57 @@ fn is_accelerator_device(device_type: LlamaBackendDeviceType) -> bool {
     )
 }
 
+/// Free memory on the largest accelerator, or zero when llama.cpp only sees the CPU.
+fn accelerator_memory_bytes() -> u64 {
+    list_llama_ggml_backend_devices()
+        .iter()
+        .filter(|d| is_accelerator_device(d.device_type))
+        .map(|d| d.memory_free as u64)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Offload count for a model whose settings leave `n_gpu_layers` unset, sized from
+/// the registered weights of every shard against `offload_bytes`. `None` when the
+/// model isn't registered or its GGUF header can't be read, which keeps llama.cpp's
+/// own default.
+fn auto_gpu_layers_for(model_id: &str, model_path: &Path, offload_bytes: u64) -> Option<u32> {
+    let (model_bytes, mmproj_bytes) = crate::registered_model_bytes(model_id)?;
+    let n_layer = read_kv_cache_shape(model_path)?.n_layer;
+    let accelerator_bytes = offload_bytes.saturating_sub(mmproj_bytes);
+
+    let layers = auto_gpu_layers(accelerator_bytes, model_bytes, n_layer);
+    tracing::info!(
+        n_gpu_layers = layers,
+        n_layer,
+        model_bytes,
+        accelerator_bytes,
+        "Auto-selected GPU offload"
+    );
+    Some(layers)
+}
+
+/// Offload every layer when the weights fit the accelerator budget, a proportional
+/// share when they only partly fit, and none without an accelerator.
+fn auto_gpu_layers(accelerator_bytes: u64, model_bytes: u64, n_layer: u64) -> u32 {
+    if accelerator_bytes == 0 || n_layer == 0 {
+        return 0;
+    }
+    let budget = accelerator_bytes as f64 * AUTO_OFFLOAD_WEIGHTS_SHARE;
+    if model_bytes as f64 <= budget {
+        // One past the repeating blocks also offloads the output layer.
+        return u32::try_from(n_layer + 1).unwrap_or(u32::MAX);
+    }
+    (n_layer as f64 * budget / model_bytes as f64) as u32
+}
+
+/// Whether llama.cpp can offload layers to anything other than the CPU.
+pub(crate) fn has_accelerator_device() -> bool {
+    list_llama_ggml_backend_devices()
+        .iter()
+        .any(|device| is_accelerator_device(device.device_type))
+}
+
 fn is_non_cpu_device(device_type: LlamaBackendDeviceType) -> bool {
     !matches!(device_type, LlamaBackendDeviceType::Cpu)
 }
@@ -688,6 +921,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn auto_gpu_layers_scales_with_accelerator_memory() {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        assert_eq!(auto_gpu_layers(0, 4 * GB, 32), 0);
+        assert_eq!(auto_gpu_layers(24 * GB, 4 * GB, 32), 33);
+        assert_eq!(auto_gpu_layers(8 * GB, 17 * GB / 2, 32), 25);
+        assert_eq!(auto_gpu_layers(2 * GB, 30 * GB, 40), 2);
+        assert_eq!(auto_gpu_layers(24 * GB, 4 * GB, 0), 0);
+    }
+
     #[test]
     fn native_tool_calling_requires_generated_parser() {
         assert!(template_result_supports_native_tool_calling(
@@ -733,6 +977,51 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn model_load_error_explains_unsupported_architecture() {
+        let path = Path::new("/models/model.gguf");
+        let err = model_load_error(
+            "test/model:Q4_K_M",
+            path,
+            "null result from llama cpp: llama_model_load: error loading model: unknown model architecture: 'futurenet'",
+            Some("futurenet"),
+        );
+        let message = err.to_string();
+        assert!(message.contains("'futurenet' architecture"));
+        assert!(message.contains("try updating goose or choosing another model"));
+
+        let err = model_load_error(
+            "test/model:Q4_K_M",
+            path,
+            "error loading model: unknown model architecture: 'futurenet'",
+            None,
+        );
+        assert!(err
+            .to_string()
+            .contains("isn't supported by the bundled llama.cpp"));
+
+        // Without llama.cpp rejecting the architecture, a null result is some other failure,
+        // whatever the architecture is called.
+        let err = model_load_error(
+            "test/model:Q4_K_M",
+            path,
+            "null result from llama cpp",
+            Some("gemma4"),
+        );
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Failed to load model test/model:Q4_K_M from /models/model.gguf: null result from llama cpp"
+        );
+
+        let err = model_load_error(
+            "test/model:Q4_K_M",
+            path,
+            "null result from llama cpp",
+            None,
+        );
+        assert!(!err.to_string().contains("architecture"));
+    }
+
     #[test]
     fn rejects_legacy_builtin_names_as_inline_templates() {
         assert!(is_legacy_builtin_template_name("gemma"));
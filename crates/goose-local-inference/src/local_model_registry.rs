@@ -15,6 +15,10 @@ pub enum SamplingConfig {
         top_p: f32,
         min_p: f32,
         seed: Option<u32>,
+        /// Fewest candidates `top_p` and `min_p` may leave, so aggressive cutoffs on
+        /// low-entropy distributions can't over-prune.
+        #[serde(default = "default_min_keep")]
+        min_keep: usize,
     },
     MirostatV2 {
         tau: f32,
@@ -31,6 +35,38 @@ impl Default for SamplingConfig {
             top_p: 0.95,
             min_p: 0.05,
             seed: None,
+            min_keep: default_min_keep(),
+        }
+    }
+}
+
+fn default_min_keep() -> usize {
+    1
+}
+
+impl SamplingConfig {
+    /// Greedy and Mirostat sampling have no temperature, so an override leaves them as-is.
+    pub fn with_temperature_override(&self, temperature: Option<f32>) -> Self {
+        match (self, temperature) {
+            (
+                SamplingConfig::Temperature {
+                    top_k,
+                    top_p,
+                    min_p,
+                    seed,
+                    min_keep,
+                    ..
+                },
+                Some(temperature),
+            ) => SamplingConfig::Temperature {
+                temperature,
+                top_k: *top_k,
+                top_p: *top_p,
+                min_p: *min_p,
+                seed: *seed,
+                min_keep: *min_keep,
+            },
+            _ => self.clone(),
         }
     }
 }
@@ -65,6 +101,13 @@ pub struct ModelSettings {
     pub backend_id: Option<String>,
     pub context_size: Option<u32>,
     pub max_output_tokens: Option<usize>,
+    /// Extra stop strings on top of the chat template's own; output ends before the match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+    /// Token ids that end generation in addition to the model's own EOG tokens, for GGUFs
+    /// whose EOS/EOT tokens are mis-tagged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_eog_token_ids: Vec<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub draft_model: Option<String>,
     #[serde(default)]
@@ -89,6 +132,40 @@ pub struct ModelSettings {
     pub chat_template: ChatTemplate,
     #[serde(default = "default_true")]
     pub enable_thinking: bool,
+    /// Ask emulated-tool models to call tools as `{"tool": ..., "args": {...}}` JSON
+    /// objects instead of only `$ command` lines.
+    #[serde(default)]
+    pub emulator_json_mode: bool,
+    /// Longest emulated `execute_typescript` block that is still run when output ends
+    /// before its closing fence; a longer one is shown as text instead. Unset uses
+    /// `DEFAULT_EMULATOR_MAX_CODE_CHARS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emulator_max_code_chars: Option<usize>,
+    /// Feed the concatenated message text to the model without a chat template and
+    /// stream its output as-is, for base models that have no template. Tools are ignored.
+    #[serde(default)]
+    pub raw_completion: bool,
+    /// When generation fills the context, drop the oldest turns from the KV cache while
+    /// keeping the system prompt, instead of failing with a context-length error.
+    #[serde(default)]
+    pub context_shift: bool,
+    /// Skip the memory-based context ceiling, for systems where the free-memory estimate
+    /// underreports (e.g. unified memory). `context_size` and the training limit still apply.
+    #[serde(default)]
+    pub ignore_memory_context_cap: bool,
+    /// Keep the model resident: it is never unloaded to make room for another model or
+    /// after sitting idle, so a competing load fails instead.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Return each generated token's logprob (and the `top_logprobs` most likely
+    /// alternatives) on the usage stats. Off by default to skip the per-token softmax.
+    #[serde(default)]
+    pub return_logprobs: bool,
+    #[serde(default)]
+    pub top_logprobs: usize,
+    /// Stream a live output-token count every this many generated tokens. Off when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_update_interval: Option<usize>,
     /// Whether this model architecture supports vision input.
     /// Derived from associated mmproj metadata, not user-configurable.
     #[serde(default)]
@@ -100,8 +177,16 @@ pub struct ModelSettings {
     /// Size of the mmproj file in bytes, used for memory accounting.
     #[serde(default)]
     pub mmproj_size_bytes: u64,
+    /// User-supplied multimodal projector for vision GGUFs outside the featured list
+    /// (LLaVA, Qwen-VL, ...). Takes precedence over the registry's mmproj path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmproj_path: Option<PathBuf>,
 }
 
+/// Cap on an unterminated emulated code block run at the end of output, so a runaway
+/// generation is never executed whole.
+pub const DEFAULT_EMULATOR_MAX_CODE_CHARS: usize = 16 * 1024;
+
 fn default_true() -> bool {
     true
 }
@@ -124,6 +209,8 @@ impl Default for ModelSettings {
             backend_id: None,
             context_size: None,
             max_output_tokens: None,
+            stop_sequences: Vec::new(),
+            extra_eog_token_ids: Vec::new(),
             draft_model: None,
             sampling: SamplingConfig::default(),
             repeat_penalty: 1.0,
@@ -138,9 +225,40 @@ impl Default for ModelSettings {
             tool_calling: ToolCallingMode::Auto,
             chat_template: ChatTemplate::Embedded,
             enable_thinking: true,
+            emulator_json_mode: false,
+            emulator_max_code_chars: None,
+            raw_completion: false,
+            context_shift: false,
+            ignore_memory_context_cap: false,
+            pinned: false,
+            return_logprobs: false,
+            top_logprobs: 0,
+            usage_update_interval: None,
             vision_capable: false,
             image_token_estimate: default_image_token_estimate(),
             mmproj_size_bytes: 0,
+            mmproj_path: None,
+        }
+    }
+}
+
+impl ModelSettings {
+    pub fn max_emulated_code_chars(&self) -> usize {
+        self.emulator_max_code_chars
+            .unwrap_or(DEFAULT_EMULATOR_MAX_CODE_CHARS)
+    }
+
+    /// `Auto` is only settled against the loaded chat template, so only models pinned to
+    /// native tool calling are known to support it up front.
+    pub fn supports_native_tools(&self) -> bool {
+        !self.raw_completion && self.tool_calling == ToolCallingMode::ForceNative
+    }
+
+    /// Settings for a single request whose `ModelConfig` carries its own temperature.
+    pub fn with_temperature_override(&self, temperature: Option<f32>) -> Self {
+        Self {
+            sampling: self.sampling.with_temperature_override(temperature),
+            ..self.clone()
         }
     }
 }
@@ -227,10 +345,50 @@ pub fn default_settings_for_model(model_id: &str) -> ModelSettings {
             ToolCallingMode::Auto
         },
         vision_capable: featured.is_some_and(|m| m.mmproj.is_some()),
+        sampling: default_sampling_for_model(model_id),
         ..ModelSettings::default()
     }
 }
 
+/// Family-appropriate sampling for a newly added model, guessed from its name.
+///
+/// Only seeds the registry entry; users can change it like any other setting.
+/// Very low-bit quantizations are noisier, so they get a slightly cooler temperature.
+pub fn default_sampling_for_model(model_id: &str) -> SamplingConfig {
+    let (repo, quant) = model_id.split_once(':').unwrap_or((model_id, ""));
+    let name = repo.to_ascii_lowercase();
+    let has = |needle: &str| name.contains(needle);
+
+    let (temperature, top_k, top_p) = if has("coder") || has("code") {
+        (0.2, 40, 0.95)
+    } else if has("qwq") || has("-r1") || has("reasoning") || has("thinking") {
+        (0.6, 40, 0.95)
+    } else if has("instruct") || has("-it-") || name.ends_with("-it") || has("chat") {
+        (0.7, 40, 0.9)
+    } else {
+        return SamplingConfig::default();
+    };
+
+    let quant = quant.to_ascii_uppercase();
+    let low_bit = ["Q2", "Q3", "IQ1", "IQ2", "IQ3"]
+        .iter()
+        .any(|prefix| quant.starts_with(prefix));
+    let temperature: f32 = if low_bit {
+        (temperature - 0.1_f32).max(0.1)
+    } else {
+        temperature
+    };
+
+    SamplingConfig::Temperature {
+        temperature,
+        top_k,
+        top_p,
+        min_p: 0.05,
+        seed: None,
+        min_keep: default_min_keep(),
+    }
+}
+
 /// Look up the `MmprojSpec` for a featured model by its model ID.
 pub fn featured_mmproj_spec(model_id: &str) -> Option<&'static MmprojSpec> {
     use super::hf_models::parse_model_spec;
@@ -252,6 +410,12 @@ pub fn mmproj_local_path(repo_id: &str, filename: &str) -> PathBuf {
     Paths::in_data_dir("models").join(repo_name).join(filename)
 }
 
+/// Embedding GGUFs (nomic-embed, bge, e5, ...) are conventionally named after their purpose.
+pub fn is_embedding_model_repo(repo_id: &str) -> bool {
+    let name = repo_id.to_ascii_lowercase();
+    name.contains("embed") || name.contains("/bge-") || name.contains("/e5-")
+}
+
 /// Check if a model ID corresponds to a featured model.
 pub fn is_featured_model(model_id: &str) -> bool {
     use super::hf_models::parse_model_spec;
@@ -290,6 +454,10 @@ pub struct ShardFile {
     pub size_bytes: u64,
 }
 
+/// A registered model. Only durable facts about the model live here: download progress
+/// is owned by the download manager and exposed through `download_status()` /
+/// `is_downloading()`, and must never become a serialized field, or `save()` would
+/// persist progress that is stale as soon as it is written.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalModelEntry {
     pub id: String,
@@ -323,6 +491,9 @@ pub struct LocalModelEntry {
     pub mmproj_checked: bool,
     #[serde(default)]
     pub shard_files: Vec<ShardFile>,
+    /// Whether this model produces embeddings rather than chat completions.
+    #[serde(default)]
+    pub embedding_capable: bool,
 }
 
 impl LocalModelEntry {
@@ -416,8 +587,17 @@ impl LocalModelEntry {
         ModelDownloadStatus::NotDownloaded
     }
 
+    /// The projector to load for this model: a user-configured one wins over the
+    /// registry's featured/downloaded mmproj.
+    pub fn effective_mmproj_path(&self) -> Option<&std::path::Path> {
+        self.settings
+            .mmproj_path
+            .as_deref()
+            .or(self.mmproj_path.as_deref())
+    }
+
     pub fn has_vision(&self) -> bool {
-        self.mmproj_path.as_ref().is_some_and(|p| p.exists())
+        self.effective_mmproj_path().is_some_and(|p| p.exists())
     }
 
     pub fn mmproj_download_status(&self) -> ModelDownloadStatus {
@@ -451,6 +631,11 @@ impl LocalModelEntry {
             return self.size_bytes;
         }
         path_size(&self.local_path)
+            + self
+                .shard_files
+                .iter()
+                .map(|shard| path_size(&shard.local_path))
+                .sum::<u64>()
     }
 }
 
@@ -466,11 +651,65 @@ pub enum ModelDownloadStatus {
     Downloaded,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Schema version written to `registry.json`. Files without a `version` field are v0.
+const REGISTRY_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalModelRegistry {
+    #[serde(default)]
+    pub version: u32,
     pub models: Vec<LocalModelEntry>,
 }
 
+impl Default for LocalModelRegistry {
+    fn default() -> Self {
+        Self {
+            version: REGISTRY_VERSION,
+            models: Vec::new(),
+        }
+    }
+}
+
+/// Computed download state that older registry files may carry on an entry.
+const TRANSIENT_ENTRY_FIELDS: &[&str] = &[
+    "status",
+    "download_status",
+    "is_downloading",
+    "progress",
+    "mmproj_download_status",
+];
+
+/// Parse registry JSON, upgrading older schema versions to [`REGISTRY_VERSION`]. Returns
+/// whether the file needs rewriting in the current format.
+fn parse_registry(contents: &str) -> Result<(LocalModelRegistry, bool)> {
+    let mut value: serde_json::Value = serde_json::from_str(contents)?;
+    let file_version = value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .map_or(0, |v| u32::try_from(v).unwrap_or(u32::MAX));
+    if file_version > REGISTRY_VERSION {
+        tracing::warn!(
+            "Model registry version {file_version} is newer than supported version {REGISTRY_VERSION}; loading read-only"
+        );
+        return Ok((serde_json::from_value(value)?, false));
+    }
+
+    let mut migrated = file_version < REGISTRY_VERSION;
+    // Stray computed state can appear in any version; entry fields added since v0 fill
+    // in from their serde defaults.
+    if let Some(models) = value.get_mut("models").and_then(|m| m.as_array_mut()) {
+        for entry in models.iter_mut().filter_map(|m| m.as_object_mut()) {
+            for field in TRANSIENT_ENTRY_FIELDS {
+                migrated |= entry.remove(*field).is_some();
+            }
+        }
+    }
+
+    let mut registry: LocalModelRegistry = serde_json::from_value(value)?;
+    registry.version = REGISTRY_VERSION;
+    Ok((registry, migrated))
+}
+
 impl LocalModelRegistry {
     fn registry_path() -> PathBuf {
         Paths::in_data_dir("models/registry.json")
@@ -484,7 +723,12 @@ impl LocalModelRegistry {
             fs2::FileExt::lock_shared(&lock_file)?;
             let contents = std::fs::read_to_string(&path)?;
             fs2::FileExt::unlock(&lock_file)?;
-            let registry: LocalModelRegistry = serde_json::from_str(&contents)?;
+            let (registry, migrated) = parse_registry(&contents)?;
+            if migrated {
+                if let Err(e) = registry.save() {
+                    tracing::warn!("Failed to rewrite migrated model registry: {e}");
+                }
+            }
             Ok(registry)
         } else {
             Ok(Self::default())
@@ -492,6 +736,13 @@ impl LocalModelRegistry {
     }
 
     pub fn save(&self) -> Result<()> {
+        // Fields this version doesn't know would be dropped on the way back out.
+        if self.version > REGISTRY_VERSION {
+            anyhow::bail!(
+                "Model registry version {} was written by a newer goose; refusing to overwrite it",
+                self.version
+            );
+        }
         let path = Self::registry_path();
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
@@ -568,6 +819,46 @@ impl LocalModelRegistry {
         }
     }
 
+    /// Replace `old_id` with `new_id`, a downloaded variant of the same repo. The entry
+    /// keeps its settings but takes the new id, files and size; the old files are deleted.
+    pub fn replace_with_variant(&mut self, old_id: &str, new_id: &str) -> Result<()> {
+        let plan = self.variant_swap_plan(old_id, new_id)?;
+        delete_model_artifacts(&plan)?;
+        self.swap_variant_entry(old_id, new_id);
+        self.save()
+    }
+
+    /// Check that `new_id` can replace `old_id` and plan the deletion of the old files.
+    fn variant_swap_plan(&self, old_id: &str, new_id: &str) -> Result<ModelDeletionPlan> {
+        let replacement = self
+            .get_model(new_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", new_id))?;
+        let old = self
+            .get_model(old_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", old_id))?;
+        if old.repo_id != replacement.repo_id {
+            anyhow::bail!("{} is not a variant of {}", new_id, old.repo_id);
+        }
+        if !replacement.is_downloaded() {
+            anyhow::bail!("Model {} has not finished downloading", new_id);
+        }
+
+        self.deletion_plan(old_id)
+    }
+
+    fn swap_variant_entry(&mut self, old_id: &str, new_id: &str) {
+        let Some(index) = self.models.iter().position(|m| m.id == new_id) else {
+            return;
+        };
+        let replacement = self.models.remove(index);
+        if let Some(entry) = self.models.iter_mut().find(|m| m.id == old_id) {
+            *entry = LocalModelEntry {
+                settings: std::mem::take(&mut entry.settings),
+                ..replacement
+            };
+        }
+    }
+
     fn deletion_plan(&self, id: &str) -> Result<ModelDeletionPlan> {
         let entry = self
             .get_model(id)
@@ -599,12 +890,16 @@ impl LocalModelRegistry {
         self.models.iter().find(|m| m.id == id).map(|m| &m.settings)
     }
 
-    pub fn update_model_settings(&mut self, id: &str, settings: ModelSettings) -> Result<()> {
+    pub fn update_model_settings(&mut self, id: &str, mut settings: ModelSettings) -> Result<()> {
         let entry = self
             .models
             .iter_mut()
             .find(|m| m.id == id)
             .ok_or_else(|| anyhow::anyhow!("Model not found: {}", id))?;
+        if let Some(path) = &settings.mmproj_path {
+            settings.vision_capable = true;
+            settings.mmproj_size_bytes = path_size(path);
+        }
         entry.settings = settings;
         self.save()
     }
@@ -675,6 +970,43 @@ mod tests {
     use super::*;
     use crate::download_manager::DownloadProgress;
 
+    fn default_temperature(model_id: &str) -> Option<f32> {
+        match default_sampling_for_model(model_id) {
+            SamplingConfig::Temperature { temperature, .. } => Some(temperature),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn default_sampling_follows_model_family() {
+        for (model_id, expected) in [
+            ("bartowski/Qwen2.5-Coder-7B-Instruct-GGUF:Q4_K_M", 0.2),
+            ("bartowski/QwQ-32B-GGUF:Q4_K_M", 0.6),
+            ("unsloth/DeepSeek-R1-Distill-Qwen-7B-GGUF:Q4_K_M", 0.6),
+            ("bartowski/Mistral-Small-24B-Instruct-2501-GGUF:Q4_K_M", 0.7),
+            ("unsloth/gemma-4-E4B-it-GGUF:Q4_K_M", 0.7),
+            ("bartowski/Hermes-2-Pro-Mistral-7B-GGUF:Q4_K_M", 0.8),
+            ("bartowski/Qwen2.5-Coder-7B-Instruct-GGUF:Q2_K", 0.1),
+            ("bartowski/Mistral-7B-Instruct-v0.3-GGUF:IQ3_XS", 0.6),
+        ] {
+            let temperature = default_temperature(model_id).unwrap();
+            assert!(
+                (temperature - expected).abs() < 1e-6,
+                "{model_id}: expected {expected}, got {temperature}"
+            );
+        }
+    }
+
+    #[test]
+    fn registry_defaults_use_family_sampling() {
+        let settings =
+            default_settings_for_model("bartowski/Qwen2.5-Coder-7B-Instruct-GGUF:Q4_K_M");
+        assert!(matches!(
+            settings.sampling,
+            SamplingConfig::Temperature { temperature, .. } if (temperature - 0.2).abs() < 1e-6
+        ));
+    }
+
     fn test_entry(id: &str) -> LocalModelEntry {
         LocalModelEntry {
             id: id.to_string(),
@@ -692,9 +1024,31 @@ mod tests {
             mmproj_size_bytes: 0,
             mmproj_checked: false,
             shard_files: vec![],
+            embedding_capable: false,
         }
     }
 
+    #[test]
+    fn file_size_counts_every_shard() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("model-00001-of-00002.gguf");
+        let second = dir.path().join("model-00002-of-00002.gguf");
+        std::fs::write(&first, vec![0u8; 300]).unwrap();
+        std::fs::write(&second, vec![0u8; 200]).unwrap();
+        let mut entry = test_entry("test-file-size-counts-every-shard");
+        entry.local_path = first;
+        entry.shard_files = vec![ShardFile {
+            filename: "model-00002-of-00002.gguf".to_string(),
+            local_path: second,
+            source_url: "https://example.test/model-00002-of-00002.gguf".to_string(),
+            size_bytes: 200,
+        }];
+
+        assert_eq!(entry.file_size(), 500);
+        entry.size_bytes = 1000;
+        assert_eq!(entry.file_size(), 1000);
+    }
+
     fn set_progress(entry: &LocalModelEntry, status: DownloadStatus) {
         get_download_manager().set_progress(DownloadProgress {
             model_id: format!("{}-model", entry.id),
@@ -705,6 +1059,7 @@ mod tests {
             speed_bps: None,
             eta_seconds: None,
             error: None,
+            final_size_bytes: None,
             task_exited: true,
         });
     }
@@ -726,6 +1081,92 @@ mod tests {
         get_download_manager().clear_completed(&download_id);
     }
 
+    #[test]
+    fn swap_variant_keeps_settings_and_takes_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut old = test_entry("test/repo:Q4_K_M");
+        old.local_path = dir.path().join("model-Q4_K_M.gguf");
+        old.size_bytes = 4;
+        old.settings.context_size = Some(16_384);
+        let mut new = test_entry("test/repo:Q6_K");
+        new.filename = "model-Q6_K.gguf".to_string();
+        new.quantization = "Q6_K".to_string();
+        new.local_path = dir.path().join("model-Q6_K.gguf");
+        new.size_bytes = 6;
+        std::fs::write(&old.local_path, b"q4").unwrap();
+        std::fs::write(&new.local_path, b"q6").unwrap();
+
+        let mut registry = LocalModelRegistry {
+            models: vec![old.clone(), new.clone()],
+            ..Default::default()
+        };
+        let plan = registry
+            .variant_swap_plan("test/repo:Q4_K_M", "test/repo:Q6_K")
+            .unwrap();
+        registry.swap_variant_entry("test/repo:Q4_K_M", "test/repo:Q6_K");
+
+        assert_eq!(plan.all_paths, vec![old.local_path]);
+        let [entry] = registry.list_models() else {
+            panic!("expected a single entry after the swap");
+        };
+        assert_eq!(entry.id, "test/repo:Q6_K");
+        assert_eq!(entry.filename, "model-Q6_K.gguf");
+        assert_eq!(entry.quantization, "Q6_K");
+        assert_eq!(entry.local_path, new.local_path);
+        assert_eq!(entry.size_bytes, 6);
+        assert_eq!(entry.settings.context_size, Some(16_384));
+    }
+
+    #[test]
+    fn swap_variant_rejects_other_repos_and_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = test_entry("test/repo:Q4_K_M");
+        let mut other = test_entry("other/repo:Q6_K");
+        other.repo_id = "other/repo".to_string();
+        other.local_path = dir.path().join("other.gguf");
+        std::fs::write(&other.local_path, b"q6").unwrap();
+        let pending = test_entry("test/repo:Q8_0");
+
+        let mut registry = LocalModelRegistry {
+            models: vec![old, other, pending],
+            ..Default::default()
+        };
+        assert!(registry
+            .replace_with_variant("test/repo:Q4_K_M", "other/repo:Q6_K")
+            .is_err());
+        assert!(registry
+            .replace_with_variant("test/repo:Q4_K_M", "test/repo:Q8_0")
+            .is_err());
+        assert_eq!(registry.list_models().len(), 3);
+    }
+
+    #[test]
+    fn replace_with_variant_keeps_the_entry_when_deletion_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut old = test_entry("test/repo:Q4_K_M");
+        // A directory in place of the model file makes `remove_file` fail.
+        old.local_path = dir.path().join("model-Q4_K_M.gguf");
+        std::fs::create_dir(&old.local_path).unwrap();
+        let mut new = test_entry("test/repo:Q6_K");
+        new.local_path = dir.path().join("model-Q6_K.gguf");
+        std::fs::write(&new.local_path, b"q6").unwrap();
+
+        let mut registry = LocalModelRegistry {
+            models: vec![old, new],
+            ..Default::default()
+        };
+        assert!(registry
+            .replace_with_variant("test/repo:Q4_K_M", "test/repo:Q6_K")
+            .is_err());
+
+        let ids: Vec<&str> = registry
+            .list_models()
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["test/repo:Q4_K_M", "test/repo:Q6_K"]);
+    }
+
     #[test]
     fn enrich_with_featured_mmproj_preserves_existing_downloaded_path() {
         let existing_path = std::env::temp_dir().join(format!(
@@ -756,4 +1197,189 @@ mod tests {
 
         let _ = std::fs::remove_file(existing_path);
     }
+
+    #[test]
+    fn settings_mmproj_path_overrides_registry_mmproj() {
+        let mut entry = test_entry("test-settings-mmproj-override");
+        entry.mmproj_path = Some(PathBuf::from("/tmp/featured-mmproj.gguf"));
+        assert_eq!(
+            entry.effective_mmproj_path(),
+            Some(std::path::Path::new("/tmp/featured-mmproj.gguf"))
+        );
+
+        entry.settings.mmproj_path = Some(PathBuf::from("/tmp/llava-mmproj.gguf"));
+        assert_eq!(
+            entry.effective_mmproj_path(),
+            Some(std::path::Path::new("/tmp/llava-mmproj.gguf"))
+        );
+    }
+
+    #[test]
+    fn settings_without_mmproj_path_deserialize() {
+        let settings: ModelSettings = serde_json::from_str("{}").unwrap();
+        assert!(settings.mmproj_path.is_none());
+        let json = serde_json::to_value(&settings).unwrap();
+        assert!(json.get("mmproj_path").is_none());
+    }
+
+    #[test]
+    fn native_tool_support_follows_tool_calling_mode() {
+        assert!(
+            default_settings_for_model("unsloth/gemma-4-E4B-it-GGUF:Q4_K_M")
+                .supports_native_tools()
+        );
+        assert!(
+            !default_settings_for_model("bartowski/Llama-3.2-1B-Instruct-GGUF:Q4_K_M")
+                .supports_native_tools()
+        );
+
+        let emulated = ModelSettings {
+            tool_calling: ToolCallingMode::ForceEmulated,
+            ..ModelSettings::default()
+        };
+        assert!(!emulated.supports_native_tools());
+
+        let raw = ModelSettings {
+            tool_calling: ToolCallingMode::ForceNative,
+            raw_completion: true,
+            ..ModelSettings::default()
+        };
+        assert!(!raw.supports_native_tools());
+    }
+
+    #[test]
+    fn temperature_override_replaces_configured_temperature() {
+        let settings = ModelSettings {
+            sampling: SamplingConfig::Temperature {
+                temperature: 0.7,
+                top_k: 20,
+                top_p: 0.8,
+                min_p: 0.0,
+                seed: Some(7),
+                min_keep: 1,
+            },
+            ..ModelSettings::default()
+        };
+
+        match settings.with_temperature_override(Some(0.0)).sampling {
+            SamplingConfig::Temperature {
+                temperature,
+                top_k,
+                top_p,
+                seed,
+                ..
+            } => {
+                assert_eq!(temperature, 0.0);
+                assert_eq!((top_k, top_p, seed), (20, 0.8, Some(7)));
+            }
+            other => panic!("expected temperature sampling, got {other:?}"),
+        }
+
+        assert!(matches!(
+            settings.with_temperature_override(None).sampling,
+            SamplingConfig::Temperature { temperature, .. } if temperature == 0.7
+        ));
+
+        let greedy = ModelSettings {
+            sampling: SamplingConfig::Greedy,
+            ..ModelSettings::default()
+        };
+        assert!(matches!(
+            greedy.with_temperature_override(Some(1.0)).sampling,
+            SamplingConfig::Greedy
+        ));
+    }
+
+    #[test]
+    fn save_never_persists_computed_status() {
+        let registry = LocalModelRegistry {
+            models: vec![test_entry("test/model:Q4_K_M")],
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&registry).unwrap();
+        let entry = value["models"][0].as_object().unwrap();
+        for field in TRANSIENT_ENTRY_FIELDS {
+            assert!(!entry.contains_key(*field), "{field} was serialized");
+        }
+    }
+
+    #[test]
+    fn load_strips_stray_status_fields() {
+        let mut value = serde_json::to_value(LocalModelRegistry {
+            models: vec![test_entry("test/model:Q4_K_M")],
+            ..Default::default()
+        })
+        .unwrap();
+        value["models"][0]["status"] = serde_json::json!({
+            "Downloading": {
+                "progress_percent": 42.0,
+                "bytes_downloaded": 42,
+                "total_bytes": 100,
+                "speed_bps": 1
+            }
+        });
+
+        let (registry, stripped) = parse_registry(&value.to_string()).unwrap();
+        assert!(stripped);
+        assert_eq!(registry.models.len(), 1);
+        assert_eq!(registry.models[0].id, "test/model:Q4_K_M");
+        let resaved = serde_json::to_value(&registry).unwrap();
+        assert!(resaved["models"][0].get("status").is_none());
+
+        let (_, stripped) = parse_registry(&resaved.to_string()).unwrap();
+        assert!(!stripped);
+    }
+
+    #[test]
+    fn load_upgrades_unversioned_registry() {
+        let v0 = r#"{
+            "models": [{
+                "id": "test/model:Q4_K_M",
+                "repo_id": "test/repo",
+                "filename": "model.gguf",
+                "quantization": "Q4_K_M",
+                "local_path": "/tmp/model.gguf",
+                "source_url": "https://example.test/model.gguf",
+                "size_bytes": 42
+            }]
+        }"#;
+
+        let (registry, migrated) = parse_registry(v0).unwrap();
+        assert!(migrated);
+        assert_eq!(registry.version, REGISTRY_VERSION);
+        assert_eq!(registry.models.len(), 1);
+        let entry = &registry.models[0];
+        assert_eq!(entry.id, "test/model:Q4_K_M");
+        assert_eq!(entry.size_bytes, 42);
+        assert!(matches!(entry.storage, LocalModelStorage::GooseManaged));
+        assert!(entry.shard_files.is_empty());
+        assert!(!entry.settings.pinned);
+
+        let resaved = serde_json::to_value(&registry).unwrap();
+        assert_eq!(resaved["version"], REGISTRY_VERSION);
+        let (_, migrated) = parse_registry(&resaved.to_string()).unwrap();
+        assert!(!migrated);
+    }
+
+    #[test]
+    fn load_keeps_newer_registry_without_rewriting() {
+        let newer = format!(r#"{{"version": {}, "models": []}}"#, REGISTRY_VERSION + 1);
+        let (registry, migrated) = parse_registry(&newer).unwrap();
+        assert!(!migrated);
+        assert!(registry.models.is_empty());
+        assert!(registry.save().is_err());
+    }
+
+    #[test]
+    fn detects_embedding_model_repos() {
+        assert!(is_embedding_model_repo(
+            "nomic-ai/nomic-embed-text-v1.5-GGUF"
+        ));
+        assert!(is_embedding_model_repo(
+            "CompendiumLabs/bge-small-en-v1.5-gguf"
+        ));
+        assert!(!is_embedding_model_repo(
+            "bartowski/Llama-3.2-1B-Instruct-GGUF"
+        ));
+    }
 }
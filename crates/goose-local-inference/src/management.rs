@@ -1,37 +1,58 @@
 use super::hf_models::{
     self, register_resolved_model, resolve_local_model_selection, resolve_local_model_spec,
-    resolve_model_spec, HfGgufFile, HfModelInfo, HfModelVariant,
+    resolve_model_spec, HfGgufFile, HfModelInfo, HfModelVariant, KvCacheShape,
 };
+use super::llamacpp::{has_accelerator_device, read_context_length, read_kv_cache_shape};
 use super::local_model_registry::{
-    default_settings_for_model, featured_mmproj_spec, get_registry, model_id_from_repo,
-    ChatTemplate, LocalModelEntry, LocalModelStorage, ModelDownloadStatus, ModelSettings,
-    SamplingConfig, ToolCallingMode, FEATURED_MODELS,
+    default_settings_for_model, featured_mmproj_spec, get_registry, is_embedding_model_repo,
+    model_id_from_repo, ChatTemplate, LocalModelEntry, LocalModelStorage, ModelDownloadStatus,
+    ModelSettings, SamplingConfig, ToolCallingMode, FEATURED_MODELS,
 };
 use super::{
     available_inference_memory_bytes, builtin_chat_template_names, recommend_local_model,
-    InferenceRuntime,
+    DebugGeneration, InferenceRuntime, StreamTestEvent,
+};
+use crate::config_resolver;
+use crate::download_manager::{
+    get_download_manager, DownloadOptions, DownloadProgress, DownloadStatus,
 };
-use crate::download_manager::{get_download_manager, DownloadProgress, DownloadStatus};
 use crate::huggingface_auth;
 use crate::paths::Paths;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use futures::future::join_all;
+use goose_provider_types::conversation::message::{Message, MessageContent};
+use goose_provider_types::model::ModelConfig;
 use goose_sdk_types::custom_requests::{
     LocalInferenceBuiltinChatTemplatesListResponse, LocalInferenceChatTemplate,
-    LocalInferenceDownloadProgressDto, LocalInferenceDownloadState, LocalInferenceHfGgufFileDto,
-    LocalInferenceHfModelInfoDto, LocalInferenceHfModelVariantDto,
+    LocalInferenceDebugMessageDto, LocalInferenceDebugMessageRole, LocalInferenceDebugToolCallDto,
+    LocalInferenceDebugToolDto, LocalInferenceDownloadProgressDto, LocalInferenceDownloadState,
+    LocalInferenceHfGgufFileDto, LocalInferenceHfModelInfoDto, LocalInferenceHfModelVariantDto,
     LocalInferenceHuggingFaceRepoVariantsResponse, LocalInferenceHuggingFaceSearchResponse,
-    LocalInferenceModelDownloadRequest, LocalInferenceModelDownloadResponse,
-    LocalInferenceModelDownloadStatusDto, LocalInferenceModelDto, LocalInferenceModelSettingsDto,
-    LocalInferenceModelSettingsReadResponse, LocalInferenceModelSettingsUpdateResponse,
-    LocalInferenceModelsListResponse, LocalInferenceSamplingConfig, LocalInferenceToolCallingMode,
+    LocalInferenceLoadedModelDto, LocalInferenceModelCountTokensRequest,
+    LocalInferenceModelCountTokensResponse, LocalInferenceModelDebugGenerateRequest,
+    LocalInferenceModelDebugGenerateResponse, LocalInferenceModelDownloadRequest,
+    LocalInferenceModelDownloadResponse, LocalInferenceModelDownloadStatusDto,
+    LocalInferenceModelDto, LocalInferenceModelRequantizeResponse, LocalInferenceModelSettingsDto,
+    LocalInferenceModelSettingsPatchResponse, LocalInferenceModelSettingsReadResponse,
+    LocalInferenceModelSettingsRecommendedResponse, LocalInferenceModelSettingsUpdateResponse,
+    LocalInferenceModelStreamTestRequest, LocalInferenceModelStreamTestResponse,
+    LocalInferenceModelValidateSpecResponse, LocalInferenceModelsListResponse,
+    LocalInferenceModelsLoadedResponse, LocalInferenceSamplingConfig,
+    LocalInferenceSpecValidationStatus, LocalInferenceStreamEventDto, LocalInferenceStreamUsageDto,
+    LocalInferenceToolCallingMode,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
 
 static MANAGEMENT_RUNTIME: OnceLock<Arc<InferenceRuntime>> = OnceLock::new();
 
+/// Caps the transfer rate of model, shard and projector downloads.
+const DOWNLOAD_MAX_BYTES_PER_SECOND_KEY: &str = "GOOSE_LOCAL_DOWNLOAD_MAX_BYTES_PER_SECOND";
+/// How many ranged requests each GGUF file or projector download is split into.
+const DOWNLOAD_SEGMENTS_KEY: &str = "GOOSE_LOCAL_DOWNLOAD_SEGMENTS";
+const DEFAULT_DOWNLOAD_SEGMENTS: usize = 4;
+
 #[derive(Clone)]
 struct LocalModelSelection {
     repo_id: String,
@@ -73,9 +94,10 @@ pub async fn list_models() -> Result<LocalInferenceModelsListResponse> {
 pub async fn search_huggingface_models(
     query: String,
     limit: Option<usize>,
+    offset: Option<usize>,
 ) -> Result<LocalInferenceHuggingFaceSearchResponse> {
     let limit = limit.unwrap_or(20).min(50);
-    let models = hf_models::search_local_models(&query, limit)
+    let models = hf_models::search_local_models(&query, limit, offset.unwrap_or(0))
         .await?
         .into_iter()
         .map(hf_model_info_to_dto)
@@ -95,17 +117,17 @@ pub async fn huggingface_repo_variants(
         .filter(|variant| variant.backend_id == "llamacpp")
         .map(|variant| hf_models::HfQuantVariant {
             quantization: variant.variant_id.clone(),
-            size_bytes: variant.size_bytes,
+            size_bytes: variant.total_size_bytes.unwrap_or(variant.size_bytes),
             filename: variant.filename.clone().unwrap_or_default(),
             download_url: variant.download_url.clone().unwrap_or_default(),
             description: "",
             quality_rank: variant.quality_rank,
             sharded: variant.sharded,
+            mmproj: None,
         })
         .collect();
-    let recommended_index = hf_models::recommend_variant(&gguf_variants, available_memory);
 
-    let (downloaded_quants, downloaded_variants) = {
+    let (downloaded_quants, downloaded_variants, downloaded_paths) = {
         let registry = get_registry()
             .lock()
             .map_err(|_| anyhow!("Failed to acquire registry lock"))?;
@@ -117,8 +139,21 @@ pub async fn huggingface_repo_variants(
         (
             models.iter().map(|m| m.quantization.clone()).collect(),
             models.iter().map(|m| m.id.clone()).collect(),
+            models
+                .iter()
+                .map(|m| m.local_path.clone())
+                .collect::<Vec<_>>(),
         )
     };
+    // Attention dimensions are only known once a GGUF is on disk. Every quantization of a
+    // repo shares them, so a downloaded variant lets the smallest recommended context be
+    // reserved alongside the weights.
+    let target_context = downloaded_paths
+        .iter()
+        .find_map(|path| read_kv_cache_shape(path))
+        .map(|shape| (MIN_RECOMMENDED_CONTEXT as u64, shape));
+    let recommended_index =
+        hf_models::recommend_variant(&gguf_variants, available_memory, target_context);
 
     Ok(LocalInferenceHuggingFaceRepoVariantsResponse {
         variants: variants.into_iter().map(hf_model_variant_to_dto).collect(),
@@ -131,6 +166,15 @@ pub async fn huggingface_repo_variants(
 
 pub async fn download_model(
     req: LocalInferenceModelDownloadRequest,
+) -> Result<LocalInferenceModelDownloadResponse> {
+    start_model_download(req, |_| {}).await
+}
+
+/// `on_registered` runs with the model id once the download has finished and the model
+/// is in the registry. It is dropped if a download for the same model is already running.
+async fn start_model_download(
+    req: LocalInferenceModelDownloadRequest,
+    on_registered: impl FnOnce(&str) + Send + 'static,
 ) -> Result<LocalInferenceModelDownloadResponse> {
     let selection = explicit_model_selection(&req)?;
     let model_id = local_model_id_from_request(&req, selection.as_ref()).await?;
@@ -144,6 +188,7 @@ pub async fn download_model(
         speed_bps: None,
         eta_seconds: None,
         error: None,
+        final_size_bytes: None,
         task_exited: false,
     })?;
     if !download_reserved {
@@ -174,8 +219,9 @@ pub async fn download_model(
                 if !model_download_completed(&model_id_for_task) {
                     return;
                 }
-                if let Err(error) = register_resolved_model(resolved, &spec) {
-                    mark_download_failed(&model_id_for_task, error);
+                match register_resolved_model(resolved, &spec) {
+                    Ok(_) => on_registered(&model_id_for_task),
+                    Err(error) => mark_download_failed(&model_id_for_task, error),
                 }
             }
             Err(error) => mark_download_failed(&model_id_for_task, error),
@@ -185,6 +231,119 @@ pub async fn download_model(
     Ok(LocalInferenceModelDownloadResponse { model_id })
 }
 
+pub async fn requantize_model(
+    model_id: &str,
+    quantization: String,
+) -> Result<LocalInferenceModelRequantizeResponse> {
+    let (repo_id, new_id, new_downloaded) = {
+        let registry = get_registry()
+            .lock()
+            .map_err(|_| anyhow!("Failed to acquire registry lock"))?;
+        let entry = registry
+            .get_model(model_id)
+            .ok_or_else(|| anyhow!("Model not found"))?;
+        if entry
+            .backend_id
+            .as_deref()
+            .is_some_and(|backend| backend != "llamacpp")
+        {
+            bail!("Only llama.cpp models can change quantization");
+        }
+        if !entry.is_downloaded() {
+            bail!("Model '{}' is not downloaded", model_id);
+        }
+        let new_id = model_id_from_repo(&entry.repo_id, &quantization);
+        if new_id == model_id {
+            bail!("Model '{}' already uses {}", model_id, quantization);
+        }
+        let new_entry = registry.get_model(&new_id);
+        if new_entry.is_some_and(|m| m.is_downloading()) {
+            bail!("Model '{}' is already downloading", new_id);
+        }
+        (
+            entry.repo_id.clone(),
+            new_id,
+            new_entry.is_some_and(|m| m.is_downloaded()),
+        )
+    };
+
+    if new_downloaded {
+        get_registry()
+            .lock()
+            .map_err(|_| anyhow!("Failed to acquire registry lock"))?
+            .replace_with_variant(model_id, &new_id)?;
+        return Ok(LocalInferenceModelRequantizeResponse { model_id: new_id });
+    }
+
+    let old_id = model_id.to_string();
+    let download = start_model_download(
+        LocalInferenceModelDownloadRequest {
+            spec: repo_id,
+            backend_id: Some("llamacpp".to_string()),
+            variant_id: Some(quantization),
+        },
+        move |new_id| {
+            let swapped = get_registry()
+                .lock()
+                .map_err(|_| anyhow!("Failed to acquire registry lock"))
+                .and_then(|mut registry| registry.replace_with_variant(&old_id, new_id));
+            if let Err(e) = swapped {
+                tracing::warn!(model_id = %old_id, new_model_id = %new_id, error = %e, "Failed to swap model quantization");
+            }
+        },
+    )
+    .await?;
+    Ok(LocalInferenceModelRequantizeResponse {
+        model_id: download.model_id,
+    })
+}
+
+pub async fn validate_model_spec(spec: &str) -> Result<LocalInferenceModelValidateSpecResponse> {
+    Ok(match hf_models::validate_model_spec(spec).await? {
+        hf_models::ModelSpecValidation::Valid {
+            repo_id,
+            quantization,
+            size_bytes,
+        } => LocalInferenceModelValidateSpecResponse {
+            status: LocalInferenceSpecValidationStatus::Valid,
+            repo_id: Some(repo_id),
+            quantization: Some(quantization),
+            size_bytes: Some(size_bytes),
+            ..Default::default()
+        },
+        hf_models::ModelSpecValidation::InvalidFormat { message } => {
+            LocalInferenceModelValidateSpecResponse {
+                status: LocalInferenceSpecValidationStatus::InvalidFormat,
+                message: Some(message),
+                ..Default::default()
+            }
+        }
+        hf_models::ModelSpecValidation::RepoNotFound { repo_id, message } => {
+            LocalInferenceModelValidateSpecResponse {
+                status: LocalInferenceSpecValidationStatus::RepoNotFound,
+                repo_id: Some(repo_id),
+                message: Some(message),
+                ..Default::default()
+            }
+        }
+        hf_models::ModelSpecValidation::QuantizationNotFound {
+            repo_id,
+            quantization,
+            available,
+        } => LocalInferenceModelValidateSpecResponse {
+            status: LocalInferenceSpecValidationStatus::QuantizationNotFound,
+            message: Some(format!(
+                "No GGUF file with quantization '{}' found in {}",
+                quantization, repo_id
+            )),
+            repo_id: Some(repo_id),
+            quantization: Some(quantization),
+            available_quantizations: available,
+            ..Default::default()
+        },
+    })
+}
+
 pub fn download_progress(model_id: &str) -> Result<Option<LocalInferenceDownloadProgressDto>> {
     Ok(get_download_manager()
         .get_progress(&format!("{}-model", model_id))
@@ -222,6 +381,19 @@ pub async fn evict_model(model_id: &str) -> Result<()> {
         .map_err(|error| anyhow!(error.to_string()))
 }
 
+pub fn list_loaded_models() -> LocalInferenceModelsLoadedResponse {
+    let models = crate::loaded_models()
+        .into_iter()
+        .map(|model| LocalInferenceLoadedModelDto {
+            model_id: model.model_id,
+            backend_id: model.backend_id.to_string(),
+            size_bytes: model.size_bytes,
+            in_use: model.in_use,
+        })
+        .collect();
+    LocalInferenceModelsLoadedResponse { models }
+}
+
 pub fn get_model_settings(model_id: &str) -> Result<LocalInferenceModelSettingsReadResponse> {
     let registry = get_registry()
         .lock()
@@ -248,13 +420,320 @@ pub fn update_model_settings(
     })
 }
 
+/// Applied under the registry lock so concurrent patches to different fields don't
+/// overwrite each other.
+pub fn patch_model_settings(
+    model_id: &str,
+    patch: HashMap<String, serde_json::Value>,
+) -> Result<LocalInferenceModelSettingsPatchResponse> {
+    let mut registry = get_registry()
+        .lock()
+        .map_err(|_| anyhow!("Failed to acquire registry lock"))?;
+    let current = registry
+        .get_model_settings(model_id)
+        .ok_or_else(|| anyhow!("Model not found"))?;
+    let settings = apply_settings_patch(current, patch)?;
+    registry.update_model_settings(model_id, settings.clone())?;
+    Ok(LocalInferenceModelSettingsPatchResponse {
+        settings: model_settings_to_dto(&settings),
+    })
+}
+
+fn apply_settings_patch(
+    current: &ModelSettings,
+    patch: HashMap<String, serde_json::Value>,
+) -> Result<ModelSettings> {
+    let mut merged = match serde_json::to_value(model_settings_to_dto(current))? {
+        serde_json::Value::Object(fields) => fields,
+        _ => bail!("Model settings did not serialize to an object"),
+    };
+    merged.extend(patch);
+    let dto: LocalInferenceModelSettingsDto =
+        serde_json::from_value(serde_json::Value::Object(merged))
+            .map_err(|e| anyhow!("Invalid settings patch: {e}"))?;
+    Ok(model_settings_from_dto(dto))
+}
+
+/// Share of memory the weights may take when the model only partly fits; the rest is left
+/// for the KV cache of the offloaded layers.
+const PARTIAL_OFFLOAD_WEIGHTS_SHARE: f64 = 0.85;
+const RECOMMENDED_CONTEXT_STEP: usize = 1024;
+const MIN_RECOMMENDED_CONTEXT: usize = 2048;
+/// Families whose chat templates reliably render tool calls, for models outside the
+/// featured list.
+const NATIVE_TOOL_FAMILIES: &[&str] = &[
+    "qwen2.5",
+    "qwen3",
+    "gemma-4",
+    "llama-3.1",
+    "llama-3.3",
+    "mistral-nemo",
+    "functionary",
+];
+
+struct ModelFootprint {
+    weights_bytes: u64,
+    mmproj_bytes: u64,
+    kv_shape: Option<KvCacheShape>,
+    /// Context length the model was trained with; larger contexts degrade output.
+    trained_context: Option<u64>,
+}
+
+struct MachineMemory {
+    available_bytes: u64,
+    has_accelerator: bool,
+}
+
+pub fn recommended_model_settings(
+    model_id: &str,
+) -> Result<LocalInferenceModelSettingsRecommendedResponse> {
+    let (current, footprint) = {
+        let registry = get_registry()
+            .lock()
+            .map_err(|_| anyhow!("Failed to acquire registry lock"))?;
+        let entry = registry
+            .get_model(model_id)
+            .ok_or_else(|| anyhow!("Model not found"))?;
+        let footprint = ModelFootprint {
+            weights_bytes: entry.size_bytes,
+            mmproj_bytes: entry.mmproj_size_bytes,
+            kv_shape: read_kv_cache_shape(&entry.local_path),
+            trained_context: read_context_length(&entry.local_path),
+        };
+        (entry.settings.clone(), footprint)
+    };
+    let runtime = management_runtime()?;
+    let machine = MachineMemory {
+        available_bytes: available_inference_memory_bytes(&runtime),
+        has_accelerator: has_accelerator_device(),
+    };
+    let settings = recommend_model_settings(&current, model_id, &footprint, &machine);
+    Ok(LocalInferenceModelSettingsRecommendedResponse {
+        settings: model_settings_to_dto(&settings),
+    })
+}
+
+fn recommend_model_settings(
+    current: &ModelSettings,
+    model_id: &str,
+    footprint: &ModelFootprint,
+    machine: &MachineMemory,
+) -> ModelSettings {
+    let defaults = default_settings_for_model(model_id);
+    let usable = machine
+        .available_bytes
+        .saturating_sub(footprint.mmproj_bytes);
+    let fits = footprint.weights_bytes <= usable;
+
+    let n_gpu_layers = if !machine.has_accelerator {
+        Some(0)
+    } else if fits {
+        None
+    } else {
+        footprint
+            .kv_shape
+            .map(|shape| {
+                let share =
+                    usable as f64 * PARTIAL_OFFLOAD_WEIGHTS_SHARE / footprint.weights_bytes as f64;
+                (shape.n_layer as f64 * share) as u32
+            })
+            .or(current.n_gpu_layers)
+    };
+
+    let kv_budget = if fits {
+        usable - footprint.weights_bytes
+    } else {
+        (usable as f64 * (1.0 - PARTIAL_OFFLOAD_WEIGHTS_SHARE)) as u64
+    };
+    let context_size = footprint
+        .kv_shape
+        .and_then(|shape| shape.max_context_for_memory(kv_budget))
+        .map(|tokens| {
+            (tokens / RECOMMENDED_CONTEXT_STEP * RECOMMENDED_CONTEXT_STEP)
+                .max(MIN_RECOMMENDED_CONTEXT) as u32
+        })
+        .or(current.context_size)
+        .map(|size| match footprint.trained_context {
+            Some(trained) => size.min(u32::try_from(trained).unwrap_or(u32::MAX)),
+            None => size,
+        });
+
+    let name = model_id.to_ascii_lowercase();
+    let tool_calling = if defaults.supports_native_tools()
+        || NATIVE_TOOL_FAMILIES
+            .iter()
+            .any(|family| name.contains(family))
+    {
+        ToolCallingMode::ForceNative
+    } else {
+        ToolCallingMode::Auto
+    };
+
+    ModelSettings {
+        context_size,
+        n_gpu_layers,
+        tool_calling,
+        sampling: defaults.sampling,
+        ..current.clone()
+    }
+}
+
+pub async fn debug_generate(
+    request: LocalInferenceModelDebugGenerateRequest,
+) -> Result<LocalInferenceModelDebugGenerateResponse> {
+    if !model_exists(&request.model_id)? {
+        return Err(anyhow!("Model not found: {}", request.model_id));
+    }
+    let messages = debug_messages(request.messages);
+    let tools = debug_tools(request.tools)?;
+    let model_config = ModelConfig::new(&request.model_id).with_max_tokens(request.max_tokens);
+
+    let generation =
+        super::debug_generate(&model_config, &request.system, &messages, &tools).await?;
+    Ok(debug_generation_to_dto(generation))
+}
+
+pub async fn count_tokens(
+    request: LocalInferenceModelCountTokensRequest,
+) -> Result<LocalInferenceModelCountTokensResponse> {
+    if !model_exists(&request.model_id)? {
+        return Err(anyhow!("Model not found: {}", request.model_id));
+    }
+    let messages = debug_messages(request.messages);
+    let tools = debug_tools(request.tools)?;
+    let model_config = ModelConfig::new(&request.model_id);
+
+    let count =
+        super::count_prompt_tokens(&model_config, &request.system, &messages, &tools).await?;
+    Ok(LocalInferenceModelCountTokensResponse {
+        token_count: count.token_count,
+        context_limit: count.context_limit,
+    })
+}
+
+/// Run a stream test, passing each chunk to `on_chunk` as it is produced. `on_chunk` returns
+/// `false` once the chunk can't be delivered, which stops generation.
+pub async fn stream_test(
+    request: LocalInferenceModelStreamTestRequest,
+    mut on_chunk: impl FnMut(LocalInferenceStreamEventDto) -> bool,
+) -> Result<LocalInferenceModelStreamTestResponse> {
+    if !model_exists(&request.model_id)? {
+        return Err(anyhow!("Model not found: {}", request.model_id));
+    }
+    let messages = debug_messages(request.messages);
+    let tools = debug_tools(request.tools)?;
+    let model_config = ModelConfig::new(&request.model_id).with_max_tokens(request.max_tokens);
+
+    let mut chunk_count = 0;
+    super::stream_test(&model_config, &request.system, &messages, &tools, |event| {
+        chunk_count += 1;
+        on_chunk(stream_event_to_dto(event))
+    })
+    .await?;
+    Ok(LocalInferenceModelStreamTestResponse { chunk_count })
+}
+
+fn debug_messages(messages: Vec<LocalInferenceDebugMessageDto>) -> Vec<Message> {
+    messages
+        .into_iter()
+        .map(|message| match message.role {
+            LocalInferenceDebugMessageRole::User => Message::user().with_text(message.content),
+            LocalInferenceDebugMessageRole::Assistant => {
+                Message::assistant().with_text(message.content)
+            }
+        })
+        .collect()
+}
+
+fn debug_tools(tools: Vec<LocalInferenceDebugToolDto>) -> Result<Vec<rmcp::model::Tool>> {
+    tools
+        .into_iter()
+        .map(|tool| {
+            let serde_json::Value::Object(schema) = tool.input_schema else {
+                return Err(anyhow!("Tool {} has a non-object input schema", tool.name));
+            };
+            Ok(rmcp::model::Tool::new(
+                tool.name,
+                tool.description,
+                Arc::new(schema),
+            ))
+        })
+        .collect()
+}
+
+fn debug_generation_to_dto(
+    generation: DebugGeneration,
+) -> LocalInferenceModelDebugGenerateResponse {
+    let mut parsed = ParsedContent::default();
+    for content in generation.messages.iter().flat_map(|m| &m.content) {
+        parsed.push(content);
+    }
+    LocalInferenceModelDebugGenerateResponse {
+        raw_text: generation.raw_text,
+        applied_prompt: generation.applied_prompt,
+        text: parsed.text,
+        thinking: parsed.thinking,
+        tool_calls: parsed.tool_calls,
+        tool_call_errors: parsed.tool_call_errors,
+    }
+}
+
+fn stream_event_to_dto(event: StreamTestEvent) -> LocalInferenceStreamEventDto {
+    let mut parsed = ParsedContent::default();
+    for content in event.message.iter().flat_map(|m| &m.content) {
+        parsed.push(content);
+    }
+    LocalInferenceStreamEventDto {
+        text: parsed.text,
+        thinking: parsed.thinking,
+        tool_calls: parsed.tool_calls,
+        tool_call_errors: parsed.tool_call_errors,
+        usage: event.usage.map(|usage| LocalInferenceStreamUsageDto {
+            input_tokens: usage.usage.input_tokens,
+            output_tokens: usage.usage.output_tokens,
+            total_tokens: usage.usage.total_tokens,
+        }),
+        elapsed_ms: event.elapsed.as_millis() as u64,
+    }
+}
+
+/// Message content split the way the debug responses report it.
+#[derive(Default)]
+struct ParsedContent {
+    text: String,
+    thinking: String,
+    tool_calls: Vec<LocalInferenceDebugToolCallDto>,
+    tool_call_errors: Vec<String>,
+}
+
+impl ParsedContent {
+    fn push(&mut self, content: &MessageContent) {
+        match content {
+            MessageContent::Text(text) => self.text.push_str(&text.text),
+            MessageContent::Thinking(thinking) => self.thinking.push_str(&thinking.thinking),
+            MessageContent::ToolRequest(request) => match &request.tool_call {
+                Ok(call) => self.tool_calls.push(LocalInferenceDebugToolCallDto {
+                    name: call.name.to_string(),
+                    arguments: call
+                        .arguments
+                        .clone()
+                        .map(serde_json::Value::Object)
+                        .unwrap_or_default(),
+                }),
+                Err(error) => self.tool_call_errors.push(error.message.to_string()),
+            },
+            _ => {}
+        }
+    }
+}
+
 pub fn list_builtin_chat_templates() -> LocalInferenceBuiltinChatTemplatesListResponse {
     LocalInferenceBuiltinChatTemplatesListResponse {
         templates: builtin_chat_template_names(),
     }
 }
 
-fn management_runtime() -> Result<Arc<InferenceRuntime>> {
+pub(crate) fn management_runtime() -> Result<Arc<InferenceRuntime>> {
     if let Some(runtime) = MANAGEMENT_RUNTIME.get() {
         return Ok(runtime.clone());
     }
@@ -355,6 +834,7 @@ pub async fn ensure_featured_models_current() -> Result<()> {
             let settings = default_settings_for_model(&pending.model_id);
             LocalModelEntry {
                 id: pending.model_id,
+                embedding_capable: is_embedding_model_repo(&pending.repo_id),
                 repo_id: pending.repo_id,
                 filename: hf_file.filename,
                 quantization: pending.quantization,
@@ -412,11 +892,11 @@ pub async fn ensure_featured_models_current() -> Result<()> {
             if !dominated_by_active {
                 tracing::info!(model_id = %model_id, "Auto-downloading vision encoder for existing model");
                 if let Err(e) = dm
-                    .download_model_with_bearer_token(
+                    .download_model_with_options(
                         download_id,
                         url,
                         path,
-                        hf_token.clone(),
+                        download_options(hf_token.clone()),
                         None,
                     )
                     .await
@@ -430,6 +910,22 @@ pub async fn ensure_featured_models_current() -> Result<()> {
     Ok(())
 }
 
+pub(crate) fn download_options(bearer_token: Option<String>) -> DownloadOptions {
+    fn numeric_param<T: std::str::FromStr>(key: &'static str) -> Option<T> {
+        config_resolver::string_param(key)
+            .ok()
+            .flatten()
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    DownloadOptions {
+        bearer_token,
+        max_bytes_per_second: numeric_param::<u64>(DOWNLOAD_MAX_BYTES_PER_SECOND_KEY)
+            .filter(|rate| *rate > 0),
+        segments: numeric_param(DOWNLOAD_SEGMENTS_KEY).unwrap_or(DEFAULT_DOWNLOAD_SEGMENTS),
+    }
+}
+
 fn local_model_to_dto(
     entry: &LocalModelEntry,
     recommended_id: &str,
@@ -492,6 +988,7 @@ fn download_progress_to_dto(progress: DownloadProgress) -> LocalInferenceDownloa
         speed_bps: progress.speed_bps,
         eta_seconds: progress.eta_seconds,
         error: progress.error,
+        final_size_bytes: progress.final_size_bytes,
         task_exited: progress.task_exited,
     }
 }
@@ -536,6 +1033,8 @@ fn hf_model_variant_to_dto(variant: HfModelVariant) -> LocalInferenceHfModelVari
         sharded: variant.sharded,
         supported: variant.supported,
         unsupported_reason: variant.unsupported_reason,
+        mmproj_filename: variant.mmproj_filename,
+        total_size_bytes: variant.total_size_bytes,
     }
 }
 
@@ -544,6 +1043,8 @@ pub fn model_settings_to_dto(settings: &ModelSettings) -> LocalInferenceModelSet
         backend_id: settings.backend_id.clone(),
         context_size: settings.context_size,
         max_output_tokens: settings.max_output_tokens,
+        stop_sequences: settings.stop_sequences.clone(),
+        extra_eog_token_ids: settings.extra_eog_token_ids.clone(),
         draft_model: settings.draft_model.clone(),
         sampling: sampling_to_dto(&settings.sampling),
         repeat_penalty: settings.repeat_penalty,
@@ -558,9 +1059,22 @@ pub fn model_settings_to_dto(settings: &ModelSettings) -> LocalInferenceModelSet
         tool_calling: tool_calling_to_dto(settings.tool_calling),
         chat_template: chat_template_to_dto(&settings.chat_template),
         enable_thinking: settings.enable_thinking,
+        emulator_json_mode: settings.emulator_json_mode,
+        emulator_max_code_chars: settings.emulator_max_code_chars,
+        raw_completion: settings.raw_completion,
+        context_shift: settings.context_shift,
+        ignore_memory_context_cap: settings.ignore_memory_context_cap,
+        pinned: settings.pinned,
+        return_logprobs: settings.return_logprobs,
+        top_logprobs: settings.top_logprobs,
+        usage_update_interval: settings.usage_update_interval,
         vision_capable: settings.vision_capable,
         image_token_estimate: settings.image_token_estimate,
         mmproj_size_bytes: settings.mmproj_size_bytes,
+        mmproj_path: settings
+            .mmproj_path
+            .as_ref()
+            .map(|path| path.display().to_string()),
     }
 }
 
@@ -569,6 +1083,8 @@ pub fn model_settings_from_dto(settings: LocalInferenceModelSettingsDto) -> Mode
         backend_id: settings.backend_id,
         context_size: settings.context_size,
         max_output_tokens: settings.max_output_tokens,
+        stop_sequences: settings.stop_sequences,
+        extra_eog_token_ids: settings.extra_eog_token_ids,
         draft_model: settings.draft_model,
         sampling: sampling_from_dto(settings.sampling),
         repeat_penalty: settings.repeat_penalty,
@@ -583,9 +1099,19 @@ pub fn model_settings_from_dto(settings: LocalInferenceModelSettingsDto) -> Mode
         tool_calling: tool_calling_from_dto(settings.tool_calling),
         chat_template: chat_template_from_dto(settings.chat_template),
         enable_thinking: settings.enable_thinking,
+        emulator_json_mode: settings.emulator_json_mode,
+        emulator_max_code_chars: settings.emulator_max_code_chars,
+        raw_completion: settings.raw_completion,
+        context_shift: settings.context_shift,
+        ignore_memory_context_cap: settings.ignore_memory_context_cap,
+        pinned: settings.pinned,
+        return_logprobs: settings.return_logprobs,
+        top_logprobs: settings.top_logprobs,
+        usage_update_interval: settings.usage_update_interval,
         vision_capable: settings.vision_capable,
         image_token_estimate: settings.image_token_estimate,
         mmproj_size_bytes: settings.mmproj_size_bytes,
+        mmproj_path: settings.mmproj_path.map(PathBuf::from),
     }
 }
 
@@ -598,12 +1124,14 @@ fn sampling_to_dto(sampling: &SamplingConfig) -> LocalInferenceSamplingConfig {
             top_p,
             min_p,
             seed,
+            min_keep,
         } => LocalInferenceSamplingConfig::Temperature {
             temperature: *temperature,
             top_k: *top_k,
             top_p: *top_p,
             min_p: *min_p,
             seed: *seed,
+            min_keep: *min_keep,
         },
         SamplingConfig::MirostatV2 { tau, eta, seed } => LocalInferenceSamplingConfig::MirostatV2 {
             tau: *tau,
@@ -622,12 +1150,14 @@ fn sampling_from_dto(sampling: LocalInferenceSamplingConfig) -> SamplingConfig {
             top_p,
             min_p,
             seed,
+            min_keep,
         } => SamplingConfig::Temperature {
             temperature,
             top_k,
             top_p,
             min_p,
             seed,
+            min_keep,
         },
         LocalInferenceSamplingConfig::MirostatV2 { tau, eta, seed } => {
             SamplingConfig::MirostatV2 { tau, eta, seed }
@@ -754,6 +1284,7 @@ fn mark_download_failed(model_id: &str, error: impl std::fmt::Display) {
             speed_bps: None,
             eta_seconds: None,
             error: Some(error.to_string()),
+            final_size_bytes: None,
             task_exited: true,
         });
         return;
@@ -809,6 +1340,7 @@ fn register_pending_download_model(
     let filename = variant_id.clone();
     registry.add_model(LocalModelEntry {
         id: model_id.to_string(),
+        embedding_capable: is_embedding_model_repo(&repo_id),
         repo_id,
         filename: filename.clone(),
         quantization: variant_id,
@@ -830,6 +1362,23 @@ fn register_pending_download_model(
 mod tests {
     use super::*;
 
+    #[test]
+    fn download_options_read_configured_limits() {
+        fn resolve(key: &'static str) -> Result<Option<String>> {
+            Ok(match key {
+                DOWNLOAD_SEGMENTS_KEY => Some("2".to_string()),
+                DOWNLOAD_MAX_BYTES_PER_SECOND_KEY => Some(" 1048576 ".to_string()),
+                _ => None,
+            })
+        }
+        config_resolver::set_string_param_resolver(resolve);
+
+        let options = download_options(Some("token".to_string()));
+        assert_eq!(options.segments, 2);
+        assert_eq!(options.max_bytes_per_second, Some(1_048_576));
+        assert_eq!(options.bearer_token.as_deref(), Some("token"));
+    }
+
     #[test]
     fn settings_round_trip_preserves_defaults() {
         let settings = ModelSettings::default();
@@ -842,6 +1391,95 @@ mod tests {
             round_trip.image_token_estimate,
             settings.image_token_estimate
         );
+        assert!(round_trip.mmproj_path.is_none());
+    }
+
+    #[test]
+    fn settings_patch_only_changes_given_fields() {
+        let current = ModelSettings {
+            context_size: Some(8192),
+            n_gpu_layers: Some(10),
+            repeat_penalty: 1.1,
+            enable_thinking: true,
+            ..ModelSettings::default()
+        };
+        let patch = serde_json::json!({ "nGpuLayers": 32 });
+
+        let patched =
+            apply_settings_patch(&current, serde_json::from_value(patch).unwrap()).unwrap();
+
+        assert_eq!(patched.n_gpu_layers, Some(32));
+        assert_eq!(patched.context_size, Some(8192));
+        assert_eq!(patched.repeat_penalty, 1.1);
+        assert!(patched.enable_thinking);
+        assert_eq!(patched.tool_calling, current.tool_calling);
+    }
+
+    #[test]
+    fn settings_patch_null_clears_optional_field_and_rejects_bad_types() {
+        let current = ModelSettings {
+            context_size: Some(8192),
+            n_gpu_layers: Some(10),
+            ..ModelSettings::default()
+        };
+
+        let patch = serde_json::json!({ "contextSize": null });
+        let patched =
+            apply_settings_patch(&current, serde_json::from_value(patch).unwrap()).unwrap();
+        assert_eq!(patched.context_size, None);
+        assert_eq!(patched.n_gpu_layers, Some(10));
+
+        let patch = serde_json::json!({ "nGpuLayers": "all" });
+        assert!(apply_settings_patch(&current, serde_json::from_value(patch).unwrap()).is_err());
+    }
+
+    #[test]
+    fn settings_round_trip_preserves_mmproj_path() {
+        let settings = ModelSettings {
+            mmproj_path: Some(PathBuf::from("/models/llava/mmproj-f16.gguf")),
+            ..ModelSettings::default()
+        };
+        let dto = model_settings_to_dto(&settings);
+        assert_eq!(
+            dto.mmproj_path.as_deref(),
+            Some("/models/llava/mmproj-f16.gguf")
+        );
+        assert_eq!(
+            model_settings_from_dto(dto).mmproj_path,
+            settings.mmproj_path
+        );
+    }
+
+    #[test]
+    fn debug_generation_splits_text_thinking_and_tool_calls() {
+        let call = rmcp::model::CallToolRequestParams::new("developer__shell")
+            .with_arguments(rmcp::object!({"command": "ls"}));
+        let generation = DebugGeneration {
+            applied_prompt: Some("<|user|>list files".to_string()),
+            raw_text: "<think>plan</think>Listing.<tool_call>...</tool_call>".to_string(),
+            messages: vec![
+                Message::assistant().with_thinking("plan", ""),
+                Message::assistant().with_text("Listing."),
+                Message::assistant().with_tool_request("call-1", Ok(call)),
+                Message::assistant().with_tool_request(
+                    "call-2",
+                    Err(rmcp::model::ErrorData::invalid_params(
+                        "bad arguments",
+                        None,
+                    )),
+                ),
+            ],
+        };
+
+        let dto = debug_generation_to_dto(generation);
+        assert_eq!(dto.applied_prompt.as_deref(), Some("<|user|>list files"));
+        assert!(dto.raw_text.contains("<tool_call>"));
+        assert_eq!(dto.thinking, "plan");
+        assert_eq!(dto.text, "Listing.");
+        assert_eq!(dto.tool_calls.len(), 1);
+        assert_eq!(dto.tool_calls[0].name, "developer__shell");
+        assert_eq!(dto.tool_calls[0].arguments["command"], "ls");
+        assert_eq!(dto.tool_call_errors, vec!["bad arguments".to_string()]);
     }
 
     #[tokio::test]
@@ -857,4 +1495,108 @@ mod tests {
             .unwrap();
         assert_eq!(model_id, "test/repo:Q4_K_M");
     }
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn llama_8b_shape() -> KvCacheShape {
+        KvCacheShape {
+            n_layer: 32,
+            n_head_kv: 8,
+            k_per_head: 128,
+            v_per_head: 128,
+        }
+    }
+
+    fn footprint(weights_bytes: u64, kv_shape: Option<KvCacheShape>) -> ModelFootprint {
+        ModelFootprint {
+            weights_bytes,
+            mmproj_bytes: 0,
+            kv_shape,
+            trained_context: None,
+        }
+    }
+
+    #[test]
+    fn recommendation_offloads_everything_when_the_model_fits() {
+        let settings = recommend_model_settings(
+            &ModelSettings::default(),
+            "bartowski/Meta-Llama-3.1-8B-Instruct-GGUF:Q4_K_M",
+            &footprint(5 * GIB, Some(llama_8b_shape())),
+            &MachineMemory {
+                available_bytes: 16 * GIB,
+                has_accelerator: true,
+            },
+        );
+
+        assert_eq!(settings.n_gpu_layers, None);
+        // 11 GiB left, half of it for 128 KiB/token of KV cache.
+        assert_eq!(settings.context_size, Some(45056));
+        assert_eq!(settings.tool_calling, ToolCallingMode::ForceNative);
+        assert!(matches!(
+            settings.sampling,
+            SamplingConfig::Temperature { temperature, .. } if temperature == 0.7
+        ));
+    }
+
+    #[test]
+    fn recommendation_caps_context_at_the_trained_length() {
+        let settings = recommend_model_settings(
+            &ModelSettings::default(),
+            "bartowski/Meta-Llama-3.1-8B-Instruct-GGUF:Q4_K_M",
+            &ModelFootprint {
+                trained_context: Some(8192),
+                ..footprint(5 * GIB, Some(llama_8b_shape()))
+            },
+            &MachineMemory {
+                available_bytes: 16 * GIB,
+                has_accelerator: true,
+            },
+        );
+
+        assert_eq!(settings.context_size, Some(8192));
+    }
+
+    #[test]
+    fn recommendation_splits_layers_when_the_model_does_not_fit() {
+        let settings = recommend_model_settings(
+            &ModelSettings::default(),
+            "someone/Big-Model-GGUF:Q4_K_M",
+            &footprint(16 * GIB, Some(llama_8b_shape())),
+            &MachineMemory {
+                available_bytes: 8 * GIB,
+                has_accelerator: true,
+            },
+        );
+
+        assert_eq!(settings.n_gpu_layers, Some(13));
+        assert_eq!(settings.context_size, Some(4096));
+        assert_eq!(settings.tool_calling, ToolCallingMode::Auto);
+    }
+
+    #[test]
+    fn recommendation_without_accelerator_or_header_keeps_current_settings() {
+        let current = ModelSettings {
+            context_size: Some(8192),
+            chat_template: ChatTemplate::Builtin {
+                name: "chatml".to_string(),
+            },
+            ..ModelSettings::default()
+        };
+        let settings = recommend_model_settings(
+            &current,
+            "someone/Tiny-Model-GGUF:Q8_0",
+            &footprint(GIB, None),
+            &MachineMemory {
+                available_bytes: 4 * GIB,
+                has_accelerator: false,
+            },
+        );
+
+        assert_eq!(settings.n_gpu_layers, Some(0));
+        assert_eq!(settings.context_size, Some(8192));
+        assert!(matches!(
+            settings.chat_template,
+            ChatTemplate::Builtin { .. }
+        ));
+    }
 }
@@ -51,10 +51,7 @@ mod imp {
             _settings: &ModelSettings,
         ) -> Result<Box<dyn BackendLoadedModel>, ProviderError> {
             if !resolved.model_path.exists() {
-                return Err(ProviderError::ExecutionError(format!(
-                    "Model not downloaded: {}. Please download it from Settings > Local Inference.",
-                    model_id
-                )));
+                return Err(crate::missing_model_error(model_id));
             }
 
             let model_dir = model_dir_from_path(&resolved.model_path)?;
@@ -102,6 +99,7 @@ mod imp {
                             .tools
                             .iter()
                             .any(|t| t.name == CODE_EXECUTION_TOOL),
+                        max_code_chars: request.settings.max_emulated_code_chars(),
                     },
                 }
             };
@@ -228,6 +226,7 @@ mod imp {
                     request.settings.enable_thinking,
                     request.message_id,
                     tool_mode,
+                    request.tools,
                     request.tx,
                 )?;
             }
@@ -257,6 +256,7 @@ mod imp {
                 elapsed_ms: Some(generation_started.elapsed().as_millis() as u64),
                 output_tokens: Some(generated_ids.len()),
                 draft: draft_stats,
+                logprobs: None,
             };
             let provider_usage = ProviderUsage::new(request.model_name, usage).with_stats(stats);
             let _ = request.tx.blocking_send(Ok((None, Some(provider_usage))));
@@ -272,7 +272,10 @@ mod imp {
     enum ToolMode {
         None,
         Native,
-        Emulated { code_mode_enabled: bool },
+        Emulated {
+            code_mode_enabled: bool,
+            max_code_chars: usize,
+        },
     }
 
     struct MlxGeneration {
@@ -366,7 +369,9 @@ mod imp {
 
                 Ok(render_prompt(system, messages))
             }
-            ToolMode::Emulated { code_mode_enabled } => {
+            ToolMode::Emulated {
+                code_mode_enabled, ..
+            } => {
                 let system_prompt = format!(
                     "{}{}",
                     load_tiny_model_prompt(),
@@ -648,6 +653,7 @@ mod imp {
         enable_thinking: bool,
         message_id: &str,
         tool_mode: ToolMode,
+        tools: &[rmcp::model::Tool],
         tx: &tokio::sync::mpsc::Sender<
             Result<(Option<Message>, Option<ProviderUsage>), ProviderError>,
         >,
@@ -664,7 +670,9 @@ mod imp {
                 emit_assistant_message(message_id, &thinking, &content, tx)?;
             }
             ToolMode::Native => {
-                if let Some(mut message) = message_from_native_tool_text(&content, message_id)? {
+                if let Some(mut message) =
+                    message_from_native_tool_text(&content, message_id, tools)?
+                {
                     prepend_thinking(&mut message, &thinking);
                     tx.blocking_send(Ok((Some(message), None))).map_err(|_| {
                         ProviderError::ExecutionError("Failed to stream MLX response".to_string())
@@ -673,9 +681,13 @@ mod imp {
                     emit_assistant_message(message_id, &thinking, &content, tx)?;
                 }
             }
-            ToolMode::Emulated { code_mode_enabled } => {
+            ToolMode::Emulated {
+                code_mode_enabled,
+                max_code_chars,
+            } => {
                 emit_assistant_message(message_id, &thinking, "", tx)?;
-                let mut parser = StreamingEmulatorParser::new(code_mode_enabled);
+                let mut parser = StreamingEmulatorParser::new(code_mode_enabled)
+                    .with_max_flush_code_chars(max_code_chars);
                 let mut actions = parser.process_chunk(&content);
                 actions.extend(parser.flush());
 
@@ -712,9 +724,13 @@ mod imp {
             >,
         ) -> Self {
             let emulator_parser = match tool_mode {
-                ToolMode::Emulated { code_mode_enabled } => {
-                    Some(StreamingEmulatorParser::new(code_mode_enabled))
-                }
+                ToolMode::Emulated {
+                    code_mode_enabled,
+                    max_code_chars,
+                } => Some(
+                    StreamingEmulatorParser::new(code_mode_enabled)
+                        .with_max_flush_code_chars(max_code_chars),
+                ),
                 ToolMode::None | ToolMode::Native => None,
             };
             Self {
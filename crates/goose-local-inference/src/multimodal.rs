@@ -80,6 +80,14 @@ pub fn extract_images_from_messages_json(
     })
 }
 
+pub fn messages_contain_images(messages: &[Message]) -> bool {
+    messages.iter().any(|msg| {
+        msg.content
+            .iter()
+            .any(|content| matches!(content, MessageContent::Image(_)))
+    })
+}
+
 /// Scan messages for `MessageContent::Image` entries. Return the extracted image
 /// bytes and a new message list with images replaced by text marker placeholders.
 pub fn extract_images_from_messages(
@@ -261,6 +269,21 @@ mod tests {
 
     // --- Tests for extract_images_from_messages (Message-based) ---
 
+    #[test]
+    fn test_messages_contain_images() {
+        let text_only = vec![
+            Message::user().with_text("hello"),
+            Message::assistant().with_text("hi"),
+        ];
+        assert!(!messages_contain_images(&text_only));
+
+        let with_image = vec![
+            Message::user().with_text("hello"),
+            Message::user().with_image(tiny_png_base64(), "image/png"),
+        ];
+        assert!(messages_contain_images(&with_image));
+    }
+
     #[test]
     fn test_messages_extract_replaces_image_with_marker() {
         let b64 = tiny_png_base64();
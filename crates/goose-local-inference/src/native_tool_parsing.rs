@@ -1,8 +1,10 @@
+use crate::tool_parsing::{canonical_tool_name, parse_lenient_json};
 use goose_provider_types::conversation::message::{Message, MessageContent};
 use goose_provider_types::errors::ProviderError;
+use goose_provider_types::formats::ollama::parse_xml_function_blocks;
 use goose_provider_types::formats::openai::is_valid_function_name;
 use goose_provider_types::json::safely_parse_json;
-use rmcp::model::{object, CallToolRequestParams, ErrorCode, ErrorData};
+use rmcp::model::{object, CallToolRequestParams, ErrorCode, ErrorData, Tool};
 use serde_json::{json, Value};
 use std::borrow::Cow;
 use uuid::Uuid;
@@ -10,16 +12,24 @@ use uuid::Uuid;
 pub(crate) fn message_from_native_tool_text(
     generated_text: &str,
     message_id: &str,
+    tools: &[Tool],
 ) -> Result<Option<Message>, ProviderError> {
     let mut content = Vec::new();
-
-    if let Some(message) = parse_openai_message_json(generated_text) {
-        append_text(&mut content, message.get("content"));
-        append_tool_calls(&mut content, message.get("tool_calls"));
-    } else if let Some(tool_calls) = parse_tool_calls_json(generated_text) {
-        append_tool_calls(&mut content, Some(&tool_calls));
+    let (prefix, json_text) =
+        split_trailing_json_fence(generated_text).unwrap_or(("", generated_text));
+
+    let messages = parse_openai_messages_json(json_text);
+    if !messages.is_empty() {
+        append_prefix(&mut content, prefix);
+        for message in &messages {
+            append_text(&mut content, message.get("content"));
+            append_tool_calls(&mut content, message.get("tool_calls"), tools);
+        }
+    } else if let Some(tool_calls) = parse_tool_calls_json(json_text) {
+        append_prefix(&mut content, prefix);
+        append_tool_calls(&mut content, Some(&tool_calls), tools);
     } else if generated_text.contains("<function=") {
-        let (prefix, tool_calls) = parse_xml_tool_calls(generated_text);
+        let (prefix, tool_calls) = parse_xml_tool_calls(generated_text, tools);
         if let Some(prefix) = prefix {
             content.push(MessageContent::text(prefix));
         }
@@ -44,10 +54,34 @@ pub(crate) fn message_from_native_tool_text(
     }
 }
 
-fn parse_openai_message_json(generated_text: &str) -> Option<Value> {
+/// Splits output ending in a ```json (or bare ```) block into the text before the fence
+/// and the fenced body, so small models that fence their tool-call JSON still parse.
+fn split_trailing_json_fence(text: &str) -> Option<(&str, &str)> {
+    let inner = text.trim_end().strip_suffix("```")?;
+    let open = inner.rfind("```")?;
+    let (info, body) = inner.get(open + 3..)?.split_once('\n')?;
+    if !matches!(info.trim(), "" | "json") {
+        return None;
+    }
+    Some((inner.get(..open)?, body))
+}
+
+/// Models sometimes emit one `{"tool_calls": [...]}` object per call, so every top-level
+/// object carrying tool calls is returned, in order. Falls back to the first nested match.
+fn parse_openai_messages_json(generated_text: &str) -> Vec<Value> {
+    let has_tool_calls = |value: &Value| value.get("tool_calls").is_some_and(is_tool_call_array);
+    let top_level: Vec<Value> = top_level_json_values(generated_text)
+        .into_iter()
+        .filter(has_tool_calls)
+        .collect();
+    if !top_level.is_empty() {
+        return top_level;
+    }
     json_candidates(generated_text)
         .into_iter()
-        .find(|value| value.get("tool_calls").is_some_and(is_tool_call_array))
+        .find(has_tool_calls)
+        .into_iter()
+        .collect()
 }
 
 fn parse_tool_calls_json(generated_text: &str) -> Option<Value> {
@@ -89,44 +123,17 @@ fn is_tool_call_value(value: &Value) -> bool {
 fn json_candidates(text: &str) -> Vec<Value> {
     let mut candidates = Vec::new();
     let trimmed = text.trim();
-    if let Ok(value) = serde_json::from_str::<Value>(trimmed) {
+    if let Ok(value) = parse_lenient_json::<Value>(trimmed) {
         candidates.push(value);
     }
 
     for (open, close) in [('{', '}'), ('[', ']')] {
         let starts = text.match_indices(open).map(|(idx, _)| idx);
         for start in starts {
-            let mut depth = 0i32;
-            let mut in_string = false;
-            let mut escaped = false;
-            for (offset, ch) in text[start..].char_indices() {
-                if escaped {
-                    escaped = false;
-                    continue;
-                }
-                if ch == '\\' && in_string {
-                    escaped = true;
-                    continue;
-                }
-                if ch == '"' {
-                    in_string = !in_string;
-                    continue;
-                }
-                if in_string {
-                    continue;
-                }
-                if ch == open {
-                    depth += 1;
-                } else if ch == close {
-                    depth -= 1;
-                    if depth == 0 {
-                        let end = start + offset + ch.len_utf8();
-                        if let Ok(value) = serde_json::from_str::<Value>(&text[start..end]) {
-                            candidates.push(value);
-                        }
-                        break;
-                    }
-                }
+            if let Some(value) = balanced_json_end(text, start, open, close)
+                .and_then(|end| parse_json_span(text, start, end))
+            {
+                candidates.push(value);
             }
         }
     }
@@ -134,6 +141,69 @@ fn json_candidates(text: &str) -> Vec<Value> {
     candidates
 }
 
+/// Parses the non-overlapping JSON values that appear at the top level of `text`, in order,
+/// skipping prose between them.
+fn top_level_json_values(text: &str) -> Vec<Value> {
+    let mut values = Vec::new();
+    let mut cursor = 0;
+    while let Some(offset) = text.get(cursor..).and_then(|rest| rest.find(['{', '['])) {
+        let start = cursor + offset;
+        let (open, close) = if text.get(start..).is_some_and(|rest| rest.starts_with('{')) {
+            ('{', '}')
+        } else {
+            ('[', ']')
+        };
+        let parsed = balanced_json_end(text, start, open, close)
+            .and_then(|end| parse_json_span(text, start, end).map(|value| (value, end)));
+        match parsed {
+            Some((value, end)) => {
+                values.push(value);
+                cursor = end;
+            }
+            None => cursor = start + 1,
+        }
+    }
+    values
+}
+
+/// Byte offset just past the `close` that balances the `open` at `start`, ignoring
+/// delimiters inside strings.
+fn balanced_json_end(text: &str, start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (offset, ch) in text.get(start..)?.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        if ch == '\\' && in_string {
+            escaped = true;
+            continue;
+        }
+        if ch == '"' {
+            in_string = !in_string;
+            continue;
+        }
+        if in_string {
+            continue;
+        }
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(start + offset + ch.len_utf8());
+            }
+        }
+    }
+    None
+}
+
+fn parse_json_span(text: &str, start: usize, end: usize) -> Option<Value> {
+    parse_lenient_json(text.get(start..end)?).ok()
+}
+
 fn append_text(content: &mut Vec<MessageContent>, value: Option<&Value>) {
     if let Some(text) = value.and_then(|value| value.as_str()) {
         if !text.is_empty() {
@@ -142,17 +212,24 @@ fn append_text(content: &mut Vec<MessageContent>, value: Option<&Value>) {
     }
 }
 
-fn append_tool_calls(content: &mut Vec<MessageContent>, value: Option<&Value>) {
+fn append_prefix(content: &mut Vec<MessageContent>, prefix: &str) {
+    let prefix = prefix.trim_end();
+    if !prefix.is_empty() {
+        content.push(MessageContent::text(prefix));
+    }
+}
+
+fn append_tool_calls(content: &mut Vec<MessageContent>, value: Option<&Value>, tools: &[Tool]) {
     let Some(tool_calls) = value.and_then(|value| value.as_array()) else {
         return;
     };
 
     for tool_call in tool_calls {
-        content.push(tool_call_content(tool_call));
+        content.push(tool_call_content(tool_call, tools));
     }
 }
 
-fn tool_call_content(tool_call: &Value) -> MessageContent {
+fn tool_call_content(tool_call: &Value, tools: &[Tool]) -> MessageContent {
     let id = tool_call
         .get("id")
         .and_then(|id| id.as_str())
@@ -163,8 +240,8 @@ fn tool_call_content(tool_call: &Value) -> MessageContent {
     let name = function
         .get("name")
         .and_then(|name| name.as_str())
-        .unwrap_or_default()
-        .to_string();
+        .unwrap_or_default();
+    let name = canonical_tool_name(name, tools).to_string();
 
     if !is_valid_function_name(&name) {
         return MessageContent::tool_request(
@@ -189,7 +266,8 @@ fn tool_call_content(tool_call: &Value) -> MessageContent {
     let raw_arguments = arguments.to_string();
     let parsed = match arguments {
         Value::String(arguments) if arguments.trim().is_empty() => Ok(json!({})),
-        Value::String(arguments) => safely_parse_json(&arguments),
+        Value::String(arguments) => safely_parse_json(&arguments)
+            .or_else(|error| parse_lenient_json(&arguments).map_err(|_| error)),
         Value::Object(_) => Ok(arguments),
         Value::Null => Ok(json!({})),
         other => Ok(other),
@@ -217,10 +295,7 @@ fn tool_call_content(tool_call: &Value) -> MessageContent {
     }
 }
 
-fn parse_xml_tool_calls(content: &str) -> (Option<String>, Vec<MessageContent>) {
-    let function_re = regex::Regex::new(r"<function=([^>]+)>([\s\S]*?)</function>").unwrap();
-    let param_re = regex::Regex::new(r"<parameter=([^>]+)>([\s\S]*?)</parameter>").unwrap();
-
+fn parse_xml_tool_calls(content: &str, tools: &[Tool]) -> (Option<String>, Vec<MessageContent>) {
     let prefix = content
         .find("<function=")
         .and_then(|idx| content.get(..idx))
@@ -228,24 +303,20 @@ fn parse_xml_tool_calls(content: &str) -> (Option<String>, Vec<MessageContent>)
         .filter(|text| !text.is_empty())
         .map(ToString::to_string);
 
-    let mut tool_calls = Vec::new();
-    for func_cap in function_re.captures_iter(content) {
-        let function_name = func_cap[1].trim().to_string();
-        let function_body = &func_cap[2];
-        let mut arguments = serde_json::Map::new();
-        for param_cap in param_re.captures_iter(function_body) {
-            arguments.insert(
-                param_cap[1].trim().to_string(),
-                Value::String(param_cap[2].trim().to_string()),
-            );
-        }
-        tool_calls.push(tool_call_content(&json!({
-            "function": {
-                "name": function_name,
-                "arguments": arguments,
-            }
-        })));
-    }
+    let tool_calls = parse_xml_function_blocks(content)
+        .into_iter()
+        .map(|(function_name, arguments)| {
+            tool_call_content(
+                &json!({
+                    "function": {
+                        "name": function_name,
+                        "arguments": arguments,
+                    }
+                }),
+                tools,
+            )
+        })
+        .collect();
 
     (prefix, tool_calls)
 }
@@ -265,35 +336,62 @@ mod tests {
     #[test]
     fn parses_openai_message_tool_calls() {
         let text = r#"{"content":null,"tool_calls":[{"id":"call_1","type":"function","function":{"name":"developer__shell","arguments":"{\"command\":\"pwd\"}"}}]}"#;
-        let message = message_from_native_tool_text(text, "msg").unwrap().unwrap();
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
         assert_eq!(tool_count(&message), 1);
     }
 
+    #[test]
+    fn merges_consecutive_tool_call_objects_in_order() {
+        let text = concat!(
+            r#"{"tool_calls":[{"name":"developer__shell","arguments":{"command":"pwd"}}]}"#,
+            "\n",
+            r#"{"tool_calls":[{"name":"developer__read_file","arguments":{"path":"a.txt"}}]}"#,
+        );
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
+        let names: Vec<_> = message
+            .content
+            .iter()
+            .filter_map(|content| content.as_tool_request())
+            .map(|request| request.tool_call.as_ref().unwrap().name.to_string())
+            .collect();
+        assert_eq!(names, vec!["developer__shell", "developer__read_file"]);
+    }
+
     #[test]
     fn parses_top_level_tool_calls() {
         let text = r#"{"tool_calls":[{"name":"developer__shell","arguments":{"command":"pwd"}}]}"#;
-        let message = message_from_native_tool_text(text, "msg").unwrap().unwrap();
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
         assert_eq!(tool_count(&message), 1);
     }
 
     #[test]
     fn parses_top_level_tool_call_array() {
         let text = r#"[{"name":"developer__shell","arguments":{"command":"pwd"}}]"#;
-        let message = message_from_native_tool_text(text, "msg").unwrap().unwrap();
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
         assert_eq!(tool_count(&message), 1);
     }
 
     #[test]
     fn parses_top_level_tool_call_object_with_arguments() {
         let text = r#"{"name":"developer__shell","arguments":{"command":"pwd"}}"#;
-        let message = message_from_native_tool_text(text, "msg").unwrap().unwrap();
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
         assert_eq!(tool_count(&message), 1);
     }
 
     #[test]
     fn ignores_plain_json_objects_with_name_fields() {
         let text = r#"{"name":"Alice","age":30}"#;
-        assert!(message_from_native_tool_text(text, "msg")
+        assert!(message_from_native_tool_text(text, "msg", &[])
             .unwrap()
             .is_none());
     }
@@ -301,7 +399,7 @@ mod tests {
     #[test]
     fn ignores_plain_json_arrays() {
         let text = r#"["a","b"]"#;
-        assert!(message_from_native_tool_text(text, "msg")
+        assert!(message_from_native_tool_text(text, "msg", &[])
             .unwrap()
             .is_none());
     }
@@ -309,15 +407,74 @@ mod tests {
     #[test]
     fn ignores_non_tool_call_arrays_in_tool_calls_field() {
         let text = r#"{"tool_calls":["a","b"]}"#;
-        assert!(message_from_native_tool_text(text, "msg")
+        assert!(message_from_native_tool_text(text, "msg", &[])
             .unwrap()
             .is_none());
     }
 
+    #[test]
+    fn parses_tool_calls_inside_json_code_fence() {
+        let text = "Let me check the directory.\n```json\n{\"tool_calls\":[{\"name\":\"developer__shell\",\"arguments\":{\"command\":\"pwd\"}}]}\n```\n";
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
+        assert_eq!(tool_count(&message), 1);
+        let text_content: Vec<_> = message
+            .content
+            .iter()
+            .filter_map(|content| content.as_text())
+            .collect();
+        assert_eq!(text_content, vec!["Let me check the directory."]);
+    }
+
+    #[test]
+    fn maps_dotted_tool_names_to_known_tools() {
+        let tools = [Tool::new(
+            "developer__shell",
+            "Run a command",
+            rmcp::object!({"type": "object"}),
+        )];
+        let json_text = r#"{"name":"developer.shell","arguments":{"command":"pwd"}}"#;
+        let xml_text = r#"<function=developer.shell><parameter=command>pwd</parameter></function>"#;
+
+        for text in [json_text, xml_text] {
+            let message = message_from_native_tool_text(text, "msg", &tools)
+                .unwrap()
+                .unwrap();
+            let request = message.content[0].as_tool_request().unwrap();
+            assert_eq!(request.tool_call.as_ref().unwrap().name, "developer__shell");
+        }
+    }
+
+    #[test]
+    fn repairs_trailing_commas_and_single_quotes_in_tool_calls() {
+        let text = r#"{"name": "developer__shell", "arguments": {'command': 'ls -a',},}"#;
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
+        let MessageContent::ToolRequest(request) = &message.content[0] else {
+            panic!("expected tool request");
+        };
+        let call = request.tool_call.as_ref().unwrap();
+        assert_eq!(call.arguments.as_ref().unwrap()["command"], "ls -a");
+
+        let text = r#"{"name": "developer__shell", "arguments": "{'command': 'pwd',}"}"#;
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
+        let MessageContent::ToolRequest(request) = &message.content[0] else {
+            panic!("expected tool request");
+        };
+        let call = request.tool_call.as_ref().unwrap();
+        assert_eq!(call.arguments.as_ref().unwrap()["command"], "pwd");
+    }
+
     #[test]
     fn parses_xml_tool_calls() {
         let text = r#"<function=developer__shell><parameter=command>pwd</parameter></function>"#;
-        let message = message_from_native_tool_text(text, "msg").unwrap().unwrap();
+        let message = message_from_native_tool_text(text, "msg", &[])
+            .unwrap()
+            .unwrap();
         assert_eq!(tool_count(&message), 1);
     }
 }
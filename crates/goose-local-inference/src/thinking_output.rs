@@ -6,6 +6,8 @@ pub(crate) struct ThinkingOutputFilter {
     think_filter: ThinkFilter,
     pending_inline_thinking: String,
     accumulated_thinking: String,
+    stream_inline_thinking: bool,
+    streamed_inline_thinking: String,
 }
 
 impl ThinkingOutputFilter {
@@ -21,18 +23,49 @@ impl ThinkingOutputFilter {
             think_filter,
             pending_inline_thinking: String::new(),
             accumulated_thinking: String::new(),
+            stream_inline_thinking: false,
+            streamed_inline_thinking: String::new(),
         }
     }
 
+    /// Return inline `<think>` content from `push_text` as it arrives instead of holding
+    /// it until `finish`. Structured reasoning that repeats what was already streamed is
+    /// not returned a second time.
+    pub(crate) fn streaming_inline_thinking(mut self) -> Self {
+        self.stream_inline_thinking = true;
+        self
+    }
+
     pub(crate) fn push_structured_reasoning(&mut self, reasoning: &str) -> Option<String> {
         if reasoning.is_empty() {
             return None;
         }
 
-        self.saw_structured_reasoning = true;
+        if !self.saw_structured_reasoning {
+            self.saw_structured_reasoning = true;
+            self.accumulated_thinking.clear();
+        }
         self.pending_inline_thinking.clear();
         self.think_filter = ThinkFilter::new();
         self.accumulated_thinking.push_str(reasoning);
+        if self.streamed_inline_thinking.is_empty() {
+            return Some(reasoning.to_string());
+        }
+
+        // Chat parsers commonly re-report the inline block as structured reasoning
+        // once it closes; only forward what goes beyond what was already shown.
+        let shown = self.streamed_inline_thinking.trim_start();
+        let all = self.accumulated_thinking.trim_start();
+        if shown.starts_with(all) {
+            return None;
+        }
+        if let Some(rest) = all.strip_prefix(shown) {
+            let unshown = reasoning.len().min(rest.len());
+            return rest
+                .get(rest.len() - unshown..)
+                .filter(|rest| !rest.is_empty())
+                .map(str::to_string);
+        }
         Some(reasoning.to_string())
     }
 
@@ -47,6 +80,9 @@ impl ThinkingOutputFilter {
         let mut filtered = self.think_filter.push(text);
         if self.saw_structured_reasoning {
             filtered.thinking.clear();
+        } else if self.stream_inline_thinking {
+            self.streamed_inline_thinking.push_str(&filtered.thinking);
+            self.accumulated_thinking.push_str(&filtered.thinking);
         } else if !filtered.thinking.is_empty() {
             self.pending_inline_thinking.push_str(&filtered.thinking);
             filtered.thinking.clear();
@@ -79,3 +115,74 @@ impl ThinkingOutputFilter {
         &self.accumulated_thinking
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream_pieces(filter: &mut ThinkingOutputFilter, pieces: &[&str]) -> (String, String) {
+        let mut content = String::new();
+        let mut thinking = String::new();
+        for piece in pieces {
+            let filtered = filter.push_text(piece);
+            content.push_str(&filtered.content);
+            thinking.push_str(&filtered.thinking);
+        }
+        (content, thinking)
+    }
+
+    #[test]
+    fn streams_thinking_split_across_pieces() {
+        let mut filter = ThinkingOutputFilter::new(true, "").streaming_inline_thinking();
+        let (content, thinking) = stream_pieces(
+            &mut filter,
+            &["<th", "ink>Let me ", "consider", " this</th", "ink>"],
+        );
+
+        assert_eq!(content, "");
+        assert_eq!(thinking, "Let me consider this");
+
+        let rest = filter.finish();
+        assert_eq!(rest.thinking, "");
+        assert_eq!(rest.content, "");
+        assert_eq!(filter.accumulated_thinking(), "Let me consider this");
+    }
+
+    #[test]
+    fn text_after_closing_tag_is_content() {
+        let mut filter = ThinkingOutputFilter::new(true, "").streaming_inline_thinking();
+        let (content, thinking) = stream_pieces(
+            &mut filter,
+            &["<think>plan</think>", "\n\nThe answer", " is 4."],
+        );
+        let rest = filter.finish();
+
+        assert_eq!(thinking, "plan");
+        assert_eq!(
+            format!("{content}{}", rest.content).trim(),
+            "The answer is 4."
+        );
+    }
+
+    #[test]
+    fn structured_reasoning_does_not_repeat_streamed_thinking() {
+        let mut filter = ThinkingOutputFilter::new(true, "").streaming_inline_thinking();
+        stream_pieces(&mut filter, &["<think>\nstep one"]);
+
+        assert_eq!(filter.push_structured_reasoning("step one"), None);
+        assert_eq!(
+            filter.push_structured_reasoning(", step two").as_deref(),
+            Some(", step two")
+        );
+        assert_eq!(filter.accumulated_thinking(), "step one, step two");
+    }
+
+    #[test]
+    fn inline_thinking_is_held_until_finish_by_default() {
+        let mut filter = ThinkingOutputFilter::new(true, "");
+        let (_, thinking) = stream_pieces(&mut filter, &["<think>plan</think>done"]);
+
+        assert_eq!(thinking, "");
+        assert_eq!(filter.finish().thinking, "plan");
+    }
+}
@@ -4,6 +4,7 @@
 //! as `$ command` on a new line and code blocks as ```execute_typescript fenced blocks.
 //! The parser converts those patterns into Goose tool-call messages.
 
+use crate::local_model_registry::DEFAULT_EMULATOR_MAX_CODE_CHARS;
 use goose_provider_types::conversation::message::{Message, MessageContent};
 use rmcp::model::{CallToolRequestParams, Tool};
 use serde_json::json;
@@ -134,6 +135,7 @@ pub(crate) struct StreamingEmulatorParser {
     buffer: String,
     state: ParserState,
     code_mode_enabled: bool,
+    max_flush_code_chars: usize,
 }
 
 impl StreamingEmulatorParser {
@@ -142,9 +144,15 @@ impl StreamingEmulatorParser {
             buffer: String::new(),
             state: ParserState::Normal,
             code_mode_enabled,
+            max_flush_code_chars: DEFAULT_EMULATOR_MAX_CODE_CHARS,
         }
     }
 
+    pub(crate) fn with_max_flush_code_chars(mut self, max_chars: usize) -> Self {
+        self.max_flush_code_chars = max_chars;
+        self
+    }
+
     pub(crate) fn process_chunk(&mut self, chunk: &str) -> Vec<EmulatorAction> {
         self.buffer.push_str(chunk);
         let mut results = Vec::new();
@@ -257,7 +265,18 @@ impl StreamingEmulatorParser {
                 }
                 ParserState::InExecuteBlock => {
                     let code = self.buffer.trim();
-                    if !code.is_empty() {
+                    let code_chars = code.chars().count();
+                    if code_chars > self.max_flush_code_chars {
+                        tracing::warn!(
+                            code_chars,
+                            max_chars = self.max_flush_code_chars,
+                            "Unterminated execute block is too long to run, emitting it as text"
+                        );
+                        results.push(EmulatorAction::Text(format!(
+                            "```execute_typescript\n{}",
+                            self.buffer
+                        )));
+                    } else if !code.is_empty() {
                         results.push(EmulatorAction::ExecuteCode(code.to_string()));
                     }
                 }
@@ -552,4 +571,18 @@ mod tests {
         assert_eq!(executes.len(), 1);
         assert_execute(executes[0], "let x = 1;");
     }
+
+    #[test]
+    fn oversized_unclosed_execute_block_is_not_run() {
+        let code = "let x = 1;\n".repeat(10);
+        let mut parser = StreamingEmulatorParser::new(true).with_max_flush_code_chars(50);
+        let mut actions = parser.process_chunk(&format!("```execute_typescript\n{code}"));
+        actions.extend(parser.flush());
+
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            EmulatorAction::Text(text) => assert!(text.ends_with(&code)),
+            other => panic!("expected Text, got {:?}", action_label(other)),
+        }
+    }
 }
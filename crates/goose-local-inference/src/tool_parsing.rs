@@ -1,4 +1,5 @@
 use rmcp::model::Tool;
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 
 pub(super) fn compact_tools_json(tools: &[Tool]) -> Option<String> {
@@ -17,6 +18,77 @@ pub(super) fn compact_tools_json(tools: &[Tool]) -> Option<String> {
     serde_json::to_string(&compact).ok()
 }
 
+/// Resolve a dotted `Namespace.method` name, the form code-mode tool descriptions render,
+/// to the internal `namespace__method` tool it refers to. Other names pass through unchanged.
+pub(super) fn canonical_tool_name<'a>(name: &'a str, tools: &'a [Tool]) -> &'a str {
+    let Some((namespace, method)) = name.split_once('.') else {
+        return name;
+    };
+    let fold = |part: &str| part.replace('_', "").to_ascii_lowercase();
+    let (namespace, method) = (fold(namespace), fold(method));
+    tools
+        .iter()
+        .find(|tool| {
+            tool.name
+                .split_once("__")
+                .is_some_and(|(ns, m)| fold(ns) == namespace && fold(m) == method)
+        })
+        .map_or(name, |tool| tool.name.as_ref())
+}
+
+/// Parse model-emitted JSON, retrying once after [`repair_lenient_json`] if the strict
+/// parse fails. The strict error is returned when the repair doesn't help either.
+pub(super) fn parse_lenient_json<T: DeserializeOwned>(text: &str) -> serde_json::Result<T> {
+    serde_json::from_str(text)
+        .or_else(|error| serde_json::from_str(&repair_lenient_json(text)).map_err(|_| error))
+}
+
+/// Rewrite the near-JSON small models tend to emit: single-quoted strings become
+/// double-quoted and commas before a closing `}` or `]` are dropped. Text inside
+/// double-quoted strings is left alone.
+fn repair_lenient_json(text: &str) -> String {
+    let mut repaired = String::with_capacity(text.len());
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for ch in text.chars() {
+        match quote {
+            Some(open) if escaped => {
+                escaped = false;
+                // `\'` is not a JSON escape; inside a single-quoted string it is just `'`.
+                if !(open == '\'' && ch == '\'') {
+                    repaired.push('\\');
+                }
+                repaired.push(ch);
+            }
+            Some(_) if ch == '\\' => escaped = true,
+            Some(open) if ch == open => {
+                repaired.push('"');
+                quote = None;
+            }
+            Some('\'') if ch == '"' => repaired.push_str("\\\""),
+            Some(_) => repaired.push(ch),
+            None => match ch {
+                '"' | '\'' => {
+                    repaired.push('"');
+                    quote = Some(ch);
+                }
+                '}' | ']' => {
+                    let kept = repaired.trim_end();
+                    if kept.ends_with(',') {
+                        let len = kept.len() - 1;
+                        repaired.truncate(len);
+                    }
+                    repaired.push(ch);
+                }
+                _ => repaired.push(ch),
+            },
+        }
+    }
+
+    repaired
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,6 +114,66 @@ mod tests {
         assert!(func.get("parameters").is_none());
     }
 
+    #[test]
+    fn test_canonical_tool_name_maps_dotted_names() {
+        use rmcp::object;
+
+        let tools: Vec<Tool> = ["developer__shell", "developer__read_file"]
+            .into_iter()
+            .map(|name| Tool::new(name, "", object!({"type": "object"})))
+            .collect();
+
+        assert_eq!(
+            canonical_tool_name("developer.shell", &tools),
+            "developer__shell"
+        );
+        assert_eq!(
+            canonical_tool_name("Developer.shell", &tools),
+            "developer__shell"
+        );
+        assert_eq!(
+            canonical_tool_name("Developer.readFile", &tools),
+            "developer__read_file"
+        );
+        assert_eq!(
+            canonical_tool_name("developer__shell", &tools),
+            "developer__shell"
+        );
+        assert_eq!(canonical_tool_name("memory.store", &tools), "memory.store");
+    }
+
+    #[test]
+    fn test_lenient_json_drops_trailing_commas() {
+        let value: Value =
+            parse_lenient_json(r#"{"command": "ls", "args": ["-l", "-a",],}"#).unwrap();
+        assert_eq!(value, json!({"command": "ls", "args": ["-l", "-a"]}));
+
+        let value: Value = parse_lenient_json(r#"{"text": "a,}"}"#).unwrap();
+        assert_eq!(value, json!({"text": "a,}"}));
+    }
+
+    #[test]
+    fn test_lenient_json_normalizes_single_quotes() {
+        let value: Value =
+            parse_lenient_json(r#"{'path': 'say "hi"', 'note': 'it\'s', "keep": "don't"}"#)
+                .unwrap();
+        assert_eq!(
+            value,
+            json!({"path": "say \"hi\"", "note": "it's", "keep": "don't"})
+        );
+    }
+
+    #[test]
+    fn test_lenient_json_keeps_strict_error_when_unrepairable() {
+        let error = parse_lenient_json::<Value>("{'path': }").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            serde_json::from_str::<Value>("{'path': }")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
     #[test]
     fn test_compact_tools_json_empty() {
         let result = compact_tools_json(&[]);
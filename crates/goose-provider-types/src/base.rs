@@ -5,9 +5,12 @@ use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 
 use crate::{
-    canonical::{map_to_canonical_model, CanonicalModelRegistry},
+    canonical::{
+        map_to_canonical_model, maybe_get_canonical_model, CanonicalModel, CanonicalModelRegistry,
+        Modality,
+    },
     conversation::{
-        message::{Message, MessageContent},
+        message::{Message, MessageContent, SystemNotificationType},
         token_usage::{ProviderUsage, Usage},
     },
     errors::ProviderError,
@@ -44,6 +47,73 @@ pub struct ProviderMetadata {
     /// compaction). When set, fast-path callers prefer this model over the main model.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fast_model: Option<String>,
+    /// What the provider can do, so UIs can render capabilities uniformly
+    #[serde(default)]
+    pub capabilities: ProviderCapabilities,
+}
+
+/// Provider-level capability flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderCapabilities {
+    /// Whether goose's tools are passed through to the provider's models
+    pub tools: bool,
+    /// Whether the provider can stream responses at all. This is a static claim about the
+    /// provider; a configured instance may still reply in one piece for a given model, which
+    /// `Provider::supports_streaming` reports
+    pub streaming: bool,
+    /// Whether the provider can produce embeddings
+    pub embeddings: bool,
+    /// Whether at least one known model accepts image input
+    pub vision: bool,
+    /// Whether at least one known model supports reasoning/thinking controls
+    pub reasoning: bool,
+    /// Whether a model that is not in the provider's list may be entered by hand
+    pub unlisted_models: bool,
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            tools: true,
+            streaming: true,
+            embeddings: false,
+            vision: false,
+            reasoning: false,
+            unlisted_models: true,
+        }
+    }
+}
+
+impl ProviderCapabilities {
+    /// Derive the model-level flags (tools, vision, reasoning) from a provider's known models,
+    /// consulting the canonical registry where the model info itself is silent.
+    pub fn from_models(provider_name: &str, models: &[ModelInfo]) -> Self {
+        let canonical: Vec<Option<CanonicalModel>> = models
+            .iter()
+            .map(|model| maybe_get_canonical_model(provider_name, &model.name))
+            .collect();
+
+        Self {
+            tools: models.is_empty()
+                || models
+                    .iter()
+                    .any(|model| model.supports_tools != Some(false)),
+            vision: canonical
+                .iter()
+                .flatten()
+                .any(|model| model.modalities.input.contains(&Modality::Image)),
+            reasoning: models
+                .iter()
+                .zip(&canonical)
+                .any(|(model, canonical_model)| {
+                    model.reasoning
+                        || canonical_model
+                            .as_ref()
+                            .is_some_and(|canonical| canonical.reasoning == Some(true))
+                }),
+            ..Self::default()
+        }
+    }
 }
 
 impl ProviderMetadata {
@@ -56,15 +126,17 @@ impl ProviderMetadata {
         model_doc_link: &str,
         config_keys: Vec<ConfigKey>,
     ) -> Self {
+        let known_models: Vec<ModelInfo> = model_names
+            .iter()
+            .map(|&model_name| model_info_for_provider_model(name, model_name))
+            .collect();
         Self {
             name: name.to_string(),
             display_name: display_name.to_string(),
             description: description.to_string(),
             default_model: default_model.to_string(),
-            known_models: model_names
-                .iter()
-                .map(|&model_name| model_info_for_provider_model(name, model_name))
-                .collect(),
+            capabilities: ProviderCapabilities::from_models(name, &known_models),
+            known_models,
             model_doc_link: model_doc_link.to_string(),
             config_keys,
             setup_steps: vec![],
@@ -87,6 +159,7 @@ impl ProviderMetadata {
             display_name: display_name.to_string(),
             description: description.to_string(),
             default_model: default_model.to_string(),
+            capabilities: ProviderCapabilities::from_models(name, &models),
             known_models: models,
             model_doc_link: model_doc_link.to_string(),
             config_keys,
@@ -108,6 +181,7 @@ impl ProviderMetadata {
             setup_steps: vec![],
             model_selection_hint: None,
             fast_model: None,
+            capabilities: ProviderCapabilities::default(),
         }
     }
 
@@ -125,6 +199,31 @@ impl ProviderMetadata {
         self.fast_model = Some(fast_model.to_string());
         self
     }
+
+    pub fn with_embeddings(mut self) -> Self {
+        self.capabilities.embeddings = true;
+        self
+    }
+
+    pub fn with_vision(mut self, vision: bool) -> Self {
+        self.capabilities.vision = vision;
+        self
+    }
+
+    pub fn without_tools(mut self) -> Self {
+        self.capabilities.tools = false;
+        self
+    }
+
+    pub fn without_streaming(mut self) -> Self {
+        self.capabilities.streaming = false;
+        self
+    }
+
+    pub fn without_unlisted_models(mut self) -> Self {
+        self.capabilities.unlisted_models = false;
+        self
+    }
 }
 
 /// Configuration key metadata for provider setup
@@ -230,10 +329,16 @@ pub struct ModelInfo {
     pub input_token_cost: Option<f64>,
     /// Cost per token for output in USD (optional)
     pub output_token_cost: Option<f64>,
+    /// Cost per cached input token in USD (optional); falls back to the input cost
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_token_cost: Option<f64>,
     /// Currency for the costs (default: "$")
     pub currency: Option<String>,
     /// Whether this model supports cache control
     pub supports_cache_control: Option<bool>,
+    /// Whether this model can call tools natively; `None` when unknown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_tools: Option<bool>,
     /// Whether this model supports reasoning/thinking controls
     #[serde(default)]
     pub reasoning: bool,
@@ -248,8 +353,10 @@ impl ModelInfo {
             context_limit,
             input_token_cost: None,
             output_token_cost: None,
+            cache_read_token_cost: None,
             currency: None,
             supports_cache_control: None,
+            supports_tools: None,
             reasoning: false,
         }
     }
@@ -267,11 +374,21 @@ impl ModelInfo {
             context_limit,
             input_token_cost: Some(input_cost),
             output_token_cost: Some(output_cost),
+            cache_read_token_cost: None,
             currency: Some("$".to_string()),
             supports_cache_control: None,
+            supports_tools: None,
             reasoning: false,
         }
     }
+
+    /// Set input/output pricing from USD per million tokens, as providers publish it
+    pub fn with_price_per_million(mut self, input: f64, output: f64) -> Self {
+        self.input_token_cost = Some(input / 1_000_000.0);
+        self.output_token_cost = Some(output / 1_000_000.0);
+        self.currency = Some("$".to_string());
+        self
+    }
 }
 
 pub trait ProviderDescriptor {
@@ -312,12 +429,21 @@ pub fn model_info_for_provider_model(provider_name: &str, model_name: &str) -> M
             .context_limit(),
         input_token_cost: None,
         output_token_cost: None,
+        cache_read_token_cost: None,
         currency: None,
         supports_cache_control: None,
+        supports_tools: None,
         reasoning,
     }
 }
 
+fn model_info_with_capabilities<P: Provider + ?Sized>(provider: &P, model_name: &str) -> ModelInfo {
+    ModelInfo {
+        supports_tools: Some(provider.supports_tools(&ModelConfig::new(model_name))),
+        ..model_info_for_provider_model(provider.get_name(), model_name)
+    }
+}
+
 /// Collect all chunks from a MessageStream into a single Message and ProviderUsage
 pub async fn collect_stream(
     mut stream: MessageStream,
@@ -375,6 +501,32 @@ pub fn stream_from_single_message(message: Message, usage: ProviderUsage) -> Mes
     Box::pin(stream)
 }
 
+/// Yield a progress notification whenever `interval` passes without an item, so a slow
+/// provider (still thinking) shows as waiting rather than stopped.
+pub fn with_keepalive(mut stream: MessageStream, interval: std::time::Duration) -> MessageStream {
+    use futures::StreamExt;
+
+    Box::pin(async_stream::stream! {
+        let started = std::time::Instant::now();
+        loop {
+            match tokio::time::timeout(interval, stream.next()).await {
+                Ok(Some(item)) => yield item,
+                Ok(None) => break,
+                Err(_) => {
+                    let waiting = Message::assistant().with_system_notification(
+                        SystemNotificationType::ProgressMessage,
+                        format!(
+                            "Waiting for the model to respond ({}s)...",
+                            started.elapsed().as_secs()
+                        ),
+                    );
+                    yield Ok((Some(waiting), None));
+                }
+            }
+        }
+    })
+}
+
 /// Base trait for AI providers (OpenAI, Anthropic, etc)
 #[async_trait]
 pub trait Provider: Send + Sync {
@@ -410,6 +562,30 @@ pub trait Provider: Send + Sync {
         Ok(model_config.context_limit())
     }
 
+    /// Count the prompt tokens a request would consume before it is sent.
+    ///
+    /// Returns `None` when the provider has no tokenizer for the model. Local providers
+    /// return an exact count; remote providers may return an estimate.
+    async fn count_tokens(
+        &self,
+        _model_config: &ModelConfig,
+        _system: &str,
+        _messages: &[Message],
+        _tools: &[Tool],
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Cheaply verify the provider is reachable and usable for `model_config`,
+    /// without sending a completion. The default assumes the provider is healthy.
+    async fn health_check(&self, _model_config: &ModelConfig) -> Result<(), ProviderError> {
+        Ok(())
+    }
+
+    /// Release anything the provider keeps running for its session, such as a spawned
+    /// CLI process. Called when the session closes; the default does nothing.
+    async fn shutdown(&self) {}
+
     fn retry_config(&self) -> RetryConfig {
         RetryConfig::default()
     }
@@ -423,12 +599,24 @@ pub trait Provider: Send + Sync {
             .fetch_supported_models()
             .await?
             .iter()
-            .map(|model_name| model_info_for_provider_model(self.get_name(), model_name))
+            .map(|model_name| model_info_with_capabilities(self, model_name))
             .collect())
     }
 
     async fn fetch_model_info(&self, model_name: &str) -> Result<ModelInfo, ProviderError> {
-        Ok(model_info_for_provider_model(self.get_name(), model_name))
+        Ok(model_info_with_capabilities(self, model_name))
+    }
+
+    /// Whether tools passed to `stream` reach the model and come back as native tool calls.
+    /// Lets agents decide before a turn instead of discovering it from the response.
+    fn supports_tools(&self, _model_config: &ModelConfig) -> bool {
+        true
+    }
+
+    /// Whether responses for `model_config` arrive incrementally. Providers without
+    /// streaming still implement `stream`, but yield the whole reply as a single message.
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        true
     }
 
     fn skip_canonical_filtering(&self) -> bool {
@@ -534,6 +722,13 @@ pub trait Provider: Send + Sync {
         false
     }
 
+    /// Whether session descriptions are derived from the first user message instead of
+    /// asking a model. CLI wrappers opt in because every request spawns a full agent turn,
+    /// which is far too slow and costly for a four-word title.
+    fn derives_session_descriptions_locally(&self) -> bool {
+        false
+    }
+
     /// Configure OAuth authentication for this provider
     ///
     /// This method is called when a provider has configuration keys marked with oauth_flow = true.
@@ -574,6 +769,17 @@ pub trait Provider: Send + Sync {
     }
 }
 
+/// Providers that can turn text into embedding vectors, e.g. for retrieval.
+#[async_trait]
+pub trait EmbeddingCapable: Send + Sync {
+    /// Embed each text with `model_name`, returning one vector per input in order.
+    async fn create_embeddings(
+        &self,
+        model_name: &str,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, ProviderError>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -689,6 +895,38 @@ mod tests {
         assert_eq!(message.agent_visible_content().as_concat_text(), "private");
     }
 
+    #[tokio::test]
+    async fn test_with_keepalive_emits_during_pause() {
+        use futures::StreamExt;
+        use std::time::Duration;
+
+        let stalled = async_stream::stream! {
+            yield Ok((Some(Message::assistant().with_text("before")), None));
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            yield Ok((Some(Message::assistant().with_text("after")), None));
+        };
+        let items: Vec<_> = with_keepalive(Box::pin(stalled), Duration::from_millis(20))
+            .collect()
+            .await;
+
+        let messages: Vec<_> = items
+            .iter()
+            .map(|item| item.as_ref().unwrap().0.as_ref().unwrap())
+            .collect();
+        let texts: Vec<_> = messages
+            .iter()
+            .map(|msg| msg.as_concat_text())
+            .filter(|text| !text.is_empty())
+            .collect();
+        assert_eq!(texts, vec!["before", "after"]);
+        assert!(matches!(
+            &messages[1].content[..],
+            [MessageContent::SystemNotification(notification)]
+                if notification.notification_type == SystemNotificationType::ProgressMessage
+        ));
+        assert_eq!(messages.last().unwrap().as_concat_text(), "after");
+    }
+
     #[test]
     fn test_model_info_creation() {
         // Test direct ModelInfo creation
@@ -698,8 +936,10 @@ mod tests {
             context_limit: 1000,
             input_token_cost: None,
             output_token_cost: None,
+            cache_read_token_cost: None,
             currency: None,
             supports_cache_control: None,
+            supports_tools: None,
             reasoning: false,
         };
         assert_eq!(info.context_limit, 1000);
@@ -711,8 +951,10 @@ mod tests {
             context_limit: 1000,
             input_token_cost: None,
             output_token_cost: None,
+            cache_read_token_cost: None,
             currency: None,
             supports_cache_control: None,
+            supports_tools: None,
             reasoning: false,
         };
         assert_eq!(info, info2);
@@ -724,13 +966,40 @@ mod tests {
             context_limit: 2000,
             input_token_cost: None,
             output_token_cost: None,
+            cache_read_token_cost: None,
             currency: None,
             supports_cache_control: None,
+            supports_tools: None,
             reasoning: false,
         };
         assert_ne!(info, info3);
     }
 
+    #[test]
+    fn test_capabilities_from_models() {
+        let no_tools = ModelInfo {
+            supports_tools: Some(false),
+            ..ModelInfo::new("custom-model", 8_000)
+        };
+        let capabilities = ProviderCapabilities::from_models("custom", &[no_tools.clone()]);
+        assert!(!capabilities.tools);
+        assert!(!capabilities.vision);
+        assert!(!capabilities.reasoning);
+
+        let thinker = ModelInfo {
+            reasoning: true,
+            ..ModelInfo::new("custom-thinker", 8_000)
+        };
+        let capabilities = ProviderCapabilities::from_models("custom", &[no_tools, thinker]);
+        assert!(capabilities.tools);
+        assert!(capabilities.reasoning);
+
+        assert_eq!(
+            ProviderCapabilities::from_models("custom", &[]),
+            ProviderCapabilities::default()
+        );
+    }
+
     #[test]
     fn test_model_info_with_cost() {
         let info = ModelInfo::with_cost("gpt-4o", 128000, 0.0000025, 0.00001);
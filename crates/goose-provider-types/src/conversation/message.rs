@@ -113,6 +113,16 @@ impl ToolRequest {
             .unwrap_or(false)
     }
 
+    /// Returns true for an interim request streamed as soon as the call was named,
+    /// before its arguments arrived. See [`TOOL_META_PARTIAL_KEY`].
+    pub fn is_partial(&self) -> bool {
+        self.tool_meta
+            .as_ref()
+            .and_then(|v| v.get(TOOL_META_PARTIAL_KEY))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
     /// Returns the persisted LLM-generated title for this tool call, if any.
     /// Set asynchronously by [`crate::acp::server`] after `provider.complete_fast`
     /// resolves; survives session reload via SQLite. Falls back to `None` for
@@ -157,6 +167,10 @@ pub struct PersistedChainSummary {
 /// executed externally; the agent loop must skip redispatch.
 pub const TOOL_META_EXTERNAL_DISPATCH_KEY: &str = "goose.external_dispatch";
 
+/// Marker key under `ToolRequest.tool_meta` for an interim request with empty
+/// arguments; a complete request with the same id follows, so it is shown but never run.
+pub const TOOL_META_PARTIAL_KEY: &str = "goose.partial";
+
 /// Key under `ToolRequest.tool_meta` storing the LLM-generated short title
 /// for this tool call. Used to make the title survive session reload.
 pub const TOOL_META_TITLE_KEY: &str = "goose.toolSummary.title";
@@ -1969,6 +1983,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn partial_marker_is_read_from_tool_meta() {
+        assert!(!make_tool_request(None).is_partial());
+        let meta = serde_json::json!({ super::TOOL_META_PARTIAL_KEY: true });
+        let req = make_tool_request(Some(meta));
+        assert!(req.is_partial());
+        assert!(!req.is_externally_dispatched());
+    }
+
     #[test]
     fn persisted_title_returns_none_when_meta_missing() {
         let req = make_tool_request(None);
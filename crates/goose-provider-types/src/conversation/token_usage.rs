@@ -2,6 +2,9 @@ use std::ops::{Add, AddAssign};
 
 use serde::{Deserialize, Serialize};
 
+use crate::base::ModelInfo;
+use crate::canonical::Pricing;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderUsage {
     pub model: String,
@@ -28,6 +31,9 @@ pub struct ProviderStats {
     pub elapsed_ms: Option<u64>,
     pub output_tokens: Option<usize>,
     pub draft: Option<DraftStats>,
+    /// Per-token log-probabilities, when the backend was asked to return them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Vec<TokenLogprob>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -40,6 +46,16 @@ pub struct DraftStats {
     pub accept_rate: f64,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenLogprob {
+    pub token: i32,
+    pub text: String,
+    pub logprob: f32,
+    /// The most likely tokens at this position as `(token, logprob)`, most likely first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_logprobs: Vec<(i32, f32)>,
+}
+
 impl ProviderUsage {
     pub fn new(model: String, usage: Usage) -> Self {
         Self {
@@ -61,6 +77,19 @@ impl ProviderUsage {
         self.cost_source = Some(source);
         self
     }
+
+    /// Estimate the cost of this usage from the model's per-token pricing.
+    /// Returns `None` when the model has no input or output price.
+    pub fn estimated_cost(&self, model: &ModelInfo) -> Option<f64> {
+        let per_million = |cost: Option<f64>| cost.map(|c| c * 1_000_000.0);
+        Pricing {
+            input: per_million(model.input_token_cost),
+            output: per_million(model.output_token_cost),
+            cache_read: per_million(model.cache_read_token_cost),
+            cache_write: None,
+        }
+        .estimate_cost(&self.usage)
+    }
 }
 
 /// `input_tokens` is the total input including cache read/write tokens;
@@ -76,6 +105,11 @@ pub struct Usage {
     pub total_tokens: Option<i32>,
     pub cache_read_input_tokens: Option<i32>,
     pub cache_write_input_tokens: Option<i32>,
+    /// Predicted-output tokens that matched the response; subsets of `output_tokens`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_prediction_tokens: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected_prediction_tokens: Option<i32>,
 }
 
 fn sum_optionals<T>(a: Option<T>, b: Option<T>) -> Option<T>
@@ -106,6 +140,16 @@ impl Add for Usage {
                 other.cache_write_input_tokens,
             ),
         )
+        .with_prediction_tokens(
+            sum_optionals(
+                self.accepted_prediction_tokens,
+                other.accepted_prediction_tokens,
+            ),
+            sum_optionals(
+                self.rejected_prediction_tokens,
+                other.rejected_prediction_tokens,
+            ),
+        )
     }
 }
 
@@ -138,6 +182,8 @@ impl Usage {
             total_tokens: calculated_total,
             cache_read_input_tokens: None,
             cache_write_input_tokens: None,
+            accepted_prediction_tokens: None,
+            rejected_prediction_tokens: None,
         }
     }
 
@@ -151,6 +197,16 @@ impl Usage {
         self
     }
 
+    pub fn with_prediction_tokens(
+        mut self,
+        accepted_prediction_tokens: Option<i32>,
+        rejected_prediction_tokens: Option<i32>,
+    ) -> Self {
+        self.accepted_prediction_tokens = accepted_prediction_tokens;
+        self.rejected_prediction_tokens = rejected_prediction_tokens;
+        self
+    }
+
     /// For providers whose reported `input_tokens`/`total_tokens` exclude
     /// cache tokens (e.g. Anthropic, Bedrock): folds the cache breakdown in.
     pub fn from_cache_exclusive_input(
@@ -209,6 +265,37 @@ mod tests {
         assert_eq!(usage.cache_write_input_tokens, Some(1000));
     }
 
+    #[test]
+    fn test_estimated_cost_from_model_pricing() {
+        let model = ModelInfo::new("gpt-4o", 128_000).with_price_per_million(2.5, 10.0);
+        let usage = ProviderUsage::new(
+            "gpt-4o".to_string(),
+            Usage::new(Some(1_000_000), Some(100_000), None),
+        );
+
+        let cost = usage.estimated_cost(&model).unwrap();
+        assert!((cost - 3.5).abs() < 1e-9);
+
+        let unpriced = ModelInfo::new("gpt-4o", 128_000);
+        assert_eq!(usage.estimated_cost(&unpriced), None);
+    }
+
+    #[test]
+    fn test_estimated_cost_discounts_cached_input() {
+        let mut model = ModelInfo::new("gpt-4o", 128_000).with_price_per_million(2.5, 10.0);
+        let usage = ProviderUsage::new(
+            "gpt-4o".to_string(),
+            Usage::new(Some(1_000_000), Some(0), None).with_cache_tokens(Some(400_000), None),
+        );
+
+        let full_price = usage.estimated_cost(&model).unwrap();
+        assert!((full_price - 2.5).abs() < 1e-9);
+
+        model.cache_read_token_cost = Some(1.25 / 1_000_000.0);
+        let discounted = usage.estimated_cost(&model).unwrap();
+        assert!((discounted - 2.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_usage_addition_includes_cached_tokens() {
         let usage_a =
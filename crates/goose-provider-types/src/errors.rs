@@ -131,6 +131,10 @@ fn provider_error_from_reqwest(error: &reqwest::Error) -> ProviderError {
 
 impl From<anyhow::Error> for ProviderError {
     fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<ProviderError>() {
+            Ok(provider_error) => return provider_error,
+            Err(error) => error,
+        };
         if let Some(reqwest_err) = error.downcast_ref::<reqwest::Error>() {
             return provider_error_from_reqwest(reqwest_err);
         }
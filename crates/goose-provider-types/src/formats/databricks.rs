@@ -1071,6 +1071,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         let obj = request.as_object().unwrap();
@@ -1105,6 +1109,10 @@ mod tests {
             toolshim_model: None,
             request_params: Some(params),
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         assert_eq!(request["reasoning_effort"], "high");
@@ -1124,6 +1132,10 @@ mod tests {
             toolshim_model: None,
             request_params: Some(params),
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         assert_eq!(request["reasoning_effort"], "low");
@@ -1144,6 +1156,10 @@ mod tests {
             toolshim_model: None,
             request_params: Some(params),
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         assert_eq!(request["reasoning_effort"], "high");
@@ -1162,6 +1178,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         assert_eq!(request["model"], "o3");
@@ -1180,6 +1200,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         assert_eq!(request["model"], "o3");
@@ -1198,6 +1222,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         let request = create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi)?;
         assert_eq!(request["model"], "databricks-gpt-5.4");
@@ -1687,6 +1715,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let messages = vec![
@@ -1739,6 +1771,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let messages = vec![Message::user().with_text("Hello")];
@@ -8,6 +8,10 @@
 //! Known affected models:
 //! - qwen3-coder
 //! - qwen3-coder-32b
+//!
+//! Models that fall back to their own JSON tool-call markers in text are handled too:
+//! Hermes-style `<tool_call>{...}</tool_call>`, Mistral's `[TOOL_CALLS][...]` and
+//! Llama 3.1's `<|python_tag|>{...}`.
 
 use crate::conversation::message::{Message, MessageContent};
 use crate::{
@@ -17,7 +21,6 @@ use crate::{
 use async_stream::try_stream;
 use chrono;
 use futures::Stream;
-use regex::Regex;
 use rmcp::model::{object, CallToolRequestParams, ErrorCode, ErrorData, Role};
 use serde_json::Value;
 use std::borrow::Cow;
@@ -27,6 +30,104 @@ pub use crate::formats::openai::{
     create_request, format_messages, format_tools, get_usage, validate_tool_schemas,
 };
 
+const FUNCTION_OPEN: &str = "<function=";
+const PARAMETER_OPEN: &str = "<parameter=";
+const PARAMETER_CLOSE: &str = "</parameter>";
+const BLOCK_CLOSE_TAGS: [&str; 2] = ["</function>", "</tool_call>"];
+/// Markers followed by JSON tool calls: one `{"name", "arguments"}` object (`parameters` in
+/// Llama's format) or an array of them.
+const JSON_TOOL_CALL_SENTINELS: [&str; 3] = ["<tool_call>", "[TOOL_CALLS]", "<|python_tag|>"];
+const TOOL_CALL_SENTINELS: [&str; 4] = [
+    "<tool_call>",
+    FUNCTION_OPEN,
+    "[TOOL_CALLS]",
+    "<|python_tag|>",
+];
+
+/// Byte index up to which `text` can be streamed as visible output.
+///
+/// A trailing partial prefix of any of `sentinels` is held back so a chunk boundary inside
+/// e.g. `[TOOL_CA` doesn't leak half a tool-call marker to the user.
+pub fn safe_stream_end(text: &str, sentinels: &[&str]) -> usize {
+    sentinels
+        .iter()
+        .filter_map(|sentinel| {
+            (1..sentinel.len())
+                .rev()
+                .filter_map(|len| sentinel.get(..len))
+                .find(|partial| text.ends_with(partial))
+                .map(|partial| text.len() - partial.len())
+        })
+        .min()
+        .unwrap_or(text.len())
+}
+
+fn first_sentinel_start(text: &str) -> Option<usize> {
+    TOOL_CALL_SENTINELS
+        .iter()
+        .filter_map(|sentinel| text.find(sentinel))
+        .min()
+}
+
+/// Split XML-style tool call blocks into (function name, string arguments).
+///
+/// Block terminators are only recognized outside `<parameter=...>...</parameter>` spans,
+/// so a value that mentions `</function>` or `</tool_call>` (e.g. documentation about tool
+/// calls) stays part of the argument. Blocks without a terminator are dropped.
+pub fn parse_xml_function_blocks(content: &str) -> Vec<(String, serde_json::Map<String, Value>)> {
+    let mut blocks = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find(FUNCTION_OPEN) {
+        let Some(after_open) = rest.get(start + FUNCTION_OPEN.len()..) else {
+            break;
+        };
+        let Some((name, mut body)) = after_open.split_once('>') else {
+            break;
+        };
+
+        let mut arguments = serde_json::Map::new();
+        let mut terminated = false;
+        loop {
+            let block_end = BLOCK_CLOSE_TAGS
+                .iter()
+                .filter_map(|tag| body.find(tag).map(|idx| (idx, tag.len())))
+                .min();
+            let parameter = body
+                .find(PARAMETER_OPEN)
+                .filter(|idx| block_end.is_none_or(|(end_idx, _)| *idx < end_idx))
+                .and_then(|idx| body.get(idx + PARAMETER_OPEN.len()..))
+                .and_then(|param| param.split_once('>'))
+                .and_then(|(param_name, value_and_rest)| {
+                    value_and_rest
+                        .split_once(PARAMETER_CLOSE)
+                        .map(|(value, after_value)| (param_name, value, after_value))
+                });
+            if let Some((param_name, value, after_value)) = parameter {
+                arguments.insert(
+                    param_name.trim().to_string(),
+                    Value::String(value.trim().to_string()),
+                );
+                body = after_value;
+                continue;
+            }
+            if let Some((end_idx, tag_len)) = block_end {
+                body = body.get(end_idx + tag_len..).unwrap_or_default();
+                terminated = true;
+            }
+            break;
+        }
+
+        if !terminated {
+            break;
+        }
+        blocks.push((name.trim().to_string(), arguments));
+        rest = body;
+    }
+
+    blocks
+}
+
 /// Parse XML-style tool calls from content (Ollama/Qwen3-coder fallback format).
 ///
 /// Format: `<function=name><parameter=key>value</parameter>...</function>`
@@ -35,47 +136,104 @@ pub use crate::formats::openai::{
 pub fn parse_xml_tool_calls(content: &str) -> (Option<String>, Vec<MessageContent>) {
     let mut tool_calls = Vec::new();
 
-    let function_re = Regex::new(r"<function=([^>]+)>([\s\S]*?)</function>").unwrap();
-    let param_re = Regex::new(r"<parameter=([^>]+)>([\s\S]*?)</parameter>").unwrap();
-
     let prefix = content
-        .find("<function=")
+        .find(FUNCTION_OPEN)
         .and_then(|idx| content.get(..idx))
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
         .map(|s| s.to_string());
 
-    for func_cap in function_re.captures_iter(content) {
-        let function_name = func_cap[1].trim().to_string();
-        let function_body = &func_cap[2];
-        let mut arguments = serde_json::Map::new();
-        for param_cap in param_re.captures_iter(function_body) {
-            let param_name = param_cap[1].trim().to_string();
-            let param_value = param_cap[2].trim().to_string();
-            arguments.insert(param_name, serde_json::Value::String(param_value));
-        }
+    for (function_name, arguments) in parse_xml_function_blocks(content) {
+        tool_calls.push(tool_request_content(function_name, arguments));
+    }
 
-        let id = Uuid::new_v4().to_string();
+    (prefix, tool_calls)
+}
 
-        if is_valid_function_name(&function_name) {
-            tool_calls.push(MessageContent::tool_request(
-                id,
-                Ok(CallToolRequestParams::new(function_name)
-                    .with_arguments(object(serde_json::Value::Object(arguments)))),
-            ));
-        } else {
-            let error = ErrorData {
-                code: ErrorCode::INVALID_REQUEST,
-                message: Cow::from(format!(
-                    "The provided function name '{}' had invalid characters, it must match this regex [a-zA-Z0-9_-]+",
-                    function_name
-                )),
-                data: None,
+fn tool_request_content(
+    function_name: String,
+    arguments: serde_json::Map<String, Value>,
+) -> MessageContent {
+    let id = Uuid::new_v4().to_string();
+
+    if is_valid_function_name(&function_name) {
+        MessageContent::tool_request(
+            id,
+            Ok(CallToolRequestParams::new(function_name)
+                .with_arguments(object(serde_json::Value::Object(arguments)))),
+        )
+    } else {
+        let error = ErrorData {
+            code: ErrorCode::INVALID_REQUEST,
+            message: Cow::from(format!(
+                "The provided function name '{}' had invalid characters, it must match this regex [a-zA-Z0-9_-]+",
+                function_name
+            )),
+            data: None,
+        };
+        MessageContent::tool_request(id, Err(error))
+    }
+}
+
+/// Parse JSON tool calls that follow one of [`JSON_TOOL_CALL_SENTINELS`].
+///
+/// A marker not followed by valid JSON (e.g. the `<tool_call>` wrapper around an XML
+/// `<function=` block) is skipped.
+pub fn parse_json_tool_calls(content: &str) -> Vec<MessageContent> {
+    let mut tool_calls = Vec::new();
+    let mut rest = content;
+
+    while let Some((start, sentinel)) = JSON_TOOL_CALL_SENTINELS
+        .iter()
+        .filter_map(|sentinel| rest.find(sentinel).map(|idx| (idx, *sentinel)))
+        .min_by_key(|(idx, _)| *idx)
+    {
+        rest = rest.get(start + sentinel.len()..).unwrap_or_default();
+        let mut values = serde_json::Deserializer::from_str(rest).into_iter::<Value>();
+        let Some(Ok(value)) = values.next() else {
+            continue;
+        };
+        rest = rest.get(values.byte_offset()..).unwrap_or_default();
+
+        let calls = match value {
+            Value::Array(calls) => calls,
+            call => vec![call],
+        };
+        for call in calls {
+            let Some(name) = call.get("name").and_then(Value::as_str) else {
+                continue;
             };
-            tool_calls.push(MessageContent::tool_request(id, Err(error)));
+            let arguments = match call.get("arguments").or_else(|| call.get("parameters")) {
+                Some(Value::Object(arguments)) => arguments.clone(),
+                // Some models encode the arguments as a JSON string, like the native API does.
+                Some(Value::String(arguments)) => {
+                    serde_json::from_str(arguments).unwrap_or_default()
+                }
+                _ => serde_json::Map::new(),
+            };
+            tool_calls.push(tool_request_content(name.to_string(), arguments));
         }
     }
 
+    tool_calls
+}
+
+/// Parse tool calls written into text in any supported format, returning the text before the
+/// first tool-call marker alongside them. XML blocks take precedence over JSON markers.
+pub fn parse_text_tool_calls(content: &str) -> (Option<String>, Vec<MessageContent>) {
+    let (_, mut tool_calls) = parse_xml_tool_calls(content);
+    if tool_calls.is_empty() {
+        tool_calls = parse_json_tool_calls(content);
+    }
+    if tool_calls.is_empty() {
+        return (None, tool_calls);
+    }
+
+    let prefix = first_sentinel_start(content)
+        .and_then(|idx| content.get(..idx))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
     (prefix, tool_calls)
 }
 
@@ -102,14 +260,14 @@ pub fn response_to_message(response: &Value) -> anyhow::Result<Message> {
 
     if let Some(original) = original {
         if let Some(text) = original.get("content").and_then(|c| c.as_str()) {
-            if text.contains("<function=") {
-                let (prefix, xml_tool_calls) = parse_xml_tool_calls(text);
-                if !xml_tool_calls.is_empty() {
+            if first_sentinel_start(text).is_some() {
+                let (prefix, text_tool_calls) = parse_text_tool_calls(text);
+                if !text_tool_calls.is_empty() {
                     let mut content = Vec::new();
                     if let Some(prefix_text) = prefix {
                         content.push(MessageContent::text(prefix_text));
                     }
-                    content.extend(xml_tool_calls);
+                    content.extend(text_tool_calls);
 
                     return Ok(Message::new(
                         Role::Assistant,
@@ -148,12 +306,12 @@ fn is_text_only_message(message: &Message) -> bool {
         .all(|c| matches!(c, MessageContent::Text(_)))
 }
 
-/// Streaming message handler with XML tool call post-processing for Ollama.
+/// Streaming message handler with text tool call post-processing for Ollama.
 ///
 /// This wraps the standard OpenAI streaming handler and post-processes messages
-/// to detect and parse XML tool calls. When XML markers are detected in text
-/// messages, it buffers them until the stream completes, then parses and emits
-/// the tool calls.
+/// to detect and parse tool calls written into text. When a tool-call marker is
+/// detected in text messages, it buffers them until the stream completes, then
+/// parses and emits the tool calls.
 ///
 /// This approach avoids exposing any internal types from openai.rs.
 pub fn response_to_streaming_message_ollama<S>(
@@ -168,8 +326,10 @@ where
         let base_stream = openai::response_to_streaming_message(stream);
         let mut base_stream = std::pin::pin!(base_stream);
 
-        let mut accumulated_text = String::new();
-        let mut xml_detected = false;
+        // Text that has not been yielded yet: either a held-back partial sentinel or,
+        // once a full sentinel shows up, everything from there to the end of the stream.
+        let mut pending_text = String::new();
+        let mut sentinel_detected = false;
         let mut last_usage: Option<ProviderUsage> = None;
 
         while let Some(result) = base_stream.next().await {
@@ -179,18 +339,27 @@ where
                 last_usage = usage.clone();
             }
 
-            if let Some(message) = message_opt {
+            if let Some(mut message) = message_opt {
                 if is_text_only_message(&message) {
-                    let text = extract_text_from_message(&message);
-                    accumulated_text.push_str(&text);
+                    pending_text.push_str(&extract_text_from_message(&message));
 
-                    if !xml_detected && accumulated_text.contains("<function=") {
-                        xml_detected = true;
+                    if !sentinel_detected && first_sentinel_start(&pending_text).is_some() {
+                        sentinel_detected = true;
                     }
 
-                    if xml_detected {
+                    if sentinel_detected {
                         continue;
                     }
+
+                    let end = safe_stream_end(&pending_text, &TOOL_CALL_SENTINELS);
+                    let ready: String = pending_text.drain(..end).collect();
+                    if ready.is_empty() {
+                        if usage.is_some() {
+                            yield (None, usage);
+                        }
+                        continue;
+                    }
+                    message.content = vec![MessageContent::text(ready)];
                 }
 
                 yield (Some(message), usage);
@@ -199,32 +368,30 @@ where
             }
         }
 
-        if xml_detected && !accumulated_text.is_empty() {
-            let (prefix, xml_tool_calls) = parse_xml_tool_calls(&accumulated_text);
-
-            if !xml_tool_calls.is_empty() {
-                let mut contents = Vec::new();
-                if let Some(prefix_text) = prefix {
-                    contents.push(MessageContent::text(prefix_text));
-                }
-                contents.extend(xml_tool_calls);
-
-                let msg = Message::new(
-                    Role::Assistant,
-                    chrono::Utc::now().timestamp(),
-                    contents,
-                );
+        if !pending_text.is_empty() {
+            let (prefix, text_tool_calls) = if sentinel_detected {
+                parse_text_tool_calls(&pending_text)
+            } else {
+                (None, Vec::new())
+            };
 
-                yield (Some(msg), last_usage);
+            let contents = if text_tool_calls.is_empty() {
+                vec![MessageContent::text(&pending_text)]
             } else {
-                let msg = Message::new(
-                    Role::Assistant,
-                    chrono::Utc::now().timestamp(),
-                    vec![MessageContent::text(&accumulated_text)],
-                );
+                prefix
+                    .map(MessageContent::text)
+                    .into_iter()
+                    .chain(text_tool_calls)
+                    .collect()
+            };
 
-                yield (Some(msg), last_usage);
-            }
+            let msg = Message::new(
+                Role::Assistant,
+                chrono::Utc::now().timestamp(),
+                contents,
+            );
+
+            yield (Some(msg), last_usage);
         }
     }
 }
@@ -353,6 +520,67 @@ hello
         }
     }
 
+    #[test]
+    fn test_parse_xml_tool_calls_value_contains_block_terminators() {
+        let content = r#"<function=developer__text_editor>
+<parameter=command>write</parameter>
+<parameter=path>/tmp/tools.md</parameter>
+<parameter=content>
+Close each call with </tool_call>, or </function> in the XML format.
+</parameter>
+</function>
+</tool_call>"#;
+
+        let (_, tool_calls) = parse_xml_tool_calls(content);
+
+        assert_eq!(tool_calls.len(), 1, "Should have 1 tool call");
+        if let MessageContent::ToolRequest(request) = &tool_calls[0] {
+            let args = request
+                .tool_call
+                .as_ref()
+                .unwrap()
+                .arguments
+                .as_ref()
+                .unwrap();
+            assert_eq!(args.get("path").unwrap(), "/tmp/tools.md");
+            assert_eq!(
+                args.get("content").unwrap(),
+                "Close each call with </tool_call>, or </function> in the XML format."
+            );
+        } else {
+            panic!("Expected ToolRequest content");
+        }
+    }
+
+    #[test]
+    fn test_safe_stream_end_holds_back_partial_sentinels() {
+        let text = "Let me check that.[TOOL_CALLS";
+        assert_eq!(
+            safe_stream_end(text, &TOOL_CALL_SENTINELS),
+            "Let me check that.".len()
+        );
+
+        let text = "Sure thing <|python_ta";
+        assert_eq!(
+            safe_stream_end(text, &TOOL_CALL_SENTINELS),
+            "Sure thing ".len()
+        );
+
+        let text = "Partial tag <tool_ca";
+        assert_eq!(
+            safe_stream_end(text, &TOOL_CALL_SENTINELS),
+            "Partial tag ".len()
+        );
+    }
+
+    #[test]
+    fn test_safe_stream_end_streams_plain_text() {
+        let text = "arrays look like [1, 2] and tags like <b>";
+        assert_eq!(safe_stream_end(text, &TOOL_CALL_SENTINELS), text.len());
+        assert_eq!(safe_stream_end("", &TOOL_CALL_SENTINELS), 0);
+        assert_eq!(safe_stream_end("ends with [", &["[TOOL_CALLS]"]), 10);
+    }
+
     #[test]
     fn test_response_to_message_xml_fallback() -> anyhow::Result<()> {
         // Test that response_to_message falls back to XML parsing when no JSON tool_calls
@@ -378,6 +606,71 @@ hello
         Ok(())
     }
 
+    fn tool_call_of(content: &MessageContent) -> &CallToolRequestParams {
+        match content {
+            MessageContent::ToolRequest(request) => request.tool_call.as_ref().unwrap(),
+            other => panic!("Expected ToolRequest, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_json_tool_calls_hermes() {
+        let content = "Listing.\n<tool_call>\n{\"name\": \"developer__shell\", \"arguments\": {\"command\": \"ls\"}}\n</tool_call>";
+
+        let (prefix, tool_calls) = parse_text_tool_calls(content);
+
+        assert_eq!(prefix.as_deref(), Some("Listing."));
+        assert_eq!(tool_calls.len(), 1);
+        let call = tool_call_of(&tool_calls[0]);
+        assert_eq!(call.name, "developer__shell");
+        assert_eq!(call.arguments.as_ref().unwrap()["command"], "ls");
+    }
+
+    #[test]
+    fn test_parse_json_tool_calls_mistral_array() {
+        let content = "[TOOL_CALLS][{\"name\": \"a\", \"arguments\": {\"x\": 1}}, {\"name\": \"b\", \"arguments\": \"{\\\"y\\\": 2}\"}]";
+
+        let (prefix, tool_calls) = parse_text_tool_calls(content);
+
+        assert_eq!(prefix, None);
+        assert_eq!(tool_calls.len(), 2);
+        assert_eq!(tool_call_of(&tool_calls[0]).name, "a");
+        let second = tool_call_of(&tool_calls[1]);
+        assert_eq!(second.name, "b");
+        assert_eq!(second.arguments.as_ref().unwrap()["y"], 2);
+    }
+
+    #[test]
+    fn test_parse_json_tool_calls_llama_python_tag() {
+        let content =
+            "<|python_tag|>{\"name\": \"weather\", \"parameters\": {\"city\": \"Paris\"}}";
+
+        let tool_calls = parse_json_tool_calls(content);
+
+        assert_eq!(tool_calls.len(), 1);
+        let call = tool_call_of(&tool_calls[0]);
+        assert_eq!(call.name, "weather");
+        assert_eq!(call.arguments.as_ref().unwrap()["city"], "Paris");
+    }
+
+    #[test]
+    fn test_parse_text_tool_calls_prefers_xml_inside_tool_call_tags() {
+        let content = "<tool_call>\n<function=developer__shell>\n<parameter=command>ls</parameter>\n</function>\n</tool_call>";
+
+        assert!(parse_json_tool_calls(content).is_empty());
+        let (_, tool_calls) = parse_text_tool_calls(content);
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_call_of(&tool_calls[0]).name, "developer__shell");
+    }
+
+    #[test]
+    fn test_parse_text_tool_calls_ignores_unparseable_markers() {
+        let (prefix, tool_calls) = parse_text_tool_calls("Use [TOOL_CALLS] to call tools.");
+
+        assert_eq!(prefix, None);
+        assert!(tool_calls.is_empty());
+    }
+
     #[test]
     fn test_response_to_message_prefers_json_over_xml() -> anyhow::Result<()> {
         // Test that JSON tool_calls take precedence over XML in content
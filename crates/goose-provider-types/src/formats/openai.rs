@@ -1,10 +1,15 @@
-use crate::conversation::message::{Message, MessageContent, ProviderMetadata};
+use crate::conversation::message::{
+    Message, MessageContent, ProviderMetadata, ToolRequest, TOOL_META_PARTIAL_KEY,
+};
 use crate::conversation::token_usage::{CostSource, ProviderUsage, Usage};
 use crate::errors::ProviderError;
-use crate::images::{convert_image, detect_image_path, load_image_file, ImageFormat};
+use crate::images::{
+    convert_image_with_detail, detect_image_path, image_from_data_url, load_image_file,
+    ImageDetail, ImageFormat,
+};
 use crate::json::{parse_tool_arguments, truncation_error_message};
 use crate::mcp_utils::extract_text_from_resource;
-use crate::model::ModelConfig;
+use crate::model::{ModelConfig, ToolChoice};
 use crate::thinking::{
     split_think_blocks, ThinkFilter, ThinkingEffort, GEMINI_THOUGHT_SIGNATURE_KEY,
 };
@@ -20,7 +25,7 @@ use rmcp::model::{
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::sync::OnceLock;
 
@@ -59,6 +64,16 @@ fn is_reserved_request_param_key(key: &str) -> bool {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct OpenAiFormatOptions {
     pub preserve_thinking_context: bool,
+    /// Detail level for images that don't set their own; `None` leaves it to OpenAI.
+    pub image_detail: Option<ImageDetail>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpenAiStreamOptions {
+    /// Yield an interim `tool_request` with empty arguments as soon as a delta names the
+    /// function, ahead of the final request once its arguments have streamed in. The
+    /// interim request is marked with [`ToolRequest::is_partial`] so it is never run.
+    pub partial_tool_calls: bool,
 }
 
 fn merge_reasoning_text(prefix: &str, suffix: &str) -> String {
@@ -109,6 +124,8 @@ struct ContentPart {
     text: Option<String>,
     #[serde(rename = "thoughtSignature")]
     thought_signature: Option<String>,
+    #[serde(default)]
+    image_url: Option<Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -120,6 +137,9 @@ struct Delta {
     reasoning_details: Option<Vec<Value>>,
     reasoning: Option<String>,
     reasoning_content: Option<String>,
+    refusal: Option<String>,
+    #[serde(default)]
+    images: Option<Vec<Value>>,
 }
 
 impl Delta {
@@ -131,6 +151,26 @@ impl Delta {
             .filter(|s| !s.is_empty())
             .or_else(|| self.reasoning.as_deref().filter(|s| !s.is_empty()))
     }
+
+    /// Images from `image_url` content parts and from `images`, in that order.
+    fn image_content(&self) -> Vec<MessageContent> {
+        let parts: &[ContentPart] = match &self.content {
+            Some(DeltaContent::Array(parts)) => parts.as_slice(),
+            _ => &[],
+        };
+        parts
+            .iter()
+            .filter(|p| p.r#type == "image_url")
+            .filter_map(|p| p.image_url.as_ref())
+            .chain(
+                self.images
+                    .iter()
+                    .flatten()
+                    .filter_map(|i| i.get("image_url")),
+            )
+            .filter_map(image_url_content)
+            .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -191,6 +231,7 @@ pub fn format_messages(messages: &[Message], image_format: &ImageFormat) -> Vec<
         image_format,
         OpenAiFormatOptions {
             preserve_thinking_context: true,
+            ..Default::default()
         },
     )
 }
@@ -251,7 +292,11 @@ pub fn format_messages_with_options(
                                 if let Ok(image) = load_image_file(image_path.as_ref()) {
                                     has_non_text_content = true;
                                     content_array.push(json!({"type": "text", "text": text.text}));
-                                    content_array.push(convert_image(&image, image_format));
+                                    content_array.push(convert_image_with_detail(
+                                        &image,
+                                        image_format,
+                                        options.image_detail,
+                                    ));
                                 } else {
                                     content_array.push(json!({"type": "text", "text": text.text}));
                                 }
@@ -345,7 +390,7 @@ pub fn format_messages_with_options(
                                         // Create a separate image message
                                         image_messages.push(json!({
                                             "role": "user",
-                                            "content": [convert_image(&image.clone().no_annotation(), image_format)]
+                                            "content": [convert_image_with_detail(&image.clone().no_annotation(), image_format, options.image_detail)]
                                         }));
                                     }
                                     RawContent::Resource(resource) => {
@@ -390,7 +435,11 @@ pub fn format_messages_with_options(
                 MessageContent::Image(image) => {
                     if message.role == Role::User {
                         has_non_text_content = true;
-                        content_array.push(convert_image(image, image_format));
+                        content_array.push(convert_image_with_detail(
+                            image,
+                            image_format,
+                            options.image_detail,
+                        ));
                     } else {
                         content_array.push(json!({
                             "type": "text",
@@ -636,6 +685,46 @@ pub fn format_tools(tools: &[Tool]) -> anyhow::Result<Vec<Value>> {
     Ok(result)
 }
 
+const FINISH_REASON_CONTENT_FILTER: &str = "content_filter";
+const CONTENT_FILTER_FALLBACK_DETAILS: &str =
+    "The response was blocked by the provider's content filter.";
+
+/// Build the error for a completion that was content-filtered or refused by the
+/// model, preserving the refusal text when the provider sent one.
+fn refusal_error(content_filtered: bool, refusal: &str) -> Option<ProviderError> {
+    if !content_filtered && refusal.is_empty() {
+        return None;
+    }
+    let details = if refusal.is_empty() {
+        CONTENT_FILTER_FALLBACK_DETAILS.to_string()
+    } else {
+        refusal.to_string()
+    };
+    Some(ProviderError::Refusal {
+        details,
+        category: content_filtered.then(|| FINISH_REASON_CONTENT_FILTER.to_string()),
+    })
+}
+
+/// Content for an `image_url` part.
+fn response_image(part: &Value) -> Option<MessageContent> {
+    image_url_content(part.get("image_url")?)
+}
+
+/// The URL may sit under `url` or be the value itself. An inline `data:` URL becomes image
+/// content; a remote image is not fetched, so it is kept as a markdown link rather than an
+/// image with no data.
+fn image_url_content(image_url: &Value) -> Option<MessageContent> {
+    let url = image_url
+        .get("url")
+        .and_then(|u| u.as_str())
+        .or_else(|| image_url.as_str())?;
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return Some(MessageContent::text(format!("![image]({url})")));
+    }
+    image_from_data_url(url).map(MessageContent::Image)
+}
+
 /// Convert OpenAI's API response to internal Message format
 pub fn response_to_message(response: &Value) -> anyhow::Result<Message> {
     let Some(original) = response
@@ -655,6 +744,16 @@ pub fn response_to_message(response: &Value) -> anyhow::Result<Message> {
         ));
     };
 
+    let content_filtered =
+        response["choices"][0]["finish_reason"].as_str() == Some(FINISH_REASON_CONTENT_FILTER);
+    let refusal = original
+        .get("refusal")
+        .and_then(|r| r.as_str())
+        .unwrap_or_default();
+    if let Some(error) = refusal_error(content_filtered, refusal) {
+        return Err(error.into());
+    }
+
     let mut content = Vec::new();
 
     // Capture reasoning content if present (DeepSeek uses "reasoning_content", vLLM uses "reasoning")
@@ -682,9 +781,27 @@ pub fn response_to_message(response: &Value) -> anyhow::Result<Message> {
             if !cleaned.is_empty() {
                 content.push(MessageContent::text(cleaned));
             }
+        } else if let Some(parts) = text.as_array() {
+            for part in parts {
+                match part.get("type").and_then(|t| t.as_str()) {
+                    Some("text") => {
+                        let text = part["text"].as_str().unwrap_or_default();
+                        if !text.is_empty() {
+                            content.push(MessageContent::text(text));
+                        }
+                    }
+                    Some("image_url") => content.extend(response_image(part)),
+                    _ => {}
+                }
+            }
         }
     }
 
+    // Image-capable models may also return generated images alongside the text
+    if let Some(images) = original.get("images").and_then(|i| i.as_array()) {
+        content.extend(images.iter().filter_map(response_image));
+    }
+
     if let Some(tool_calls) = original.get("tool_calls") {
         if let Some(tool_calls_array) = tool_calls.as_array() {
             for tool_call in tool_calls_array {
@@ -837,8 +954,20 @@ pub fn get_usage(usage: &Value) -> Usage {
             _ => None,
         });
 
+    let prediction_tokens = |key: &str| {
+        usage
+            .get("completion_tokens_details")
+            .and_then(|d| d.get(key))
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32)
+    };
+
     Usage::new(input_tokens, output_tokens, total_tokens)
         .with_cache_tokens(cache_read_input_tokens, cache_write_input_tokens)
+        .with_prediction_tokens(
+            prediction_tokens("accepted_prediction_tokens"),
+            prediction_tokens("rejected_prediction_tokens"),
+        )
 }
 
 pub fn get_cost(usage: &Value) -> Option<f64> {
@@ -1030,8 +1159,155 @@ fn parse_streaming_chunk(line: &str) -> Result<StreamingChunk, ProviderError> {
     })
 }
 
+/// Append a streamed tool-call fragment to the call it belongs to. Fragments are keyed
+/// by `index`, falling back to their position for servers that omit it; a fragment
+/// carrying a new id under an index that is already taken starts a separate call, as
+/// some servers number every parallel call 0.
+/// `slots` remembers where a call moved after its id collided with an earlier call at
+/// the same wire index, so the id-less fragments that follow land on the same call.
+fn accumulate_tool_call_delta(
+    tool_call_data: &mut ToolCallData,
+    slots: &mut HashMap<i32, i32>,
+    position: usize,
+    delta_call: &DeltaToolCall,
+) {
+    let wire_index = delta_call.index.unwrap_or(position as i32);
+    let mut index = slots.get(&wire_index).copied().unwrap_or(wire_index);
+    if let (Some(id), Some((existing_id, ..))) = (&delta_call.id, tool_call_data.get(&index)) {
+        if !id.is_empty() && id != existing_id {
+            index = tool_call_data.keys().max().map_or(0, |max| max + 1);
+            slots.insert(wire_index, index);
+        }
+    }
+
+    if let Some((_, _, args, extra)) = tool_call_data.get_mut(&index) {
+        args.push_str(&delta_call.function.arguments);
+        match (extra.as_mut(), &delta_call.extra) {
+            (None, Some(new_extra)) => *extra = Some(new_extra.clone()),
+            (Some(existing), Some(new_extra)) => {
+                for (key, value) in new_extra {
+                    existing.entry(key.clone()).or_insert(value.clone());
+                }
+            }
+            (_, None) => {}
+        }
+    } else if let (Some(id), Some(name)) = (&delta_call.id, &delta_call.function.name) {
+        tool_call_data.insert(
+            index,
+            (
+                id.clone(),
+                name.clone(),
+                delta_call.function.arguments.clone(),
+                delta_call.extra.clone(),
+            ),
+        );
+    }
+}
+
+/// Build a message with an empty-argument tool request, marked partial, for each call
+/// whose name has arrived but which has not been announced yet.
+fn announce_new_tool_calls(
+    tool_call_data: &ToolCallData,
+    announced: &mut HashSet<i32>,
+    chunk_id: Option<&String>,
+) -> Option<Message> {
+    let mut indices: Vec<_> = tool_call_data
+        .keys()
+        .filter(|index| !announced.contains(index))
+        .cloned()
+        .collect();
+    if indices.is_empty() {
+        return None;
+    }
+    indices.sort();
+
+    let contents = indices
+        .into_iter()
+        .filter_map(|index| {
+            let (id, function_name, _, _) = tool_call_data.get(&index)?;
+            announced.insert(index);
+            Some(MessageContent::ToolRequest(ToolRequest {
+                id: id.clone(),
+                tool_call: Ok(CallToolRequestParams::new(function_name.clone())
+                    .with_arguments(object(json!({})))),
+                metadata: None,
+                tool_meta: Some(json!({ TOOL_META_PARTIAL_KEY: true })),
+            }))
+        })
+        .collect();
+    let msg = Message::new(Role::Assistant, chrono::Utc::now().timestamp(), contents);
+    Some(match chunk_id {
+        Some(id) => msg.with_id(id.clone()),
+        None => msg,
+    })
+}
+
+/// Reassemble `data:` frames whose JSON the server split across lines, either
+/// pretty-printed or as several SSE `data:` fields, into one `data: {...}` line each.
+/// A blank line ends the event, so a frame that never completes is passed on as it
+/// is rather than swallowing the events after it.
+pub fn join_split_sse_frames<S>(
+    mut lines: S,
+) -> impl Stream<Item = anyhow::Result<String>> + Send + 'static
+where
+    S: Stream<Item = anyhow::Result<String>> + Unpin + Send + 'static,
+{
+    try_stream! {
+        use futures::StreamExt;
+
+        let mut pending = String::new();
+        while let Some(line) = lines.next().await {
+            let line = line?;
+            if line.trim().is_empty() {
+                if !pending.is_empty() {
+                    yield format!("data: {}", std::mem::take(&mut pending));
+                }
+                yield line;
+                continue;
+            }
+            let data = strip_data_prefix(&line);
+            if data == Some("[DONE]") {
+                if !pending.is_empty() {
+                    yield format!("data: {}", std::mem::take(&mut pending));
+                }
+                yield line;
+                continue;
+            }
+            if pending.is_empty() {
+                match data {
+                    Some(data) if !data.is_empty() => pending.push_str(data),
+                    _ => {
+                        yield line;
+                        continue;
+                    }
+                }
+            } else {
+                pending.push('\n');
+                pending.push_str(data.unwrap_or(line.trim()));
+            }
+
+            if serde_json::from_str::<serde::de::IgnoredAny>(&pending).is_ok() {
+                yield format!("data: {}", std::mem::take(&mut pending));
+            }
+        }
+        if !pending.is_empty() {
+            yield format!("data: {pending}");
+        }
+    }
+}
+
 pub fn response_to_streaming_message<S>(
+    stream: S,
+) -> impl Stream<Item = anyhow::Result<(Option<Message>, Option<ProviderUsage>)>> + 'static
+where
+    S: Stream<Item = anyhow::Result<String>> + Unpin + Send + 'static,
+{
+    response_to_streaming_message_with_options(stream, OpenAiStreamOptions::default())
+}
+
+pub fn response_to_streaming_message_with_options<S>(
     mut stream: S,
+    options: OpenAiStreamOptions,
 ) -> impl Stream<Item = anyhow::Result<(Option<Message>, Option<ProviderUsage>)>> + 'static
 where
     S: Stream<Item = anyhow::Result<String>> + Unpin + Send + 'static,
@@ -1050,6 +1326,8 @@ where
         // reasoning_content in a later chunk would produce duplicated reasoning.
         let mut pending_inline_thinking = String::new();
         let mut last_seen_model: Option<String> = None;
+        let mut accumulated_refusal = String::new();
+        let mut content_filtered = false;
 
         'outer: while let Some(response) = stream.next().await {
             let response_str = response?;
@@ -1074,6 +1352,12 @@ where
                 if let Some(details) = &chunk.choices[0].delta.reasoning_details {
                     accumulated_reasoning.extend(details.iter().cloned());
                 }
+                if let Some(refusal) = &chunk.choices[0].delta.refusal {
+                    accumulated_refusal.push_str(refusal);
+                }
+                if chunk.choices[0].finish_reason.as_deref() == Some(FINISH_REASON_CONTENT_FILTER) {
+                    content_filtered = true;
+                }
                 if let Some(rc) = chunk.choices[0].delta.reasoning_text() {
                     accumulated_reasoning_content.push_str(rc);
                     if !rc.is_empty() {
@@ -1089,13 +1373,17 @@ where
                 yield (None, usage)
             } else if chunk.choices[0].delta.tool_calls.as_ref().is_some_and(|tc| !tc.is_empty()) {
                 let mut tool_call_data: ToolCallData = HashMap::new();
+                let mut tool_call_slots = HashMap::new();
+                let mut announced_tool_calls = HashSet::new();
 
                 if let Some(tool_calls) = &chunk.choices[0].delta.tool_calls {
                     for (position, tool_call) in tool_calls.iter().enumerate() {
-                        if let (Some(id), Some(name)) = (&tool_call.id, &tool_call.function.name) {
-                            let index = tool_call.index.unwrap_or(position as i32);
-                            tool_call_data.insert(index, (id.clone(), name.clone(), tool_call.function.arguments.clone(), tool_call.extra.clone()));
-                        }
+                        accumulate_tool_call_delta(&mut tool_call_data, &mut tool_call_slots, position, tool_call);
+                    }
+                }
+                if options.partial_tool_calls {
+                    if let Some(msg) = announce_new_tool_calls(&tool_call_data, &mut announced_tool_calls, chunk.id.as_ref()) {
+                        yield (Some(msg), None);
                     }
                 }
 
@@ -1132,23 +1420,18 @@ where
                                         }
                                     }
                                     if let Some(delta_tool_calls) = &tool_chunk.choices[0].delta.tool_calls {
-                                        for delta_call in delta_tool_calls {
-                                            if let Some(index) = delta_call.index {
-                                                if let Some((_, _, ref mut args, ref mut extra)) = tool_call_data.get_mut(&index) {
-                                                    args.push_str(&delta_call.function.arguments);
-                                                    if extra.is_none() && delta_call.extra.is_some() {
-                                                        *extra = delta_call.extra.clone();
-                                                    } else if let (Some(existing), Some(new_extra)) = (extra.as_mut(), &delta_call.extra) {
-                                                        for (key, value) in new_extra {
-                                                            existing.entry(key.clone()).or_insert(value.clone());
-                                                        }
-                                                    }
-                                                } else if let (Some(id), Some(name)) = (&delta_call.id, &delta_call.function.name) {
-                                                    tool_call_data.insert(index, (id.clone(), name.clone(), delta_call.function.arguments.clone(), delta_call.extra.clone()));
-                                                }
-                                            }
+                                        for (position, delta_call) in delta_tool_calls.iter().enumerate() {
+                                            accumulate_tool_call_delta(&mut tool_call_data, &mut tool_call_slots, position, delta_call);
+                                        }
+                                    }
+                                    if options.partial_tool_calls {
+                                        if let Some(msg) = announce_new_tool_calls(&tool_call_data, &mut announced_tool_calls, chunk.id.as_ref()) {
+                                            yield (Some(msg), None);
                                         }
                                     }
+                                    if tool_chunk.choices[0].finish_reason.as_deref() == Some(FINISH_REASON_CONTENT_FILTER) {
+                                        content_filtered = true;
+                                    }
                                     if tool_chunk.choices[0].finish_reason.is_some() {
                                         done = true;
                                     }
@@ -1291,7 +1574,10 @@ where
                     Some(msg),
                     usage,
                 )
-            } else if chunk.choices[0].delta.content.is_some() || chunk.choices[0].delta.reasoning_text().is_some() {
+            } else if chunk.choices[0].delta.content.is_some()
+                || chunk.choices[0].delta.reasoning_text().is_some()
+                || chunk.choices[0].delta.images.is_some()
+            {
                 let mut content = Vec::new();
 
                 if let Some(reasoning) = chunk.choices[0].delta.reasoning_text() {
@@ -1318,6 +1604,8 @@ where
                     }
                 }
 
+                content.extend(chunk.choices[0].delta.image_content());
+
                 if !content.is_empty() {
                     let mut msg = Message::new(
                         Role::Assistant,
@@ -1373,6 +1661,20 @@ where
                 None,
             )
         }
+
+        if let Some(error) = refusal_error(content_filtered, &accumulated_refusal) {
+            Err::<(), ProviderError>(error)?;
+        }
+    }
+}
+
+const OPENAI_MAX_STOP_SEQUENCES: usize = 4;
+
+fn format_tool_choice(choice: &ToolChoice) -> Value {
+    match choice {
+        ToolChoice::Auto => json!("auto"),
+        ToolChoice::None => json!("none"),
+        ToolChoice::Function { name } => json!({"type": "function", "function": {"name": name}}),
     }
 }
 
@@ -1393,6 +1695,7 @@ pub fn create_request(
         for_streaming,
         OpenAiFormatOptions {
             preserve_thinking_context: true,
+            ..Default::default()
         },
     )
 }
@@ -1448,6 +1751,10 @@ pub fn create_request_with_options(
 
     if !tools_spec.is_empty() {
         payload["tools"] = json!(tools_spec);
+        // The API rejects tool_choice on requests without tools.
+        if let Some(choice) = &model_config.tool_choice {
+            payload["tool_choice"] = format_tool_choice(choice);
+        }
     }
 
     if !is_reasoning_model {
@@ -1456,6 +1763,22 @@ pub fn create_request_with_options(
         }
     }
 
+    if let Some(prediction) = &model_config.prediction {
+        payload["prediction"] = json!({"type": "content", "content": prediction});
+    }
+
+    // Reasoning models reject `stop`.
+    if !is_reasoning_model && !model_config.stop_sequences.is_empty() {
+        if model_config.stop_sequences.len() > OPENAI_MAX_STOP_SEQUENCES {
+            return Err(anyhow!(
+                "OpenAI accepts at most {} stop sequences, but {} were configured",
+                OPENAI_MAX_STOP_SEQUENCES,
+                model_config.stop_sequences.len()
+            ));
+        }
+        payload["stop"] = json!(model_config.stop_sequences);
+    }
+
     // Only emit max_tokens / max_completion_tokens when the user (via
     // GOOSE_MAX_TOKENS) or a canonical model record has supplied a value.
     // For unknown models on OpenAI-compatible endpoints (e.g. llama_swap,
@@ -1998,6 +2321,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_format_messages_image_detail() {
+        use crate::images::with_image_detail;
+        use rmcp::model::{AnnotateAble, RawImageContent};
+
+        let high = with_image_detail(
+            RawImageContent {
+                data: "aGlnaA==".to_string(),
+                mime_type: "image/png".to_string(),
+                meta: None,
+            }
+            .no_annotation(),
+            ImageDetail::High,
+        );
+        let message = Message::user()
+            .with_text("Compare these")
+            .with_content(MessageContent::Image(high))
+            .with_image("ZGVmYXVsdA==", "image/jpeg");
+
+        let spec = format_messages(std::slice::from_ref(&message), &ImageFormat::OpenAi);
+        let content = spec[0]["content"].as_array().unwrap();
+        assert_eq!(content.len(), 3);
+        assert_eq!(
+            content[1]["image_url"],
+            json!({"url": "data:image/png;base64,aGlnaA==", "detail": "high"})
+        );
+        assert_eq!(
+            content[2]["image_url"],
+            json!({"url": "data:image/jpeg;base64,ZGVmYXVsdA=="})
+        );
+
+        let spec = format_messages_with_options(
+            &[message],
+            &ImageFormat::OpenAi,
+            OpenAiFormatOptions {
+                image_detail: Some(ImageDetail::Low),
+                ..Default::default()
+            },
+        );
+        let content = spec[0]["content"].as_array().unwrap();
+        assert_eq!(content[1]["image_url"]["detail"], "high");
+        assert_eq!(content[2]["image_url"]["detail"], "low");
+    }
+
     #[test]
     fn test_format_messages_with_text_and_image_preserves_order() {
         // Text before image: order should be [text, image]
@@ -2060,6 +2427,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_response_to_message_image_parts() -> anyhow::Result<()> {
+        let response = json!({
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": [
+                        {"type": "text", "text": "Here is your cat."},
+                        {"type": "image_url", "image_url": {"url": "data:image/png;base64,Y2F0"}}
+                    ],
+                    "images": [
+                        {"type": "image_url", "image_url": {"url": "https://example.com/cat.jpg"}}
+                    ]
+                }
+            }]
+        });
+
+        let message = response_to_message(&response)?;
+        assert_eq!(message.content.len(), 3);
+        assert_eq!(message.content[0].as_text(), Some("Here is your cat."));
+        let MessageContent::Image(inline) = &message.content[1] else {
+            panic!("Expected inline image content");
+        };
+        assert_eq!(inline.mime_type, "image/png");
+        assert_eq!(inline.data, "Y2F0");
+        assert_eq!(
+            message.content[2].as_text(),
+            Some("![image](https://example.com/cat.jpg)")
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_response_to_message_valid_toolrequest() -> anyhow::Result<()> {
         let response: Value = serde_json::from_str(OPENAI_TOOL_USE_RESPONSE)?;
@@ -2278,6 +2678,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_response_to_message_refusal() {
+        let response = json!({
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "refusal": "I can't help with that."
+                },
+                "finish_reason": "stop"
+            }]
+        });
+
+        let err = response_to_message(&response).unwrap_err();
+        match err.downcast_ref::<ProviderError>() {
+            Some(ProviderError::Refusal { details, category }) => {
+                assert_eq!(details, "I can't help with that.");
+                assert_eq!(category, &None);
+            }
+            other => panic!("expected ProviderError::Refusal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_response_to_message_content_filter() {
+        let response = json!({
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": ""},
+                "finish_reason": "content_filter"
+            }]
+        });
+
+        let err = response_to_message(&response).unwrap_err();
+        match err.downcast_ref::<ProviderError>() {
+            Some(ProviderError::Refusal { details, category }) => {
+                assert_eq!(details, CONTENT_FILTER_FALLBACK_DETAILS);
+                assert_eq!(category.as_deref(), Some("content_filter"));
+            }
+            other => panic!("expected ProviderError::Refusal, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_format_messages_tool_request_with_none_arguments() -> anyhow::Result<()> {
         // Test that tool calls with None arguments are formatted as "{}" string
@@ -2428,6 +2872,138 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_create_request_tool_choice() -> anyhow::Result<()> {
+        let tool = Tool::new(
+            "shell",
+            "Run a command",
+            object!({"type": "object", "properties": {}}),
+        );
+        let request_with = |choice: Option<ToolChoice>, tools: &[Tool]| {
+            create_request(
+                &test_model_config("gpt-4o").with_tool_choice(choice),
+                "system",
+                &[],
+                tools,
+                &ImageFormat::OpenAi,
+                false,
+            )
+        };
+
+        for (choice, expected) in [
+            (ToolChoice::Auto, json!("auto")),
+            (ToolChoice::None, json!("none")),
+            (
+                ToolChoice::Function {
+                    name: "shell".to_string(),
+                },
+                json!({"type": "function", "function": {"name": "shell"}}),
+            ),
+        ] {
+            let request = request_with(Some(choice), std::slice::from_ref(&tool))?;
+            assert_eq!(request["tool_choice"], expected);
+        }
+
+        let request = request_with(None, std::slice::from_ref(&tool))?;
+        assert!(request.get("tool_choice").is_none());
+        let request = request_with(Some(ToolChoice::None), &[])?;
+        assert!(request.get("tool_choice").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_request_includes_prediction() -> anyhow::Result<()> {
+        let model_config = test_model_config("gpt-4o")
+            .with_prediction(Some("fn main() {\n    println!(\"hi\");\n}\n".to_string()));
+        let request = create_request(
+            &model_config,
+            "system",
+            &[],
+            &[],
+            &ImageFormat::OpenAi,
+            false,
+        )?;
+
+        assert_eq!(
+            request["prediction"],
+            json!({
+                "type": "content",
+                "content": "fn main() {\n    println!(\"hi\");\n}\n"
+            })
+        );
+
+        let request = create_request(
+            &test_model_config("gpt-4o"),
+            "system",
+            &[],
+            &[],
+            &ImageFormat::OpenAi,
+            false,
+        )?;
+        assert!(request.get("prediction").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_request_includes_stop_sequences() -> anyhow::Result<()> {
+        let model_config = test_model_config("gpt-4o")
+            .with_stop_sequences(vec!["\n\n".to_string(), "END".to_string()]);
+        let request = create_request(
+            &model_config,
+            "system",
+            &[],
+            &[],
+            &ImageFormat::OpenAi,
+            false,
+        )?;
+        assert_eq!(request["stop"], json!(["\n\n", "END"]));
+
+        let request = create_request(
+            &test_model_config("gpt-4o"),
+            "system",
+            &[],
+            &[],
+            &ImageFormat::OpenAi,
+            false,
+        )?;
+        assert!(request.get("stop").is_none());
+
+        let model_config =
+            test_model_config("o3-mini").with_stop_sequences(vec!["END".to_string()]);
+        let request = create_request(
+            &model_config,
+            "system",
+            &[],
+            &[],
+            &ImageFormat::OpenAi,
+            false,
+        )?;
+        assert!(request.get("stop").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_request_rejects_more_than_four_stop_sequences() {
+        let model_config = test_model_config("gpt-4o")
+            .with_stop_sequences((1..=5).map(|i| format!("stop{i}")).collect());
+        let err = create_request(
+            &model_config,
+            "system",
+            &[],
+            &[],
+            &ImageFormat::OpenAi,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "OpenAI accepts at most 4 stop sequences, but 5 were configured"
+        );
+    }
+
     #[test]
     fn test_create_request_omits_max_tokens_when_unset() -> anyhow::Result<()> {
         // Unknown models on OpenAI-compatible local providers (llama_swap,
@@ -2758,6 +3334,28 @@ mod tests {
         assert_eq!(usage.cache_write_input_tokens, Some(10));
     }
 
+    #[test]
+    fn test_get_usage_reads_prediction_tokens() {
+        let usage = get_usage(&json!({
+            "prompt_tokens": 200,
+            "completion_tokens": 90,
+            "total_tokens": 290,
+            "completion_tokens_details": {
+                "reasoning_tokens": 0,
+                "accepted_prediction_tokens": 70,
+                "rejected_prediction_tokens": 12
+            }
+        }));
+
+        assert_eq!(usage.output_tokens, Some(90));
+        assert_eq!(usage.accepted_prediction_tokens, Some(70));
+        assert_eq!(usage.rejected_prediction_tokens, Some(12));
+
+        let usage = get_usage(&json!({"prompt_tokens": 10, "completion_tokens": 5}));
+        assert_eq!(usage.accepted_prediction_tokens, None);
+        assert_eq!(usage.rejected_prediction_tokens, None);
+    }
+
     #[tokio::test]
     async fn test_streamed_multi_tool_response_to_messages() -> anyhow::Result<()> {
         let response_lines = r#"
@@ -3052,6 +3650,73 @@ data: [DONE]"#;
         panic!("expected a tool request message");
     }
 
+    #[tokio::test]
+    async fn test_streaming_partial_tool_calls_announce_then_finalize() -> anyhow::Result<()> {
+        let response_lines = concat!(
+            "data: {\"id\":\"chunk-1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",\"function\":{\"name\":\"shell\",\"arguments\":\"\"}}]},\"index\":0,\"finish_reason\":null}]}\n",
+            "data: {\"id\":\"chunk-1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"command\\\":\"}}]},\"index\":0,\"finish_reason\":null}]}\n",
+            "data: {\"id\":\"chunk-1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"ls\\\"}\"}}]},\"index\":0,\"finish_reason\":null}]}\n",
+            "data: {\"id\":\"chunk-1\",\"choices\":[{\"delta\":{},\"index\":0,\"finish_reason\":\"tool_calls\"}]}\n",
+            "data: [DONE]\n"
+        );
+
+        let collect_requests = |options: OpenAiStreamOptions| async move {
+            let response_stream =
+                tokio_stream::iter(response_lines.lines().map(|line| Ok(line.to_string())));
+            let mut messages = std::pin::pin!(response_to_streaming_message_with_options(
+                response_stream,
+                options
+            ));
+            let mut requests = Vec::new();
+            while let Some(result) = messages.next().await {
+                let (message, _) = result?;
+                for content in message.into_iter().flat_map(|m| m.content) {
+                    if let MessageContent::ToolRequest(request) = content {
+                        let partial = request.is_partial();
+                        let call = request.tool_call.expect("tool call should parse");
+                        requests.push((request.id, call.name.to_string(), call.arguments, partial));
+                    }
+                }
+            }
+            anyhow::Ok(requests)
+        };
+
+        let requests = collect_requests(OpenAiStreamOptions {
+            partial_tool_calls: true,
+        })
+        .await?;
+        assert_eq!(
+            requests,
+            vec![
+                (
+                    "call_1".to_string(),
+                    "shell".to_string(),
+                    Some(object!({})),
+                    true
+                ),
+                (
+                    "call_1".to_string(),
+                    "shell".to_string(),
+                    Some(object!({"command": "ls"})),
+                    false
+                ),
+            ]
+        );
+
+        let requests = collect_requests(OpenAiStreamOptions::default()).await?;
+        assert_eq!(
+            requests,
+            vec![(
+                "call_1".to_string(),
+                "shell".to_string(),
+                Some(object!({"command": "ls"})),
+                false
+            )]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_streaming_response_extracts_inline_think_blocks() -> anyhow::Result<()> {
         let response_lines = concat!(
@@ -3251,6 +3916,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3309,6 +3975,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: false,
+                ..Default::default()
             },
         );
 
@@ -3361,6 +4028,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3391,6 +4059,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3419,6 +4088,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3443,6 +4113,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3479,6 +4150,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3537,6 +4209,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3584,6 +4257,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
 
@@ -3804,6 +4478,168 @@ data: [DONE]"#;
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_streaming_parallel_tool_call_argument_fragments() -> anyhow::Result<()> {
+        let chunk = |tool_calls: Value, finish_reason: Value| {
+            format!(
+                "data: {}",
+                json!({
+                    "id": "x",
+                    "object": "chat.completion.chunk",
+                    "model": "m",
+                    "choices": [{
+                        "index": 0,
+                        "delta": {"tool_calls": tool_calls},
+                        "finish_reason": finish_reason,
+                    }],
+                })
+            )
+        };
+        let fragment = |index: i32, arguments: &str| json!([{"index": index, "function": {"arguments": arguments}}]);
+        let lines = vec![
+            chunk(
+                json!([{"index": 0, "id": "call_a", "type": "function", "function": {"name": "read_file", "arguments": "{\"pa"}}]),
+                Value::Null,
+            ),
+            chunk(
+                json!([{"index": 1, "id": "call_b", "type": "function", "function": {"name": "list_dir", "arguments": ""}}]),
+                Value::Null,
+            ),
+            chunk(fragment(1, "{\"dir\": "), Value::Null),
+            chunk(fragment(0, "th\": \"src/"), Value::Null),
+            chunk(fragment(1, "\"tests\"}"), Value::Null),
+            chunk(fragment(0, "main.rs\"}"), json!("tool_calls")),
+            "data: [DONE]".to_string(),
+        ];
+
+        let response_stream = tokio_stream::iter(lines.into_iter().map(Ok));
+        let mut messages = std::pin::pin!(response_to_streaming_message(response_stream));
+        let mut requests = Vec::new();
+        while let Some(result) = messages.next().await {
+            if let (Some(message), _) = result? {
+                for content in message.content {
+                    if let MessageContent::ToolRequest(request) = content {
+                        let call = request.tool_call.expect("arguments should parse");
+                        requests.push((request.id, call.name.to_string(), call.arguments));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(
+            requests,
+            vec![
+                (
+                    "call_a".to_string(),
+                    "read_file".to_string(),
+                    Some(object(json!({"path": "src/main.rs"})))
+                ),
+                (
+                    "call_b".to_string(),
+                    "list_dir".to_string(),
+                    Some(object(json!({"dir": "tests"})))
+                ),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tool_call_fragments_follow_a_call_moved_by_id_collision() {
+        let delta = |value: Value| -> DeltaToolCall { serde_json::from_value(value).unwrap() };
+        let mut tool_call_data: ToolCallData = HashMap::new();
+        let mut slots = HashMap::new();
+        for value in [
+            json!({"index": 0, "id": "call_a", "type": "function", "function": {"name": "read_file", "arguments": "{\"path\": \"a\"}"}}),
+            json!({"index": 0, "id": "call_b", "type": "function", "function": {"name": "list_dir", "arguments": "{\"dir\": "}}),
+            json!({"index": 0, "function": {"arguments": "\"tests\"}"}}),
+        ] {
+            accumulate_tool_call_delta(&mut tool_call_data, &mut slots, 0, &delta(value));
+        }
+
+        let (id, name, args, _) = &tool_call_data[&0];
+        assert_eq!((id.as_str(), name.as_str()), ("call_a", "read_file"));
+        assert_eq!(args, "{\"path\": \"a\"}");
+        let (id, name, args, _) = &tool_call_data[&1];
+        assert_eq!((id.as_str(), name.as_str()), ("call_b", "list_dir"));
+        assert_eq!(args, "{\"dir\": \"tests\"}");
+    }
+
+    async fn collect_split_frame_text(lines: &[&str]) -> anyhow::Result<String> {
+        let lines: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+        let frames = Box::pin(join_split_sse_frames(tokio_stream::iter(
+            lines.into_iter().map(Ok),
+        )));
+        let mut messages = std::pin::pin!(response_to_streaming_message(frames));
+        let mut text = String::new();
+        while let Some(result) = messages.next().await {
+            if let (Some(message), _) = result? {
+                text.push_str(&message.as_concat_text());
+            }
+        }
+        Ok(text)
+    }
+
+    #[tokio::test]
+    async fn test_streaming_json_frame_split_across_lines() -> anyhow::Result<()> {
+        let text = collect_split_frame_text(&[
+            r#"data: {"id":"x","object":"chat.completion.chunk","model":"m","#,
+            r#"  "choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+            "",
+            r#"data: {"id":"x","object":"chat.completion.chunk","model":"m","choices":[{"index":0,"delta":{"content":" world"},"finish_reason":null}]}"#,
+            "",
+            "data: [DONE]",
+        ])
+        .await?;
+        assert_eq!(text, "Hello world");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_split_frame_ends_at_event_boundary() -> anyhow::Result<()> {
+        let complete = r#"data: {"id":"x","object":"chat.completion.chunk","model":"m","choices":[{"index":0,"delta":{"content":"Hi"},"finish_reason":null}]}"#;
+        let lines: Vec<anyhow::Result<String>> = [
+            r#"data: {"id":"x","object":"chat.completion.chunk","#,
+            "",
+            complete,
+            "",
+            "data: [DONE]",
+        ]
+        .into_iter()
+        .map(|line| Ok(line.to_string()))
+        .collect();
+        let frames: Vec<String> = Box::pin(join_split_sse_frames(tokio_stream::iter(lines)))
+            .collect::<anyhow::Result<_>>()
+            .await?;
+
+        assert_eq!(
+            frames,
+            vec![
+                r#"data: {"id":"x","object":"chat.completion.chunk","#,
+                "",
+                complete,
+                "",
+                "data: [DONE]",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_streaming_json_frame_split_across_data_fields() -> anyhow::Result<()> {
+        let text = collect_split_frame_text(&[
+            "data: {",
+            r#"data:   "id": "x", "object": "chat.completion.chunk", "model": "m","#,
+            r#"data:   "choices": [{"index": 0, "delta": {"content": "Hi"}, "finish_reason": null}]"#,
+            "data: }",
+            "",
+            "data: [DONE]",
+        ])
+        .await?;
+        assert_eq!(text, "Hi");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_streaming_chunk_with_only_reasoning_content() -> anyhow::Result<()> {
         let response_lines = "data: {\"id\":\"x\",\"object\":\"chat.completion.chunk\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"reasoning_content\":\"hi\"},\"finish_reason\":null}]}\ndata: [DONE]";
@@ -3816,6 +4652,40 @@ data: [DONE]"#;
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_streaming_keeps_image_parts() -> anyhow::Result<()> {
+        let response_lines = concat!(
+            "data: {\"id\":\"x\",\"choices\":[{\"index\":0,\"delta\":{\"content\":[{\"type\":\"text\",\"text\":\"Here is your cat.\"},{\"type\":\"image_url\",\"image_url\":{\"url\":\"data:image/png;base64,Y2F0\"}}]},\"finish_reason\":null}]}\n",
+            "data: {\"id\":\"x\",\"choices\":[{\"index\":0,\"delta\":{\"images\":[{\"type\":\"image_url\",\"image_url\":{\"url\":\"https://example.com/cat.jpg\"}}]},\"finish_reason\":\"stop\"}]}\n",
+            "data: [DONE]"
+        );
+        let lines: Vec<String> = response_lines.lines().map(|s| s.to_string()).collect();
+        let response_stream = tokio_stream::iter(lines.into_iter().map(Ok));
+        let mut messages = std::pin::pin!(response_to_streaming_message(response_stream));
+
+        let mut content = Vec::new();
+        while let Some(result) = messages.next().await {
+            let (message, _usage) = result?;
+            if let Some(msg) = message {
+                content.extend(msg.content);
+            }
+        }
+
+        assert_eq!(content.len(), 3);
+        assert_eq!(content[0].as_text(), Some("Here is your cat."));
+        let MessageContent::Image(inline) = &content[1] else {
+            panic!("Expected inline image content");
+        };
+        assert_eq!(inline.mime_type, "image/png");
+        assert_eq!(inline.data, "Y2F0");
+        assert_eq!(
+            content[2].as_text(),
+            Some("![image](https://example.com/cat.jpg)")
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_streaming_tool_call_does_not_duplicate_yielded_reasoning() -> anyhow::Result<()> {
         let response_lines = concat!(
@@ -3854,6 +4724,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
         assert_eq!(spec.len(), 1);
@@ -3888,6 +4759,7 @@ data: [DONE]"#;
             &ImageFormat::OpenAi,
             OpenAiFormatOptions {
                 preserve_thinking_context: true,
+                ..Default::default()
             },
         );
         assert_eq!(spec.len(), 1);
@@ -4213,4 +5085,36 @@ data: [DONE]"#;
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_streaming_content_filter_surfaces_refusal_after_usage() {
+        let response_lines = concat!(
+            "data: {\"id\":\"x\",\"object\":\"chat.completion.chunk\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"refusal\":\"I can't \"},\"finish_reason\":null}]}\n",
+            "data: {\"id\":\"x\",\"object\":\"chat.completion.chunk\",\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"refusal\":\"help with that.\"},\"finish_reason\":\"content_filter\"}]}\n",
+            "data: {\"id\":\"x\",\"object\":\"chat.completion.chunk\",\"model\":\"m\",\"choices\":[],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":4,\"total_tokens\":16}}\n",
+            "data: [DONE]"
+        );
+        let lines: Vec<String> = response_lines.lines().map(|s| s.to_string()).collect();
+        let response_stream = tokio_stream::iter(lines.into_iter().map(Ok));
+        let messages = std::pin::pin!(response_to_streaming_message(response_stream));
+        let results: Vec<_> = messages.collect().await;
+
+        let usage = results
+            .iter()
+            .find_map(|r| r.as_ref().ok().and_then(|(_, usage)| usage.clone()))
+            .expect("usage should be yielded before the refusal");
+        assert_eq!(usage.usage.output_tokens, Some(4));
+
+        let err = results
+            .into_iter()
+            .find_map(|r| r.err())
+            .expect("content filter should surface as a stream error");
+        match err.downcast_ref::<ProviderError>() {
+            Some(ProviderError::Refusal { details, category }) => {
+                assert_eq!(details, "I can't help with that.");
+                assert_eq!(category.as_deref(), Some("content_filter"));
+            }
+            other => panic!("expected ProviderError::Refusal, got {:?}", other),
+        }
+    }
 }
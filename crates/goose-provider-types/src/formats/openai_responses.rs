@@ -6,7 +6,7 @@ use crate::formats::openai::{
     sanitize_function_name,
 };
 use crate::mcp_utils::extract_text_from_resource;
-use crate::model::ModelConfig;
+use crate::model::{ModelConfig, ToolChoice};
 use anyhow::{anyhow, Error};
 use async_stream::try_stream;
 use chrono;
@@ -674,6 +674,15 @@ pub fn create_responses_request(
             .as_object_mut()
             .unwrap()
             .insert("tools".to_string(), json!(tools_spec));
+
+        if let Some(choice) = &model_config.tool_choice {
+            let choice = match choice {
+                ToolChoice::Auto => json!("auto"),
+                ToolChoice::None => json!("none"),
+                ToolChoice::Function { name } => json!({"type": "function", "name": name}),
+            };
+            payload["tool_choice"] = choice;
+        }
     }
 
     if !is_reasoning_model {
@@ -1330,6 +1339,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let messages = vec![
@@ -1421,6 +1434,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let tool = Tool::new(
@@ -1448,6 +1465,36 @@ mod tests {
             "Responses API defaults strict to true, but MCP tool schemas are not strict-compatible; must explicitly set strict: false");
     }
 
+    #[test]
+    fn test_responses_request_tool_choice() {
+        let tool = Tool::new(
+            "shell",
+            "Execute a shell command",
+            object!({"type": "object", "properties": {}}),
+        );
+
+        for (choice, expected) in [
+            (ToolChoice::Auto, json!("auto")),
+            (ToolChoice::None, json!("none")),
+            (
+                ToolChoice::Function {
+                    name: "shell".to_string(),
+                },
+                json!({"type": "function", "name": "shell"}),
+            ),
+        ] {
+            let model_config = ModelConfig::new("gpt-5.4").with_tool_choice(Some(choice));
+            let result =
+                create_responses_request(&model_config, "", &[], std::slice::from_ref(&tool))
+                    .unwrap();
+            assert_eq!(result["tool_choice"], expected);
+        }
+
+        let result =
+            create_responses_request(&ModelConfig::new("gpt-5.4"), "", &[], &[tool]).unwrap();
+        assert!(result.get("tool_choice").is_none());
+    }
+
     #[test]
     fn test_responses_request_with_explicit_effort_suffix() {
         for (model_name, expected_model, expected_effort) in [
@@ -1464,6 +1511,10 @@ mod tests {
                 toolshim_model: None,
                 request_params: None,
                 reasoning: None,
+                prediction: None,
+                tool_choice: None,
+                stop_sequences: Vec::new(),
+                request_timeout_secs: None,
             };
 
             let result =
@@ -1544,6 +1595,10 @@ mod tests {
                 toolshim_model: None,
                 request_params: None,
                 reasoning: None,
+                prediction: None,
+                tool_choice: None,
+                stop_sequences: Vec::new(),
+                request_timeout_secs: None,
             };
 
             let result =
@@ -1594,6 +1649,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "You are helpful.", &[], &[]).unwrap();
@@ -1619,6 +1678,10 @@ mod tests {
                 serde_json::json!(true),
             )])),
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &[], &[]).unwrap();
@@ -1643,6 +1706,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result =
@@ -1689,6 +1756,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1725,6 +1796,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1756,6 +1831,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1786,6 +1865,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1820,6 +1903,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1856,6 +1943,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1881,6 +1972,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1912,6 +2007,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -1943,6 +2042,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -2085,6 +2188,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -2125,6 +2232,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -2162,6 +2273,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
@@ -2199,6 +2314,10 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
 
         let result = create_responses_request(&model_config, "", &messages, &[]).unwrap();
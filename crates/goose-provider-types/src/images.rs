@@ -1,7 +1,7 @@
 use std::{borrow::Cow, io::Read as _, path::Path};
 
 use base64::Engine as _;
-use rmcp::model::{AnnotateAble as _, ImageContent, RawImageContent};
+use rmcp::model::{AnnotateAble as _, ImageContent, Meta, RawImageContent};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -13,15 +13,77 @@ pub enum ImageFormat {
     Anthropic,
 }
 
+/// OpenAI's `detail` level for an image: `Low` caps the token cost of each image at the
+/// expense of fine detail. Left unset, OpenAI picks the level itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageDetail {
+    Auto,
+    Low,
+    High,
+}
+
+const IMAGE_DETAIL_META_KEY: &str = "detail";
+
+/// Record the detail level to request for this image, carried in the image's `_meta`.
+pub fn with_image_detail(mut image: ImageContent, detail: ImageDetail) -> ImageContent {
+    image
+        .raw
+        .meta
+        .get_or_insert_with(Meta::new)
+        .insert(IMAGE_DETAIL_META_KEY.to_string(), json!(detail));
+    image
+}
+
+pub fn image_detail(image: &ImageContent) -> Option<ImageDetail> {
+    image
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.get(IMAGE_DETAIL_META_KEY))
+        .and_then(|detail| serde_json::from_value(detail.clone()).ok())
+}
+
+/// Build image content from an inline `data:` URL a model returned.
+pub fn image_from_data_url(url: &str) -> Option<ImageContent> {
+    let (mime_type, data) = url.strip_prefix("data:")?.split_once(";base64,")?;
+    if !mime_type.starts_with("image/") || data.is_empty() {
+        return None;
+    }
+    Some(
+        RawImageContent {
+            mime_type: mime_type.to_string(),
+            data: data.to_string(),
+            meta: None,
+        }
+        .no_annotation(),
+    )
+}
+
 /// Convert an image content into an image json based on format
 pub fn convert_image(image: &ImageContent, image_format: &ImageFormat) -> Value {
+    convert_image_with_detail(image, image_format, None)
+}
+
+/// Like [`convert_image`], requesting `default_detail` for an OpenAI image that doesn't
+/// carry its own detail level. With neither set, the `detail` field is left out.
+pub fn convert_image_with_detail(
+    image: &ImageContent,
+    image_format: &ImageFormat,
+    default_detail: Option<ImageDetail>,
+) -> Value {
     match image_format {
-        ImageFormat::OpenAi => json!({
-            "type": "image_url",
-            "image_url": {
-                "url": format!("data:{};base64,{}", image.mime_type, image.data)
+        ImageFormat::OpenAi => {
+            let mut image_url = json!({
+                "url": format!("data:{};base64,{}", image.mime_type, image.data),
+            });
+            if let Some(detail) = image_detail(image).or(default_detail) {
+                image_url["detail"] = json!(detail);
             }
-        }),
+            json!({
+                "type": "image_url",
+                "image_url": image_url,
+            })
+        }
         ImageFormat::Anthropic => json!({
             "type": "image",
             "source": {
@@ -458,6 +520,16 @@ mod tests {
         assert_eq!(detect_image_path(&text).as_deref(), None);
     }
 
+    #[test]
+    fn test_image_from_data_url() {
+        let inline = image_from_data_url("data:image/jpeg;base64,aW1n").unwrap();
+        assert_eq!(inline.mime_type, "image/jpeg");
+        assert_eq!(inline.data, "aW1n");
+
+        assert!(image_from_data_url("data:text/plain;base64,aGk=").is_none());
+        assert!(image_from_data_url("https://cdn.example.com/out.webp").is_none());
+    }
+
     #[test]
     fn test_load_image_file() {
         // Create a temporary PNG file with valid PNG magic numbers
@@ -20,6 +20,15 @@ const INHERITED_SESSION_PARAM_KEYS: &[&str] = &[
     "preserve_unsigned_thinking",
 ];
 
+/// Whether the model may call tools on a request. Leaving it unset lets the model decide.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolChoice {
+    Auto,
+    None,
+    Function { name: String },
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ModelConfig {
     pub model_name: String,
@@ -33,6 +42,17 @@ pub struct ModelConfig {
     pub request_params: Option<HashMap<String, Value>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<bool>,
+    /// Expected output content for providers that support predicted outputs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prediction: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    /// Strings that end generation, for providers that accept stop sequences.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+    /// Per-request timeout in seconds, overriding the provider client's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
 }
 
 impl<'de> Deserialize<'de> for ModelConfig {
@@ -52,6 +72,14 @@ impl<'de> Deserialize<'de> for ModelConfig {
             request_params: Option<HashMap<String, Value>>,
             #[serde(default, skip_serializing_if = "Option::is_none")]
             reasoning: Option<bool>,
+            #[serde(default)]
+            prediction: Option<String>,
+            #[serde(default)]
+            tool_choice: Option<ToolChoice>,
+            #[serde(default)]
+            stop_sequences: Vec<String>,
+            #[serde(default)]
+            request_timeout_secs: Option<u64>,
         }
 
         let raw = RawModelConfig::deserialize(deserializer)?;
@@ -64,6 +92,10 @@ impl<'de> Deserialize<'de> for ModelConfig {
             toolshim_model: raw.toolshim_model,
             request_params: raw.request_params,
             reasoning: raw.reasoning,
+            prediction: raw.prediction,
+            tool_choice: raw.tool_choice,
+            stop_sequences: raw.stop_sequences,
+            request_timeout_secs: raw.request_timeout_secs,
         };
         config.normalize_effort_suffix();
         Ok(config)
@@ -81,6 +113,10 @@ impl ModelConfig {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         };
         config.normalize_effort_suffix();
         config
@@ -152,6 +188,31 @@ impl ModelConfig {
         self
     }
 
+    pub fn with_prediction(mut self, prediction: Option<String>) -> Self {
+        self.prediction = prediction;
+        self
+    }
+
+    pub fn with_tool_choice(mut self, tool_choice: Option<ToolChoice>) -> Self {
+        self.tool_choice = tool_choice;
+        self
+    }
+
+    pub fn with_stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.stop_sequences = stop_sequences;
+        self
+    }
+
+    pub fn with_request_timeout_secs(mut self, request_timeout_secs: Option<u64>) -> Self {
+        self.request_timeout_secs = request_timeout_secs;
+        self
+    }
+
+    pub fn request_timeout(&self) -> Option<std::time::Duration> {
+        self.request_timeout_secs
+            .map(std::time::Duration::from_secs)
+    }
+
     pub fn with_toolshim(mut self, toolshim: bool) -> Self {
         self.toolshim = toolshim;
         self
@@ -1,11 +1,11 @@
 use std::{
     error::Error,
     fmt::Display,
-    sync::{Arc, OnceLock},
+    sync::{Arc, OnceLock, RwLock},
 };
 
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use crate::conversation::token_usage::Usage;
 
@@ -13,6 +13,26 @@ type RequestLogError = Box<dyn Error + Send + Sync>;
 
 static LOGGER: OnceLock<Arc<dyn RequestLogger>> = OnceLock::new();
 
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Keys (compared case-insensitively) whose values are masked before a payload is logged.
+/// Any key ending in `_api_key` is masked as well.
+const DEFAULT_REDACTED_KEYS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "api_key",
+    "api-key",
+    "apikey",
+    "x-api-key",
+    "x-goog-api-key",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "password",
+];
+
+static EXTRA_REDACTED_KEYS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
 #[derive(Debug)]
 pub struct LoggerAlreadyInstalled;
 
@@ -28,6 +48,44 @@ pub fn install_logger<R: RequestLogger + 'static>(r: R) -> Result<(), LoggerAlre
     LOGGER.set(Arc::new(r)).map_err(|_| LoggerAlreadyInstalled)
 }
 
+/// Masks values stored under `key` in every logged payload, in addition to the built-in list.
+pub fn register_redacted_key(key: impl Into<String>) {
+    let key = key.into().to_ascii_lowercase();
+    let mut keys = EXTRA_REDACTED_KEYS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !keys.contains(&key) {
+        keys.push(key);
+    }
+}
+
+fn is_redacted_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    if DEFAULT_REDACTED_KEYS.contains(&key.as_str()) || key.ends_with("_api_key") {
+        return true;
+    }
+    EXTRA_REDACTED_KEYS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .contains(&key)
+}
+
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_redacted_key(key) && !v.is_null() {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
 pub trait RequestLogger: Send + Sync {
     fn start(&self) -> Result<Box<dyn RequestLogHandle>, RequestLogError>;
 }
@@ -59,8 +117,9 @@ impl From<RequestLogError> for LogError {
     }
 }
 
-fn serialize(v: &serde_json::Value) -> Result<String, LogError> {
-    serde_json::to_string(v).map_err(LogError::SerializeError)
+fn serialize(mut v: Value) -> Result<String, LogError> {
+    redact_secrets(&mut v);
+    serde_json::to_string(&v).map_err(LogError::SerializeError)
 }
 
 pub fn start_log<M, P>(
@@ -83,7 +142,7 @@ where
         "input": payload,
     });
 
-    handle.write(serialize(&payload)?.as_str())?;
+    handle.write(serialize(payload)?.as_str())?;
     Ok(Some(handle))
 }
 
@@ -107,7 +166,7 @@ impl LoggerHandleExt for Option<Box<dyn RequestLogHandle>> {
             return Ok(());
         };
 
-        let line = serialize(&json!({
+        let line = serialize(json!({
             "data": data,
             "usage": usage,
         }))?;
@@ -125,10 +184,83 @@ impl LoggerHandleExt for Option<Box<dyn RequestLogHandle>> {
             return Ok(());
         };
 
-        let line = serialize(&json!({
+        let line = serialize(json!({
             "error": format!("{}", error),
         }))?;
 
         Ok(log.write(line.as_str())?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct CapturingLogger {
+        lines: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct CapturingHandle {
+        lines: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RequestLogger for CapturingLogger {
+        fn start(&self) -> Result<Box<dyn RequestLogHandle>, RequestLogError> {
+            Ok(Box::new(CapturingHandle {
+                lines: self.lines.clone(),
+            }))
+        }
+    }
+
+    impl RequestLogHandle for CapturingHandle {
+        fn write(&mut self, s: &str) -> Result<(), RequestLogError> {
+            self.lines.lock().unwrap().push(s.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn start_log_masks_authorization_header() {
+        let logger = CapturingLogger::default();
+        install_logger(logger.clone()).unwrap();
+
+        let payload = json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "hello"}],
+            "headers": {"Authorization": "Bearer sk-secret", "X-Title": "goose"},
+        });
+        let mut handle = start_log(json!({"model_name": "gpt-4o"}), &payload).unwrap();
+        handle
+            .write(&json!({"OPENAI_API_KEY": "sk-secret"}), None)
+            .unwrap();
+
+        let lines = logger.lines.lock().unwrap();
+        assert!(lines.iter().all(|line| !line.contains("sk-secret")));
+
+        let logged: Value = serde_json::from_str(&lines[0]).unwrap();
+        let input = &logged["input"];
+        assert_eq!(input["headers"]["Authorization"], REDACTED);
+        assert_eq!(input["headers"]["X-Title"], "goose");
+        assert_eq!(input["model"], "gpt-4o");
+        assert_eq!(input["messages"][0]["content"], "hello");
+    }
+
+    #[test]
+    fn redact_secrets_uses_registered_keys() {
+        register_redacted_key("X-Custom-Token");
+        let mut value = json!({
+            "nested": [{"x-custom-token": "abc", "api_key": null}],
+            "max_tokens": 100,
+        });
+        redact_secrets(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "nested": [{"x-custom-token": REDACTED, "api_key": null}],
+                "max_tokens": 100,
+            })
+        );
+    }
+}
@@ -232,6 +232,10 @@ impl Provider for AnthropicProvider {
         &self.name
     }
 
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        self.supports_streaming
+    }
+
     fn skip_canonical_filtering(&self) -> bool {
         self.skip_canonical_filtering
     }
@@ -400,3 +404,25 @@ pub fn from_declarative_config(
         .skip_canonical_filtering(config.skip_canonical_filtering)
         .format_options(format_options))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> AnthropicProviderBuilder {
+        AnthropicProviderBuilder::new(
+            ApiClient::new_with_tls("http://localhost".to_string(), AuthMethod::NoAuth, None)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn supports_streaming_reflects_configuration() {
+        let model_config = ModelConfig::new("claude-sonnet-4");
+        assert!(builder().build().supports_streaming(&model_config));
+        assert!(!builder()
+            .supports_streaming(false)
+            .build()
+            .supports_streaming(&model_config));
+    }
+}
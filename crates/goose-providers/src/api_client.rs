@@ -27,6 +27,7 @@ pub struct ApiClient {
     default_query: Vec<(String, String)>,
     timeout: Duration,
     tls_config: Option<TlsConfig>,
+    pool_config: PoolConfig,
     request_builder: Option<RequestBuilderDecorator>,
 }
 
@@ -121,6 +122,30 @@ impl TlsConfig {
     }
 }
 
+/// Connection pool and protocol settings for agents that issue many parallel requests.
+/// Unset fields keep reqwest's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub idle_timeout: Option<Duration>,
+    pub max_idle_per_host: Option<usize>,
+    pub http2_prior_knowledge: bool,
+}
+
+impl PoolConfig {
+    fn apply(&self, mut client_builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(idle_timeout) = self.idle_timeout {
+            client_builder = client_builder.pool_idle_timeout(idle_timeout);
+        }
+        if let Some(max_idle) = self.max_idle_per_host {
+            client_builder = client_builder.pool_max_idle_per_host(max_idle);
+        }
+        if self.http2_prior_knowledge {
+            client_builder = client_builder.http2_prior_knowledge();
+        }
+        client_builder
+    }
+}
+
 impl Default for TlsConfig {
     fn default() -> Self {
         Self::new()
@@ -229,6 +254,7 @@ pub struct ApiRequestBuilder<'a> {
     client: &'a ApiClient,
     path: &'a str,
     headers: HeaderMap,
+    timeout: Option<Duration>,
 }
 
 impl ApiClient {
@@ -267,6 +293,7 @@ impl ApiClient {
             default_query: Vec::new(),
             timeout,
             tls_config,
+            pool_config: PoolConfig::default(),
             request_builder: None,
         })
     }
@@ -279,6 +306,7 @@ impl ApiClient {
         let mut client_builder = Client::builder()
             .timeout(self.timeout)
             .default_headers(self.default_headers.clone());
+        client_builder = self.pool_config.apply(client_builder);
 
         // Configure TLS if needed
         if let Some(ref tls_config) = self.tls_config {
@@ -330,6 +358,16 @@ impl ApiClient {
         Ok(self)
     }
 
+    pub fn with_pool_config(mut self, pool_config: PoolConfig) -> Result<Self> {
+        self.pool_config = pool_config;
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    pub fn pool_config(&self) -> &PoolConfig {
+        &self.pool_config
+    }
+
     pub fn with_query(mut self, params: Vec<(String, String)>) -> Self {
         self.default_query = params;
         self
@@ -353,6 +391,7 @@ impl ApiClient {
             client: self,
             path,
             headers: HeaderMap::new(),
+            timeout: None,
         }
     }
 
@@ -408,6 +447,12 @@ impl<'a> ApiRequestBuilder<'a> {
         self
     }
 
+    /// Override the client-wide timeout for this request; `None` keeps the client default.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     pub async fn api_post(self, payload: &Value) -> Result<ApiResponse> {
         let response = self.response_post(payload).await?;
         ApiResponse::from_response(response).await
@@ -441,6 +486,9 @@ impl<'a> ApiRequestBuilder<'a> {
         let headers = self.headers.clone();
         let mut request = request_builder(url, &self.client.client);
         request = request.headers(headers);
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
 
         if let Some(decorator) = &self.client.request_builder {
             request = decorator(request)?;
@@ -625,4 +673,86 @@ mod tests {
             assert_eq!(actual, Some("test-session_id-456"));
         });
     }
+
+    #[tokio::test]
+    async fn test_request_timeout_overrides_client_default() {
+        let client = ApiClient::with_timeout_and_tls(
+            "http://localhost:8080".to_string(),
+            AuthMethod::NoAuth,
+            Duration::from_secs(600),
+            None,
+        )
+        .unwrap();
+
+        let request = client
+            .request("/test")
+            .timeout(Some(Duration::from_secs(30)))
+            .send_request(|url, client| client.post(url))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(30)));
+
+        // Without an override reqwest falls back to the client's own timeout.
+        let request = client
+            .request("/test")
+            .timeout(None)
+            .send_request(|url, client| client.post(url))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.timeout(), None);
+    }
+
+    async fn response_version(pool_config: PoolConfig) -> reqwest::Version {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = ApiClient::new_with_tls(server.uri(), AuthMethod::NoAuth, None)
+            .unwrap()
+            .with_pool_config(pool_config)
+            .unwrap();
+        client.response_get("ping").await.unwrap().version()
+    }
+
+    #[tokio::test]
+    async fn test_pool_config_enables_http2_prior_knowledge() {
+        assert_eq!(
+            response_version(PoolConfig::default()).await,
+            reqwest::Version::HTTP_11
+        );
+        let pool_config = PoolConfig {
+            http2_prior_knowledge: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            response_version(pool_config).await,
+            reqwest::Version::HTTP_2
+        );
+    }
+
+    #[test]
+    fn test_pool_config_survives_client_rebuild() {
+        let pool_config = PoolConfig {
+            idle_timeout: Some(Duration::from_secs(30)),
+            max_idle_per_host: Some(8),
+            http2_prior_knowledge: false,
+        };
+        let client = ApiClient::new_with_tls(
+            "http://localhost:8080".to_string(),
+            AuthMethod::NoAuth,
+            None,
+        )
+        .unwrap()
+        .with_pool_config(pool_config.clone())
+        .unwrap()
+        .with_header("x-test", "1")
+        .unwrap();
+        assert_eq!(client.pool_config(), &pool_config);
+    }
 }
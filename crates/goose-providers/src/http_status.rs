@@ -1,8 +1,8 @@
 //! Format-agnostic HTTP status → `ProviderError` mapping.
 //!
 //! Used by providers regardless of their wire format (OpenAI, Anthropic,
-//! Google, etc.). Parses both `{"error":{"message":"..."}}` and
-//! `{"message":"..."}` error shapes.
+//! Google, etc.). Parses the OpenAI `{"error":{"message":"..."}}` shape as well
+//! as the Ollama, vLLM and bare `{"message":"..."}` variants.
 
 use std::time::{Duration, SystemTime};
 
@@ -178,25 +178,80 @@ pub fn is_context_length_exceeded_message(text: &str) -> bool {
     mentions_prompt_input_tokens && mentions_limit && mentions_overflow
 }
 
+/// Pull the human-readable message out of the error shapes OpenAI-compatible servers
+/// return: `{"error":{"message":..}}` (OpenAI, llama.cpp server), `{"error":".."}`
+/// (Ollama), `{"detail":".."}` or `{"detail":[{"msg":..}]}` (vLLM/FastAPI) and a bare
+/// `{"message":..}`.
+fn error_body_message(payload: &Value) -> Option<String> {
+    let error = payload.get("error");
+    if let Some(message) = error
+        .and_then(|e| e.get("message"))
+        .or(error.filter(|e| e.is_string()))
+        .or_else(|| payload.get("message"))
+        .and_then(|m| m.as_str())
+    {
+        return Some(message.to_string());
+    }
+
+    match payload.get("detail")? {
+        Value::String(detail) => Some(detail.clone()),
+        Value::Array(details) => {
+            let messages: Vec<&str> = details
+                .iter()
+                .filter_map(|d| d.get("msg").and_then(|m| m.as_str()))
+                .collect();
+            (!messages.is_empty()).then(|| messages.join("; "))
+        }
+        _ => None,
+    }
+}
+
+/// Map an explicit error `type`/`code` to a variant regardless of the HTTP status, since
+/// compatible servers disagree on statuses (llama.cpp reports context overflow as 400,
+/// some proxies as 500).
+fn error_from_body_type(payload: &Value, message: &str) -> Option<ProviderError> {
+    let error = payload.get("error")?;
+    let error_type = ["type", "code"]
+        .iter()
+        .find_map(|key| error.get(key).and_then(|v| v.as_str()))?;
+    match error_type {
+        "context_length_exceeded" | "exceed_context_size_error" => {
+            Some(ProviderError::ContextLengthExceeded(message.to_string()))
+        }
+        "rate_limit_exceeded" | "rate_limit_error" => Some(ProviderError::RateLimitExceeded {
+            details: message.to_string(),
+            retry_delay: None,
+        }),
+        "authentication_error" | "invalid_api_key" => {
+            Some(ProviderError::Authentication(message.to_string()))
+        }
+        // OpenAI reports an empty balance as a 429, which must not be retried like a rate limit.
+        "insufficient_quota" => Some(ProviderError::CreditsExhausted {
+            details: message.to_string(),
+            top_up_url: None,
+        }),
+        _ => None,
+    }
+}
+
 pub fn map_http_error_to_provider_error(
     status: StatusCode,
     payload: Option<Value>,
     url: &str,
 ) -> ProviderError {
+    let body_message = payload.as_ref().and_then(error_body_message);
     let extract_message = || -> String {
-        payload
-            .as_ref()
-            .and_then(|p| {
-                p.get("error")
-                    .and_then(|e| e.get("message"))
-                    .or_else(|| p.get("message"))
-                    .and_then(|m| m.as_str())
-                    .map(String::from)
-            })
+        body_message
+            .clone()
             .unwrap_or_else(|| payload.as_ref().map(|p| p.to_string()).unwrap_or_default())
     };
 
-    let error = match status {
+    let typed_error = payload
+        .as_ref()
+        .zip(body_message.as_deref())
+        .and_then(|(payload, message)| error_from_body_type(payload, message));
+
+    let error = typed_error.unwrap_or_else(|| match status {
         StatusCode::OK => unreachable!("Should not call this function with OK status"),
         StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ProviderError::Authentication(format!(
             "Authentication failed for {url}. Status: {}. Response: {}",
@@ -234,7 +289,7 @@ pub fn map_http_error_to_provider_error(
             status,
             extract_message()
         )),
-    };
+    });
 
     if !status.is_success() {
         tracing::warn!(
@@ -393,6 +448,80 @@ mod tests {
         assert!(extract_retry_after(&empty_headers(), Some(&payload)).is_none());
     }
 
+    fn map_error(status: StatusCode, payload: Value) -> ProviderError {
+        map_http_error_to_provider_error(status, Some(payload), "http://localhost/v1")
+    }
+
+    #[test]
+    fn maps_openai_shaped_error_by_type() {
+        let err = map_error(
+            StatusCode::BAD_REQUEST,
+            json!({"error": {"message": "the request exceeds the available context size", "type": "exceed_context_size_error", "code": 400}}),
+        );
+        assert!(matches!(
+            err,
+            ProviderError::ContextLengthExceeded(ref msg)
+                if msg == "the request exceeds the available context size"
+        ));
+
+        let err = map_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            json!({"error": {"message": "slow down", "type": "rate_limit_error"}}),
+        );
+        assert!(matches!(
+            err,
+            ProviderError::RateLimitExceeded { ref details, .. } if details == "slow down"
+        ));
+    }
+
+    #[test]
+    fn maps_insufficient_quota_to_credits_exhausted() {
+        let err = map_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({"error": {
+                "message": "You exceeded your current quota, please check your plan and billing details.",
+                "type": "insufficient_quota",
+                "param": null,
+                "code": "insufficient_quota"
+            }}),
+        );
+        assert!(matches!(
+            err,
+            ProviderError::CreditsExhausted { ref details, top_up_url: None }
+                if details.starts_with("You exceeded your current quota")
+        ));
+    }
+
+    #[test]
+    fn maps_ollama_string_error() {
+        let err = map_error(
+            StatusCode::NOT_FOUND,
+            json!({"error": "model \"qwen3\" not found, try pulling it first"}),
+        );
+        assert_eq!(
+            err.to_string(),
+            ProviderError::RequestFailed(
+                "Resource not found (404) at http://localhost/v1: model \"qwen3\" not found, try pulling it first".to_string()
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn maps_fastapi_detail_errors() {
+        let err = map_error(
+            StatusCode::BAD_REQUEST,
+            json!({"detail": "This model's maximum context length is 4096 tokens"}),
+        );
+        assert!(matches!(err, ProviderError::ContextLengthExceeded(_)));
+
+        let err = map_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            json!({"detail": [{"loc": ["body", "messages"], "msg": "field required"}, {"msg": "bad role"}]}),
+        );
+        assert!(err.to_string().ends_with(": field required; bad role"));
+    }
+
     #[test]
     fn context_length_classifier_accepts_context_window_errors() {
         let messages = [
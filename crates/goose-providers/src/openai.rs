@@ -8,14 +8,16 @@ use crate::declarative::{DeclarativeProviderConfig, KeyResolver};
 use crate::errors::ProviderError;
 use crate::formats::openai::is_openai_responses_model;
 use crate::formats::openai::{
-    create_request_with_options, get_cost, get_usage, response_to_message, OpenAiFormatOptions,
+    create_request_with_options, format_tools, get_cost, get_usage, response_to_message,
+    OpenAiFormatOptions, OpenAiStreamOptions,
 };
 use crate::formats::openai_responses::{
     create_responses_request, get_responses_usage, responses_api_to_message, ResponsesApiResponse,
 };
-use crate::images::ImageFormat;
+use crate::images::{ImageDetail, ImageFormat};
 use crate::openai_compatible::{
-    handle_response_openai_compat, handle_status, stream_openai_compat, stream_responses_compat,
+    handle_response_openai_compat, handle_status, log_response_headers,
+    stream_openai_compat_with_options, stream_responses_compat,
 };
 use crate::request_log::{start_log, LoggerHandleExt};
 use crate::thinking::ThinkingEffort;
@@ -24,7 +26,8 @@ use async_trait::async_trait;
 use reqwest::StatusCode;
 use serde_json::json;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, LazyLock, Mutex};
+use tiktoken_rs::CoreBPE;
 
 use crate::base::{MessageStream, ProviderDescriptor};
 use crate::model::ModelConfig;
@@ -37,41 +40,62 @@ const OPEN_AI_DEFAULT_RESPONSES_PATH: &str = "v1/responses";
 const OPEN_AI_DEFAULT_MODELS_PATH: &str = "v1/models";
 pub const OPEN_AI_DEFAULT_MODEL: &str = "gpt-4o";
 pub const OPEN_AI_DEFAULT_FAST_MODEL: &str = "gpt-4o-mini";
-pub const OPEN_AI_KNOWN_MODELS: &[(&str, usize)] = &[
-    ("gpt-4o", 128_000),
-    ("gpt-4o-mini", 128_000),
-    ("gpt-4.1", 128_000),
-    ("gpt-4.1-mini", 128_000),
-    ("o1", 200_000),
-    ("o3", 200_000),
-    ("gpt-3.5-turbo", 16_385),
-    ("gpt-4-turbo", 128_000),
-    ("o4-mini", 128_000),
-    ("gpt-5", 400_000),
-    ("gpt-5-mini", 400_000),
-    ("gpt-5-nano", 400_000),
-    ("gpt-5-pro", 400_000),
-    ("gpt-5-codex", 400_000),
-    ("gpt-5.1", 400_000),
-    ("gpt-5.1-codex", 400_000),
-    ("gpt-5.2", 400_000),
-    ("gpt-5.2-codex", 400_000),
-    ("gpt-5.2-pro", 400_000),
-    ("gpt-5.3-codex", 400_000),
-    ("gpt-5.4", 1_050_000),
-    ("gpt-5.4-mini", 400_000),
-    ("gpt-5.4-nano", 400_000),
-    ("gpt-5.4-pro", 1_050_000),
-    ("gpt-5.5", 1_050_000),
-    ("gpt-5.5-pro", 1_050_000),
-    ("gpt-5.6", 1_050_000),
-    ("gpt-5.6-sol", 1_050_000),
-    ("gpt-5.6-terra", 1_050_000),
-    ("gpt-5.6-luna", 1_050_000),
+/// Known models as (name, context limit, (input, output) USD per million tokens).
+pub const OPEN_AI_KNOWN_MODELS: &[(&str, usize, Option<(f64, f64)>)] = &[
+    ("gpt-4o", 128_000, Some((2.5, 10.0))),
+    ("gpt-4o-mini", 128_000, Some((0.15, 0.6))),
+    ("gpt-4.1", 128_000, Some((2.0, 8.0))),
+    ("gpt-4.1-mini", 128_000, Some((0.4, 1.6))),
+    ("o1", 200_000, Some((15.0, 60.0))),
+    ("o3", 200_000, Some((2.0, 8.0))),
+    ("gpt-3.5-turbo", 16_385, Some((0.5, 1.5))),
+    ("gpt-4-turbo", 128_000, Some((10.0, 30.0))),
+    ("o4-mini", 128_000, Some((1.1, 4.4))),
+    ("gpt-5", 400_000, Some((1.25, 10.0))),
+    ("gpt-5-mini", 400_000, Some((0.25, 2.0))),
+    ("gpt-5-nano", 400_000, Some((0.05, 0.4))),
+    ("gpt-5-pro", 400_000, Some((15.0, 120.0))),
+    ("gpt-5-codex", 400_000, Some((1.25, 10.0))),
+    ("gpt-5.1", 400_000, Some((1.25, 10.0))),
+    ("gpt-5.1-codex", 400_000, Some((1.25, 10.0))),
+    ("gpt-5.2", 400_000, Some((1.75, 14.0))),
+    ("gpt-5.2-codex", 400_000, Some((1.75, 14.0))),
+    ("gpt-5.2-pro", 400_000, Some((21.0, 168.0))),
+    ("gpt-5.3-codex", 400_000, None),
+    ("gpt-5.4", 1_050_000, None),
+    ("gpt-5.4-mini", 400_000, None),
+    ("gpt-5.4-nano", 400_000, None),
+    ("gpt-5.4-pro", 1_050_000, None),
+    ("gpt-5.5", 1_050_000, None),
+    ("gpt-5.5-pro", 1_050_000, None),
+    ("gpt-5.6", 1_050_000, None),
+    ("gpt-5.6-sol", 1_050_000, None),
+    ("gpt-5.6-terra", 1_050_000, None),
+    ("gpt-5.6-luna", 1_050_000, None),
 ];
 
 pub const OPEN_AI_DOC_URL: &str = "https://platform.openai.com/docs/models";
+pub const OPEN_AI_BILLING_URL: &str = "https://platform.openai.com/settings/organization/billing";
 const DEFAULT_TIMEOUT_SECONDS: u64 = 600;
+/// Limits OpenAI enforces on the request `metadata` object.
+const MAX_METADATA_PAIRS: usize = 16;
+const MAX_METADATA_KEY_CHARS: usize = 64;
+const MAX_METADATA_VALUE_CHARS: usize = 512;
+
+/// Context limit for a model, preferring a configured override over the built-in table.
+pub fn openai_model_context_limit(
+    model_name: &str,
+    overrides: &HashMap<String, usize>,
+) -> Option<usize> {
+    overrides.get(model_name).copied().or_else(|| {
+        OPEN_AI_KNOWN_MODELS
+            .iter()
+            .find(|(name, _, _)| *name == model_name)
+            .map(|&(_, limit, _)| limit)
+    })
+}
+
+static TOKENIZER: LazyLock<Option<CoreBPE>> = LazyLock::new(|| tiktoken_rs::o200k_base().ok());
 
 type OpenAiBaseUrlParts = (String, Vec<(String, String)>, bool);
 
@@ -130,6 +154,27 @@ pub fn parse_openai_base_url(raw_url: &str) -> Result<OpenAiBaseUrlParts> {
     Ok((format!("{}{}", authority, path), query_params, false))
 }
 
+/// Supplies the `user` identifier OpenAI uses for abuse detection and per-user rate
+/// limits. Evaluated per request so it can follow the active session.
+#[derive(Clone)]
+pub struct OpenAiEndUser(Arc<dyn Fn() -> Option<String> + Send + Sync>);
+
+impl OpenAiEndUser {
+    pub fn new(resolve: impl Fn() -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(resolve))
+    }
+
+    fn resolve(&self) -> Option<String> {
+        (self.0)().filter(|user| !user.is_empty())
+    }
+}
+
+impl std::fmt::Debug for OpenAiEndUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OpenAiEndUser")
+    }
+}
+
 #[derive(Debug, serde::Serialize)]
 pub struct OpenAiProvider {
     #[serde(skip)]
@@ -144,6 +189,12 @@ pub struct OpenAiProvider {
     dynamic_models: Option<bool>,
     skip_canonical_filtering: bool,
     preserve_thinking_context: bool,
+    force_chat_completions: bool,
+    partial_tool_calls: bool,
+    image_detail: Option<ImageDetail>,
+    #[serde(skip)]
+    end_user: Option<OpenAiEndUser>,
+    metadata: Option<HashMap<String, String>>,
     #[serde(skip)]
     n_ctx_cache: Arc<Mutex<HashMap<String, Option<usize>>>>,
 }
@@ -165,6 +216,11 @@ pub struct OpenAiProviderBuilder {
     dynamic_models: Option<bool>,
     skip_canonical_filtering: bool,
     preserve_thinking_context: bool,
+    force_chat_completions: bool,
+    partial_tool_calls: bool,
+    image_detail: Option<ImageDetail>,
+    end_user: Option<OpenAiEndUser>,
+    metadata: Option<HashMap<String, String>>,
 }
 
 impl OpenAiProviderBuilder {
@@ -181,6 +237,11 @@ impl OpenAiProviderBuilder {
             dynamic_models: None,
             skip_canonical_filtering: false,
             preserve_thinking_context: false,
+            force_chat_completions: false,
+            partial_tool_calls: false,
+            image_detail: None,
+            end_user: None,
+            metadata: None,
         }
     }
 
@@ -252,6 +313,39 @@ impl OpenAiProviderBuilder {
         self
     }
 
+    /// Never route requests to the Responses API, whatever the model name or base path.
+    pub fn force_chat_completions(mut self, force_chat_completions: bool) -> Self {
+        self.force_chat_completions = force_chat_completions;
+        self
+    }
+
+    /// Stream an interim, partial tool request as soon as a chat-completions call is
+    /// named, ahead of the complete request.
+    pub fn partial_tool_calls(mut self, partial_tool_calls: bool) -> Self {
+        self.partial_tool_calls = partial_tool_calls;
+        self
+    }
+
+    /// Detail level to request for images that don't carry their own. Unset, OpenAI picks.
+    pub fn image_detail(mut self, image_detail: Option<ImageDetail>) -> Self {
+        self.image_detail = image_detail;
+        self
+    }
+
+    /// Send a `user` field with each request. Unset by default so no identifier
+    /// leaves the machine unless configured.
+    pub fn end_user(mut self, end_user: Option<OpenAiEndUser>) -> Self {
+        self.end_user = end_user;
+        self
+    }
+
+    /// Tag each request with a `metadata` object for OpenAI's dashboard and exports.
+    /// Validate the map with [`validate_request_metadata`] first.
+    pub fn metadata(mut self, metadata: Option<HashMap<String, String>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     pub fn build(self) -> OpenAiProvider {
         OpenAiProvider {
             api_client: self.api_client,
@@ -265,6 +359,11 @@ impl OpenAiProviderBuilder {
             dynamic_models: self.dynamic_models,
             skip_canonical_filtering: self.skip_canonical_filtering,
             preserve_thinking_context: self.preserve_thinking_context,
+            force_chat_completions: self.force_chat_completions,
+            partial_tool_calls: self.partial_tool_calls,
+            image_detail: self.image_detail,
+            end_user: self.end_user,
+            metadata: self.metadata,
             n_ctx_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -285,6 +384,11 @@ impl OpenAiProvider {
             dynamic_models: None,
             skip_canonical_filtering: false,
             preserve_thinking_context: false,
+            force_chat_completions: false,
+            partial_tool_calls: false,
+            image_detail: None,
+            end_user: None,
+            metadata: None,
             n_ctx_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -420,8 +524,41 @@ impl OpenAiProvider {
         payload
     }
 
+    fn apply_end_user(&self, payload: &mut serde_json::Value) {
+        let Some(user) = self.end_user.as_ref().and_then(OpenAiEndUser::resolve) else {
+            return;
+        };
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("user".to_string(), json!(user));
+        }
+    }
+
+    fn apply_metadata(&self, payload: &mut serde_json::Value) {
+        let Some(metadata) = self.metadata.as_ref().filter(|m| !m.is_empty()) else {
+            return;
+        };
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("metadata".to_string(), json!(metadata));
+        }
+    }
+
+    fn enrich_credits_error(&self, err: ProviderError) -> ProviderError {
+        match err {
+            ProviderError::CreditsExhausted {
+                details,
+                top_up_url: None,
+            } if self.name == OPEN_AI_PROVIDER_NAME => ProviderError::CreditsExhausted {
+                details,
+                top_up_url: Some(OPEN_AI_BILLING_URL.to_string()),
+            },
+            other => other,
+        }
+    }
+
     fn should_use_responses_api_for_provider(&self, model_name: &str) -> bool {
-        if Self::PROVIDERS_NEEDING_STANDARD_CHAT_PARAMS.contains(&self.name.as_str()) {
+        if self.force_chat_completions
+            || Self::PROVIDERS_NEEDING_STANDARD_CHAT_PARAMS.contains(&self.name.as_str())
+        {
             return false;
         }
 
@@ -468,13 +605,9 @@ impl OpenAiProvider {
             return Err(ProviderError::Authentication(msg.to_string()));
         }
 
-        let data = json.get("data").and_then(|v| v.as_array()).ok_or_else(|| {
+        let mut models = parse_model_ids(&json).ok_or_else(|| {
             ProviderError::UsageError("Missing data field in JSON response".into())
         })?;
-        let mut models: Vec<String> = data
-            .iter()
-            .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(str::to_string))
-            .collect();
         models.sort();
         Ok(models)
     }
@@ -496,6 +629,68 @@ impl OpenAiProvider {
     }
 }
 
+/// Model ids from a `/v1/models` response body. Besides OpenAI's `{"data": [...]}`,
+/// compatible servers answer with a bare array or a `{"models": [...]}` envelope, and
+/// name entries by `id`, `name`, or a plain string. `None` when no list is present.
+pub(crate) fn parse_model_ids(json: &serde_json::Value) -> Option<Vec<String>> {
+    let entries = match json {
+        serde_json::Value::Array(entries) => entries,
+        _ => json
+            .get("data")
+            .or_else(|| json.get("models"))
+            .and_then(|v| v.as_array())?,
+    };
+    Some(
+        entries
+            .iter()
+            .filter_map(|entry| match entry {
+                serde_json::Value::String(id) => Some(id.as_str()),
+                _ => entry
+                    .get("id")
+                    .or_else(|| entry.get("name"))
+                    .and_then(|v| v.as_str()),
+            })
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Approximate prompt size using the o200k tokenizer, with the per-message framing overhead
+/// from OpenAI's token counting guide. Tool schemas are counted as their serialized JSON.
+fn estimate_prompt_tokens(system: &str, messages: &[Message], tools: &[Tool]) -> Option<usize> {
+    const TOKENS_PER_MESSAGE: usize = 4;
+    const REPLY_PRIMER: usize = 3;
+
+    let bpe = TOKENIZER.as_ref()?;
+    let count = |text: &str| bpe.encode_with_special_tokens(text).len();
+
+    let mut total = count(system) + TOKENS_PER_MESSAGE + REPLY_PRIMER;
+    for message in messages.iter().filter(|m| m.metadata.agent_visible) {
+        total += TOKENS_PER_MESSAGE;
+        for content in &message.content {
+            if let Some(text) = content.as_text() {
+                total += count(text);
+            } else if let Some(request) = content.as_tool_request() {
+                if let Ok(call) = &request.tool_call {
+                    total += count(&call.name);
+                    if let Some(arguments) = &call.arguments {
+                        total += count(&serde_json::to_string(arguments).ok()?);
+                    }
+                }
+            } else if let Some(text) = content.as_tool_response_text() {
+                total += count(&text);
+            }
+        }
+    }
+
+    if !tools.is_empty() {
+        let spec = format_tools(tools).ok()?;
+        total += count(&serde_json::to_string(&spec).ok()?);
+    }
+
+    Some(total)
+}
+
 /// Extract `meta.n_ctx` for `model_name` from a `/v1/models` response body.
 fn parse_n_ctx_from_models(json: &serde_json::Value, model_name: &str) -> Option<usize> {
     let data = json.get("data")?.as_array()?;
@@ -528,7 +723,12 @@ impl ProviderDescriptor for OpenAiProvider {
     fn metadata() -> ProviderMetadata {
         let models = OPEN_AI_KNOWN_MODELS
             .iter()
-            .map(|(name, limit)| ModelInfo::new(*name, *limit))
+            .map(|&(name, limit, price)| match price {
+                Some((input, output)) => {
+                    ModelInfo::new(name, limit).with_price_per_million(input, output)
+                }
+                None => ModelInfo::new(name, limit),
+            })
             .collect();
         ProviderMetadata::with_models(
             OPEN_AI_PROVIDER_NAME,
@@ -558,6 +758,39 @@ impl ProviderDescriptor for OpenAiProvider {
                 ConfigKey::new("OPENAI_PROJECT", false, false, None, false),
                 ConfigKey::new("OPENAI_CUSTOM_HEADERS", false, true, None, false),
                 ConfigKey::new("OPENAI_TIMEOUT", false, false, Some("600"), false),
+                ConfigKey::new("OPENAI_USER", false, false, None, false),
+                ConfigKey::new("OPENAI_METADATA", false, false, None, false),
+                ConfigKey::new(
+                    "OPENAI_USER_FROM_SESSION",
+                    false,
+                    false,
+                    Some("false"),
+                    false,
+                ),
+                ConfigKey::new(
+                    "OPENAI_FORCE_CHAT_COMPLETIONS",
+                    false,
+                    false,
+                    Some("false"),
+                    false,
+                ),
+                ConfigKey::new(
+                    "OPENAI_PARTIAL_TOOL_CALLS",
+                    false,
+                    false,
+                    Some("false"),
+                    false,
+                ),
+                ConfigKey::new("OPENAI_IMAGE_DETAIL", false, false, None, false),
+                ConfigKey::new("OPENAI_POOL_IDLE_TIMEOUT", false, false, None, false),
+                ConfigKey::new("OPENAI_POOL_MAX_IDLE_PER_HOST", false, false, None, false),
+                ConfigKey::new(
+                    "OPENAI_HTTP2_PRIOR_KNOWLEDGE",
+                    false,
+                    false,
+                    Some("false"),
+                    false,
+                ),
             ],
         )
         .with_setup_steps(vec![
@@ -575,6 +808,10 @@ impl Provider for OpenAiProvider {
         &self.name
     }
 
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        self.supports_streaming
+    }
+
     fn skip_canonical_filtering(&self) -> bool {
         self.skip_canonical_filtering
     }
@@ -616,6 +853,20 @@ impl Provider for OpenAiProvider {
         Ok(probed.unwrap_or_else(|| model_config.context_limit()))
     }
 
+    async fn count_tokens(
+        &self,
+        _model_config: &ModelConfig,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Option<usize> {
+        estimate_prompt_tokens(system, messages, tools)
+    }
+
+    async fn health_check(&self, _model_config: &ModelConfig) -> Result<(), ProviderError> {
+        self.fetch_supported_models().await.map(|_| ())
+    }
+
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         if let Some(custom_models) = &self.custom_models {
             if self.dynamic_models == Some(false) {
@@ -648,6 +899,8 @@ impl Provider for OpenAiProvider {
         if self.should_use_responses_api_for_provider(&model_config.model_name) {
             let mut payload = create_responses_request(model_config, system, messages, tools)?;
             payload["stream"] = serde_json::Value::Bool(self.supports_streaming);
+            self.apply_end_user(&mut payload);
+            self.apply_metadata(&mut payload);
 
             let mut log = start_log(model_config, &payload)?;
 
@@ -656,25 +909,26 @@ impl Provider for OpenAiProvider {
                     let payload_clone = payload.clone();
                     let resp = self
                         .api_client
-                        .response_post(
-                            &Self::map_base_path(
-                                &self.base_path,
-                                "responses",
-                                OPEN_AI_DEFAULT_RESPONSES_PATH,
-                            ),
-                            &payload_clone,
-                        )
+                        .request(&Self::map_base_path(
+                            &self.base_path,
+                            "responses",
+                            OPEN_AI_DEFAULT_RESPONSES_PATH,
+                        ))
+                        .timeout(model_config.request_timeout())
+                        .response_post(&payload_clone)
                         .await?;
                     handle_status(resp).await
                 })
                 .await
                 .inspect_err(|e| {
                     let _ = log.error(e);
-                })?;
+                })
+                .map_err(|e| self.enrich_credits_error(e))?;
 
             if self.supports_streaming {
                 stream_responses_compat(response, log)
             } else {
+                log_response_headers(&mut log, response.headers())?;
                 let json: serde_json::Value = response.json().await.map_err(|e| {
                     ProviderError::RequestFailed(format!("Failed to parse JSON: {}", e))
                 })?;
@@ -712,33 +966,48 @@ impl Provider for OpenAiProvider {
                 self.supports_streaming,
                 OpenAiFormatOptions {
                     preserve_thinking_context: self.preserve_thinking_context,
+                    image_detail: self.image_detail,
                 },
             )?;
-            let payload = self.sanitize_request_for_compat(payload, model_config);
+            let mut payload = self.sanitize_request_for_compat(payload, model_config);
+            self.apply_end_user(&mut payload);
+            self.apply_metadata(&mut payload);
             let mut log = start_log(model_config, &payload)?;
 
             let response = self
                 .with_retry(|| async {
                     let resp = self
                         .api_client
-                        .response_post(&self.base_path, &payload)
+                        .request(&self.base_path)
+                        .timeout(model_config.request_timeout())
+                        .response_post(&payload)
                         .await?;
                     handle_status(resp).await
                 })
                 .await
                 .inspect_err(|e| {
                     let _ = log.error(e);
-                })?;
+                })
+                .map_err(|e| self.enrich_credits_error(e))?;
 
             if self.supports_streaming {
-                stream_openai_compat(response, log)
+                stream_openai_compat_with_options(
+                    response,
+                    log,
+                    OpenAiStreamOptions {
+                        partial_tool_calls: self.partial_tool_calls,
+                    },
+                )
             } else {
+                log_response_headers(&mut log, response.headers())?;
                 let json: serde_json::Value = response.json().await.map_err(|e| {
                     ProviderError::RequestFailed(format!("Failed to parse JSON: {}", e))
                 })?;
 
                 let message = response_to_message(&json).map_err(|e| {
-                    ProviderError::RequestFailed(format!("Failed to parse message: {}", e))
+                    e.downcast::<ProviderError>().unwrap_or_else(|e| {
+                        ProviderError::RequestFailed(format!("Failed to parse message: {}", e))
+                    })
                 })?;
 
                 let usage_json = json.get("usage").unwrap_or(&serde_json::Value::Null);
@@ -861,6 +1130,37 @@ pub fn parse_custom_headers(s: String) -> HashMap<String, String> {
         .collect()
 }
 
+/// Check request metadata against OpenAI's limits: at most 16 pairs, keys up to
+/// 64 characters and values up to 512.
+pub fn validate_request_metadata(
+    metadata: HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    if metadata.len() > MAX_METADATA_PAIRS {
+        anyhow::bail!(
+            "OpenAI metadata allows at most {} key-value pairs, got {}",
+            MAX_METADATA_PAIRS,
+            metadata.len()
+        );
+    }
+    for (key, value) in &metadata {
+        if key.is_empty() || key.chars().count() > MAX_METADATA_KEY_CHARS {
+            anyhow::bail!(
+                "OpenAI metadata key '{}' must be 1 to {} characters",
+                key,
+                MAX_METADATA_KEY_CHARS
+            );
+        }
+        if value.chars().count() > MAX_METADATA_VALUE_CHARS {
+            anyhow::bail!(
+                "OpenAI metadata value for '{}' exceeds {} characters",
+                key,
+                MAX_METADATA_VALUE_CHARS
+            );
+        }
+    }
+    Ok(metadata)
+}
+
 pub fn derive_base_path(url_path: &str) -> String {
     let stripped = url_path.trim_start_matches('/');
     let normalized = stripped.trim_end_matches('/');
@@ -886,6 +1186,7 @@ mod tests {
     use super::*;
     use crate::api_client::AuthMethod;
     use serde_json::json;
+    use test_case::test_case;
 
     fn make_provider(name: &str) -> OpenAiProvider {
         OpenAiProvider {
@@ -905,10 +1206,48 @@ mod tests {
             dynamic_models: None,
             skip_canonical_filtering: false,
             preserve_thinking_context: false,
+            force_chat_completions: false,
+            partial_tool_calls: false,
+            image_detail: None,
+            end_user: None,
+            metadata: None,
             n_ctx_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    #[test]
+    fn supports_streaming_reflects_configuration() {
+        let model_config = ModelConfig::new("gpt-4o");
+        let provider = make_provider("openai");
+        assert!(provider.supports_streaming(&model_config));
+
+        let provider = OpenAiProvider {
+            supports_streaming: false,
+            ..make_provider("custom")
+        };
+        assert!(!provider.supports_streaming(&model_config));
+    }
+
+    #[test]
+    fn credits_errors_link_openai_billing_only_for_openai() {
+        let quota_error = || ProviderError::CreditsExhausted {
+            details: "You exceeded your current quota".to_string(),
+            top_up_url: None,
+        };
+
+        match make_provider(OPEN_AI_PROVIDER_NAME).enrich_credits_error(quota_error()) {
+            ProviderError::CreditsExhausted { top_up_url, .. } => {
+                assert_eq!(top_up_url.as_deref(), Some(OPEN_AI_BILLING_URL))
+            }
+            other => panic!("expected credits exhausted, got {other:?}"),
+        }
+
+        match make_provider("mistral").enrich_credits_error(quota_error()) {
+            ProviderError::CreditsExhausted { top_up_url, .. } => assert!(top_up_url.is_none()),
+            other => panic!("expected credits exhausted, got {other:?}"),
+        }
+    }
+
     #[test]
     fn sanitize_remaps_max_completion_tokens_for_compat_provider() {
         let provider = make_provider("mistral");
@@ -1116,6 +1455,96 @@ mod tests {
         assert!(!provider.should_use_responses_api_for_provider("openai/o3"));
     }
 
+    #[test]
+    fn user_field_is_sent_only_when_configured() {
+        let mut provider = make_provider(OPEN_AI_PROVIDER_NAME);
+        let mut payload = json!({"model": "gpt-4o", "messages": []});
+        provider.apply_end_user(&mut payload);
+        assert!(payload.get("user").is_none());
+
+        provider.end_user = Some(OpenAiEndUser::new(|| None));
+        provider.apply_end_user(&mut payload);
+        assert!(payload.get("user").is_none());
+
+        provider.end_user = Some(OpenAiEndUser::new(|| Some("user-1234".to_string())));
+        provider.apply_end_user(&mut payload);
+        assert_eq!(payload["user"], json!("user-1234"));
+    }
+
+    #[test_case(json!({"object": "list", "data": [{"id": "gpt-4o"}, {"id": "o3"}]}) ; "openai data envelope")]
+    #[test_case(json!([{"id": "gpt-4o"}, {"id": "o3"}]) ; "bare array")]
+    #[test_case(json!({"models": [{"name": "gpt-4o"}, {"name": "o3"}]}) ; "models envelope")]
+    #[test_case(json!({"data": ["gpt-4o", "o3"]}) ; "plain string entries")]
+    fn parse_model_ids_accepts_common_envelopes(body: serde_json::Value) {
+        assert_eq!(
+            parse_model_ids(&body),
+            Some(vec!["gpt-4o".to_string(), "o3".to_string()])
+        );
+    }
+
+    #[test]
+    fn parse_model_ids_rejects_bodies_without_a_list() {
+        assert_eq!(parse_model_ids(&json!({"object": "list"})), None);
+        assert_eq!(parse_model_ids(&json!({"data": {"id": "gpt-4o"}})), None);
+    }
+
+    #[test]
+    fn metadata_is_attached_only_when_configured() {
+        let mut provider = make_provider(OPEN_AI_PROVIDER_NAME);
+        let mut payload = json!({"model": "gpt-4o", "messages": []});
+        provider.apply_metadata(&mut payload);
+        assert!(payload.get("metadata").is_none());
+
+        provider.metadata = Some(HashMap::new());
+        provider.apply_metadata(&mut payload);
+        assert!(payload.get("metadata").is_none());
+
+        provider.metadata = Some(HashMap::from([
+            ("team".to_string(), "infra".to_string()),
+            ("environment".to_string(), "prod".to_string()),
+        ]));
+        provider.apply_metadata(&mut payload);
+        assert_eq!(
+            payload["metadata"],
+            json!({"team": "infra", "environment": "prod"})
+        );
+    }
+
+    #[test]
+    fn request_metadata_limits_are_enforced() {
+        let pairs = |n: usize| (0..n).map(|i| (format!("k{i}"), "v".to_string())).collect();
+        assert!(validate_request_metadata(pairs(16)).is_ok());
+        assert!(validate_request_metadata(pairs(17)).is_err());
+
+        let single = |key: String, value: String| HashMap::from([(key, value)]);
+        assert!(validate_request_metadata(single("k".repeat(64), "v".repeat(512))).is_ok());
+        assert!(validate_request_metadata(single("k".repeat(65), "v".to_string())).is_err());
+        assert!(validate_request_metadata(single("k".to_string(), "v".repeat(513))).is_err());
+        assert!(validate_request_metadata(single(String::new(), "v".to_string())).is_err());
+    }
+
+    #[tokio::test]
+    async fn model_info_reports_tool_support() {
+        let provider = make_provider(OPEN_AI_PROVIDER_NAME);
+        assert!(provider.supports_tools(&ModelConfig::new("gpt-4o")));
+
+        let info = provider.fetch_model_info("gpt-4o").await.unwrap();
+        assert_eq!(info.supports_tools, Some(true));
+    }
+
+    #[test]
+    fn force_chat_completions_overrides_responses_routing() {
+        let mut provider = make_provider(OPEN_AI_PROVIDER_NAME);
+        assert!(provider.should_use_responses_api_for_provider("gpt-5-codex"));
+
+        provider.force_chat_completions = true;
+        assert!(!provider.should_use_responses_api_for_provider("gpt-5-codex"));
+        assert!(!provider.should_use_responses_api_for_provider("gpt-5.4"));
+
+        provider.base_path = "v1/responses".to_string();
+        assert!(!provider.should_use_responses_api_for_provider("gpt-4o"));
+    }
+
     #[test]
     fn responses_api_routing_uses_model_family_unless_path_forces_chat() {
         for (model_name, base_path, expected) in [
@@ -1264,6 +1693,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn from_custom_config_forwards_base_url_query_to_requests() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(query_param("api-version", "2024-06-01"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = from_declarative_config(
+            custom_config(&format!(
+                "{}/openai/deployments/gpt-4o?api-version=2024-06-01",
+                server.uri()
+            )),
+            None,
+            crate::declarative::EnvKeyResolver,
+        )
+        .unwrap()
+        .build();
+
+        let response = provider
+            .api_client
+            .response_post(&provider.base_path, &json!({}))
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let requests = server.received_requests().await.unwrap();
+        let url = &requests[0].url;
+        assert!(url.path().ends_with("/chat/completions"), "{url}");
+        assert_eq!(url.query(), Some("api-version=2024-06-01"));
+    }
+
     #[test]
     fn parse_n_ctx_falls_back_to_sole_entry_when_id_differs() {
         let body = json!({
@@ -1285,6 +1751,27 @@ mod tests {
         assert_eq!(parse_n_ctx_from_models(&body, "model-c"), None);
     }
 
+    #[test]
+    fn model_context_limit_override_wins_over_built_in() {
+        let overrides = HashMap::from([
+            ("gpt-4o".to_string(), 64_000),
+            ("my-finetune".to_string(), 32_768),
+        ]);
+        assert_eq!(
+            openai_model_context_limit("gpt-4o", &overrides),
+            Some(64_000)
+        );
+        assert_eq!(
+            openai_model_context_limit("my-finetune", &overrides),
+            Some(32_768)
+        );
+        assert_eq!(
+            openai_model_context_limit("gpt-4o-mini", &overrides),
+            Some(128_000)
+        );
+        assert_eq!(openai_model_context_limit("unknown", &overrides), None);
+    }
+
     #[test]
     fn derive_base_path_not_removing_api_path() {
         let r = derive_base_path("https://opencode.ai/zen/go");
@@ -1317,4 +1804,30 @@ mod tests {
         let r = derive_base_path("/api/voice");
         assert_eq!(r, "api/voice/v1/chat/completions");
     }
+
+    #[test]
+    fn estimate_prompt_tokens_grows_with_messages_and_tools() {
+        let empty = estimate_prompt_tokens("", &[], &[]).unwrap();
+        let with_message = estimate_prompt_tokens(
+            "You are helpful.",
+            &[Message::user().with_text("hello world")],
+            &[],
+        )
+        .unwrap();
+        let tool = Tool::new(
+            "developer__shell",
+            "Run a shell command",
+            rmcp::object!({"type": "object", "properties": {}}),
+        );
+        let with_tool = estimate_prompt_tokens(
+            "You are helpful.",
+            &[Message::user().with_text("hello world")],
+            &[tool],
+        )
+        .unwrap();
+
+        assert_eq!(empty, 7);
+        assert!(with_message > empty);
+        assert!(with_tool > with_message);
+    }
 }
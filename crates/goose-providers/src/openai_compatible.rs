@@ -1,12 +1,14 @@
 use crate::conversation::token_usage::{CostSource, ProviderUsage};
 use crate::images::ImageFormat;
+use crate::openai::parse_model_ids;
 use anyhow::Error;
 use async_stream::try_stream;
 use futures::TryStreamExt;
+use reqwest::header::HeaderMap;
 use reqwest::Response;
 #[cfg(test)]
 use reqwest::StatusCode;
-use serde_json::Value;
+use serde_json::{json, Value};
 use tokio::pin;
 use tokio_stream::StreamExt;
 use tokio_util::codec::{FramedRead, LinesCodec};
@@ -18,11 +20,12 @@ use super::retry::ProviderRetry;
 use crate::conversation::message::Message;
 use crate::errors::ProviderError;
 use crate::formats::openai::{
-    create_request, get_cost, get_usage, response_to_message, response_to_streaming_message,
+    create_request, get_cost, get_usage, join_split_sse_frames, response_to_message,
+    response_to_streaming_message_with_options, OpenAiStreamOptions,
 };
 use crate::formats::openai_responses::responses_api_to_streaming_message;
 use crate::model::ModelConfig;
-use crate::request_log::{start_log, LoggerHandleExt, RequestLogHandle};
+use crate::request_log::{start_log, LogError, LoggerHandleExt, RequestLogHandle};
 use rmcp::model::Tool;
 
 pub struct OpenAiCompatibleProvider {
@@ -75,6 +78,10 @@ impl Provider for OpenAiCompatibleProvider {
         &self.name
     }
 
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        self.supports_streaming
+    }
+
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         let response = self
             .api_client
@@ -91,13 +98,9 @@ impl Provider for OpenAiCompatibleProvider {
             return Err(ProviderError::Authentication(msg.to_string()));
         }
 
-        let arr = json.get("data").and_then(|v| v.as_array()).ok_or_else(|| {
+        let mut models = parse_model_ids(&json).ok_or_else(|| {
             ProviderError::RequestFailed("Missing 'data' array in models response".to_string())
         })?;
-        let mut models: Vec<String> = arr
-            .iter()
-            .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(str::to_string))
-            .collect();
         models.sort();
         Ok(models)
     }
@@ -135,12 +138,15 @@ impl Provider for OpenAiCompatibleProvider {
         if self.supports_streaming {
             stream_openai_compat(response, log)
         } else {
+            log_response_headers(&mut log, response.headers())?;
             let json: serde_json::Value = response.json().await.map_err(|e| {
                 ProviderError::RequestFailed(format!("Failed to parse JSON: {}", e))
             })?;
 
             let message = response_to_message(&json).map_err(|e| {
-                ProviderError::RequestFailed(format!("Failed to parse message: {}", e))
+                e.downcast::<ProviderError>().unwrap_or_else(|e| {
+                    ProviderError::RequestFailed(format!("Failed to parse message: {}", e))
+                })
             })?;
 
             let usage_json = json.get("usage").unwrap_or(&serde_json::Value::Null);
@@ -169,10 +175,40 @@ pub use super::http_status::{
 // Legacy alias kept for callers that haven't migrated their import path yet.
 pub use super::http_status::handle_response as handle_response_openai_compat;
 
+fn is_debug_header(name: &str) -> bool {
+    name == "x-request-id" || name.starts_with("x-ratelimit-")
+}
+
+/// Record the request id and rate-limit headers, which OpenAI support asks for when a
+/// request misbehaves.
+pub fn log_response_headers(
+    log: &mut Option<Box<dyn RequestLogHandle>>,
+    headers: &HeaderMap,
+) -> Result<(), LogError> {
+    let captured: serde_json::Map<String, Value> = headers
+        .iter()
+        .filter(|(name, _)| is_debug_header(name.as_str()))
+        .filter_map(|(name, value)| Some((name.to_string(), json!(value.to_str().ok()?))))
+        .collect();
+    if captured.is_empty() {
+        return Ok(());
+    }
+    log.write(&json!({ "response_headers": captured }), None)
+}
+
 pub fn stream_openai_compat(
+    response: Response,
+    log: Option<Box<dyn RequestLogHandle>>,
+) -> Result<MessageStream, ProviderError> {
+    stream_openai_compat_with_options(response, log, OpenAiStreamOptions::default())
+}
+
+pub fn stream_openai_compat_with_options(
     response: Response,
     mut log: Option<Box<dyn RequestLogHandle>>,
+    options: OpenAiStreamOptions,
 ) -> Result<MessageStream, ProviderError> {
+    log_response_headers(&mut log, response.headers())?;
     let stream = response.bytes_stream().map_err(std::io::Error::other);
 
     Ok(Box::pin(try_stream! {
@@ -180,7 +216,10 @@ pub fn stream_openai_compat(
         let framed = FramedRead::new(stream_reader, LinesCodec::new())
             .map_err(Error::from);
 
-        let message_stream = response_to_streaming_message(framed);
+        let message_stream = response_to_streaming_message_with_options(
+            Box::pin(join_split_sse_frames(framed)),
+            options,
+        );
         pin!(message_stream);
         while let Some(message) = message_stream.next().await {
             let (message, usage) = message.map_err(|e|
@@ -197,6 +236,7 @@ pub fn stream_responses_compat(
     response: Response,
     mut log: Option<Box<dyn RequestLogHandle>>,
 ) -> Result<MessageStream, ProviderError> {
+    log_response_headers(&mut log, response.headers())?;
     let stream = response.bytes_stream().map_err(std::io::Error::other);
 
     Ok(Box::pin(try_stream! {
@@ -221,7 +261,6 @@ pub fn stream_responses_compat(
 mod tests {
     use super::*;
     use crate::model::ModelConfig;
-    use serde_json::json;
     use test_case::test_case;
 
     #[test_case(
@@ -294,6 +333,55 @@ mod tests {
         );
     }
 
+    #[derive(Clone, Default)]
+    struct CapturedLog(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl RequestLogHandle for CapturedLog {
+        fn write(&mut self, s: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.0.lock().unwrap().push(s.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_logs_request_id_and_rate_limit_headers() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "req_123")
+                    .insert_header("x-ratelimit-remaining-requests", "59")
+                    .insert_header("x-ratelimit-remaining-tokens", "149000")
+                    .insert_header("x-unrelated", "ignored")
+                    .set_body_raw("data: [DONE]\n\n", "text/event-stream"),
+            )
+            .mount(&server)
+            .await;
+
+        let response = reqwest::Client::new()
+            .post(server.uri())
+            .send()
+            .await
+            .unwrap();
+        let captured = CapturedLog::default();
+        let log: Option<Box<dyn RequestLogHandle>> = Some(Box::new(captured.clone()));
+        let _stream = stream_openai_compat(response, log).unwrap();
+
+        let lines = captured.0.lock().unwrap();
+        let first: Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(
+            first["data"]["response_headers"],
+            json!({
+                "x-request-id": "req_123",
+                "x-ratelimit-remaining-requests": "59",
+                "x-ratelimit-remaining-tokens": "149000",
+            })
+        );
+    }
+
     #[test]
     fn build_request_respects_non_streaming_mode() {
         let provider = OpenAiCompatibleProvider::new(
@@ -315,5 +403,6 @@ mod tests {
 
         assert_eq!(payload.get("stream"), None);
         assert_eq!(payload.get("stream_options"), None);
+        assert!(!provider.supports_streaming(&model));
     }
 }
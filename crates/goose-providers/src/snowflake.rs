@@ -287,6 +287,7 @@ impl crate::base::ProviderDescriptor for SnowflakeProvider {
                 ConfigKey::new("SNOWFLAKE_TOKEN", true, true, None, true),
             ],
         )
+        .without_streaming()
     }
 }
 
@@ -296,6 +297,11 @@ impl Provider for SnowflakeProvider {
         &self.name
     }
 
+    /// Cortex replies are read whole and yielded as one message.
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         Ok(SNOWFLAKE_KNOWN_MODELS
             .iter()
@@ -334,3 +340,15 @@ impl Provider for SnowflakeProvider {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snowflake_does_not_stream() {
+        let provider =
+            SnowflakeProvider::new("account".to_string(), "token".to_string(), None, None).unwrap();
+        assert!(!provider.supports_streaming(&ModelConfig::new(SNOWFLAKE_DEFAULT_MODEL)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::custom_requests::CustomMethodSchema;
+use crate::custom_requests::{CustomMethodSchema, LocalInferenceStreamEventDto};
 use agent_client_protocol::{JsonRpcMessage, JsonRpcNotification};
 use schemars::{JsonSchema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
@@ -119,6 +119,15 @@ pub enum StatusMessage {
     Progress { message: String },
 }
 
+/// One chunk of a local inference `stream-test` run, sent as soon as the model produces it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcNotification)]
+#[notification(method = "_goose/unstable/local-inference/models/stream-test/chunk")]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceStreamTestChunkNotification {
+    pub stream_id: String,
+    pub event: LocalInferenceStreamEventDto,
+}
+
 fn notification_schema<T>(generator: &mut SchemaGenerator) -> CustomMethodSchema
 where
     T: Default + JsonRpcMessage + JsonSchema,
@@ -142,7 +151,10 @@ where
 /// notification, define the struct above (with `JsonRpcNotification` +
 /// `Default`) and add one line below.
 pub fn custom_notification_schemas(generator: &mut SchemaGenerator) -> Vec<CustomMethodSchema> {
-    vec![notification_schema::<GooseSessionNotification>(generator)]
+    vec![
+        notification_schema::<GooseSessionNotification>(generator),
+        notification_schema::<LocalInferenceStreamTestChunkNotification>(generator),
+    ]
 }
 
 #[cfg(test)]
@@ -223,4 +235,32 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn stream_test_chunk_serializes_to_expected_wire_shape() {
+        let notification = LocalInferenceStreamTestChunkNotification {
+            stream_id: "stream-1".to_string(),
+            event: LocalInferenceStreamEventDto {
+                text: "one, two".to_string(),
+                elapsed_ms: 120,
+                ..Default::default()
+            },
+        };
+
+        let value = serde_json::to_value(notification).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "streamId": "stream-1",
+                "event": {
+                    "text": "one, two",
+                    "thinking": "",
+                    "toolCalls": [],
+                    "toolCallErrors": [],
+                    "elapsedMs": 120
+                }
+            })
+        );
+    }
 }
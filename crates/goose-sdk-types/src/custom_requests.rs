@@ -1782,6 +1782,27 @@ pub struct ProviderInventoryEntryDto {
     /// Guidance message shown when this provider manages its own model selection externally.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_selection_hint: Option<String>,
+    /// What the provider can do.
+    #[serde(default)]
+    pub capabilities: ProviderCapabilitiesDto,
+}
+
+/// Provider-level capability flags.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCapabilitiesDto {
+    /// Whether goose's tools are passed through to the provider's models.
+    pub tools: bool,
+    /// Whether the provider can stream responses; a given model or configuration may not.
+    pub streaming: bool,
+    /// Whether the provider can produce embeddings.
+    pub embeddings: bool,
+    /// Whether at least one known model accepts image input.
+    pub vision: bool,
+    /// Whether at least one known model supports reasoning/extended thinking.
+    pub reasoning: bool,
+    /// Whether a model missing from the inventory may be entered by hand.
+    pub unlisted_models: bool,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -1817,6 +1838,8 @@ pub enum LocalInferenceSamplingConfig {
         min_p: f32,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         seed: Option<u32>,
+        #[serde(default = "default_min_keep")]
+        min_keep: usize,
     },
     MirostatV2 {
         tau: f32,
@@ -1834,10 +1857,15 @@ impl Default for LocalInferenceSamplingConfig {
             top_p: 0.95,
             min_p: 0.05,
             seed: None,
+            min_keep: default_min_keep(),
         }
     }
 }
 
+fn default_min_keep() -> usize {
+    1
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LocalInferenceModelSettingsDto {
@@ -1847,6 +1875,10 @@ pub struct LocalInferenceModelSettingsDto {
     pub context_size: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_output_tokens: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_eog_token_ids: Vec<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub draft_model: Option<String>,
     #[serde(default)]
@@ -1869,9 +1901,29 @@ pub struct LocalInferenceModelSettingsDto {
     #[serde(default)]
     pub chat_template: LocalInferenceChatTemplate,
     pub enable_thinking: bool,
+    #[serde(default)]
+    pub emulator_json_mode: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emulator_max_code_chars: Option<usize>,
+    #[serde(default)]
+    pub raw_completion: bool,
+    #[serde(default)]
+    pub context_shift: bool,
+    #[serde(default)]
+    pub ignore_memory_context_cap: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub return_logprobs: bool,
+    #[serde(default)]
+    pub top_logprobs: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_update_interval: Option<usize>,
     pub vision_capable: bool,
     pub image_token_estimate: usize,
     pub mmproj_size_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmproj_path: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -1910,6 +1962,8 @@ pub struct LocalInferenceDownloadProgressDto {
     pub eta_seconds: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_size_bytes: Option<u64>,
     pub task_exited: bool,
 }
 
@@ -1950,6 +2004,10 @@ pub struct LocalInferenceHfModelVariantDto {
     pub supported: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unsupported_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmproj_filename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_size_bytes: Option<u64>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
@@ -2008,6 +2066,64 @@ pub struct LocalInferenceModelDownloadResponse {
     pub model_id: String,
 }
 
+/// Swap a downloaded llama.cpp model to another quantization from the same repo. The
+/// new variant downloads under the returned `model_id`; once it completes, the registry
+/// entry takes the new id, file and size, keeps its settings, and the old file is deleted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/requantize",
+    response = LocalInferenceModelRequantizeResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelRequantizeRequest {
+    pub model_id: String,
+    pub quantization: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelRequantizeResponse {
+    pub model_id: String,
+}
+
+/// Check a `user/repo:quantization` spec before downloading it. Never fails for a bad
+/// spec; the outcome is reported in `status`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/validate-spec",
+    response = LocalInferenceModelValidateSpecResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelValidateSpecRequest {
+    pub spec: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum LocalInferenceSpecValidationStatus {
+    #[default]
+    Valid,
+    InvalidFormat,
+    RepoNotFound,
+    QuantizationNotFound,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelValidateSpecResponse {
+    pub status: LocalInferenceSpecValidationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Quantizations the repo does offer, when the requested one is missing.
+    #[serde(default)]
+    pub available_quantizations: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
 #[request(
     method = "_goose/unstable/local-inference/models/download/progress",
@@ -2055,6 +2171,33 @@ pub struct LocalInferenceModelEvictRequest {
     pub model_id: String,
 }
 
+/// Models currently held in memory, so clients can show which are resident and warn
+/// before an action that would evict one.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/loaded",
+    response = LocalInferenceModelsLoadedResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelsLoadedRequest {}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelsLoadedResponse {
+    pub models: Vec<LocalInferenceLoadedModelDto>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceLoadedModelDto {
+    pub model_id: String,
+    pub backend_id: String,
+    /// Approximate memory footprint, estimated from the model files on disk.
+    pub size_bytes: u64,
+    /// A generation is running on the model.
+    pub in_use: bool,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
 #[request(
     method = "_goose/unstable/local-inference/models/settings/read",
@@ -2071,6 +2214,24 @@ pub struct LocalInferenceModelSettingsReadResponse {
     pub settings: LocalInferenceModelSettingsDto,
 }
 
+/// Settings sized for this machine's memory and the model's family. Nothing is saved;
+/// the client applies them with `settings/update` if the user accepts.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/settings/recommended",
+    response = LocalInferenceModelSettingsRecommendedResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelSettingsRecommendedRequest {
+    pub model_id: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelSettingsRecommendedResponse {
+    pub settings: LocalInferenceModelSettingsDto,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
 #[request(
     method = "_goose/unstable/local-inference/models/settings/update",
@@ -2088,6 +2249,168 @@ pub struct LocalInferenceModelSettingsUpdateResponse {
     pub settings: LocalInferenceModelSettingsDto,
 }
 
+/// Merge a partial settings object into a model's current settings. Top-level fields in
+/// `patch` replace the stored values; fields it omits are left unchanged.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/settings/patch",
+    response = LocalInferenceModelSettingsPatchResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelSettingsPatchRequest {
+    pub model_id: String,
+    pub patch: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelSettingsPatchResponse {
+    pub settings: LocalInferenceModelSettingsDto,
+}
+
+/// Run one generation against a local model and return the raw output alongside what was
+/// parsed from it, for debugging chat templates and tool-call parsing.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/debug-generate",
+    response = LocalInferenceModelDebugGenerateResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelDebugGenerateRequest {
+    pub model_id: String,
+    #[serde(default)]
+    pub system: String,
+    pub messages: Vec<LocalInferenceDebugMessageDto>,
+    #[serde(default)]
+    pub tools: Vec<LocalInferenceDebugToolDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceDebugMessageDto {
+    pub role: LocalInferenceDebugMessageRole,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalInferenceDebugMessageRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceDebugToolDto {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub input_schema: serde_json::Value,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelDebugGenerateResponse {
+    /// Text exactly as the model generated it, before thinking and tool-call parsing.
+    pub raw_text: String,
+    /// Prompt rendered by the chat template, when the backend reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub applied_prompt: Option<String>,
+    pub text: String,
+    pub thinking: String,
+    pub tool_calls: Vec<LocalInferenceDebugToolCallDto>,
+    /// Tool calls the parser recognized but could not turn into a valid request.
+    pub tool_call_errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceDebugToolCallDto {
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+/// Count the prompt tokens a conversation would use with a loaded local model's chat
+/// template, without generating.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/count-tokens",
+    response = LocalInferenceModelCountTokensResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelCountTokensRequest {
+    pub model_id: String,
+    #[serde(default)]
+    pub system: String,
+    pub messages: Vec<LocalInferenceDebugMessageDto>,
+    #[serde(default)]
+    pub tools: Vec<LocalInferenceDebugToolDto>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelCountTokensResponse {
+    pub token_count: usize,
+    /// Context window the request would get after settings and memory limits are applied.
+    pub context_limit: usize,
+}
+
+/// Run a conversation through a local model's streaming path. Each chunk is sent as a
+/// `stream-test/chunk` notification as soon as the model produces it, ending with the usage
+/// chunk; the response arrives once the stream has finished.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
+#[request(
+    method = "_goose/unstable/local-inference/models/stream-test",
+    response = LocalInferenceModelStreamTestResponse
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelStreamTestRequest {
+    /// Chosen by the client and echoed on every chunk notification of this run.
+    pub stream_id: String,
+    pub model_id: String,
+    #[serde(default)]
+    pub system: String,
+    pub messages: Vec<LocalInferenceDebugMessageDto>,
+    #[serde(default)]
+    pub tools: Vec<LocalInferenceDebugToolDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<i32>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceModelStreamTestResponse {
+    /// Number of chunk notifications sent for this run, the usage chunk included.
+    pub chunk_count: usize,
+}
+
+/// One streamed chunk. Message chunks carry content; the terminal chunk carries usage.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceStreamEventDto {
+    pub text: String,
+    pub thinking: String,
+    pub tool_calls: Vec<LocalInferenceDebugToolCallDto>,
+    pub tool_call_errors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<LocalInferenceStreamUsageDto>,
+    /// Milliseconds from the start of the request until the model produced this chunk.
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalInferenceStreamUsageDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<i32>,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcRequest)]
 #[request(
     method = "_goose/unstable/local-inference/huggingface/search",
@@ -2098,6 +2421,9 @@ pub struct LocalInferenceHuggingFaceSearchRequest {
     pub query: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
+    /// Number of results to skip, for paging past the first `limit` results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, JsonRpcResponse)]
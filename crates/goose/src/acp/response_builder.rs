@@ -478,6 +478,7 @@ mod tests {
             last_refresh_attempt_at: None,
             last_refresh_error: None,
             model_selection_hint: None,
+            capabilities: Default::default(),
         };
         build_model_state("unused", &inventory)
     }
@@ -86,6 +86,7 @@ mod agent_mentions;
 mod apps;
 mod config;
 mod custom_dispatch;
+pub use custom_dispatch::custom_client;
 mod diagnostics;
 mod dictation;
 mod dispatch;
@@ -764,6 +765,39 @@ fn extend_chain_membership(
     }
 }
 
+/// Feed one piece of streamed message content into the running tool chain buffer.
+/// Returns `false` for a partial tool request: it only previews the complete request
+/// with the same id that follows, so it is neither counted in a chain nor forwarded
+/// to the client.
+fn record_tool_chain_content(
+    content: &MessageContent,
+    message_id: Option<&str>,
+    chain_buffer: &mut Vec<(String, String)>,
+    chain_membership: &mut HashMap<String, Arc<ToolChain>>,
+) -> bool {
+    match content {
+        MessageContent::ToolRequest(tr) if tr.is_partial() => return false,
+        MessageContent::ToolRequest(tr) => {
+            if let Some(msg_id) = message_id {
+                chain_buffer.push((tr.id.clone(), msg_id.to_string()));
+                // Re-register eagerly so the chain is in place by the time the
+                // matching `tool_response` triggers `maybe_summarize_chain`
+                // (sequential tool use interleaves request/response events).
+                extend_chain_membership(chain_buffer, chain_membership);
+            }
+        }
+        MessageContent::ToolResponse(_) => {
+            // Chain-neutral: a response between two requests doesn't break the
+            // run, matching the frontend's `groupContentSections`.
+        }
+        _ => {
+            // Text, thinking, image, etc. end the run.
+            chain_buffer.clear();
+        }
+    }
+    true
+}
+
 fn pending_tool_call_from_request(tool_request: &ToolRequest) -> PendingToolCall {
     let tool_name = match &tool_request.tool_call {
         Ok(tool_call) => tool_call.name.to_string(),
@@ -2719,31 +2753,13 @@ impl GooseAcpAgent {
                             break;
                         }
 
-                        match content_item {
-                            MessageContent::ToolRequest(tr) => {
-                                if let Some(msg_id) = stored_message_id.as_deref() {
-                                    chain_buffer.push((tr.id.clone(), msg_id.to_string()));
-                                    // Re-register eagerly so the chain is in
-                                    // place by the time the matching
-                                    // `tool_response` triggers
-                                    // `maybe_summarize_chain` (sequential
-                                    // tool use interleaves request/response
-                                    // events).
-                                    extend_chain_membership(
-                                        &chain_buffer,
-                                        &mut session.chain_membership,
-                                    );
-                                }
-                            }
-                            MessageContent::ToolResponse(_) => {
-                                // Chain-neutral: a response between two
-                                // requests doesn't break the run, matching
-                                // the frontend's `groupContentSections`.
-                            }
-                            _ => {
-                                // Text, thinking, image, etc. end the run.
-                                chain_buffer.clear();
-                            }
+                        if !record_tool_chain_content(
+                            content_item,
+                            stored_message_id.as_deref(),
+                            &mut chain_buffer,
+                            &mut session.chain_membership,
+                        ) {
+                            continue;
                         }
 
                         if let Err(error) = self
@@ -3495,6 +3511,44 @@ print(\"hello, world\")
         );
     }
 
+    #[test]
+    fn record_tool_chain_content_skips_partial_tool_requests() {
+        let request = |id: &str, tool_meta: Option<serde_json::Value>| {
+            MessageContent::ToolRequest(ToolRequest {
+                id: id.to_string(),
+                tool_call: Ok(CallToolRequestParams::new("developer__shell")),
+                metadata: None,
+                tool_meta,
+            })
+        };
+        let partial_meta = || {
+            Some(serde_json::json!({ crate::conversation::message::TOOL_META_PARTIAL_KEY: true }))
+        };
+        let mut buffer = Vec::new();
+        let mut membership: HashMap<String, Arc<ToolChain>> = HashMap::new();
+
+        for (content, forwarded) in [
+            (request("a", partial_meta()), false),
+            (request("a", None), true),
+            (request("b", partial_meta()), false),
+            (request("b", None), true),
+        ] {
+            assert_eq!(
+                record_tool_chain_content(&content, Some("row_1"), &mut buffer, &mut membership),
+                forwarded,
+            );
+        }
+
+        assert_eq!(
+            buffer,
+            vec![buf_entry("a", "row_1"), buf_entry("b", "row_1")]
+        );
+        assert_eq!(
+            membership.get("a").expect("a registered").ids,
+            vec!["a".to_string(), "b".to_string()],
+        );
+    }
+
     #[test]
     fn with_tool_chain_summary_meta_creates_fresh_when_none() {
         let meta = with_tool_chain_summary_meta(None, "applied dark mode", 4)
@@ -1,7 +1,7 @@
 use super::*;
 use goose_acp_macros::custom_methods;
 
-#[custom_methods]
+#[custom_methods(client = custom_client)]
 impl GooseAcpAgent {
     pub async fn dispatch_custom_request(
         &self,
@@ -859,6 +859,22 @@ impl GooseAcpAgent {
         self.on_local_inference_model_download(req).await
     }
 
+    #[custom_method(LocalInferenceModelRequantizeRequest)]
+    async fn dispatch_local_inference_model_requantize(
+        &self,
+        req: LocalInferenceModelRequantizeRequest,
+    ) -> Result<LocalInferenceModelRequantizeResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_requantize(req).await
+    }
+
+    #[custom_method(LocalInferenceModelValidateSpecRequest)]
+    async fn dispatch_local_inference_model_validate_spec(
+        &self,
+        req: LocalInferenceModelValidateSpecRequest,
+    ) -> Result<LocalInferenceModelValidateSpecResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_validate_spec(req).await
+    }
+
     #[custom_method(LocalInferenceModelDownloadProgressRequest)]
     async fn dispatch_local_inference_model_download_progress(
         &self,
@@ -891,6 +907,14 @@ impl GooseAcpAgent {
         self.on_local_inference_model_evict(req).await
     }
 
+    #[custom_method(LocalInferenceModelsLoadedRequest)]
+    async fn dispatch_local_inference_models_loaded(
+        &self,
+        req: LocalInferenceModelsLoadedRequest,
+    ) -> Result<LocalInferenceModelsLoadedResponse, agent_client_protocol::Error> {
+        self.on_local_inference_models_loaded(req).await
+    }
+
     #[custom_method(LocalInferenceModelSettingsReadRequest)]
     async fn dispatch_local_inference_model_settings_read(
         &self,
@@ -899,6 +923,15 @@ impl GooseAcpAgent {
         self.on_local_inference_model_settings_read(req).await
     }
 
+    #[custom_method(LocalInferenceModelSettingsRecommendedRequest)]
+    async fn dispatch_local_inference_model_settings_recommended(
+        &self,
+        req: LocalInferenceModelSettingsRecommendedRequest,
+    ) -> Result<LocalInferenceModelSettingsRecommendedResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_settings_recommended(req)
+            .await
+    }
+
     #[custom_method(LocalInferenceModelSettingsUpdateRequest)]
     async fn dispatch_local_inference_model_settings_update(
         &self,
@@ -907,6 +940,38 @@ impl GooseAcpAgent {
         self.on_local_inference_model_settings_update(req).await
     }
 
+    #[custom_method(LocalInferenceModelSettingsPatchRequest)]
+    async fn dispatch_local_inference_model_settings_patch(
+        &self,
+        req: LocalInferenceModelSettingsPatchRequest,
+    ) -> Result<LocalInferenceModelSettingsPatchResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_settings_patch(req).await
+    }
+
+    #[custom_method(LocalInferenceModelDebugGenerateRequest)]
+    async fn dispatch_local_inference_model_debug_generate(
+        &self,
+        req: LocalInferenceModelDebugGenerateRequest,
+    ) -> Result<LocalInferenceModelDebugGenerateResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_debug_generate(req).await
+    }
+
+    #[custom_method(LocalInferenceModelCountTokensRequest)]
+    async fn dispatch_local_inference_model_count_tokens(
+        &self,
+        req: LocalInferenceModelCountTokensRequest,
+    ) -> Result<LocalInferenceModelCountTokensResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_count_tokens(req).await
+    }
+
+    #[custom_method(LocalInferenceModelStreamTestRequest)]
+    async fn dispatch_local_inference_model_stream_test(
+        &self,
+        req: LocalInferenceModelStreamTestRequest,
+    ) -> Result<LocalInferenceModelStreamTestResponse, agent_client_protocol::Error> {
+        self.on_local_inference_model_stream_test(req).await
+    }
+
     #[custom_method(LocalInferenceHuggingFaceSearchRequest)]
     async fn dispatch_local_inference_huggingface_search(
         &self,
@@ -41,6 +41,47 @@ impl GooseAcpAgent {
         }
     }
 
+    pub(super) async fn on_local_inference_model_requantize(
+        &self,
+        req: LocalInferenceModelRequantizeRequest,
+    ) -> Result<LocalInferenceModelRequantizeResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            crate::providers::local_inference::configure_huggingface_auth();
+            crate::providers::local_inference::management::requantize_model(
+                &req.model_id,
+                req.quantization,
+            )
+            .await
+            .invalid_params_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
+    pub(super) async fn on_local_inference_model_validate_spec(
+        &self,
+        req: LocalInferenceModelValidateSpecRequest,
+    ) -> Result<LocalInferenceModelValidateSpecResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            crate::providers::local_inference::configure_huggingface_auth();
+            crate::providers::local_inference::management::validate_model_spec(&req.spec)
+                .await
+                .internal_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
     pub(super) async fn on_local_inference_model_download_progress(
         &self,
         req: LocalInferenceModelDownloadProgressRequest,
@@ -118,6 +159,19 @@ impl GooseAcpAgent {
         }
     }
 
+    pub(super) async fn on_local_inference_models_loaded(
+        &self,
+        _req: LocalInferenceModelsLoadedRequest,
+    ) -> Result<LocalInferenceModelsLoadedResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            Ok(crate::providers::local_inference::management::list_loaded_models())
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        Err(local_inference_unavailable())
+    }
+
     pub(super) async fn on_local_inference_model_settings_read(
         &self,
         req: LocalInferenceModelSettingsReadRequest,
@@ -136,6 +190,23 @@ impl GooseAcpAgent {
         }
     }
 
+    pub(super) async fn on_local_inference_model_settings_recommended(
+        &self,
+        req: LocalInferenceModelSettingsRecommendedRequest,
+    ) -> Result<LocalInferenceModelSettingsRecommendedResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            crate::providers::local_inference::management::recommended_model_settings(&req.model_id)
+                .invalid_params_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
     pub(super) async fn on_local_inference_model_settings_update(
         &self,
         req: LocalInferenceModelSettingsUpdateRequest,
@@ -157,6 +228,92 @@ impl GooseAcpAgent {
         }
     }
 
+    pub(super) async fn on_local_inference_model_settings_patch(
+        &self,
+        req: LocalInferenceModelSettingsPatchRequest,
+    ) -> Result<LocalInferenceModelSettingsPatchResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            crate::providers::local_inference::management::patch_model_settings(
+                &req.model_id,
+                req.patch,
+            )
+            .invalid_params_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
+    pub(super) async fn on_local_inference_model_debug_generate(
+        &self,
+        req: LocalInferenceModelDebugGenerateRequest,
+    ) -> Result<LocalInferenceModelDebugGenerateResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            crate::providers::local_inference::management::debug_generate(req)
+                .await
+                .invalid_params_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
+    pub(super) async fn on_local_inference_model_count_tokens(
+        &self,
+        req: LocalInferenceModelCountTokensRequest,
+    ) -> Result<LocalInferenceModelCountTokensResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            crate::providers::local_inference::management::count_tokens(req)
+                .await
+                .invalid_params_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
+    pub(super) async fn on_local_inference_model_stream_test(
+        &self,
+        req: LocalInferenceModelStreamTestRequest,
+    ) -> Result<LocalInferenceModelStreamTestResponse, agent_client_protocol::Error> {
+        #[cfg(feature = "local-inference")]
+        {
+            // Chunks go out as notifications while the model generates; once the client is
+            // gone they fail to send and the run stops.
+            let cx = self.client_cx.get().cloned();
+            let stream_id = req.stream_id.clone();
+            crate::providers::local_inference::management::stream_test(req, |event| {
+                cx.as_ref().is_some_and(|cx| {
+                    cx.send_notification(LocalInferenceStreamTestChunkNotification {
+                        stream_id: stream_id.clone(),
+                        event,
+                    })
+                    .is_ok()
+                })
+            })
+            .await
+            .invalid_params_err()
+        }
+
+        #[cfg(not(feature = "local-inference"))]
+        {
+            let _ = req;
+            Err(local_inference_unavailable())
+        }
+    }
+
     pub(super) async fn on_local_inference_huggingface_search(
         &self,
         req: LocalInferenceHuggingFaceSearchRequest,
@@ -165,7 +322,7 @@ impl GooseAcpAgent {
         {
             crate::providers::local_inference::configure_huggingface_auth();
             crate::providers::local_inference::management::search_huggingface_models(
-                req.query, req.limit,
+                req.query, req.limit, req.offset,
             )
             .await
             .internal_err()
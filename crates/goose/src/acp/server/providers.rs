@@ -69,6 +69,20 @@ fn inventory_entry_to_dto(entry: ProviderInventoryEntry) -> ProviderInventoryEnt
         last_refresh_error: entry.last_refresh_error,
         stale,
         model_selection_hint: entry.model_selection_hint,
+        capabilities: provider_capabilities_to_dto(entry.capabilities),
+    }
+}
+
+fn provider_capabilities_to_dto(
+    capabilities: crate::providers::base::ProviderCapabilities,
+) -> ProviderCapabilitiesDto {
+    ProviderCapabilitiesDto {
+        tools: capabilities.tools,
+        streaming: capabilities.streaming,
+        embeddings: capabilities.embeddings,
+        vision: capabilities.vision,
+        reasoning: capabilities.reasoning,
+        unlisted_models: capabilities.unlisted_models,
     }
 }
 
@@ -2095,11 +2095,14 @@ impl Agent {
                             }
 
                             if let Some(response) = response {
+                                // Notifications and partial tool requests are for display only;
+                                // the complete request with the same id is the one to run.
                                 if !response.content.is_empty()
-                                    && response
-                                    .content
-                                    .iter()
-                                    .all(|content| matches!(content, MessageContent::SystemNotification(_)))
+                                    && response.content.iter().all(|content| match content {
+                                        MessageContent::SystemNotification(_) => true,
+                                        MessageContent::ToolRequest(request) => request.is_partial(),
+                                        _ => false,
+                                    })
                                 {
                                     yield AgentEvent::Message(response);
                                     tokio::task::yield_now().await;
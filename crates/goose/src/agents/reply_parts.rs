@@ -16,7 +16,7 @@ use crate::conversation::message::{Message, MessageContent, MessageUsage, ToolRe
 use crate::conversation::{fix_conversation, Conversation};
 #[cfg(test)]
 use crate::providers::base::stream_from_single_message;
-use crate::providers::base::{MessageStream, Provider};
+use crate::providers::base::{with_keepalive, MessageStream, Provider};
 use crate::providers::toolshim::{
     augment_message_with_selected_tool_interpreter, convert_tool_messages_to_text,
     modify_system_prompt_for_tool_json, sanitize_residual_markers,
@@ -26,6 +26,25 @@ use goose_providers::model::ModelConfig;
 use rmcp::model::Tool;
 use tracing::warn;
 
+/// Keepalive interval for providers that only answer once the whole reply is ready.
+const NON_STREAMING_KEEPALIVE_SECS: u64 = 15;
+
+/// How long a response may stay silent before a keepalive. A provider that doesn't stream
+/// is silent for the whole turn, so it gets one by default; 0 turns it off.
+fn keepalive_interval(
+    provider: &dyn Provider,
+    model_config: &ModelConfig,
+    configured_secs: Option<u64>,
+) -> Option<std::time::Duration> {
+    let default_secs = if provider.supports_streaming(model_config) {
+        0
+    } else {
+        NON_STREAMING_KEEPALIVE_SECS
+    };
+    let secs = configured_secs.unwrap_or(default_secs);
+    (secs > 0).then(|| std::time::Duration::from_secs(secs))
+}
+
 async fn enhance_model_error(
     error: ProviderError,
     provider: &Arc<dyn Provider>,
@@ -329,7 +348,15 @@ impl Agent {
 
         // If there was an error creating the stream, return a stream that yields that error
         let mut stream = match stream_result {
-            Ok(s) => s,
+            Ok(s) => {
+                let configured_secs = Config::global()
+                    .get_param::<u64>("GOOSE_STREAM_KEEPALIVE_SECS")
+                    .ok();
+                match keepalive_interval(provider.as_ref(), &model_config, configured_secs) {
+                    Some(interval) => with_keepalive(s, interval),
+                    None => s,
+                }
+            }
             Err(e) => {
                 let enhanced_error = enhance_model_error(e, &provider, config.toolshim).await;
                 // Return a stream that immediately yields the error
@@ -706,6 +733,51 @@ mod tests {
         }
     }
 
+    struct NonStreamingProvider;
+
+    #[async_trait]
+    impl Provider for NonStreamingProvider {
+        fn get_name(&self) -> &str {
+            "non-streaming"
+        }
+
+        fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+            false
+        }
+
+        async fn stream(
+            &self,
+            _model_config: &ModelConfig,
+            _system: &str,
+            _messages: &[Message],
+            _tools: &[Tool],
+        ) -> Result<MessageStream, ProviderError> {
+            let message = Message::assistant().with_text("ok");
+            let usage = ProviderUsage::new("non-streaming".to_string(), Usage::default());
+            Ok(stream_from_single_message(message, usage))
+        }
+    }
+
+    #[test]
+    fn keepalive_defaults_on_for_providers_that_do_not_stream() {
+        let model_config = ModelConfig::new("test-model");
+        let default = Some(Duration::from_secs(NON_STREAMING_KEEPALIVE_SECS));
+
+        assert_eq!(
+            keepalive_interval(&NonStreamingProvider, &model_config, None),
+            default
+        );
+        assert_eq!(
+            keepalive_interval(&NonStreamingProvider, &model_config, Some(0)),
+            None
+        );
+        assert_eq!(keepalive_interval(&MockProvider, &model_config, None), None);
+        assert_eq!(
+            keepalive_interval(&MockProvider, &model_config, Some(5)),
+            Some(Duration::from_secs(5))
+        );
+    }
+
     #[derive(Clone)]
     struct CapturingProvider {
         messages: Arc<Mutex<Vec<Message>>>,
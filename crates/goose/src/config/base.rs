@@ -1099,6 +1099,10 @@ impl Config {
 
 config_value!(CLAUDE_CODE_COMMAND, String, "claude");
 config_value!(GEMINI_CLI_COMMAND, String, "gemini");
+config_value!(GEMINI_CLI_MODEL_FLAG, String, "-m");
+config_value!(GEMINI_CLI_RESUME_FLAG, String, "-r");
+config_value!(GEMINI_CLI_PROMPT_FLAG, String, "-p");
+config_value!(GEMINI_CLI_OUTPUT_FORMAT_FLAG, String, "--output-format");
 config_value!(CURSOR_AGENT_COMMAND, String, "cursor-agent");
 config_value!(CODEX_COMMAND, String, "codex");
 config_value!(CODEX_ENABLE_SKILLS, String, "true");
@@ -1178,6 +1182,16 @@ impl Config {
         self.get_param::<bool>("OPENAI_STORE").ok()
     }
 
+    /// Context limits keyed by model name, for OpenAI-compatible models the built-in
+    /// tables don't know. Zero entries are ignored.
+    pub fn get_openai_model_context_limits(&self) -> HashMap<String, usize> {
+        self.get_param::<HashMap<String, usize>>("OPENAI_MODEL_CONTEXT_LIMITS")
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, limit)| *limit > 0)
+            .collect()
+    }
+
     fn legacy_thinking_effort(&self) -> Option<ThinkingEffort> {
         if let Ok(value) = self.get_param::<String>("CLAUDE_THINKING_TYPE") {
             if let Some(effort) = match value.to_lowercase().as_str() {
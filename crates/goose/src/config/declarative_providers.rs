@@ -546,8 +546,10 @@ mod tests {
                 context_limit: 128_000,
                 input_token_cost: None,
                 output_token_cost: None,
+                cache_read_token_cost: None,
                 currency: None,
                 supports_cache_control: None,
+                supports_tools: None,
                 reasoning: false,
             }],
             headers: None,
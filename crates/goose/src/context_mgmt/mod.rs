@@ -644,6 +644,10 @@ mod tests {
                     toolshim_model: None,
                     request_params: None,
                     reasoning: None,
+                    prediction: None,
+                    tool_choice: None,
+                    stop_sequences: Vec::new(),
+                    request_timeout_secs: None,
                 },
                 max_tool_responses: None,
             }
@@ -315,10 +315,11 @@ impl AgentManager {
             token.cancel();
         }
         let mut sessions = self.sessions.write().await;
-        sessions
+        let agent = sessions
             .pop(session_id)
             .ok_or_else(|| anyhow::anyhow!("Session {} not found", session_id))?;
         drop(sessions);
+        self.shutdown_session_provider(&agent).await;
         // Best-effort prune of the per-session creation lock so the
         // HashMap doesn't grow unbounded.  Any caller still holding a
         // clone of the Arc keeps the underlying Mutex alive until it
@@ -334,15 +335,33 @@ impl AgentManager {
             token.cancel();
         }
         let mut sessions = self.sessions.write().await;
-        if sessions.pop(session_id).is_none() {
+        let Some(agent) = sessions.pop(session_id) else {
             return Ok(());
-        }
+        };
         drop(sessions);
+        self.shutdown_session_provider(&agent).await;
         self.prune_creation_lock(session_id).await;
         info!("Removed session {}", session_id);
         Ok(())
     }
 
+    /// The default provider is shared by every session that has none of its own, so
+    /// only a session's own provider is shut down with it.
+    async fn shutdown_session_provider(&self, agent: &Agent) {
+        let Ok(provider) = agent.provider().await else {
+            return;
+        };
+        let is_default = self
+            .default_provider
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|default| Arc::ptr_eq(default, &provider));
+        if !is_default {
+            provider.shutdown().await;
+        }
+    }
+
     pub async fn has_session(&self, session_id: &str) -> bool {
         self.sessions.read().await.contains(session_id)
     }
@@ -55,6 +55,11 @@ fn materialize_model_config_inner(
         model = model.with_toolshim_model(get_goose_toolshim_model(config)?);
     }
 
+    if provider_name == goose_providers::openai::OPEN_AI_PROVIDER_NAME {
+        let limits = config.get_openai_model_context_limits();
+        model = model.with_default_context_limit(limits.get(&model.model_name).copied());
+    }
+
     model = model
         .with_default_context_limit(config.get_goose_context_limit()?)
         .with_default_max_tokens(config.get_goose_max_tokens()?);
@@ -105,7 +110,8 @@ pub async fn get_fast_model(
 
 /// Run a completion for a lightweight "fast" task (session naming, compaction,
 /// summarization) using the provider's fast model, falling back to the supplied
-/// main `model_config` if the fast model errors.
+/// main `model_config` if the fast model errors. Session description requests to
+/// providers that derive them locally never reach a model.
 pub async fn complete_fast(
     provider: &dyn Provider,
     model_config: &ModelConfig,
@@ -119,6 +125,15 @@ pub async fn complete_fast(
         .map_err(|e| ProviderError::ExecutionError(e.to_string()))?
         .with_thinking_effort(ThinkingEffort::Off);
 
+    if provider.derives_session_descriptions_locally()
+        && crate::providers::cli_common::is_session_description_request(system)
+    {
+        return crate::providers::cli_common::generate_simple_session_description(
+            &fast_model_config.model_name,
+            messages,
+        );
+    }
+
     match crate::session_context::with_session_id(
         Some(session_id.to_string()),
         provider.complete(&fast_model_config, system, messages, tools),
@@ -179,6 +194,10 @@ fn base_model_config_from_user_config(model_name: &str) -> Result<ModelConfig> {
         toolshim_model: get_goose_toolshim_model(config)?,
         request_params: None,
         reasoning: None,
+        prediction: None,
+        tool_choice: None,
+        stop_sequences: Vec::new(),
+        request_timeout_secs: None,
     };
     model.normalize_effort_suffix();
     Ok(model)
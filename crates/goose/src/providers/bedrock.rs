@@ -728,6 +728,11 @@ impl Provider for BedrockProvider {
         &self.name
     }
 
+    /// `ConverseStream` unless the blocking-`Converse` escape hatch is set.
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        !self.streaming_disabled()
+    }
+
     fn retry_config(&self) -> RetryConfig {
         self.retry_config.clone()
     }
@@ -941,6 +946,10 @@ mod tests {
                 toolshim_model: None,
                 request_params: None,
                 reasoning: None,
+                prediction: None,
+                tool_choice: None,
+                stop_sequences: Vec::new(),
+                request_timeout_secs: None,
             },
         )
     }
@@ -18,8 +18,7 @@ use tokio::process::Command;
 use tokio::sync::oneshot;
 
 use super::base::{
-    stream_from_single_message, ConfigKey, MessageStream, PermissionRouting, Provider, ProviderDef,
-    ProviderMetadata,
+    ConfigKey, MessageStream, PermissionRouting, Provider, ProviderDef, ProviderMetadata,
 };
 use super::utils::filter_extensions_from_system_prompt;
 use crate::config::paths::Paths;
@@ -604,6 +603,7 @@ impl goose_providers::base::ProviderDescriptor for ClaudeCodeProvider {
                 true,
             )],
         )
+        .without_tools()
     }
 }
 
@@ -650,6 +650,15 @@ impl Provider for ClaudeCodeProvider {
         true
     }
 
+    fn derives_session_descriptions_locally(&self) -> bool {
+        true
+    }
+
+    /// Claude Code calls goose's extensions itself over MCP; no tool calls come back.
+    fn supports_tools(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         // Uses a separate short-lived process because --system-prompt is a CLI-only
         // flag with no NDJSON equivalent. The persistent process needs it at spawn,
@@ -719,14 +728,6 @@ impl Provider for ClaudeCodeProvider {
         _tools: &[Tool],
     ) -> Result<MessageStream, ProviderError> {
         let session_id = crate::session_context::current_session_id().unwrap_or_default();
-        if super::cli_common::is_session_description_request(system) {
-            let (message, usage) = super::cli_common::generate_simple_session_description(
-                &model_config.model_name,
-                messages,
-            )?;
-            return Ok(stream_from_single_message(message, usage));
-        }
-
         let filtered_system = filter_extensions_from_system_prompt(system);
         let process_arc = Arc::clone(
             self.get_or_init_process(model_config, &filtered_system)
@@ -43,9 +43,73 @@ pub(crate) fn is_session_description_request(system: &str) -> bool {
     system.contains("four words or less") || system.contains("4 words or less")
 }
 
+/// Length and style limits for locally generated session descriptions, which are shown
+/// in the session list without wrapping.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SessionDescriptionBudget {
+    pub max_words: usize,
+    pub max_chars: usize,
+    pub strip_trailing_punctuation: bool,
+}
+
+impl Default for SessionDescriptionBudget {
+    fn default() -> Self {
+        Self {
+            max_words: 4,
+            max_chars: 100,
+            strip_trailing_punctuation: false,
+        }
+    }
+}
+
+impl SessionDescriptionBudget {
+    pub(crate) fn from_config() -> Self {
+        let config = crate::config::Config::global();
+        let defaults = Self::default();
+        Self {
+            max_words: config
+                .get_param::<usize>("GOOSE_SESSION_DESCRIPTION_MAX_WORDS")
+                .unwrap_or(defaults.max_words),
+            max_chars: config
+                .get_param::<usize>("GOOSE_SESSION_DESCRIPTION_MAX_CHARS")
+                .unwrap_or(defaults.max_chars),
+            strip_trailing_punctuation: config
+                .get_param::<bool>("GOOSE_SESSION_DESCRIPTION_STRIP_PUNCTUATION")
+                .unwrap_or(defaults.strip_trailing_punctuation),
+        }
+    }
+
+    fn apply(&self, text: &str) -> String {
+        let mut words: Vec<&str> = text.split_whitespace().take(self.max_words).collect();
+        while words.len() > 1 && words.join(" ").chars().count() > self.max_chars {
+            words.pop();
+        }
+        let mut description = safe_truncate(&words.join(" "), self.max_chars);
+        if self.strip_trailing_punctuation {
+            let trimmed_len = description
+                .trim_end_matches(|c: char| c.is_ascii_punctuation())
+                .len();
+            description.truncate(trimmed_len);
+        }
+        description
+    }
+}
+
 pub(crate) fn generate_simple_session_description(
     model_name: &str,
     messages: &[Message],
+) -> Result<(Message, ProviderUsage), ProviderError> {
+    generate_session_description_with_budget(
+        model_name,
+        messages,
+        &SessionDescriptionBudget::from_config(),
+    )
+}
+
+pub(crate) fn generate_session_description_with_budget(
+    model_name: &str,
+    messages: &[Message],
+    budget: &SessionDescriptionBudget,
 ) -> Result<(Message, ProviderUsage), ProviderError> {
     let description = messages
         .iter()
@@ -74,15 +138,11 @@ pub(crate) fn generate_simple_session_description(
                 .unwrap_or(stripped)
                 .trim();
 
-            let desc: String = stripped
-                .split_whitespace()
-                .take(4)
-                .collect::<Vec<_>>()
-                .join(" ");
+            let desc = budget.apply(stripped);
             if desc.is_empty() {
                 "Simple task".to_string()
             } else {
-                safe_truncate(&desc, 100)
+                desc
             }
         })
         .unwrap_or_else(|| "Simple task".to_string());
@@ -103,3 +163,44 @@ pub(crate) fn generate_simple_session_description(
         ProviderUsage::new(model_name.to_string(), Usage::default()),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(text: &str, budget: &SessionDescriptionBudget) -> String {
+        let messages = vec![Message::user().with_text(text)];
+        let (message, _) =
+            generate_session_description_with_budget("test-model", &messages, budget).unwrap();
+        message.as_concat_text()
+    }
+
+    #[test]
+    fn session_description_truncates_at_word_boundary() {
+        let budget = SessionDescriptionBudget {
+            max_words: 10,
+            max_chars: 20,
+            strip_trailing_punctuation: false,
+        };
+        assert_eq!(
+            describe("Refactor the authentication middleware today", &budget),
+            "Refactor the"
+        );
+    }
+
+    #[test]
+    fn session_description_strips_trailing_punctuation() {
+        let budget = SessionDescriptionBudget {
+            strip_trailing_punctuation: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            describe("Why is CI failing?!", &budget),
+            "Why is CI failing"
+        );
+        assert_eq!(
+            describe("Why is CI failing?!", &SessionDescriptionBudget::default()),
+            "Why is CI failing?!"
+        );
+    }
+}
@@ -637,6 +637,8 @@ impl goose_providers::base::ProviderDescriptor for CodexProvider {
                 ConfigKey::new("CODEX_SKIP_GIT_CHECK", false, false, Some("false"), true),
             ],
         )
+        .without_tools()
+        .without_streaming()
     }
 }
 
@@ -680,6 +682,20 @@ impl Provider for CodexProvider {
         &self.name
     }
 
+    /// The CLI's output is parsed once the process exits.
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
+    fn derives_session_descriptions_locally(&self) -> bool {
+        true
+    }
+
+    /// Codex reaches goose's extensions through its own MCP config and runs them itself.
+    fn supports_tools(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
     async fn stream(
         &self,
         model_config: &ModelConfig,
@@ -688,17 +704,6 @@ impl Provider for CodexProvider {
         tools: &[Tool],
     ) -> Result<MessageStream, ProviderError> {
         let session_id = crate::session_context::current_session_id().unwrap_or_default();
-        if super::cli_common::is_session_description_request(system) {
-            let (message, provider_usage) = super::cli_common::generate_simple_session_description(
-                &model_config.model_name,
-                messages,
-            )?;
-            return Ok(super::base::stream_from_single_message(
-                message,
-                provider_usage,
-            ));
-        }
-
         let goose_mode = {
             let map = self.mode_by_session.read().await;
             map.get(&session_id).copied().unwrap_or_default()
@@ -928,6 +933,30 @@ mod tests {
         assert!(temp_files.is_empty());
     }
 
+    #[test]
+    fn test_reports_no_tool_support() {
+        let provider = CodexProvider {
+            command: PathBuf::from("codex"),
+            name: "codex".to_string(),
+            skip_git_check: false,
+            mcp_config_overrides: Vec::new(),
+            mode_by_session: tokio::sync::RwLock::new(HashMap::new()),
+        };
+        assert!(!provider.supports_tools(&ModelConfig::new(CODEX_DEFAULT_MODEL)));
+    }
+
+    #[test]
+    fn test_reports_no_streaming_support() {
+        let provider = CodexProvider {
+            command: PathBuf::from("codex"),
+            name: "codex".to_string(),
+            skip_git_check: false,
+            mcp_config_overrides: Vec::new(),
+            mode_by_session: tokio::sync::RwLock::new(HashMap::new()),
+        };
+        assert!(!provider.supports_streaming(&ModelConfig::new(CODEX_DEFAULT_MODEL)));
+    }
+
     #[test]
     fn test_parse_response_plain_text() {
         let provider = CodexProvider {
@@ -294,6 +294,8 @@ impl goose_providers::base::ProviderDescriptor for CursorAgentProvider {
                 true,
             )],
         )
+        .without_tools()
+        .without_streaming()
     }
 }
 
@@ -314,6 +316,20 @@ impl Provider for CursorAgentProvider {
         &self.name
     }
 
+    /// The CLI's output is parsed once the process exits.
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
+    fn derives_session_descriptions_locally(&self) -> bool {
+        true
+    }
+
+    /// The agent CLI uses its own tools and never sees the ones goose passes.
+    fn supports_tools(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         Ok(CURSOR_AGENT_KNOWN_MODELS
             .iter()
@@ -328,14 +344,6 @@ impl Provider for CursorAgentProvider {
         messages: &[Message],
         tools: &[Tool],
     ) -> Result<MessageStream, ProviderError> {
-        if super::cli_common::is_session_description_request(system) {
-            let (message, provider_usage) = super::cli_common::generate_simple_session_description(
-                &model_config.model_name,
-                messages,
-            )?;
-            return Ok(stream_from_single_message(message, provider_usage));
-        }
-
         let lines = self
             .execute_command(model_config, system, messages, tools)
             .await?;
@@ -362,3 +370,17 @@ impl Provider for CursorAgentProvider {
         Ok(stream_from_single_message(message, provider_usage))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_agent_does_not_stream() {
+        let provider = CursorAgentProvider {
+            command: PathBuf::from("cursor-agent"),
+            name: CURSOR_AGENT_PROVIDER_NAME.to_string(),
+        };
+        assert!(!provider.supports_streaming(&ModelConfig::new(CURSOR_AGENT_DEFAULT_MODEL)));
+    }
+}
@@ -1,15 +1,16 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::process::Stdio;
-use std::sync::{Arc, OnceLock};
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
-use tokio::process::Command;
+use tokio::process::{Child, Command};
 
-use super::base::{
-    stream_from_single_message, MessageStream, Provider, ProviderDef, ProviderMetadata,
-};
+use super::base::{MessageStream, Provider, ProviderDef, ProviderMetadata};
 use super::cli_common::{error_from_event, extract_usage_tokens};
 use super::utils::filter_extensions_from_system_prompt;
 use crate::config::search_path::SearchPaths;
@@ -22,6 +23,7 @@ use futures::future::BoxFuture;
 use goose_providers::conversation::token_usage::{ProviderUsage, Usage};
 use goose_providers::errors::ProviderError;
 use goose_providers::model::ModelConfig;
+use goose_providers::request_log::{start_log, LoggerHandleExt};
 use rmcp::model::Role;
 use rmcp::model::Tool;
 
@@ -35,13 +37,134 @@ pub const GEMINI_CLI_KNOWN_MODELS: &[&str] = &[
 
 pub const GEMINI_CLI_DOC_URL: &str = "https://ai.google.dev/gemini-api/docs";
 
+const GEMINI_CLI_AUTH_HINT: &str = "run `gemini auth login` to sign in again";
+
+const CLI_VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+const QUOTA_ERROR_PATTERNS: &[&str] = &[
+    "quota exceeded",
+    "exceeded your current quota",
+    "exhausted your daily quota",
+    "resource_exhausted",
+];
+
+const AUTH_ERROR_PATTERNS: &[&str] = &[
+    "unauthenticated",
+    "login required",
+    "not logged in",
+    "please set an auth method",
+    "api key not valid",
+    "invalid authentication credentials",
+];
+
+/// Pull the version number out of `gemini --version` output. Releases print a bare
+/// semver, but wrappers may prefix it with the program name or a `v`.
+fn parse_cli_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()) && token.contains('.'))
+        .map(str::to_string)
+}
+
+/// How errors name the CLI, with its version when known so bug reports carry it.
+fn cli_label(version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("Gemini CLI {version}"),
+        None => "Gemini CLI".to_string(),
+    }
+}
+
+/// Map a failed gemini invocation to a provider error, surfacing auth and quota failures
+/// distinctly so users get an actionable message instead of raw stderr.
+fn classify_command_failure(
+    stderr: &str,
+    exit_code: Option<i32>,
+    version: Option<&str>,
+) -> ProviderError {
+    let stderr = stderr.trim();
+    let lower = stderr.to_lowercase();
+    let label = cli_label(version);
+
+    if QUOTA_ERROR_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return ProviderError::CreditsExhausted {
+            details: format!("{label} quota exhausted: {stderr}"),
+            top_up_url: None,
+        };
+    }
+
+    if AUTH_ERROR_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return ProviderError::Authentication(format!(
+            "{label} is not authenticated ({GEMINI_CLI_AUTH_HINT}): {stderr}"
+        ));
+    }
+
+    let detail = if stderr.is_empty() {
+        format!("exit code {exit_code:?}")
+    } else {
+        format!("exit code {exit_code:?}: {stderr}")
+    };
+    ProviderError::RequestFailed(format!("{label} command failed ({detail})"))
+}
+
+/// Flag names passed to the gemini binary. Forks and wrappers don't always follow the
+/// upstream CLI conventions, so each flag can be overridden through config.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GeminiCliFlags {
+    pub model: String,
+    pub resume: String,
+    pub prompt: String,
+    pub output_format: String,
+}
+
+impl Default for GeminiCliFlags {
+    fn default() -> Self {
+        Self {
+            model: "-m".to_string(),
+            resume: "-r".to_string(),
+            prompt: "-p".to_string(),
+            output_format: "--output-format".to_string(),
+        }
+    }
+}
+
+impl GeminiCliFlags {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            model: config
+                .get_gemini_cli_model_flag()
+                .unwrap_or_default()
+                .into(),
+            resume: config
+                .get_gemini_cli_resume_flag()
+                .unwrap_or_default()
+                .into(),
+            prompt: config
+                .get_gemini_cli_prompt_flag()
+                .unwrap_or_default()
+                .into(),
+            output_format: config
+                .get_gemini_cli_output_format_flag()
+                .unwrap_or_default()
+                .into(),
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize)]
 pub struct GeminiCliProvider {
     command: PathBuf,
+    flags: GeminiCliFlags,
     #[serde(skip)]
     name: String,
     #[serde(skip)]
-    cli_session_id: Arc<OnceLock<String>>,
+    cli_session_id: Arc<Mutex<Option<String>>>,
+    /// The CLI processes of the turns in flight, so `shutdown` can stop them.
+    #[serde(skip)]
+    active_children: ActiveChildren,
+    /// `gemini --version`, queried on the first turn.
+    #[serde(skip)]
+    cli_version: tokio::sync::OnceCell<Option<String>>,
 }
 
 impl GeminiCliProvider {
@@ -54,13 +177,46 @@ impl GeminiCliProvider {
 
         Ok(Self {
             command: resolved_command,
+            flags: GeminiCliFlags::from_config(config),
             name: GEMINI_CLI_PROVIDER_NAME.to_string(),
-            cli_session_id: Arc::new(OnceLock::new()),
+            cli_session_id: Arc::new(Mutex::new(None)),
+            active_children: ActiveChildren::default(),
+            cli_version: tokio::sync::OnceCell::new(),
         })
     }
 
-    fn session_id(&self) -> Option<&str> {
-        self.cli_session_id.get().map(|s| s.as_str())
+    /// Stream-json output varies between CLI releases, so the version goes into request
+    /// logs and errors. `None` when the CLI doesn't report one in time.
+    async fn cli_version(&self) -> Option<String> {
+        self.cli_version
+            .get_or_init(|| async {
+                let mut cmd = Command::new(&self.command);
+                configure_subprocess(&mut cmd);
+                if let Ok(path) = SearchPaths::builder().with_npm().path() {
+                    cmd.env("PATH", path);
+                }
+                cmd.arg("--version")
+                    .stdin(Stdio::null())
+                    .kill_on_drop(true);
+
+                let version = match tokio::time::timeout(CLI_VERSION_TIMEOUT, cmd.output()).await
+                {
+                    Ok(Ok(output)) if output.status.success() => {
+                        parse_cli_version(&String::from_utf8_lossy(&output.stdout))
+                    }
+                    _ => None,
+                };
+                if version.is_none() {
+                    tracing::debug!(command = ?self.command, "Could not determine Gemini CLI version");
+                }
+                version
+            })
+            .await
+            .clone()
+    }
+
+    fn session_id(&self) -> Option<String> {
+        self.cli_session_id.lock().ok().and_then(|sid| sid.clone())
     }
 
     fn last_user_message_text(messages: &[Message]) -> String {
@@ -72,12 +228,58 @@ impl GeminiCliProvider {
             .unwrap_or_default()
     }
 
+    /// Serialize the tool results that arrived after the last assistant turn, in
+    /// the order that turn requested the tools. Results without a matching request
+    /// follow in arrival order.
+    fn pending_tool_results(messages: &[Message]) -> Vec<String> {
+        let Some(last_assistant) = messages.iter().rposition(|m| m.role == Role::Assistant) else {
+            return Vec::new();
+        };
+        let mut responses: Vec<_> = messages[last_assistant + 1..]
+            .iter()
+            .flat_map(|m| m.content.iter())
+            .filter_map(|c| c.as_tool_response())
+            .collect();
+
+        let mut ordered = Vec::with_capacity(responses.len());
+        for content in &messages[last_assistant].content {
+            if let MessageContent::ToolRequest(req) = content {
+                if let Some(pos) = responses.iter().position(|resp| resp.id == req.id) {
+                    ordered.push(responses.remove(pos));
+                }
+            }
+        }
+        ordered.extend(responses);
+
+        ordered
+            .into_iter()
+            .map(|resp| match &resp.tool_result {
+                Ok(result) => {
+                    let text = result
+                        .content
+                        .iter()
+                        .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+                        .collect::<Vec<&str>>()
+                        .join("\n");
+                    format!("[tool_result id={}] {}", resp.id, text)
+                }
+                Err(error) => format!("[tool_error id={}] {}", resp.id, error.message),
+            })
+            .collect()
+    }
+
     /// Build the prompt for the CLI invocation. When resuming a session the CLI
     /// maintains conversation context internally, so only the latest user
-    /// message is needed. On the first turn (no session yet) the system prompt
-    /// is prepended — there is typically only one user message at that point.
+    /// message is needed, preceded by any tool results it has not seen yet. On
+    /// the first turn (no session yet) the system prompt is prepended — there is
+    /// typically only one user message at that point.
     fn build_prompt(&self, system: &str, messages: &[Message]) -> String {
-        let user_text = Self::last_user_message_text(messages);
+        let mut parts = Self::pending_tool_results(messages);
+        let last_text = Self::last_user_message_text(messages);
+        if !last_text.is_empty() {
+            parts.push(last_text);
+        }
+        let user_text = parts.join("\n");
 
         if self.session_id().is_some() {
             user_text
@@ -99,15 +301,15 @@ impl GeminiCliProvider {
             cmd.env("PATH", path);
         }
 
-        cmd.arg("-m").arg(model_name);
+        cmd.arg(&self.flags.model).arg(model_name);
 
         if let Some(sid) = self.session_id() {
-            cmd.arg("-r").arg(sid);
+            cmd.arg(&self.flags.resume).arg(sid);
         }
 
-        cmd.arg("-p")
+        cmd.arg(&self.flags.prompt)
             .arg(prompt)
-            .arg("--output-format")
+            .arg(&self.flags.output_format)
             .arg("stream-json")
             .arg("--yolo");
 
@@ -153,6 +355,75 @@ impl GeminiCliProvider {
     }
 }
 
+static NEXT_TURN_ID: AtomicU64 = AtomicU64::new(0);
+
+/// CLI processes keyed by turn. Turns can overlap (session naming runs alongside the
+/// user's turn), so each keeps its own entry until its process has exited.
+type ActiveChildren = Arc<Mutex<HashMap<u64, TrackedChild>>>;
+
+struct TrackedChild {
+    /// `None` while the turn is waiting on the process, or once `shutdown` took it.
+    child: Option<Child>,
+    /// Tells a turn that is waiting on its process to kill it instead.
+    stop: Arc<tokio::sync::Notify>,
+}
+
+/// A turn's CLI process, parked in the provider so `shutdown` can stop it. Dropping the
+/// handle with its stream kills the process, as owning the child directly would.
+struct ActiveChild {
+    children: ActiveChildren,
+    turn_id: u64,
+}
+
+impl ActiveChild {
+    fn track(children: &ActiveChildren, child: Child) -> Self {
+        let turn_id = NEXT_TURN_ID.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut children) = children.lock() {
+            children.insert(
+                turn_id,
+                TrackedChild {
+                    child: Some(child),
+                    stop: Arc::new(tokio::sync::Notify::new()),
+                },
+            );
+        }
+        Self {
+            children: Arc::clone(children),
+            turn_id,
+        }
+    }
+
+    /// `None` means `shutdown` stopped the process.
+    async fn wait(&self) -> Option<std::io::Result<ExitStatus>> {
+        let (mut child, stop) = {
+            let mut children = self.children.lock().ok()?;
+            let tracked = children.get_mut(&self.turn_id)?;
+            (tracked.child.take()?, Arc::clone(&tracked.stop))
+        };
+        let status = tokio::select! {
+            status = child.wait() => Some(status),
+            _ = stop.notified() => {
+                if let Err(e) = child.kill().await {
+                    tracing::debug!("Failed to stop Gemini CLI process: {e}");
+                }
+                None
+            }
+        };
+        self.forget();
+        status
+    }
+
+    fn forget(&self) -> Option<TrackedChild> {
+        self.children.lock().ok()?.remove(&self.turn_id)
+    }
+}
+
+impl Drop for ActiveChild {
+    fn drop(&mut self) {
+        drop(self.forget());
+    }
+}
+
 impl goose_providers::base::ProviderDescriptor for GeminiCliProvider {
     fn metadata() -> ProviderMetadata {
         ProviderMetadata::new(
@@ -162,14 +433,21 @@ impl goose_providers::base::ProviderDescriptor for GeminiCliProvider {
             GEMINI_CLI_DEFAULT_MODEL,
             GEMINI_CLI_KNOWN_MODELS.to_vec(),
             GEMINI_CLI_DOC_URL,
-            vec![ConfigKey::new(
-                "GEMINI_CLI_COMMAND",
-                true,
-                false,
-                Some("gemini"),
-                true,
-            )],
+            vec![
+                ConfigKey::new("GEMINI_CLI_COMMAND", true, false, Some("gemini"), true),
+                ConfigKey::new("GEMINI_CLI_MODEL_FLAG", false, false, Some("-m"), false),
+                ConfigKey::new("GEMINI_CLI_RESUME_FLAG", false, false, Some("-r"), false),
+                ConfigKey::new("GEMINI_CLI_PROMPT_FLAG", false, false, Some("-p"), false),
+                ConfigKey::new(
+                    "GEMINI_CLI_OUTPUT_FORMAT_FLAG",
+                    false,
+                    false,
+                    Some("--output-format"),
+                    false,
+                ),
+            ],
         )
+        .without_tools()
     }
 }
 
@@ -194,6 +472,48 @@ impl Provider for GeminiCliProvider {
         true
     }
 
+    fn derives_session_descriptions_locally(&self) -> bool {
+        true
+    }
+
+    /// The CLI runs its own built-in tools; goose's tools are never passed through to it.
+    fn supports_tools(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
+    async fn health_check(&self, _model_config: &ModelConfig) -> Result<(), ProviderError> {
+        SearchPaths::builder()
+            .with_npm()
+            .resolve(&self.command)
+            .map(|_| ())
+            .map_err(|e| ProviderError::ExecutionError(e.to_string()))
+    }
+
+    async fn shutdown(&self) {
+        // Parked processes are killed here; turns waiting on theirs are told to kill them.
+        let parked: Vec<Child> = match self.active_children.lock() {
+            Ok(mut children) => children
+                .values_mut()
+                .filter_map(|tracked| {
+                    let child = tracked.child.take();
+                    if child.is_none() {
+                        tracked.stop.notify_one();
+                    }
+                    child
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        for mut child in parked {
+            if let Err(e) = child.kill().await {
+                tracing::debug!("Failed to stop Gemini CLI process: {e}");
+            }
+        }
+        if let Ok(mut session_id) = self.cli_session_id.lock() {
+            *session_id = None;
+        }
+    }
+
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         Ok(GEMINI_CLI_KNOWN_MODELS
             .iter()
@@ -208,13 +528,14 @@ impl Provider for GeminiCliProvider {
         messages: &[Message],
         _tools: &[Tool],
     ) -> Result<MessageStream, ProviderError> {
-        if super::cli_common::is_session_description_request(system) {
-            let (message, provider_usage) = super::cli_common::generate_simple_session_description(
-                &model_config.model_name,
-                messages,
-            )?;
-            return Ok(stream_from_single_message(message, provider_usage));
-        }
+        let cli_version = self.cli_version().await;
+        let payload = serde_json::json!({
+            "command": self.command,
+            "cli_version": cli_version,
+            "model": model_config.model_name,
+            "messages_count": messages.len(),
+        });
+        let mut log = start_log(model_config, &payload)?;
 
         let (mut child, mut reader) =
             self.spawn_command(system, messages, &model_config.model_name)?;
@@ -223,6 +544,7 @@ impl Provider for GeminiCliProvider {
         let message_id = uuid::Uuid::new_v4().to_string();
 
         let stderr = child.stderr.take();
+        let active_child = ActiveChild::track(&self.active_children, child);
         let stderr_drain = tokio::spawn(async move {
             let mut buf = String::new();
             if let Some(mut stderr) = stderr {
@@ -252,7 +574,9 @@ impl Provider for GeminiCliProvider {
                                     if let Some(sid) =
                                         parsed.get("session_id").and_then(|s| s.as_str())
                                     {
-                                        let _ = session_id_lock.set(sid.to_string());
+                                        if let Ok(mut session_id) = session_id_lock.lock() {
+                                            session_id.get_or_insert_with(|| sid.to_string());
+                                        }
                                     }
                                 }
                                 Some("message") => {
@@ -279,8 +603,11 @@ impl Provider for GeminiCliProvider {
                                     break;
                                 }
                                 Some("error") => {
-                                    let _ = child.wait().await;
-                                    Err(error_from_event("Gemini CLI", &parsed))?;
+                                    let _ = active_child.wait().await;
+                                    let error =
+                                        error_from_event(&cli_label(cli_version.as_deref()), &parsed);
+                                    let _ = log.error(&error);
+                                    Err(error)?;
                                 }
                                 _ => {}
                             }
@@ -289,7 +616,7 @@ impl Provider for GeminiCliProvider {
                         }
                     }
                     Err(e) => {
-                        let _ = child.wait().await;
+                        let _ = active_child.wait().await;
                         Err(ProviderError::RequestFailed(format!(
                             "Failed to read streaming output: {e}"
                         )))?;
@@ -298,22 +625,30 @@ impl Provider for GeminiCliProvider {
             }
 
             let stderr_text = stderr_drain.await.unwrap_or_default();
-            let exit_status = child.wait().await.map_err(|e| {
-                ProviderError::RequestFailed(format!("Failed to wait for command: {e}"))
-            })?;
+            let exit_status = active_child
+                .wait()
+                .await
+                .ok_or_else(|| {
+                    ProviderError::RequestFailed("Gemini CLI was stopped before it finished".to_string())
+                })?
+                .map_err(|e| {
+                    ProviderError::RequestFailed(format!("Failed to wait for command: {e}"))
+                })?;
 
             if !exit_status.success() {
-                let stderr_snippet = stderr_text.trim();
-                let detail = if stderr_snippet.is_empty() {
-                    format!("exit code {:?}", exit_status.code())
-                } else {
-                    format!("exit code {:?}: {stderr_snippet}", exit_status.code())
-                };
-                Err(ProviderError::RequestFailed(format!(
-                    "Gemini CLI command failed ({detail})"
-                )))?;
+                let error = classify_command_failure(
+                    &stderr_text,
+                    exit_status.code(),
+                    cli_version.as_deref(),
+                );
+                let _ = log.error(&error);
+                Err(error)?;
             }
 
+            let _ = log.write(
+                &serde_json::json!({ "usage": accumulated_usage }),
+                Some(&accumulated_usage),
+            );
             let provider_usage = ProviderUsage::new(model_name, accumulated_usage);
             yield (None, Some(provider_usage));
         }))
@@ -323,15 +658,24 @@ impl Provider for GeminiCliProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rmcp::model::CallToolRequestParams;
+    use test_case::test_case;
 
     fn make_provider() -> GeminiCliProvider {
         GeminiCliProvider {
             command: PathBuf::from("gemini"),
+            flags: GeminiCliFlags::default(),
             name: "gemini-cli".to_string(),
-            cli_session_id: Arc::new(OnceLock::new()),
+            cli_session_id: Arc::new(Mutex::new(None)),
+            active_children: ActiveChildren::default(),
+            cli_version: tokio::sync::OnceCell::new_with(Some(None)),
         }
     }
 
+    fn set_session_id(provider: &GeminiCliProvider, sid: &str) {
+        *provider.cli_session_id.lock().unwrap() = Some(sid.to_string());
+    }
+
     #[test]
     fn test_build_prompt_first_and_resume() {
         let provider = make_provider();
@@ -345,7 +689,7 @@ mod tests {
         assert!(prompt.contains("You are helpful."));
         assert!(prompt.contains("Hello"));
 
-        let _ = provider.cli_session_id.set("session-123".to_string());
+        set_session_id(&provider, "session-123");
         let messages = vec![
             Message::new(Role::User, 0, vec![MessageContent::text("Hello")]),
             Message::new(Role::Assistant, 0, vec![MessageContent::text("Hi!")]),
@@ -358,4 +702,267 @@ mod tests {
         let prompt = provider.build_prompt("You are helpful.", &messages);
         assert_eq!(prompt, "Follow up question");
     }
+
+    fn text_result(text: &str) -> rmcp::model::CallToolResult {
+        rmcp::model::CallToolResult::success(vec![rmcp::model::Content::text(text)])
+    }
+
+    #[test]
+    fn test_build_prompt_forwards_tool_results_in_request_order() {
+        let provider = make_provider();
+        set_session_id(&provider, "session-123");
+        let messages = vec![
+            Message::user().with_text("List and read"),
+            Message::assistant()
+                .with_tool_request("call_a", Ok(CallToolRequestParams::new("list_files")))
+                .with_tool_request("call_b", Ok(CallToolRequestParams::new("read_file"))),
+            Message::user()
+                .with_tool_response(
+                    "call_b",
+                    Err(rmcp::model::ErrorData::invalid_params("no such file", None)),
+                )
+                .with_tool_response("call_a", Ok(text_result("a.txt\nb.txt"))),
+        ];
+
+        assert_eq!(
+            provider.build_prompt("You are helpful.", &messages),
+            "[tool_result id=call_a] a.txt\nb.txt\n[tool_error id=call_b] no such file"
+        );
+    }
+
+    #[test]
+    fn test_build_prompt_ignores_tool_results_from_earlier_turns() {
+        let provider = make_provider();
+        set_session_id(&provider, "session-123");
+        let messages = vec![
+            Message::assistant()
+                .with_tool_request("call_a", Ok(CallToolRequestParams::new("list_files"))),
+            Message::user().with_tool_response("call_a", Ok(text_result("a.txt"))),
+            Message::assistant().with_text("Found a.txt"),
+            Message::user().with_text("Thanks"),
+        ];
+
+        assert_eq!(provider.build_prompt("", &messages), "Thanks");
+    }
+
+    fn command_args(cmd: &Command) -> Vec<String> {
+        cmd.as_std()
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_command_default_flags() {
+        let provider = make_provider();
+        set_session_id(&provider, "sid-1");
+
+        let cmd = provider.build_command("hi", "gemini-2.5-pro");
+        assert_eq!(
+            command_args(&cmd),
+            [
+                "-m",
+                "gemini-2.5-pro",
+                "-r",
+                "sid-1",
+                "-p",
+                "hi",
+                "--output-format",
+                "stream-json",
+                "--yolo"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_command_overridden_flags() {
+        let mut provider = make_provider();
+        provider.flags = GeminiCliFlags {
+            model: "--model".to_string(),
+            resume: "--resume".to_string(),
+            prompt: "--prompt".to_string(),
+            output_format: "--format".to_string(),
+        };
+
+        let cmd = provider.build_command("hi", "gemini-2.5-pro");
+        assert_eq!(
+            command_args(&cmd),
+            [
+                "--model",
+                "gemini-2.5-pro",
+                "--prompt",
+                "hi",
+                "--format",
+                "stream-json",
+                "--yolo"
+            ]
+        );
+
+        set_session_id(&provider, "sid-1");
+        let args = command_args(&provider.build_command("hi", "gemini-2.5-pro"));
+        assert_eq!(
+            args.get(2..4),
+            Some(&["--resume".to_string(), "sid-1".to_string()][..])
+        );
+        assert!(!args.iter().any(|a| a == "-m" || a == "-r" || a == "-p"));
+    }
+
+    #[tokio::test]
+    async fn test_model_info_reports_no_tool_support() {
+        let provider = make_provider();
+        let model_config = ModelConfig::new(GEMINI_CLI_DEFAULT_MODEL);
+        assert!(!provider.supports_tools(&model_config));
+
+        let info = provider
+            .fetch_model_info(GEMINI_CLI_DEFAULT_MODEL)
+            .await
+            .unwrap();
+        assert_eq!(info.supports_tools, Some(false));
+    }
+
+    #[test_case(
+        "Error: Quota exceeded for quota metric 'Gemini 2.5 Pro Requests' and limit 'Gemini 2.5 Pro Requests per day per user'";
+        "daily quota"
+    )]
+    #[test_case(
+        r#"[API Error: {"error":{"code":429,"status":"RESOURCE_EXHAUSTED"}}]"#;
+        "resource exhausted"
+    )]
+    fn test_classify_command_failure_quota(stderr: &str) {
+        assert!(matches!(
+            classify_command_failure(stderr, Some(1), None),
+            ProviderError::CreditsExhausted { .. }
+        ));
+    }
+
+    #[test_case("Error: Request had invalid authentication credentials. UNAUTHENTICATED"; "unauthenticated")]
+    #[test_case("Login required. Please sign in with Google."; "login required")]
+    #[test_case("Please set an Auth method in your settings.json"; "no auth method")]
+    fn test_classify_command_failure_auth(stderr: &str) {
+        match classify_command_failure(stderr, Some(1), None) {
+            ProviderError::Authentication(msg) => assert!(msg.contains("gemini auth login")),
+            other => panic!("expected authentication error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_classify_command_failure_other() {
+        match classify_command_failure("  unexpected crash\n", Some(2), None) {
+            ProviderError::RequestFailed(msg) => {
+                assert_eq!(
+                    msg,
+                    "Gemini CLI command failed (exit code Some(2): unexpected crash)"
+                )
+            }
+            other => panic!("expected request failure, got {other:?}"),
+        }
+        match classify_command_failure("", None, Some("0.9.0")) {
+            ProviderError::RequestFailed(msg) => {
+                assert_eq!(msg, "Gemini CLI 0.9.0 command failed (exit code None)")
+            }
+            other => panic!("expected request failure, got {other:?}"),
+        }
+    }
+
+    #[test_case("0.9.0\n", Some("0.9.0"); "bare semver")]
+    #[test_case("gemini v0.10.1-nightly.20250801\n", Some("0.10.1-nightly.20250801"); "prefixed")]
+    #[test_case("\n", None; "empty")]
+    #[test_case("Unknown argument: version", None; "unsupported flag")]
+    fn test_parse_cli_version(output: &str, expected: Option<&str>) {
+        assert_eq!(parse_cli_version(output).as_deref(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_requires_resolvable_command() {
+        let model_config = ModelConfig::new(GEMINI_CLI_DEFAULT_MODEL);
+
+        let mut provider = make_provider();
+        provider.command = std::env::temp_dir().join("goose-missing-gemini-cli");
+        assert!(provider.health_check(&model_config).await.is_err());
+
+        provider.command = std::env::current_exe().unwrap();
+        assert!(provider.health_check(&model_config).await.is_ok());
+    }
+
+    #[cfg(target_os = "linux")]
+    fn sleeping_cli_provider(dir: &tempfile::TempDir) -> GeminiCliProvider {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = dir.path().join("gemini");
+        std::fs::write(&script, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut provider = make_provider();
+        provider.command = script;
+        provider
+    }
+
+    #[cfg(target_os = "linux")]
+    fn running_pids(provider: &GeminiCliProvider) -> Vec<u32> {
+        provider
+            .active_children
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|tracked| tracked.child.as_ref().and_then(|child| child.id()))
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_shutdown_reaps_running_cli_and_forgets_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = sleeping_cli_provider(&dir);
+        set_session_id(&provider, "sid-1");
+
+        let model_config = ModelConfig::new(GEMINI_CLI_DEFAULT_MODEL);
+        let messages = vec![Message::user().with_text("hi")];
+        let _stream = provider
+            .stream(&model_config, "You are helpful.", &messages, &[])
+            .await
+            .unwrap();
+        let pids = running_pids(&provider);
+        assert_eq!(pids.len(), 1);
+        assert!(PathBuf::from(format!("/proc/{}", pids[0])).exists());
+
+        provider.shutdown().await;
+
+        assert!(running_pids(&provider).is_empty());
+        assert!(!PathBuf::from(format!("/proc/{}", pids[0])).exists());
+        assert_eq!(provider.session_id(), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_overlapping_turns_keep_each_cli_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = sleeping_cli_provider(&dir);
+
+        let model_config = ModelConfig::new(GEMINI_CLI_DEFAULT_MODEL);
+        let messages = vec![Message::user().with_text("hi")];
+        let main_turn = provider
+            .stream(&model_config, "You are helpful.", &messages, &[])
+            .await
+            .unwrap();
+        let main_pid = running_pids(&provider)[0];
+
+        let naming_turn = provider
+            .stream(&model_config, "Name this session.", &messages, &[])
+            .await
+            .unwrap();
+        let pids = running_pids(&provider);
+        assert_eq!(pids.len(), 2);
+        assert!(pids.contains(&main_pid));
+        for pid in &pids {
+            assert!(PathBuf::from(format!("/proc/{pid}")).exists());
+        }
+
+        drop(naming_turn);
+        assert_eq!(running_pids(&provider), vec![main_pid]);
+
+        provider.shutdown().await;
+        assert!(!PathBuf::from(format!("/proc/{main_pid}")).exists());
+        drop(main_turn);
+        assert!(provider.active_children.lock().unwrap().is_empty());
+    }
 }
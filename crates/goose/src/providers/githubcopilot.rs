@@ -81,6 +81,12 @@ pub const GITHUB_COPILOT_STREAM_MODELS: &[&str] = &[
     "gemini-3.1-pro-preview",
 ];
 
+fn streams_chat_completions(model_name: &str) -> bool {
+    GITHUB_COPILOT_STREAM_MODELS
+        .iter()
+        .any(|prefix| model_name.starts_with(prefix))
+}
+
 const GITHUB_COPILOT_DOC_URL: &str =
     "https://docs.github.com/en/copilot/using-github-copilot/ai-models";
 const DEFAULT_GITHUB_HOST: &str = "github.com";
@@ -439,11 +445,7 @@ impl GithubCopilotProvider {
         tools: &[Tool],
         has_images: bool,
     ) -> Result<MessageStream, ProviderError> {
-        let supports_streaming = GITHUB_COPILOT_STREAM_MODELS
-            .iter()
-            .any(|prefix| model_config.model_name.starts_with(prefix));
-
-        if supports_streaming {
+        if streams_chat_completions(&model_config.model_name) {
             let payload = create_request(
                 model_config,
                 system,
@@ -566,6 +568,12 @@ impl Provider for GithubCopilotProvider {
             .await
     }
 
+    /// Models on `/responses` always stream; on `/chat/completions` only the listed ones do.
+    fn supports_streaming(&self, model_config: &ModelConfig) -> bool {
+        is_openai_responses_model(&model_config.model_name)
+            || streams_chat_completions(&model_config.model_name)
+    }
+
     async fn stream(
         &self,
         model_config: &ModelConfig,
@@ -764,6 +772,25 @@ mod tests {
         assert!(!is_openai_responses_model("gemini-2.5-pro"));
     }
 
+    #[test]
+    fn supports_streaming_follows_the_model_endpoint() {
+        let provider = GithubCopilotProvider {
+            client: Client::new(),
+            cache: DiskCache {
+                cache_path: PathBuf::from("info.json"),
+            },
+            mu: tokio::sync::Mutex::new(RefCell::new(None)),
+            urls: GithubCopilotUrls::new(DEFAULT_GITHUB_HOST, None),
+            client_id: DEFAULT_GITHUB_COPILOT_CLIENT_ID.to_string(),
+            name: GITHUB_COPILOT_PROVIDER_NAME.to_string(),
+            tls_config: None,
+        };
+
+        assert!(provider.supports_streaming(&ModelConfig::new("gpt-5")));
+        assert!(provider.supports_streaming(&ModelConfig::new("gpt-4o")));
+        assert!(!provider.supports_streaming(&ModelConfig::new("claude-sonnet-4")));
+    }
+
     #[test]
     fn detects_images_in_messages() {
         use crate::conversation::message::Message;
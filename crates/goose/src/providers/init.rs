@@ -283,6 +283,7 @@ pub async fn create_with_named_model(
 mod tests {
     use super::*;
     use crate::config::paths::Paths;
+    use crate::providers::base::ProviderCapabilities;
     use goose_providers::model::ModelConfig;
     use std::fs;
 
@@ -302,6 +303,85 @@ mod tests {
             .any(|key| key.name == "HF_TOKEN" && key.secret));
     }
 
+    #[tokio::test]
+    async fn test_provider_capabilities() {
+        let full = ProviderCapabilities {
+            tools: true,
+            streaming: true,
+            embeddings: false,
+            vision: true,
+            reasoning: true,
+            unlisted_models: true,
+        };
+        let cases = [
+            ("openai", full),
+            ("anthropic", full),
+            (
+                "gemini-cli",
+                ProviderCapabilities {
+                    tools: false,
+                    ..full
+                },
+            ),
+            #[cfg(feature = "aws-providers")]
+            (
+                "sagemaker_tgi",
+                ProviderCapabilities {
+                    streaming: false,
+                    vision: false,
+                    reasoning: false,
+                    ..full
+                },
+            ),
+        ];
+
+        for (name, expected) in cases {
+            let entry = get_from_registry(name)
+                .await
+                .unwrap_or_else(|_| panic!("{name} provider should be registered"));
+            assert_eq!(
+                entry.metadata().capabilities,
+                expected,
+                "unexpected capabilities for {name}"
+            );
+        }
+
+        // (name, tools, streaming) for providers whose CLI or API limits them.
+        let limited = [
+            ("claude-code", false, true),
+            ("codex", false, false),
+            ("cursor-agent", false, false),
+            ("snowflake", true, false),
+            ("litellm", true, false),
+        ];
+        for (name, tools, streaming) in limited {
+            let capabilities = get_from_registry(name)
+                .await
+                .unwrap_or_else(|_| panic!("{name} provider should be registered"))
+                .metadata()
+                .capabilities;
+            assert_eq!(
+                (capabilities.tools, capabilities.streaming),
+                (tools, streaming),
+                "unexpected tool or streaming support for {name}"
+            );
+        }
+    }
+
+    #[cfg(feature = "local-inference")]
+    #[tokio::test]
+    async fn test_local_inference_capabilities() {
+        let local = get_from_registry("local")
+            .await
+            .expect("local provider should be registered");
+        let capabilities = local.metadata().capabilities;
+
+        assert!(capabilities.tools);
+        assert!(capabilities.streaming);
+        assert!(capabilities.embeddings);
+        assert!(!capabilities.unlisted_models);
+    }
+
     #[tokio::test]
     async fn test_openai_compatible_providers_config_keys() {
         let providers_list = providers().await;
@@ -479,6 +559,34 @@ mod tests {
         std::env::remove_var("GOOSE_PATH_ROOT");
     }
 
+    #[tokio::test]
+    async fn test_openai_model_context_limits_override_known_models() {
+        let _guard = env_lock::lock_env([
+            (
+                "OPENAI_MODEL_CONTEXT_LIMITS",
+                Some(r#"{"gpt-4o": 64000, "my-finetune": 32768}"#),
+            ),
+            ("GOOSE_CONTEXT_LIMIT", None::<&str>),
+            ("GOOSE_MAX_TOKENS", None::<&str>),
+            ("GOOSE_TEMPERATURE", None::<&str>),
+            ("GOOSE_TOOLSHIM", None::<&str>),
+            ("GOOSE_TOOLSHIM_OLLAMA_MODEL", None::<&str>),
+            ("GOOSE_THINKING_EFFORT", None::<&str>),
+        ]);
+
+        let openai = get_from_registry("openai")
+            .await
+            .expect("openai provider should be registered");
+        let known = openai
+            .normalize_model_config(ModelConfig::new("gpt-4o"))
+            .expect("known model config should normalize");
+        assert_eq!(known.context_limit(), 64_000);
+        let custom = openai
+            .normalize_model_config(ModelConfig::new("my-finetune"))
+            .expect("custom model config should normalize");
+        assert_eq!(custom.context_limit(), 32_768);
+    }
+
     #[tokio::test]
     async fn test_litellm_supports_inventory_refresh() {
         let entry = get_from_registry("litellm")
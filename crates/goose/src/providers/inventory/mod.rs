@@ -6,7 +6,7 @@ pub use resolver::{
     InventoryRegistration, InventoryResolvers,
 };
 
-use super::base::{ConfigKey, ModelInfo, Provider, ProviderType};
+use super::base::{ConfigKey, ModelInfo, Provider, ProviderCapabilities, ProviderType};
 use super::canonical::{map_provider_name, map_to_canonical_model, CanonicalModelRegistry};
 use super::catalog::ProviderSetupCategory;
 use crate::config::declarative_providers::{DeclarativeProviderConfig, ProviderEngine};
@@ -45,6 +45,7 @@ pub struct ProviderInventoryEntry {
     pub last_refresh_attempt_at: Option<DateTime<Utc>>,
     pub last_refresh_error: Option<String>,
     pub model_selection_hint: Option<String>,
+    pub capabilities: ProviderCapabilities,
 }
 
 /// Families whose latest model should be surfaced in the compact picker.
@@ -267,6 +268,7 @@ struct ProviderDescriptor {
     supports_refresh: bool,
     static_models: Vec<ModelInfo>,
     model_selection_hint: Option<String>,
+    capabilities: ProviderCapabilities,
 }
 
 impl ProviderInventoryService {
@@ -317,6 +319,7 @@ impl ProviderInventoryService {
                 .and_then(|snapshot| snapshot.last_refresh_attempt_at),
             last_refresh_error: snapshot.and_then(|snapshot| snapshot.last_refresh_error),
             model_selection_hint: descriptor.model_selection_hint,
+            capabilities: descriptor.capabilities,
         }))
     }
 
@@ -723,6 +726,7 @@ impl ProviderInventoryService {
             supports_refresh: entry.supports_inventory_refresh(),
             static_models: metadata.known_models,
             model_selection_hint: metadata.model_selection_hint,
+            capabilities: metadata.capabilities,
         }))
     }
 
@@ -184,6 +184,7 @@ impl goose_providers::base::ProviderDescriptor for LiteLLMProvider {
                 ConfigKey::new("LITELLM_TIMEOUT", false, false, Some("600"), false),
             ],
         )
+        .without_streaming()
     }
 }
 
@@ -204,6 +205,11 @@ impl Provider for LiteLLMProvider {
         &self.name
     }
 
+    /// Requests go to the proxy without `stream`, so the reply comes back in one piece.
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
     async fn get_context_limit(&self, model_config: &ModelConfig) -> Result<usize, ProviderError> {
         if let Some(limit) = model_config.context_limit {
             return Ok(limit);
@@ -348,3 +354,24 @@ fn parse_custom_headers(headers_str: String) -> HashMap<String, String> {
     }
     headers
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn litellm_does_not_stream() {
+        let provider = LiteLLMProvider {
+            api_client: ApiClient::new_with_tls(
+                "http://localhost:4000".to_string(),
+                AuthMethod::NoAuth,
+                None,
+            )
+            .unwrap(),
+            base_path: "v1/chat/completions".to_string(),
+            name: LITELLM_PROVIDER_NAME.to_string(),
+            cached_model_info: tokio::sync::OnceCell::new(),
+        };
+        assert!(!provider.supports_streaming(&ModelConfig::new(LITELLM_DEFAULT_MODEL)));
+    }
+}
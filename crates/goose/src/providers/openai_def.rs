@@ -7,9 +7,11 @@ use crate::config::declarative_providers::DeclarativeProviderConfig;
 use crate::config::Config;
 use crate::providers::base::{ProviderDef, DEFAULT_PROVIDER_TIMEOUT_SECS};
 use crate::providers::custom_provider_config::ConfigKeyResolver;
-use goose_providers::api_client::{ApiClient, AuthMethod};
+use goose_providers::api_client::{ApiClient, AuthMethod, PoolConfig};
+use goose_providers::images::ImageDetail;
 use goose_providers::openai::{
-    parse_custom_headers, parse_openai_base_url, OpenAiProvider, OpenAiProviderBuilder,
+    openai_model_context_limit, parse_custom_headers, parse_openai_base_url,
+    validate_request_metadata, OpenAiEndUser, OpenAiProvider, OpenAiProviderBuilder,
     OPEN_AI_DEFAULT_BASE_PATH, OPEN_AI_DEFAULT_FAST_MODEL, OPEN_AI_VERSIONLESS_BASE_PATH,
 };
 
@@ -22,7 +24,14 @@ impl ProviderDescriptor for OpenAiProviderDef {
         // init time, but the OpenAI base URL can change at runtime (e.g.
         // switching to an OpenAI-compatible endpoint), which would otherwise
         // leave the cached fast model stale.
-        OpenAiProvider::metadata()
+        let mut metadata = OpenAiProvider::metadata();
+        let overrides = Config::global().get_openai_model_context_limits();
+        for model in &mut metadata.known_models {
+            if let Some(limit) = openai_model_context_limit(&model.name, &overrides) {
+                model.context_limit = limit;
+            }
+        }
+        metadata
     }
 }
 
@@ -104,6 +113,15 @@ pub async fn from_env(
     let timeout_secs: u64 = config
         .get_param("OPENAI_TIMEOUT")
         .unwrap_or(DEFAULT_PROVIDER_TIMEOUT_SECS);
+    let force_chat_completions: bool = config
+        .get_param("OPENAI_FORCE_CHAT_COMPLETIONS")
+        .unwrap_or(false);
+    let partial_tool_calls: bool = config
+        .get_param("OPENAI_PARTIAL_TOOL_CALLS")
+        .unwrap_or(false);
+    let image_detail: Option<ImageDetail> = config.get_param("OPENAI_IMAGE_DETAIL").ok();
+    let end_user = resolve_end_user(config);
+    let metadata = resolve_metadata(config)?;
 
     let auth = match api_key {
         Some(key) if !key.is_empty() => AuthMethod::BearerToken(key),
@@ -117,6 +135,8 @@ pub async fn from_env(
     )?
     .with_request_builder(crate::session_context::session_id_request_builder());
 
+    api_client = apply_pool_config(api_client, config)?;
+
     if !parsed.query_params.is_empty() {
         api_client = api_client.with_query(parsed.query_params);
     }
@@ -145,6 +165,11 @@ pub async fn from_env(
         .project(project)
         .custom_headers(custom_headers)
         .preserve_thinking_context(!is_openai)
+        .force_chat_completions(force_chat_completions)
+        .partial_tool_calls(partial_tool_calls)
+        .image_detail(image_detail)
+        .end_user(end_user)
+        .metadata(metadata)
         .build();
 
     // TODO(jack): replace this
@@ -153,6 +178,70 @@ pub async fn from_env(
     Ok(provider)
 }
 
+/// Only rebuilds the HTTP client when a pool setting is configured.
+fn apply_pool_config(api_client: ApiClient, config: &Config) -> Result<ApiClient> {
+    let pool_config = resolve_pool_config(config);
+    if pool_config == PoolConfig::default() {
+        return Ok(api_client);
+    }
+    api_client.with_pool_config(pool_config)
+}
+
+fn resolve_pool_config(config: &Config) -> PoolConfig {
+    PoolConfig {
+        idle_timeout: config
+            .get_param::<u64>("OPENAI_POOL_IDLE_TIMEOUT")
+            .ok()
+            .map(std::time::Duration::from_secs),
+        max_idle_per_host: config.get_param("OPENAI_POOL_MAX_IDLE_PER_HOST").ok(),
+        http2_prior_knowledge: config
+            .get_param("OPENAI_HTTP2_PRIOR_KNOWLEDGE")
+            .unwrap_or(false),
+    }
+}
+
+/// `OPENAI_METADATA` is a map in the config file, or a JSON object or
+/// `key=value,key=value` list when set through the environment.
+fn resolve_metadata(config: &Config) -> Result<Option<HashMap<String, String>>> {
+    let metadata = match config.get_param::<HashMap<String, String>>("OPENAI_METADATA") {
+        Ok(metadata) => metadata,
+        Err(_) => match config.get_param::<String>("OPENAI_METADATA") {
+            Ok(list) => parse_custom_headers(list),
+            Err(_) => return Ok(None),
+        },
+    };
+    if metadata.is_empty() {
+        return Ok(None);
+    }
+    validate_request_metadata(metadata).map(Some)
+}
+
+/// `OPENAI_USER` pins a fixed identifier; `OPENAI_USER_FROM_SESSION` derives one from
+/// the active session id, hashed so the raw id is never sent.
+fn resolve_end_user(config: &Config) -> Option<OpenAiEndUser> {
+    if let Ok(user) = config.get_param::<String>("OPENAI_USER") {
+        if !user.trim().is_empty() {
+            let user = user.trim().to_string();
+            return Some(OpenAiEndUser::new(move || Some(user.clone())));
+        }
+    }
+    config
+        .get_param::<bool>("OPENAI_USER_FROM_SESSION")
+        .unwrap_or(false)
+        .then(|| {
+            OpenAiEndUser::new(|| {
+                crate::session_context::current_session_id().map(|id| hashed_session_user(&id))
+            })
+        })
+}
+
+fn hashed_session_user(session_id: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(session_id.as_bytes());
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("goose-{}", hex.get(..32).unwrap_or(&hex))
+}
+
 /// Resolve the API key from a declarative provider config.
 ///
 /// Returns `Some(key)` if a key is found, `None` if the key is optional/missing,
@@ -377,6 +466,44 @@ mod tests {
         assert!(r.has_v1);
     }
 
+    #[test]
+    fn apply_pool_config_builds_the_client_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::new_with_file_secrets(
+            dir.path().join("config.yaml"),
+            dir.path().join("secrets.yaml"),
+        )
+        .unwrap();
+        let client = || {
+            ApiClient::new_with_tls(
+                "http://localhost:8080".to_string(),
+                AuthMethod::NoAuth,
+                None,
+            )
+            .unwrap()
+        };
+
+        let unset = apply_pool_config(client(), &config).unwrap();
+        assert_eq!(unset.pool_config(), &PoolConfig::default());
+
+        config.set_param("OPENAI_POOL_IDLE_TIMEOUT", 30).unwrap();
+        config
+            .set_param("OPENAI_POOL_MAX_IDLE_PER_HOST", 8)
+            .unwrap();
+        config
+            .set_param("OPENAI_HTTP2_PRIOR_KNOWLEDGE", true)
+            .unwrap();
+        let configured = apply_pool_config(client(), &config).unwrap();
+        assert_eq!(
+            configured.pool_config(),
+            &PoolConfig {
+                idle_timeout: Some(std::time::Duration::from_secs(30)),
+                max_idle_per_host: Some(8),
+                http2_prior_knowledge: true,
+            }
+        );
+    }
+
     #[test]
     fn parse_base_url_rejects_empty_string() {
         assert!(parse_base_url("").is_err());
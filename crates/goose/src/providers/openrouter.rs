@@ -233,6 +233,10 @@ impl Provider for OpenRouterProvider {
         &self.name
     }
 
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        self.supports_streaming
+    }
+
     /// Fetch supported models from OpenRouter API (only models with tool support)
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         let response = self
@@ -369,9 +373,31 @@ mod tests {
             toolshim_model: None,
             request_params: None,
             reasoning: None,
+            prediction: None,
+            tool_choice: None,
+            stop_sequences: Vec::new(),
+            request_timeout_secs: None,
         }
     }
 
+    #[test]
+    fn supports_streaming_reflects_configuration() {
+        let provider = |supports_streaming| OpenRouterProvider {
+            api_client: ApiClient::new_with_tls(
+                "http://localhost".to_string(),
+                AuthMethod::NoAuth,
+                None,
+            )
+            .unwrap(),
+            supports_streaming,
+            name: OPENROUTER_PROVIDER_NAME.to_string(),
+            configured_parameters: None,
+        };
+        let model_config = ModelConfig::new("anthropic/claude-sonnet-4");
+        assert!(provider(true).supports_streaming(&model_config));
+        assert!(!provider(false).supports_streaming(&model_config));
+    }
+
     #[test]
     fn metadata_includes_openrouter_parameters_config_key() {
         let metadata = OpenRouterProvider::metadata();
@@ -1,5 +1,8 @@
 use super::api_client::TlsConfig;
-use super::base::{ConfigKey, ModelInfo, Provider, ProviderDef, ProviderMetadata, ProviderType};
+use super::base::{
+    ConfigKey, ModelInfo, Provider, ProviderCapabilities, ProviderDef, ProviderMetadata,
+    ProviderType,
+};
 use super::inventory::{InventoryIdentityInput, InventoryRegistration, InventoryResolvers};
 use crate::config::{DeclarativeProviderConfig, ExtensionConfig};
 use anyhow::Result;
@@ -237,8 +240,10 @@ impl ProviderRegistry {
                 context_limit: m.context_limit,
                 input_token_cost: m.input_token_cost,
                 output_token_cost: m.output_token_cost,
+                cache_read_token_cost: m.cache_read_token_cost,
                 currency: m.currency.clone(),
                 supports_cache_control: Some(m.supports_cache_control.unwrap_or(false)),
+                supports_tools: None,
                 reasoning: m.reasoning,
             })
             .collect();
@@ -284,6 +289,12 @@ impl ProviderRegistry {
             }
         }
 
+        let capabilities = ProviderCapabilities {
+            streaming: config
+                .supports_streaming
+                .unwrap_or(base_metadata.capabilities.streaming),
+            ..ProviderCapabilities::from_models(&config.name, &known_models)
+        };
         let custom_metadata = ProviderMetadata {
             name: config.name.clone(),
             display_name: config.display_name.clone(),
@@ -298,6 +309,7 @@ impl ProviderRegistry {
             setup_steps: config.setup_steps.clone(),
             model_selection_hint: None,
             fast_model: config.fast_model.clone(),
+            capabilities,
         };
         let inventory_config_keys = custom_metadata.config_keys.clone();
         let default_inventory_configured = Arc::new(move || {
@@ -295,6 +295,7 @@ impl goose_providers::base::ProviderDescriptor for SageMakerTgiProvider {
                 ConfigKey::new("AWS_PROFILE", false, false, Some("default"), true),
             ],
         )
+        .without_streaming()
     }
 }
 
@@ -315,6 +316,10 @@ impl Provider for SageMakerTgiProvider {
         &self.name
     }
 
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        false
+    }
+
     async fn stream(
         &self,
         model_config: &ModelConfig,
@@ -368,3 +373,22 @@ impl Provider for SageMakerTgiProvider {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sagemaker_tgi_does_not_stream() {
+        let sdk_config = aws_config::SdkConfig::builder()
+            .behavior_version(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new("us-east-1"))
+            .build();
+        let provider = SageMakerTgiProvider {
+            sagemaker_client: SageMakerClient::new(&sdk_config),
+            endpoint_name: "tgi-endpoint".to_string(),
+            name: SAGEMAKER_TGI_PROVIDER_NAME.to_string(),
+        };
+        assert!(!provider.supports_streaming(&ModelConfig::new(SAGEMAKER_TGI_DEFAULT_MODEL)));
+    }
+}
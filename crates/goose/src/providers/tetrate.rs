@@ -1,5 +1,8 @@
 use super::api_client::{ApiClient, AuthMethod};
-use super::base::{ConfigKey, MessageStream, Provider, ProviderDef, ProviderMetadata};
+use super::base::{
+    model_info_for_provider_model, ConfigKey, MessageStream, Provider, ProviderDef,
+    ProviderMetadata,
+};
 use super::openai_compatible::{
     handle_response_openai_compat, handle_status, map_http_error_to_provider_error,
     stream_openai_compat,
@@ -23,17 +26,18 @@ pub const TETRATE_PROVIDER_NAME: &str = "tetrate";
 pub const TETRATE_DOC_URL: &str = "https://router.tetrate.ai";
 pub const TETRATE_BILLING_URL: &str = "https://router.tetrate.ai/billing";
 
-pub const TETRATE_KNOWN_MODELS: &[&str] = &[
-    "claude-opus-4-1",
-    "claude-3-7-sonnet-latest",
-    "claude-sonnet-4-20250514",
-    "gemini-2.5-pro",
-    "gemini-2.0-flash",
-    "gemini-2.0-flash-lite",
-    "gpt-5",
-    "gpt-5-mini",
-    "gpt-5-nano",
-    "gpt-4.1",
+/// Known models as (name, input USD per million tokens, output USD per million tokens).
+pub const TETRATE_KNOWN_MODELS: &[(&str, f64, f64)] = &[
+    ("claude-opus-4-1", 15.0, 75.0),
+    ("claude-3-7-sonnet-latest", 3.0, 15.0),
+    ("claude-sonnet-4-20250514", 3.0, 15.0),
+    ("gemini-2.5-pro", 1.25, 10.0),
+    ("gemini-2.0-flash", 0.1, 0.4),
+    ("gemini-2.0-flash-lite", 0.075, 0.3),
+    ("gpt-5", 1.25, 10.0),
+    ("gpt-5-mini", 0.25, 2.0),
+    ("gpt-5-nano", 0.05, 0.4),
+    ("gpt-4.1", 2.0, 8.0),
 ];
 
 #[derive(serde::Serialize)]
@@ -92,12 +96,19 @@ impl TetrateProvider {
 
 impl goose_providers::base::ProviderDescriptor for TetrateProvider {
     fn metadata() -> ProviderMetadata {
-        ProviderMetadata::new(
+        let models = TETRATE_KNOWN_MODELS
+            .iter()
+            .map(|&(name, input, output)| {
+                model_info_for_provider_model(TETRATE_PROVIDER_NAME, name)
+                    .with_price_per_million(input, output)
+            })
+            .collect();
+        ProviderMetadata::with_models(
             TETRATE_PROVIDER_NAME,
             "Tetrate Agent Router Service",
             "Enterprise router for AI models",
             TETRATE_DEFAULT_MODEL,
-            TETRATE_KNOWN_MODELS.to_vec(),
+            models,
             TETRATE_DOC_URL,
             vec![
                 ConfigKey::new("TETRATE_API_KEY", true, true, None, true),
@@ -130,6 +141,10 @@ impl Provider for TetrateProvider {
         &self.name
     }
 
+    fn supports_streaming(&self, _model_config: &ModelConfig) -> bool {
+        self.supports_streaming
+    }
+
     async fn stream(
         &self,
         model_config: &ModelConfig,
@@ -194,6 +209,10 @@ impl Provider for TetrateProvider {
         stream_openai_compat(response, log)
     }
 
+    async fn health_check(&self, _model_config: &ModelConfig) -> Result<(), ProviderError> {
+        self.fetch_supported_models().await.map(|_| ())
+    }
+
     /// Fetch supported models from Tetrate Agent Router Service API
     async fn fetch_supported_models(&self) -> Result<Vec<String>, ProviderError> {
         let response = self
@@ -225,6 +244,23 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn supports_streaming_reflects_configuration() {
+        let provider = |supports_streaming| TetrateProvider {
+            api_client: ApiClient::new_with_tls(
+                "http://localhost".to_string(),
+                AuthMethod::NoAuth,
+                None,
+            )
+            .unwrap(),
+            supports_streaming,
+            name: TETRATE_PROVIDER_NAME.to_string(),
+        };
+        let model_config = ModelConfig::new("claude-sonnet-4");
+        assert!(provider(true).supports_streaming(&model_config));
+        assert!(!provider(false).supports_streaming(&model_config));
+    }
+
     #[test]
     fn enrich_adds_dashboard_url() {
         let err = ProviderError::CreditsExhausted {
@@ -1,4 +1,5 @@
 use crate::config::paths::Paths;
+use crate::config::Config;
 use anyhow::{anyhow, Result};
 use fs_err::File;
 use goose_providers::errors::{GoogleErrorCode, ProviderError};
@@ -7,8 +8,8 @@ use reqwest::{Response, StatusCode};
 use serde_json::Value;
 use std::error::Error;
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, PoisonError};
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -211,88 +212,187 @@ fn unescape_json_values_in_place(value: &mut Value) {
 }
 
 pub const LOGS_TO_KEEP: usize = 10;
+pub const DEFAULT_LOG_MAX_BYTES: u64 = 50 * 1024 * 1024;
+pub const ROTATED_LOG_PARTS_TO_KEEP: usize = 3;
 
 static INIT_LOGGER: OnceLock<Result<()>> = OnceLock::new();
 
+/// Serializes the renames that shift `llm_request.N.jsonl` files so concurrent requests
+/// finishing at the same time can't clobber each other's logs.
+static ROTATION_LOCK: Mutex<()> = Mutex::new(());
+
 pub fn init_goose_request_log() -> Result<()> {
     INIT_LOGGER
-        .get_or_init(|| Ok(install_logger(RequestLog::new(LOGS_TO_KEEP)?)?))
+        .get_or_init(|| {
+            let max_file_bytes = Config::global()
+                .get_param::<u64>("GOOSE_REQUEST_LOG_MAX_BYTES")
+                .unwrap_or(DEFAULT_LOG_MAX_BYTES);
+            let log = RequestLog::new(LOGS_TO_KEEP)?
+                .with_max_file_size(max_file_bytes, ROTATED_LOG_PARTS_TO_KEEP);
+            Ok(install_logger(log)?)
+        })
         .as_ref()
         .map_err(|e| anyhow::anyhow!("failed to set up logger: {}", e))?;
     Ok(())
 }
 
 pub struct RequestLog {
+    logs_dir: PathBuf,
     logs_to_keep: usize,
+    max_file_bytes: u64,
+    parts_to_keep: usize,
 }
 
 impl RequestLog {
     pub fn new(logs_to_keep: usize) -> Result<Self> {
-        let logs_dir = Paths::in_state_dir("logs");
+        Self::in_dir(Paths::in_state_dir("logs"), logs_to_keep)
+    }
+
+    fn in_dir(logs_dir: PathBuf, logs_to_keep: usize) -> Result<Self> {
         fs_err::create_dir_all(&logs_dir)?;
-        Ok(Self { logs_to_keep })
+        Ok(Self {
+            logs_dir,
+            logs_to_keep,
+            max_file_bytes: u64::MAX,
+            parts_to_keep: 0,
+        })
+    }
+
+    /// Once a request log reaches `max_file_bytes`, its contents move to `<log>.1` (shifting
+    /// older parts up to `<log>.<parts_to_keep>`) and writing continues in a fresh file.
+    pub fn with_max_file_size(mut self, max_file_bytes: u64, parts_to_keep: usize) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self.parts_to_keep = parts_to_keep;
+        self
     }
 }
 
 struct FileLogHandle {
     writer: Option<BufWriter<File>>,
     temp_path: PathBuf,
+    logs_dir: PathBuf,
     logs_to_keep: usize,
+    max_file_bytes: u64,
+    parts_to_keep: usize,
+    bytes_written: u64,
+}
+
+fn open_log_writer(path: &Path) -> std::io::Result<BufWriter<File>> {
+    Ok(BufWriter::new(
+        File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?,
+    ))
+}
+
+fn log_part_path(path: &Path, part: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{part}"));
+    PathBuf::from(name)
+}
+
+fn remove_log_parts(path: &Path, parts_to_keep: usize) {
+    for part in 1..=parts_to_keep {
+        let _ = fs_err::remove_file(log_part_path(path, part));
+    }
+}
+
+/// Moves a log file together with its rotated parts, dropping any stale parts at the target.
+fn move_log(from: &Path, to: &Path, parts_to_keep: usize) -> std::io::Result<()> {
+    for part in 1..=parts_to_keep {
+        let target = log_part_path(to, part);
+        let _ = fs_err::remove_file(&target);
+        let _ = fs_err::rename(log_part_path(from, part), target);
+    }
+    fs_err::rename(from, to)
 }
 
 impl RequestLogger for RequestLog {
     fn start(&self) -> Result<Box<dyn RequestLogHandle>, Box<dyn Error + Send + Sync>> {
-        let logs_dir = Paths::in_state_dir("logs");
-        fs_err::create_dir_all(&logs_dir)?;
+        fs_err::create_dir_all(&self.logs_dir)?;
 
         let request_id = Uuid::new_v4();
         let temp_name = format!("llm_request.{request_id}.jsonl");
-        let temp_path = logs_dir.join(PathBuf::from(temp_name));
-
-        let writer = BufWriter::new(
-            File::options()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(&temp_path)?,
-        );
+        let temp_path = self.logs_dir.join(PathBuf::from(temp_name));
+        let writer = open_log_writer(&temp_path)?;
 
         Ok(Box::new(FileLogHandle {
             writer: Some(writer),
             temp_path,
+            logs_dir: self.logs_dir.clone(),
             logs_to_keep: self.logs_to_keep,
+            max_file_bytes: self.max_file_bytes,
+            parts_to_keep: self.parts_to_keep,
+            bytes_written: 0,
         }))
     }
 }
 
 impl RequestLogHandle for FileLogHandle {
     fn write(&mut self, s: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let line_bytes = s.len() as u64 + 1;
+        if self.bytes_written > 0
+            && self.bytes_written.saturating_add(line_bytes) > self.max_file_bytes
+        {
+            self.rotate()?;
+        }
+
         let writer = self
             .writer
             .as_mut()
             .ok_or_else(|| anyhow!("logger is finished"))?;
         writeln!(writer, "{}", s)?;
+        self.bytes_written += line_bytes;
         Ok(())
     }
 }
 
 impl FileLogHandle {
+    fn rotate(&mut self) -> Result<()> {
+        let mut writer = self
+            .writer
+            .take()
+            .ok_or_else(|| anyhow!("logger is finished"))?;
+        writer.flush()?;
+        drop(writer);
+
+        if self.parts_to_keep == 0 {
+            fs_err::remove_file(&self.temp_path)?;
+        } else {
+            for part in (1..self.parts_to_keep).rev() {
+                let _ = fs_err::rename(
+                    log_part_path(&self.temp_path, part),
+                    log_part_path(&self.temp_path, part + 1),
+                );
+            }
+            fs_err::rename(&self.temp_path, log_part_path(&self.temp_path, 1))?;
+        }
+
+        self.writer = Some(open_log_writer(&self.temp_path)?);
+        self.bytes_written = 0;
+        Ok(())
+    }
+
     fn finish(&mut self) -> Result<()> {
         if let Some(mut writer) = self.writer.take() {
             writer.flush()?;
-            let logs_dir = Paths::in_state_dir("logs");
-            let log_path = |i| logs_dir.join(format!("llm_request.{}.jsonl", i));
+            drop(writer);
+            let log_path = |i| self.logs_dir.join(format!("llm_request.{}.jsonl", i));
 
             if self.logs_to_keep == 0 {
+                remove_log_parts(&self.temp_path, self.parts_to_keep);
                 fs_err::remove_file(&self.temp_path)?;
                 return Ok(());
             }
 
+            let _guard = ROTATION_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
             for i in (0..self.logs_to_keep.saturating_sub(1)).rev() {
-                let _ = fs_err::rename(log_path(i), log_path(i + 1));
+                let _ = move_log(&log_path(i), &log_path(i + 1), self.parts_to_keep);
             }
 
-            fs_err::rename(&self.temp_path, log_path(0))?;
+            move_log(&self.temp_path, &log_path(0), self.parts_to_keep)?;
         }
         Ok(())
     }
@@ -428,6 +528,86 @@ mod tests {
         }
     }
 
+    fn write_request(log: &RequestLog, lines: &[&str]) {
+        let mut handle = log.start().unwrap();
+        for line in lines {
+            handle.write(line).unwrap();
+        }
+    }
+
+    #[test]
+    fn request_log_rotates_when_size_cap_is_reached() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = RequestLog::in_dir(dir.path().to_path_buf(), 2)
+            .unwrap()
+            .with_max_file_size(40, 2);
+        let line = "x".repeat(15);
+
+        write_request(&log, &[line.as_str(); 9]);
+
+        let current = dir.path().join("llm_request.0.jsonl");
+        for path in [
+            current.clone(),
+            log_part_path(&current, 1),
+            log_part_path(&current, 2),
+        ] {
+            let len = fs_err::metadata(&path).unwrap().len();
+            assert!(len <= 40, "{} is {len} bytes", path.display());
+        }
+        assert!(!log_part_path(&current, 3).exists());
+        assert_eq!(fs_err::read_to_string(&current).unwrap().lines().count(), 1);
+
+        write_request(&log, &["small"]);
+
+        let previous = dir.path().join("llm_request.1.jsonl");
+        assert_eq!(fs_err::read_to_string(&current).unwrap(), "small\n");
+        assert!(!log_part_path(&current, 1).exists());
+        assert!(log_part_path(&previous, 1).exists());
+        assert!(log_part_path(&previous, 2).exists());
+
+        let leftovers: Vec<_> = fs_err::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| {
+                !name.starts_with("llm_request.0.") && !name.starts_with("llm_request.1.")
+            })
+            .collect();
+        assert!(leftovers.is_empty(), "unexpected files: {leftovers:?}");
+    }
+
+    #[test]
+    fn request_log_rotation_is_safe_under_concurrent_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = std::sync::Arc::new(
+            RequestLog::in_dir(dir.path().to_path_buf(), 4)
+                .unwrap()
+                .with_max_file_size(64, 1),
+        );
+
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let log = log.clone();
+                std::thread::spawn(move || {
+                    let line = format!("request-{i}-{}", "y".repeat(20));
+                    write_request(&log, &[line.as_str(); 6]);
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        for i in 0..4 {
+            let path = dir.path().join(format!("llm_request.{i}.jsonl"));
+            let contents = fs_err::read_to_string(&path).unwrap();
+            let first = contents.lines().next().unwrap();
+            assert!(contents.lines().all(|line| line == first));
+            assert!(log_part_path(&path, 1).exists());
+        }
+        assert!(!dir.path().join("llm_request.4.jsonl").exists());
+    }
+
     #[test]
     fn test_parse_google_retry_delay() {
         let payload = json!({
@@ -172,6 +172,89 @@ pub(crate) async fn generate_session_name(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::base::MessageStream;
+    use goose_providers::conversation::token_usage::ProviderUsage;
+    use goose_providers::errors::ProviderError;
+    use goose_providers::model::ModelConfig;
+    use std::sync::Mutex;
+    use test_case::test_case;
+
+    struct RecordingProvider {
+        derives_locally: bool,
+        models: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for RecordingProvider {
+        fn get_name(&self) -> &str {
+            "naming-recorder"
+        }
+
+        async fn stream(
+            &self,
+            _model_config: &ModelConfig,
+            _system: &str,
+            _messages: &[Message],
+            _tools: &[rmcp::model::Tool],
+        ) -> Result<MessageStream, ProviderError> {
+            unimplemented!("session naming calls complete")
+        }
+
+        async fn complete(
+            &self,
+            model_config: &ModelConfig,
+            _system: &str,
+            _messages: &[Message],
+            _tools: &[rmcp::model::Tool],
+        ) -> Result<(Message, ProviderUsage), ProviderError> {
+            self.models
+                .lock()
+                .unwrap()
+                .push(model_config.model_name.clone());
+            Ok((
+                Message::assistant().with_text("Generated title"),
+                ProviderUsage::new(model_config.model_name.clone(), Default::default()),
+            ))
+        }
+
+        fn derives_session_descriptions_locally(&self) -> bool {
+            self.derives_locally
+        }
+    }
+
+    #[test_case(false, "Generated title", &["fast-model"]; "model providers use the fast model")]
+    #[test_case(true, "List the project files", &[]; "cli providers skip the model")]
+    #[tokio::test]
+    async fn test_session_name_takes_the_fast_path(
+        derives_locally: bool,
+        expected_name: &str,
+        expected_models: &[&str],
+    ) {
+        let _guard = env_lock::lock_env([
+            ("GOOSE_FAST_MODEL", Some("fast-model")),
+            ("GOOSE_SESSION_DESCRIPTION_MAX_WORDS", None),
+            ("GOOSE_SESSION_DESCRIPTION_MAX_CHARS", None),
+            ("GOOSE_SESSION_DESCRIPTION_STRIP_PUNCTUATION", None),
+        ]);
+        let provider = RecordingProvider {
+            derives_locally,
+            models: Mutex::new(Vec::new()),
+        };
+        let conversation =
+            Conversation::new_unvalidated([Message::user().with_text("List the project files")]);
+
+        let name = generate_session_name(
+            &provider,
+            &ModelConfig::new("main-model"),
+            "session",
+            &conversation,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(name, expected_name);
+        assert_eq!(*provider.models.lock().unwrap(), expected_models);
+    }
 
     #[test]
     fn test_strip_xml_tags() {
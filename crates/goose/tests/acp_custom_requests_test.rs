@@ -10,7 +10,8 @@ use common_tests::fixtures::{
     run_test, send_custom, Connection, PermissionDecision, Session, SessionData,
     TestConnectionConfig,
 };
-use goose::acp::server::AcpProviderFactory;
+use goose::acp::custom_requests::GetToolsRequest;
+use goose::acp::server::{custom_client, AcpProviderFactory};
 use goose::providers::base::{MessageStream, Provider};
 use goose_providers::errors::ProviderError;
 use goose_providers::model::ModelConfig;
@@ -186,6 +187,39 @@ fn test_custom_get_tools() {
     });
 }
 
+#[test]
+#[serial]
+fn test_custom_client_helper_get_tools() {
+    write_acp_global_config(DEFAULT_ACP_TEST_CONFIG);
+    run_test(async move {
+        let openai = OpenAiFixture::new(vec![], Arc::new(EnforceSessionId::default())).await;
+        let config = TestConnectionConfig {
+            builtins: vec!["developer".to_string()],
+            ..Default::default()
+        };
+        let mut conn = AcpServerConnection::new(config, openai).await;
+
+        let SessionData { session, .. } = conn.new_session().await.unwrap();
+        let session_id = session.session_id().0.to_string();
+
+        let response = custom_client::get_tools(
+            conn.cx(),
+            GetToolsRequest {
+                session_id,
+                extension_name: None,
+            },
+        )
+        .await
+        .expect("typed get_tools helper should succeed");
+
+        assert!(
+            !response.tools.is_empty(),
+            "the developer extension should contribute tools"
+        );
+        assert!(response.tools.iter().all(|tool| !tool.name.is_empty()));
+    });
+}
+
 #[test]
 #[serial]
 fn test_custom_get_extensions() {
@@ -524,6 +524,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -696,6 +697,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -876,6 +878,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -1228,6 +1231,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -1499,6 +1503,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -1699,6 +1704,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -1850,6 +1856,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -2154,6 +2161,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -3006,6 +3014,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
@@ -3080,6 +3089,7 @@ mod tests {
                     setup_steps: vec![],
                     model_selection_hint: None,
                     fast_model: None,
+                    capabilities: Default::default(),
                 }
             }
         }
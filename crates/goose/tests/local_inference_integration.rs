@@ -12,11 +12,23 @@
 //!
 //! Run vision tests (requires a vision-capable model like gemma-4):
 //!   TEST_VISION_MODEL="unsloth/gemma-4-E4B-it-GGUF:Q4_K_M" cargo test -p goose --test local_inference_integration test_local_inference_vision -- --ignored
+//!
+//! Run embedding tests (requires a downloaded embedding GGUF like nomic-embed):
+//!   TEST_EMBEDDING_MODEL="nomic-ai/nomic-embed-text-v1.5-GGUF:Q4_K_M" cargo test -p goose --test local_inference_integration test_local_inference_embeddings -- --ignored
 
 use base64::prelude::*;
 use futures::StreamExt;
+use goose::acp::custom_requests::{
+    LocalInferenceDebugMessageDto, LocalInferenceDebugMessageRole, LocalInferenceDebugToolDto,
+    LocalInferenceModelCountTokensRequest, LocalInferenceModelDebugGenerateRequest,
+    LocalInferenceModelStreamTestRequest,
+};
 use goose::conversation::message::Message;
+use goose::providers::base::EmbeddingCapable;
 use goose::providers::create;
+use goose::providers::local_inference::{
+    configure_local_inference, management, LocalInferenceProvider,
+};
 use goose_providers::model::ModelConfig;
 
 const DEFAULT_TEST_MODEL: &str = "bartowski/Llama-3.2-1B-Instruct-GGUF:Q4_K_M";
@@ -96,6 +108,192 @@ async fn test_local_inference_large_prompt() {
     );
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_local_inference_count_tokens_matches_prompt_usage() {
+    let model_config = ModelConfig::new(test_model()).with_max_tokens(Some(8));
+    let provider = create("local", Vec::new())
+        .await
+        .expect("provider creation should succeed");
+
+    let system = "You are a helpful assistant. Be brief.";
+    let messages = vec![Message::user().with_text("Name three colors.")];
+
+    let (_response, usage) = provider
+        .complete(&model_config, system, &messages, &[])
+        .await
+        .expect("completion should succeed");
+    let input_tokens = usage
+        .usage
+        .input_tokens
+        .expect("usage should report input tokens");
+
+    let counted = provider
+        .count_tokens(&model_config, system, &messages, &[])
+        .await
+        .expect("loaded local model should count tokens");
+
+    assert_eq!(counted, input_tokens as usize);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_local_inference_count_tokens_request_reports_prompt_size() {
+    let model_config = ModelConfig::new(test_model()).with_max_tokens(Some(1));
+    let provider = create("local", Vec::new())
+        .await
+        .expect("provider creation should succeed");
+    // Counting requires a loaded model; one short completion loads it.
+    provider
+        .complete(&model_config, "", &[Message::user().with_text("Hi.")], &[])
+        .await
+        .expect("completion should succeed");
+
+    let request = LocalInferenceModelCountTokensRequest {
+        model_id: test_model(),
+        system: "You are a helpful assistant.".to_string(),
+        messages: vec![LocalInferenceDebugMessageDto {
+            role: LocalInferenceDebugMessageRole::User,
+            content: "Name three colors.".to_string(),
+        }],
+        tools: Vec::new(),
+    };
+    let response = management::count_tokens(request)
+        .await
+        .expect("loaded model should count tokens");
+
+    assert!(response.token_count > 0, "prompt should have tokens");
+    assert!(response.context_limit > response.token_count);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_local_inference_debug_generate_returns_raw_and_parsed_output() {
+    configure_local_inference();
+    let request = LocalInferenceModelDebugGenerateRequest {
+        model_id: test_model(),
+        system: "You are a helpful assistant. Use tools when asked.".to_string(),
+        messages: vec![LocalInferenceDebugMessageDto {
+            role: LocalInferenceDebugMessageRole::User,
+            content: "List the files in the current directory.".to_string(),
+        }],
+        tools: vec![LocalInferenceDebugToolDto {
+            name: "shell".to_string(),
+            description: "Run a shell command".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {"command": {"type": "string"}},
+                "required": ["command"],
+            }),
+        }],
+        max_tokens: Some(64),
+    };
+
+    let response = management::debug_generate(request)
+        .await
+        .expect("debug generation should succeed");
+
+    assert!(!response.raw_text.is_empty(), "raw text should be captured");
+    let applied_prompt = response
+        .applied_prompt
+        .expect("llama.cpp should report the applied prompt");
+    assert!(applied_prompt.contains("List the files in the current directory."));
+    assert!(
+        !response.text.is_empty()
+            || !response.thinking.is_empty()
+            || !response.tool_calls.is_empty()
+            || !response.tool_call_errors.is_empty(),
+        "parsed output should not be empty"
+    );
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_local_inference_stream_test_ends_with_usage_event() {
+    configure_local_inference();
+    let request = LocalInferenceModelStreamTestRequest {
+        stream_id: "stream-1".to_string(),
+        model_id: test_model(),
+        system: "You are a helpful assistant.".to_string(),
+        messages: vec![LocalInferenceDebugMessageDto {
+            role: LocalInferenceDebugMessageRole::User,
+            content: "Count from one to ten in words.".to_string(),
+        }],
+        tools: Vec::new(),
+        max_tokens: Some(64),
+    };
+
+    let mut events = Vec::new();
+    let response = management::stream_test(request, |event| {
+        events.push((event, std::time::Instant::now()));
+        true
+    })
+    .await
+    .expect("stream test should succeed");
+
+    assert_eq!(response.chunk_count, events.len());
+    let ((last, last_received), chunks) =
+        events.split_last().expect("stream should produce events");
+    assert!(chunks.len() > 1, "output should arrive in several chunks");
+    assert!(chunks.iter().all(|(event, _)| event.usage.is_none()));
+    assert!(
+        chunks[0].1 < *last_received,
+        "the first chunk should be delivered before generation finishes"
+    );
+    let usage = last.usage.as_ref().expect("last event should carry usage");
+    assert!(usage.output_tokens.unwrap_or(0) > 0);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_local_inference_stream_test_stops_when_chunks_are_refused() {
+    configure_local_inference();
+    let request = LocalInferenceModelStreamTestRequest {
+        stream_id: "stream-2".to_string(),
+        model_id: test_model(),
+        system: "You are a helpful assistant.".to_string(),
+        messages: vec![LocalInferenceDebugMessageDto {
+            role: LocalInferenceDebugMessageRole::User,
+            content: "Count from one to ten in words.".to_string(),
+        }],
+        tools: Vec::new(),
+        max_tokens: Some(64),
+    };
+
+    let response = management::stream_test(request, |_| false)
+        .await
+        .expect("stream test should succeed");
+
+    assert_eq!(response.chunk_count, 1);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_local_inference_embeddings_have_consistent_dimension() {
+    let Ok(model_id) = std::env::var("TEST_EMBEDDING_MODEL") else {
+        eprintln!("Skipping embedding test: TEST_EMBEDDING_MODEL not set.");
+        return;
+    };
+
+    configure_local_inference();
+    let provider = LocalInferenceProvider::from_env()
+        .await
+        .expect("provider creation should succeed");
+
+    let texts = vec![
+        "The quick brown fox jumps over the lazy dog.".to_string(),
+        "Embeddings".to_string(),
+    ];
+    let embeddings = provider
+        .create_embeddings(&model_id, texts)
+        .await
+        .expect("embedding should succeed");
+
+    assert_eq!(embeddings.len(), 2);
+    assert!(!embeddings[0].is_empty());
+    assert_eq!(embeddings[0].len(), embeddings[1].len());
+}
+
 fn vision_test_model() -> Option<String> {
     std::env::var("TEST_VISION_MODEL").ok()
 }
@@ -21,6 +21,7 @@ export type ModelInfo = {
   reasoning?: boolean;
   resolved_model?: string | null;
   supports_cache_control?: boolean | null;
+  supports_tools?: boolean | null;
 };
 
 export type ProviderMetadata = {
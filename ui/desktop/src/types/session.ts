@@ -9,18 +9,22 @@ export type ModelConfig = {
   context_limit?: number | null;
   max_tokens?: number | null;
   model_name: string;
+  prediction?: string | null;
   reasoning?: boolean | null;
   request_params?: Record<string, unknown> | null;
   temperature?: number | null;
+  tool_choice?: 'auto' | 'none' | { function: { name: string } } | null;
   toolshim: boolean;
   toolshim_model?: string | null;
 };
 
 export type Usage = {
+  accepted_prediction_tokens?: number | null;
   cache_read_input_tokens?: number | null;
   cache_write_input_tokens?: number | null;
   input_tokens?: number | null;
   output_tokens?: number | null;
+  rejected_prediction_tokens?: number | null;
   total_tokens?: number | null;
 };
 
@@ -117,6 +117,10 @@ import type {
   LocalInferenceHuggingFaceRepoVariantsResponse_unstable,
   LocalInferenceHuggingFaceSearchRequest_unstable,
   LocalInferenceHuggingFaceSearchResponse_unstable,
+  LocalInferenceModelCountTokensRequest_unstable,
+  LocalInferenceModelCountTokensResponse_unstable,
+  LocalInferenceModelDebugGenerateRequest_unstable,
+  LocalInferenceModelDebugGenerateResponse_unstable,
   LocalInferenceModelDeleteRequest_unstable,
   LocalInferenceModelDownloadCancelRequest_unstable,
   LocalInferenceModelDownloadProgressRequest_unstable,
@@ -124,12 +128,25 @@ import type {
   LocalInferenceModelDownloadRequest_unstable,
   LocalInferenceModelDownloadResponse_unstable,
   LocalInferenceModelEvictRequest_unstable,
+  LocalInferenceModelRequantizeRequest_unstable,
+  LocalInferenceModelRequantizeResponse_unstable,
+  LocalInferenceModelSettingsPatchRequest_unstable,
+  LocalInferenceModelSettingsPatchResponse_unstable,
   LocalInferenceModelSettingsReadRequest_unstable,
   LocalInferenceModelSettingsReadResponse_unstable,
+  LocalInferenceModelSettingsRecommendedRequest_unstable,
+  LocalInferenceModelSettingsRecommendedResponse_unstable,
   LocalInferenceModelSettingsUpdateRequest_unstable,
   LocalInferenceModelSettingsUpdateResponse_unstable,
   LocalInferenceModelsListRequest_unstable,
   LocalInferenceModelsListResponse_unstable,
+  LocalInferenceModelsLoadedRequest_unstable,
+  LocalInferenceModelsLoadedResponse_unstable,
+  LocalInferenceModelStreamTestRequest_unstable,
+  LocalInferenceModelStreamTestResponse_unstable,
+  LocalInferenceModelValidateSpecRequest_unstable,
+  LocalInferenceModelValidateSpecResponse_unstable,
+  LocalInferenceStreamTestChunkNotification_unstable,
   OnboardingImportApplyRequest_unstable,
   OnboardingImportApplyResponse_unstable,
   OnboardingImportScanRequest_unstable,
@@ -247,11 +264,20 @@ import {
   zLocalInferenceBuiltinChatTemplatesListResponse_unstable,
   zLocalInferenceHuggingFaceRepoVariantsResponse_unstable,
   zLocalInferenceHuggingFaceSearchResponse_unstable,
+  zLocalInferenceModelCountTokensResponse_unstable,
+  zLocalInferenceModelDebugGenerateResponse_unstable,
   zLocalInferenceModelDownloadProgressResponse_unstable,
   zLocalInferenceModelDownloadResponse_unstable,
+  zLocalInferenceModelRequantizeResponse_unstable,
+  zLocalInferenceModelSettingsPatchResponse_unstable,
   zLocalInferenceModelSettingsReadResponse_unstable,
+  zLocalInferenceModelSettingsRecommendedResponse_unstable,
   zLocalInferenceModelSettingsUpdateResponse_unstable,
   zLocalInferenceModelsListResponse_unstable,
+  zLocalInferenceModelsLoadedResponse_unstable,
+  zLocalInferenceModelStreamTestResponse_unstable,
+  zLocalInferenceModelValidateSpecResponse_unstable,
+  zLocalInferenceStreamTestChunkNotification_unstable,
   zOnboardingImportApplyResponse_unstable,
   zOnboardingImportScanResponse_unstable,
   zParseRecipeResponse_unstable,
@@ -1363,6 +1389,30 @@ export class GooseExtClient {
     ) as LocalInferenceModelDownloadResponse_unstable;
   }
 
+  async localInferenceModelsRequantize_unstable(
+    params: LocalInferenceModelRequantizeRequest_unstable,
+  ): Promise<LocalInferenceModelRequantizeResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/requantize",
+      params,
+    );
+    return zLocalInferenceModelRequantizeResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelRequantizeResponse_unstable;
+  }
+
+  async localInferenceModelsValidateSpec_unstable(
+    params: LocalInferenceModelValidateSpecRequest_unstable,
+  ): Promise<LocalInferenceModelValidateSpecResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/validate-spec",
+      params,
+    );
+    return zLocalInferenceModelValidateSpecResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelValidateSpecResponse_unstable;
+  }
+
   async localInferenceModelsDownloadProgress_unstable(
     params: LocalInferenceModelDownloadProgressRequest_unstable,
   ): Promise<LocalInferenceModelDownloadProgressResponse_unstable> {
@@ -1402,6 +1452,18 @@ export class GooseExtClient {
     );
   }
 
+  async localInferenceModelsLoaded_unstable(
+    params: LocalInferenceModelsLoadedRequest_unstable,
+  ): Promise<LocalInferenceModelsLoadedResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/loaded",
+      params,
+    );
+    return zLocalInferenceModelsLoadedResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelsLoadedResponse_unstable;
+  }
+
   async localInferenceModelsSettingsRead_unstable(
     params: LocalInferenceModelSettingsReadRequest_unstable,
   ): Promise<LocalInferenceModelSettingsReadResponse_unstable> {
@@ -1414,6 +1476,18 @@ export class GooseExtClient {
     ) as LocalInferenceModelSettingsReadResponse_unstable;
   }
 
+  async localInferenceModelsSettingsRecommended_unstable(
+    params: LocalInferenceModelSettingsRecommendedRequest_unstable,
+  ): Promise<LocalInferenceModelSettingsRecommendedResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/settings/recommended",
+      params,
+    );
+    return zLocalInferenceModelSettingsRecommendedResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelSettingsRecommendedResponse_unstable;
+  }
+
   async localInferenceModelsSettingsUpdate_unstable(
     params: LocalInferenceModelSettingsUpdateRequest_unstable,
   ): Promise<LocalInferenceModelSettingsUpdateResponse_unstable> {
@@ -1426,6 +1500,54 @@ export class GooseExtClient {
     ) as LocalInferenceModelSettingsUpdateResponse_unstable;
   }
 
+  async localInferenceModelsSettingsPatch_unstable(
+    params: LocalInferenceModelSettingsPatchRequest_unstable,
+  ): Promise<LocalInferenceModelSettingsPatchResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/settings/patch",
+      params,
+    );
+    return zLocalInferenceModelSettingsPatchResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelSettingsPatchResponse_unstable;
+  }
+
+  async localInferenceModelsDebugGenerate_unstable(
+    params: LocalInferenceModelDebugGenerateRequest_unstable,
+  ): Promise<LocalInferenceModelDebugGenerateResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/debug-generate",
+      params,
+    );
+    return zLocalInferenceModelDebugGenerateResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelDebugGenerateResponse_unstable;
+  }
+
+  async localInferenceModelsCountTokens_unstable(
+    params: LocalInferenceModelCountTokensRequest_unstable,
+  ): Promise<LocalInferenceModelCountTokensResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/count-tokens",
+      params,
+    );
+    return zLocalInferenceModelCountTokensResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelCountTokensResponse_unstable;
+  }
+
+  async localInferenceModelsStreamTest_unstable(
+    params: LocalInferenceModelStreamTestRequest_unstable,
+  ): Promise<LocalInferenceModelStreamTestResponse_unstable> {
+    const raw = await this.conn.extMethod(
+      "_goose/unstable/local-inference/models/stream-test",
+      params,
+    );
+    return zLocalInferenceModelStreamTestResponse_unstable.parse(
+      raw,
+    ) as LocalInferenceModelStreamTestResponse_unstable;
+  }
+
   async localInferenceHuggingfaceSearch_unstable(
     params: LocalInferenceHuggingFaceSearchRequest_unstable,
   ): Promise<LocalInferenceHuggingFaceSearchResponse_unstable> {
@@ -1467,6 +1589,9 @@ export interface GooseExtNotifications {
   unstable_sessionUpdate?: (
     notification: GooseSessionNotification_unstable,
   ) => Promise<void>;
+  unstable_localInferenceModelsStreamTestChunk?: (
+    notification: LocalInferenceStreamTestChunkNotification_unstable,
+  ) => Promise<void>;
 }
 
 export interface GooseExtAgentRequests {
@@ -1496,6 +1621,13 @@ export function installGooseExtNotificationDispatcher(
           await callbacks.unstable_sessionUpdate?.(parsed);
           return;
         }
+        case "_goose/unstable/local-inference/models/stream-test/chunk": {
+          const parsed = zLocalInferenceStreamTestChunkNotification_unstable.parse(
+            params,
+          ) as LocalInferenceStreamTestChunkNotification_unstable;
+          await callbacks.unstable_localInferenceModelsStreamTestChunk?.(parsed);
+          return;
+        }
         default:
           await callbacks.extNotification?.(method, params);
           return;
@@ -925,6 +925,10 @@ export type ProviderInventoryEntryDto = {
      * Guidance message shown when this provider manages its own model selection externally.
      */
     modelSelectionHint?: string | null;
+    /**
+     * What the provider can do.
+     */
+    capabilities?: ProviderCapabilitiesDto;
 };
 
 export type ProviderSetupCategoryDto = 'agent' | 'model';
@@ -969,6 +973,36 @@ export type ProviderInventoryModelDto = {
     recommended?: boolean;
 };
 
+/**
+ * Provider-level capability flags.
+ */
+export type ProviderCapabilitiesDto = {
+    /**
+     * Whether goose's tools are passed through to the provider's models.
+     */
+    tools: boolean;
+    /**
+     * Whether the provider can stream responses; a given model or configuration may not.
+     */
+    streaming: boolean;
+    /**
+     * Whether the provider can produce embeddings.
+     */
+    embeddings: boolean;
+    /**
+     * Whether at least one known model accepts image input.
+     */
+    vision: boolean;
+    /**
+     * Whether at least one known model supports reasoning/extended thinking.
+     */
+    reasoning: boolean;
+    /**
+     * Whether a model missing from the inventory may be entered by hand.
+     */
+    unlistedModels: boolean;
+};
+
 /**
  * List the raw model identifiers returned by a provider's live supported-models API.
  */
@@ -2358,6 +2392,8 @@ export type LocalInferenceModelSettingsDto = {
     backendId?: string | null;
     contextSize?: number | null;
     maxOutputTokens?: number | null;
+    stopSequences?: Array<string>;
+    extraEogTokenIds?: Array<number>;
     draftModel?: string | null;
     sampling?: LocalInferenceSamplingConfig;
     repeatPenalty: number;
@@ -2372,9 +2408,19 @@ export type LocalInferenceModelSettingsDto = {
     toolCalling?: LocalInferenceToolCallingMode;
     chatTemplate?: LocalInferenceChatTemplate;
     enableThinking: boolean;
+    emulatorJsonMode?: boolean;
+    emulatorMaxCodeChars?: number | null;
+    rawCompletion?: boolean;
+    contextShift?: boolean;
+    ignoreMemoryContextCap?: boolean;
+    pinned?: boolean;
+    returnLogprobs?: boolean;
+    topLogprobs?: number;
+    usageUpdateInterval?: number | null;
     visionCapable: boolean;
     imageTokenEstimate: number;
     mmprojSizeBytes: number;
+    mmprojPath?: string | null;
 };
 
 export type LocalInferenceSamplingConfig = {
@@ -2385,6 +2431,7 @@ export type LocalInferenceSamplingConfig = {
     topP: number;
     minP: number;
     seed?: number | null;
+    minKeep?: number;
     type: 'Temperature';
 } | {
     tau: number;
@@ -2415,6 +2462,42 @@ export type LocalInferenceModelDownloadResponse_unstable = {
     modelId: string;
 };
 
+/**
+ * Swap a downloaded llama.cpp model to another quantization from the same repo. The
+ * new variant downloads under the returned `model_id`; once it completes, the registry
+ * entry takes the new id, file and size, keeps its settings, and the old file is deleted.
+ */
+export type LocalInferenceModelRequantizeRequest_unstable = {
+    modelId: string;
+    quantization: string;
+};
+
+export type LocalInferenceModelRequantizeResponse_unstable = {
+    modelId: string;
+};
+
+/**
+ * Check a `user/repo:quantization` spec before downloading it. Never fails for a bad
+ * spec; the outcome is reported in `status`.
+ */
+export type LocalInferenceModelValidateSpecRequest_unstable = {
+    spec: string;
+};
+
+export type LocalInferenceModelValidateSpecResponse_unstable = {
+    status: LocalInferenceSpecValidationStatus;
+    repoId?: string | null;
+    quantization?: string | null;
+    sizeBytes?: number | null;
+    /**
+     * Quantizations the repo does offer, when the requested one is missing.
+     */
+    availableQuantizations?: Array<string>;
+    message?: string | null;
+};
+
+export type LocalInferenceSpecValidationStatus = 'Valid' | 'InvalidFormat' | 'RepoNotFound' | 'QuantizationNotFound';
+
 export type LocalInferenceModelDownloadProgressRequest_unstable = {
     modelId: string;
 };
@@ -2432,6 +2515,7 @@ export type LocalInferenceDownloadProgressDto = {
     speedBps?: number | null;
     etaSeconds?: number | null;
     error?: string | null;
+    finalSizeBytes?: number | null;
     taskExited: boolean;
 };
 
@@ -2447,6 +2531,31 @@ export type LocalInferenceModelEvictRequest_unstable = {
     modelId: string;
 };
 
+/**
+ * Models currently held in memory, so clients can show which are resident and warn
+ * before an action that would evict one.
+ */
+export type LocalInferenceModelsLoadedRequest_unstable = {
+    [key: string]: unknown;
+};
+
+export type LocalInferenceModelsLoadedResponse_unstable = {
+    models: Array<LocalInferenceLoadedModelDto>;
+};
+
+export type LocalInferenceLoadedModelDto = {
+    modelId: string;
+    backendId: string;
+    /**
+     * Approximate memory footprint, estimated from the model files on disk.
+     */
+    sizeBytes: number;
+    /**
+     * A generation is running on the model.
+     */
+    inUse: boolean;
+};
+
 export type LocalInferenceModelSettingsReadRequest_unstable = {
     modelId: string;
 };
@@ -2455,6 +2564,18 @@ export type LocalInferenceModelSettingsReadResponse_unstable = {
     settings: LocalInferenceModelSettingsDto;
 };
 
+/**
+ * Settings sized for this machine's memory and the model's family. Nothing is saved;
+ * the client applies them with `settings/update` if the user accepts.
+ */
+export type LocalInferenceModelSettingsRecommendedRequest_unstable = {
+    modelId: string;
+};
+
+export type LocalInferenceModelSettingsRecommendedResponse_unstable = {
+    settings: LocalInferenceModelSettingsDto;
+};
+
 export type LocalInferenceModelSettingsUpdateRequest_unstable = {
     modelId: string;
     settings: LocalInferenceModelSettingsDto;
@@ -2464,9 +2585,140 @@ export type LocalInferenceModelSettingsUpdateResponse_unstable = {
     settings: LocalInferenceModelSettingsDto;
 };
 
+/**
+ * Merge a partial settings object into a model's current settings. Top-level fields in
+ * `patch` replace the stored values; fields it omits are left unchanged.
+ */
+export type LocalInferenceModelSettingsPatchRequest_unstable = {
+    modelId: string;
+    patch: {
+        [key: string]: unknown;
+    };
+};
+
+export type LocalInferenceModelSettingsPatchResponse_unstable = {
+    settings: LocalInferenceModelSettingsDto;
+};
+
+/**
+ * Run one generation against a local model and return the raw output alongside what was
+ * parsed from it, for debugging chat templates and tool-call parsing.
+ */
+export type LocalInferenceModelDebugGenerateRequest_unstable = {
+    modelId: string;
+    system?: string;
+    messages: Array<LocalInferenceDebugMessageDto>;
+    tools?: Array<LocalInferenceDebugToolDto>;
+    maxTokens?: number | null;
+};
+
+export type LocalInferenceDebugMessageDto = {
+    role: LocalInferenceDebugMessageRole;
+    content: string;
+};
+
+export type LocalInferenceDebugMessageRole = 'user' | 'assistant';
+
+export type LocalInferenceDebugToolDto = {
+    name: string;
+    description?: string;
+    inputSchema: unknown;
+};
+
+export type LocalInferenceModelDebugGenerateResponse_unstable = {
+    /**
+     * Text exactly as the model generated it, before thinking and tool-call parsing.
+     */
+    rawText: string;
+    /**
+     * Prompt rendered by the chat template, when the backend reports it.
+     */
+    appliedPrompt?: string | null;
+    text: string;
+    thinking: string;
+    toolCalls: Array<LocalInferenceDebugToolCallDto>;
+    /**
+     * Tool calls the parser recognized but could not turn into a valid request.
+     */
+    toolCallErrors: Array<string>;
+};
+
+export type LocalInferenceDebugToolCallDto = {
+    name: string;
+    arguments: unknown;
+};
+
+/**
+ * Count the prompt tokens a conversation would use with a loaded local model's chat
+ * template, without generating.
+ */
+export type LocalInferenceModelCountTokensRequest_unstable = {
+    modelId: string;
+    system?: string;
+    messages: Array<LocalInferenceDebugMessageDto>;
+    tools?: Array<LocalInferenceDebugToolDto>;
+};
+
+export type LocalInferenceModelCountTokensResponse_unstable = {
+    tokenCount: number;
+    /**
+     * Context window the request would get after settings and memory limits are applied.
+     */
+    contextLimit: number;
+};
+
+/**
+ * Run a conversation through a local model's streaming path. Each chunk is sent as a
+ * `stream-test/chunk` notification as soon as the model produces it, ending with the usage
+ * chunk; the response arrives once the stream has finished.
+ */
+export type LocalInferenceModelStreamTestRequest_unstable = {
+    /**
+     * Chosen by the client and echoed on every chunk notification of this run.
+     */
+    streamId: string;
+    modelId: string;
+    system?: string;
+    messages: Array<LocalInferenceDebugMessageDto>;
+    tools?: Array<LocalInferenceDebugToolDto>;
+    maxTokens?: number | null;
+};
+
+export type LocalInferenceModelStreamTestResponse_unstable = {
+    /**
+     * Number of chunk notifications sent for this run, the usage chunk included.
+     */
+    chunkCount: number;
+};
+
+/**
+ * One streamed chunk. Message chunks carry content; the terminal chunk carries usage.
+ */
+export type LocalInferenceStreamEventDto = {
+    text: string;
+    thinking: string;
+    toolCalls: Array<LocalInferenceDebugToolCallDto>;
+    toolCallErrors: Array<string>;
+    usage?: LocalInferenceStreamUsageDto | null;
+    /**
+     * Milliseconds from the start of the request until the model produced this chunk.
+     */
+    elapsedMs: number;
+};
+
+export type LocalInferenceStreamUsageDto = {
+    inputTokens?: number | null;
+    outputTokens?: number | null;
+    totalTokens?: number | null;
+};
+
 export type LocalInferenceHuggingFaceSearchRequest_unstable = {
     query: string;
     limit?: number | null;
+    /**
+     * Number of results to skip, for paging past the first `limit` results.
+     */
+    offset?: number | null;
 };
 
 export type LocalInferenceHuggingFaceSearchResponse_unstable = {
@@ -2504,6 +2756,8 @@ export type LocalInferenceHfModelVariantDto = {
     sharded: boolean;
     supported: boolean;
     unsupportedReason?: string | null;
+    mmprojFilename?: string | null;
+    totalSizeBytes?: number | null;
 };
 
 export type LocalInferenceHuggingFaceRepoVariantsRequest_unstable = {
@@ -2615,6 +2869,14 @@ export type MessageUsageUpdate = {
     usage: MessageUsageData;
 };
 
+/**
+ * One chunk of a local inference `stream-test` run, sent as soon as the model produces it.
+ */
+export type LocalInferenceStreamTestChunkNotification_unstable = {
+    streamId: string;
+    event: LocalInferenceStreamEventDto;
+};
+
 export type RequestRecipeParams_unstable = {
     sessionId: string;
     parameters: Array<RecipeParameterDto>;
@@ -2632,14 +2894,14 @@ export type RecipeParamsAction = 'submit' | 'cancel';
 export type ExtRequest = {
     id: string;
     method: string;
-    params?: AddSessionExtensionRequest_unstable | RemoveSessionExtensionRequest_unstable | GetToolsRequest_unstable | SetToolPermissionsRequest_unstable | GooseToolCallRequest_unstable | ReadResourceRequest_unstable | AppsListRequest_unstable | AppsExportRequest_unstable | AppsImportRequest_unstable | AppsDeleteRequest_unstable | UpdateWorkingDirRequest_unstable | SetSessionSystemPromptRequest_unstable | SteerSessionRequest_unstable | DiagnosticsGetRequest_unstable | ListPromptsRequest_unstable | GetPromptRequest_unstable | SavePromptRequest_unstable | ResetPromptRequest_unstable | DeleteSessionRequest | GetConfigExtensionsRequest_unstable | GetAvailableExtensionsRequest_unstable | AddConfigExtensionRequest_unstable | RemoveConfigExtensionRequest_unstable | SetConfigExtensionEnabledRequest_unstable | GetSessionExtensionsRequest_unstable | ListProvidersRequest_unstable | ProviderSupportedModelsListRequest_unstable | ProviderCatalogListRequest_unstable | ProviderSetupCatalogListRequest_unstable | ProviderCatalogTemplateRequest_unstable | CustomProviderCreateRequest_unstable | CustomProviderReadRequest_unstable | CustomProviderUpdateRequest_unstable | CustomProviderDeleteRequest_unstable | RefreshProviderInventoryRequest_unstable | ProviderConfigReadRequest_unstable | ProviderConfigStatusRequest_unstable | ProviderConfigSaveRequest_unstable | ProviderConfigDeleteRequest_unstable | ProviderConfigAuthenticateRequest_unstable | ProviderSecretsListRequest_unstable | ProviderSecretDeleteRequest_unstable | CanonicalModelInfoRequest_unstable | PreferencesReadRequest_unstable | PreferencesSaveRequest_unstable | PreferencesRemoveRequest_unstable | ConfigReadRequest_unstable | ConfigUpsertRequest_unstable | ConfigRemoveRequest_unstable | ConfigReadAllRequest_unstable | DefaultsReadRequest_unstable | DefaultsSaveRequest_unstable | DefaultsClearRequest_unstable | OnboardingImportScanRequest_unstable | OnboardingImportApplyRequest_unstable | ExportSessionRequest_unstable | ImportSessionRequest_unstable | ShareSessionNostrRequest_unstable | EncodeRecipeRequest_unstable | DecodeRecipeRequest_unstable | ScanRecipeRequest_unstable | ListRecipesRequest_unstable | DeleteRecipeRequest_unstable | ScheduleRecipeRequest_unstable | SetRecipeSlashCommandRequest_unstable | SaveRecipeRequest_unstable | ParseRecipeRequest_unstable | RecipeToYamlRequest_unstable | ListSchedulesRequest_unstable | ListScheduleSessionsRequest_unstable | CreateScheduleRequest_unstable | DeleteScheduleRequest_unstable | PauseScheduleRequest_unstable | UnpauseScheduleRequest_unstable | UpdateScheduleRequest_unstable | RunScheduleNowRequest_unstable | KillRunningJobRequest_unstable | InspectRunningJobRequest_unstable | GetSessionInfoRequest_unstable | TruncateSessionConversationRequest_unstable | UpdateSessionProjectRequest_unstable | RenameSessionRequest_unstable | ArchiveSessionRequest_unstable | UnarchiveSessionRequest_unstable | CreateSourceRequest_unstable | ListSourcesRequest_unstable | ListAgentMentionsRequest_unstable | ListSlashCommandsRequest_unstable | UpdateSourceRequest_unstable | DeleteSourceRequest_unstable | ExportSourceRequest_unstable | ImportSourcesRequest_unstable | DictationTranscribeRequest_unstable | DictationConfigRequest_unstable | DictationSecretSaveRequest_unstable | DictationSecretDeleteRequest_unstable | DictationModelsListRequest_unstable | DictationModelDownloadRequest_unstable | DictationModelDownloadProgressRequest_unstable | DictationModelCancelRequest_unstable | DictationModelDeleteRequest_unstable | DictationModelSelectRequest_unstable | LocalInferenceModelsListRequest_unstable | LocalInferenceModelDownloadRequest_unstable | LocalInferenceModelDownloadProgressRequest_unstable | LocalInferenceModelDownloadCancelRequest_unstable | LocalInferenceModelDeleteRequest_unstable | LocalInferenceModelEvictRequest_unstable | LocalInferenceModelSettingsReadRequest_unstable | LocalInferenceModelSettingsUpdateRequest_unstable | LocalInferenceHuggingFaceSearchRequest_unstable | LocalInferenceHuggingFaceRepoVariantsRequest_unstable | LocalInferenceBuiltinChatTemplatesListRequest_unstable | {
+    params?: AddSessionExtensionRequest_unstable | RemoveSessionExtensionRequest_unstable | GetToolsRequest_unstable | SetToolPermissionsRequest_unstable | GooseToolCallRequest_unstable | ReadResourceRequest_unstable | AppsListRequest_unstable | AppsExportRequest_unstable | AppsImportRequest_unstable | AppsDeleteRequest_unstable | UpdateWorkingDirRequest_unstable | SetSessionSystemPromptRequest_unstable | SteerSessionRequest_unstable | DiagnosticsGetRequest_unstable | ListPromptsRequest_unstable | GetPromptRequest_unstable | SavePromptRequest_unstable | ResetPromptRequest_unstable | DeleteSessionRequest | GetConfigExtensionsRequest_unstable | GetAvailableExtensionsRequest_unstable | AddConfigExtensionRequest_unstable | RemoveConfigExtensionRequest_unstable | SetConfigExtensionEnabledRequest_unstable | GetSessionExtensionsRequest_unstable | ListProvidersRequest_unstable | ProviderSupportedModelsListRequest_unstable | ProviderCatalogListRequest_unstable | ProviderSetupCatalogListRequest_unstable | ProviderCatalogTemplateRequest_unstable | CustomProviderCreateRequest_unstable | CustomProviderReadRequest_unstable | CustomProviderUpdateRequest_unstable | CustomProviderDeleteRequest_unstable | RefreshProviderInventoryRequest_unstable | ProviderConfigReadRequest_unstable | ProviderConfigStatusRequest_unstable | ProviderConfigSaveRequest_unstable | ProviderConfigDeleteRequest_unstable | ProviderConfigAuthenticateRequest_unstable | ProviderSecretsListRequest_unstable | ProviderSecretDeleteRequest_unstable | CanonicalModelInfoRequest_unstable | PreferencesReadRequest_unstable | PreferencesSaveRequest_unstable | PreferencesRemoveRequest_unstable | ConfigReadRequest_unstable | ConfigUpsertRequest_unstable | ConfigRemoveRequest_unstable | ConfigReadAllRequest_unstable | DefaultsReadRequest_unstable | DefaultsSaveRequest_unstable | DefaultsClearRequest_unstable | OnboardingImportScanRequest_unstable | OnboardingImportApplyRequest_unstable | ExportSessionRequest_unstable | ImportSessionRequest_unstable | ShareSessionNostrRequest_unstable | EncodeRecipeRequest_unstable | DecodeRecipeRequest_unstable | ScanRecipeRequest_unstable | ListRecipesRequest_unstable | DeleteRecipeRequest_unstable | ScheduleRecipeRequest_unstable | SetRecipeSlashCommandRequest_unstable | SaveRecipeRequest_unstable | ParseRecipeRequest_unstable | RecipeToYamlRequest_unstable | ListSchedulesRequest_unstable | ListScheduleSessionsRequest_unstable | CreateScheduleRequest_unstable | DeleteScheduleRequest_unstable | PauseScheduleRequest_unstable | UnpauseScheduleRequest_unstable | UpdateScheduleRequest_unstable | RunScheduleNowRequest_unstable | KillRunningJobRequest_unstable | InspectRunningJobRequest_unstable | GetSessionInfoRequest_unstable | TruncateSessionConversationRequest_unstable | UpdateSessionProjectRequest_unstable | RenameSessionRequest_unstable | ArchiveSessionRequest_unstable | UnarchiveSessionRequest_unstable | CreateSourceRequest_unstable | ListSourcesRequest_unstable | ListAgentMentionsRequest_unstable | ListSlashCommandsRequest_unstable | UpdateSourceRequest_unstable | DeleteSourceRequest_unstable | ExportSourceRequest_unstable | ImportSourcesRequest_unstable | DictationTranscribeRequest_unstable | DictationConfigRequest_unstable | DictationSecretSaveRequest_unstable | DictationSecretDeleteRequest_unstable | DictationModelsListRequest_unstable | DictationModelDownloadRequest_unstable | DictationModelDownloadProgressRequest_unstable | DictationModelCancelRequest_unstable | DictationModelDeleteRequest_unstable | DictationModelSelectRequest_unstable | LocalInferenceModelsListRequest_unstable | LocalInferenceModelDownloadRequest_unstable | LocalInferenceModelRequantizeRequest_unstable | LocalInferenceModelValidateSpecRequest_unstable | LocalInferenceModelDownloadProgressRequest_unstable | LocalInferenceModelDownloadCancelRequest_unstable | LocalInferenceModelDeleteRequest_unstable | LocalInferenceModelEvictRequest_unstable | LocalInferenceModelsLoadedRequest_unstable | LocalInferenceModelSettingsReadRequest_unstable | LocalInferenceModelSettingsRecommendedRequest_unstable | LocalInferenceModelSettingsUpdateRequest_unstable | LocalInferenceModelSettingsPatchRequest_unstable | LocalInferenceModelDebugGenerateRequest_unstable | LocalInferenceModelCountTokensRequest_unstable | LocalInferenceModelStreamTestRequest_unstable | LocalInferenceHuggingFaceSearchRequest_unstable | LocalInferenceHuggingFaceRepoVariantsRequest_unstable | LocalInferenceBuiltinChatTemplatesListRequest_unstable | {
         [key: string]: unknown;
     } | null;
 };
 
 export type ExtResponse = {
     id: string;
-    result?: EmptyResponse | GetToolsResponse_unstable | SetToolPermissionsResponse_unstable | GooseToolCallResponse_unstable | ReadResourceResponse_unstable | AppsListResponse_unstable | AppsExportResponse_unstable | AppsImportResponse_unstable | AppsDeleteResponse_unstable | SteerSessionResponse_unstable | DiagnosticsGetResponse_unstable | ListPromptsResponse_unstable | GetPromptResponse_unstable | PromptOperationResponse_unstable | GetConfigExtensionsResponse_unstable | GetAvailableExtensionsResponse_unstable | GetSessionExtensionsResponse_unstable | ListProvidersResponse_unstable | ProviderSupportedModelsListResponse_unstable | ProviderCatalogListResponse_unstable | ProviderSetupCatalogListResponse_unstable | ProviderCatalogTemplateResponse_unstable | CustomProviderCreateResponse_unstable | CustomProviderReadResponse_unstable | CustomProviderUpdateResponse_unstable | CustomProviderDeleteResponse_unstable | RefreshProviderInventoryResponse_unstable | ProviderConfigReadResponse_unstable | ProviderConfigStatusResponse_unstable | ProviderConfigChangeResponse_unstable | ProviderSecretsListResponse_unstable | CanonicalModelInfoResponse_unstable | PreferencesReadResponse_unstable | ConfigReadResponse_unstable | ConfigReadAllResponse_unstable | DefaultsReadResponse_unstable | OnboardingImportScanResponse_unstable | OnboardingImportApplyResponse_unstable | ExportSessionResponse_unstable | ImportSessionResponse_unstable | ShareSessionNostrResponse_unstable | EncodeRecipeResponse_unstable | DecodeRecipeResponse_unstable | ScanRecipeResponse_unstable | ListRecipesResponse_unstable | SaveRecipeResponse_unstable | ParseRecipeResponse_unstable | RecipeToYamlResponse_unstable | ListSchedulesResponse_unstable | ListScheduleSessionsResponse_unstable | CreateScheduleResponse_unstable | UpdateScheduleResponse_unstable | RunScheduleNowResponse_unstable | KillRunningJobResponse_unstable | InspectRunningJobResponse_unstable | GetSessionInfoResponse_unstable | CreateSourceResponse_unstable | ListSourcesResponse_unstable | ListAgentMentionsResponse_unstable | ListSlashCommandsResponse_unstable | UpdateSourceResponse_unstable | ExportSourceResponse_unstable | ImportSourcesResponse_unstable | DictationTranscribeResponse_unstable | DictationConfigResponse_unstable | DictationModelsListResponse_unstable | DictationModelDownloadProgressResponse_unstable | LocalInferenceModelsListResponse_unstable | LocalInferenceModelDownloadResponse_unstable | LocalInferenceModelDownloadProgressResponse_unstable | LocalInferenceModelSettingsReadResponse_unstable | LocalInferenceModelSettingsUpdateResponse_unstable | LocalInferenceHuggingFaceSearchResponse_unstable | LocalInferenceHuggingFaceRepoVariantsResponse_unstable | LocalInferenceBuiltinChatTemplatesListResponse_unstable | unknown;
+    result?: EmptyResponse | GetToolsResponse_unstable | SetToolPermissionsResponse_unstable | GooseToolCallResponse_unstable | ReadResourceResponse_unstable | AppsListResponse_unstable | AppsExportResponse_unstable | AppsImportResponse_unstable | AppsDeleteResponse_unstable | SteerSessionResponse_unstable | DiagnosticsGetResponse_unstable | ListPromptsResponse_unstable | GetPromptResponse_unstable | PromptOperationResponse_unstable | GetConfigExtensionsResponse_unstable | GetAvailableExtensionsResponse_unstable | GetSessionExtensionsResponse_unstable | ListProvidersResponse_unstable | ProviderSupportedModelsListResponse_unstable | ProviderCatalogListResponse_unstable | ProviderSetupCatalogListResponse_unstable | ProviderCatalogTemplateResponse_unstable | CustomProviderCreateResponse_unstable | CustomProviderReadResponse_unstable | CustomProviderUpdateResponse_unstable | CustomProviderDeleteResponse_unstable | RefreshProviderInventoryResponse_unstable | ProviderConfigReadResponse_unstable | ProviderConfigStatusResponse_unstable | ProviderConfigChangeResponse_unstable | ProviderSecretsListResponse_unstable | CanonicalModelInfoResponse_unstable | PreferencesReadResponse_unstable | ConfigReadResponse_unstable | ConfigReadAllResponse_unstable | DefaultsReadResponse_unstable | OnboardingImportScanResponse_unstable | OnboardingImportApplyResponse_unstable | ExportSessionResponse_unstable | ImportSessionResponse_unstable | ShareSessionNostrResponse_unstable | EncodeRecipeResponse_unstable | DecodeRecipeResponse_unstable | ScanRecipeResponse_unstable | ListRecipesResponse_unstable | SaveRecipeResponse_unstable | ParseRecipeResponse_unstable | RecipeToYamlResponse_unstable | ListSchedulesResponse_unstable | ListScheduleSessionsResponse_unstable | CreateScheduleResponse_unstable | UpdateScheduleResponse_unstable | RunScheduleNowResponse_unstable | KillRunningJobResponse_unstable | InspectRunningJobResponse_unstable | GetSessionInfoResponse_unstable | CreateSourceResponse_unstable | ListSourcesResponse_unstable | ListAgentMentionsResponse_unstable | ListSlashCommandsResponse_unstable | UpdateSourceResponse_unstable | ExportSourceResponse_unstable | ImportSourcesResponse_unstable | DictationTranscribeResponse_unstable | DictationConfigResponse_unstable | DictationModelsListResponse_unstable | DictationModelDownloadProgressResponse_unstable | LocalInferenceModelsListResponse_unstable | LocalInferenceModelDownloadResponse_unstable | LocalInferenceModelRequantizeResponse_unstable | LocalInferenceModelValidateSpecResponse_unstable | LocalInferenceModelDownloadProgressResponse_unstable | LocalInferenceModelsLoadedResponse_unstable | LocalInferenceModelSettingsReadResponse_unstable | LocalInferenceModelSettingsRecommendedResponse_unstable | LocalInferenceModelSettingsUpdateResponse_unstable | LocalInferenceModelSettingsPatchResponse_unstable | LocalInferenceModelDebugGenerateResponse_unstable | LocalInferenceModelCountTokensResponse_unstable | LocalInferenceModelStreamTestResponse_unstable | LocalInferenceHuggingFaceSearchResponse_unstable | LocalInferenceHuggingFaceRepoVariantsResponse_unstable | LocalInferenceBuiltinChatTemplatesListResponse_unstable | unknown;
 } | {
     error: {
         code: number;
@@ -2651,7 +2913,7 @@ export type ExtResponse = {
 
 export type ExtNotification = {
     method: string;
-    params?: GooseSessionNotification_unstable | {
+    params?: GooseSessionNotification_unstable | LocalInferenceStreamTestChunkNotification_unstable | {
         [key: string]: unknown;
     } | null;
 };
@@ -767,6 +767,18 @@ export const zProviderInventoryModelDto = z.object({
     recommended: z.boolean().optional().default(false)
 });
 
+/**
+ * Provider-level capability flags.
+ */
+export const zProviderCapabilitiesDto = z.object({
+    tools: z.boolean(),
+    streaming: z.boolean(),
+    embeddings: z.boolean(),
+    vision: z.boolean(),
+    reasoning: z.boolean(),
+    unlistedModels: z.boolean()
+});
+
 /**
  * Provider inventory entry.
  */
@@ -799,7 +811,15 @@ export const zProviderInventoryEntryDto = z.object({
     modelSelectionHint: z.union([
         z.string(),
         z.null()
-    ]).optional()
+    ]).optional(),
+    capabilities: zProviderCapabilitiesDto.optional().default({
+        tools: false,
+        streaming: false,
+        embeddings: false,
+        vision: false,
+        reasoning: false,
+        unlistedModels: false
+    })
 });
 
 /**
@@ -2420,6 +2440,7 @@ export const zLocalInferenceSamplingConfig = z.union([
             z.number().int().gte(0),
             z.null()
         ]).optional(),
+        minKeep: z.number().int().gte(0).optional().default(1),
         type: z.literal('Temperature')
     }),
     z.object({
@@ -2466,6 +2487,8 @@ export const zLocalInferenceModelSettingsDto = z.object({
         z.number().int().gte(0),
         z.null()
     ]).optional(),
+    stopSequences: z.array(z.string()).optional(),
+    extraEogTokenIds: z.array(z.number().int().gte(0)).optional(),
     draftModel: z.union([
         z.string(),
         z.null()
@@ -2501,9 +2524,28 @@ export const zLocalInferenceModelSettingsDto = z.object({
     toolCalling: zLocalInferenceToolCallingMode.optional().default('auto'),
     chatTemplate: zLocalInferenceChatTemplate.optional().default({ type: 'embedded' }),
     enableThinking: z.boolean(),
+    emulatorJsonMode: z.boolean().optional().default(false),
+    emulatorMaxCodeChars: z.union([
+        z.number().int().gte(0),
+        z.null()
+    ]).optional(),
+    rawCompletion: z.boolean().optional().default(false),
+    contextShift: z.boolean().optional().default(false),
+    ignoreMemoryContextCap: z.boolean().optional().default(false),
+    pinned: z.boolean().optional().default(false),
+    returnLogprobs: z.boolean().optional().default(false),
+    topLogprobs: z.number().int().gte(0).optional().default(0),
+    usageUpdateInterval: z.union([
+        z.number().int().gte(0),
+        z.null()
+    ]).optional(),
     visionCapable: z.boolean(),
     imageTokenEstimate: z.number().int().gte(0),
-    mmprojSizeBytes: z.number().int().gte(0)
+    mmprojSizeBytes: z.number().int().gte(0),
+    mmprojPath: z.union([
+        z.string(),
+        z.null()
+    ]).optional()
 });
 
 export const zLocalInferenceModelDto = z.object({
@@ -2543,6 +2585,56 @@ export const zLocalInferenceModelDownloadResponse_unstable = z.object({
     modelId: z.string()
 });
 
+/**
+ * Swap a downloaded llama.cpp model to another quantization from the same repo. The
+ * new variant downloads under the returned `model_id`; once it completes, the registry
+ * entry takes the new id, file and size, keeps its settings, and the old file is deleted.
+ */
+export const zLocalInferenceModelRequantizeRequest_unstable = z.object({
+    modelId: z.string(),
+    quantization: z.string()
+});
+
+export const zLocalInferenceModelRequantizeResponse_unstable = z.object({
+    modelId: z.string()
+});
+
+/**
+ * Check a `user/repo:quantization` spec before downloading it. Never fails for a bad
+ * spec; the outcome is reported in `status`.
+ */
+export const zLocalInferenceModelValidateSpecRequest_unstable = z.object({
+    spec: z.string()
+});
+
+export const zLocalInferenceSpecValidationStatus = z.enum([
+    'Valid',
+    'InvalidFormat',
+    'RepoNotFound',
+    'QuantizationNotFound'
+]);
+
+export const zLocalInferenceModelValidateSpecResponse_unstable = z.object({
+    status: zLocalInferenceSpecValidationStatus,
+    repoId: z.union([
+        z.string(),
+        z.null()
+    ]).optional(),
+    quantization: z.union([
+        z.string(),
+        z.null()
+    ]).optional(),
+    sizeBytes: z.union([
+        z.number().int().gte(0),
+        z.null()
+    ]).optional(),
+    availableQuantizations: z.array(z.string()).optional().default([]),
+    message: z.union([
+        z.string(),
+        z.null()
+    ]).optional()
+});
+
 export const zLocalInferenceModelDownloadProgressRequest_unstable = z.object({
     modelId: z.string()
 });
@@ -2565,6 +2657,10 @@ export const zLocalInferenceDownloadProgressDto = z.object({
         z.string(),
         z.null()
     ]).optional(),
+    finalSizeBytes: z.union([
+        z.number().int().gte(0),
+        z.null()
+    ]).optional(),
     taskExited: z.boolean()
 });
 
@@ -2587,6 +2683,23 @@ export const zLocalInferenceModelEvictRequest_unstable = z.object({
     modelId: z.string()
 });
 
+/**
+ * Models currently held in memory, so clients can show which are resident and warn
+ * before an action that would evict one.
+ */
+export const zLocalInferenceModelsLoadedRequest_unstable = z.record(z.unknown());
+
+export const zLocalInferenceLoadedModelDto = z.object({
+    modelId: z.string(),
+    backendId: z.string(),
+    sizeBytes: z.number().int().gte(0),
+    inUse: z.boolean()
+});
+
+export const zLocalInferenceModelsLoadedResponse_unstable = z.object({
+    models: z.array(zLocalInferenceLoadedModelDto)
+});
+
 export const zLocalInferenceModelSettingsReadRequest_unstable = z.object({
     modelId: z.string()
 });
@@ -2595,6 +2708,18 @@ export const zLocalInferenceModelSettingsReadResponse_unstable = z.object({
     settings: zLocalInferenceModelSettingsDto
 });
 
+/**
+ * Settings sized for this machine's memory and the model's family. Nothing is saved;
+ * the client applies them with `settings/update` if the user accepts.
+ */
+export const zLocalInferenceModelSettingsRecommendedRequest_unstable = z.object({
+    modelId: z.string()
+});
+
+export const zLocalInferenceModelSettingsRecommendedResponse_unstable = z.object({
+    settings: zLocalInferenceModelSettingsDto
+});
+
 export const zLocalInferenceModelSettingsUpdateRequest_unstable = z.object({
     modelId: z.string(),
     settings: zLocalInferenceModelSettingsDto
@@ -2604,11 +2729,143 @@ export const zLocalInferenceModelSettingsUpdateResponse_unstable = z.object({
     settings: zLocalInferenceModelSettingsDto
 });
 
+/**
+ * Merge a partial settings object into a model's current settings. Top-level fields in
+ * `patch` replace the stored values; fields it omits are left unchanged.
+ */
+export const zLocalInferenceModelSettingsPatchRequest_unstable = z.object({
+    modelId: z.string(),
+    patch: z.record(z.unknown())
+});
+
+export const zLocalInferenceModelSettingsPatchResponse_unstable = z.object({
+    settings: zLocalInferenceModelSettingsDto
+});
+
+export const zLocalInferenceDebugMessageRole = z.enum([
+    'user',
+    'assistant'
+]);
+
+export const zLocalInferenceDebugMessageDto = z.object({
+    role: zLocalInferenceDebugMessageRole,
+    content: z.string()
+});
+
+export const zLocalInferenceDebugToolDto = z.object({
+    name: z.string(),
+    description: z.string().optional().default(''),
+    inputSchema: z.unknown()
+});
+
+export const zLocalInferenceDebugToolCallDto = z.object({
+    name: z.string(),
+    arguments: z.unknown()
+});
+
+/**
+ * Run one generation against a local model and return the raw output alongside what was
+ * parsed from it, for debugging chat templates and tool-call parsing.
+ */
+export const zLocalInferenceModelDebugGenerateRequest_unstable = z.object({
+    modelId: z.string(),
+    system: z.string().optional().default(''),
+    messages: z.array(zLocalInferenceDebugMessageDto),
+    tools: z.array(zLocalInferenceDebugToolDto).optional().default([]),
+    maxTokens: z.union([
+        z.number().int(),
+        z.null()
+    ]).optional()
+});
+
+export const zLocalInferenceModelDebugGenerateResponse_unstable = z.object({
+    rawText: z.string(),
+    appliedPrompt: z.union([
+        z.string(),
+        z.null()
+    ]).optional(),
+    text: z.string(),
+    thinking: z.string(),
+    toolCalls: z.array(zLocalInferenceDebugToolCallDto),
+    toolCallErrors: z.array(z.string())
+});
+
+/**
+ * Count the prompt tokens a conversation would use with a loaded local model's chat
+ * template, without generating.
+ */
+export const zLocalInferenceModelCountTokensRequest_unstable = z.object({
+    modelId: z.string(),
+    system: z.string().optional().default(''),
+    messages: z.array(zLocalInferenceDebugMessageDto),
+    tools: z.array(zLocalInferenceDebugToolDto).optional().default([])
+});
+
+export const zLocalInferenceModelCountTokensResponse_unstable = z.object({
+    tokenCount: z.number().int().gte(0),
+    contextLimit: z.number().int().gte(0)
+});
+
+/**
+ * Run a conversation through a local model's streaming path. Each chunk is sent as a
+ * `stream-test/chunk` notification as soon as the model produces it, ending with the usage
+ * chunk; the response arrives once the stream has finished.
+ */
+export const zLocalInferenceModelStreamTestRequest_unstable = z.object({
+    streamId: z.string(),
+    modelId: z.string(),
+    system: z.string().optional().default(''),
+    messages: z.array(zLocalInferenceDebugMessageDto),
+    tools: z.array(zLocalInferenceDebugToolDto).optional().default([]),
+    maxTokens: z.union([
+        z.number().int(),
+        z.null()
+    ]).optional()
+});
+
+export const zLocalInferenceStreamUsageDto = z.object({
+    inputTokens: z.union([
+        z.number().int(),
+        z.null()
+    ]).optional(),
+    outputTokens: z.union([
+        z.number().int(),
+        z.null()
+    ]).optional(),
+    totalTokens: z.union([
+        z.number().int(),
+        z.null()
+    ]).optional()
+});
+
+/**
+ * One streamed chunk. Message chunks carry content; the terminal chunk carries usage.
+ */
+export const zLocalInferenceStreamEventDto = z.object({
+    text: z.string(),
+    thinking: z.string(),
+    toolCalls: z.array(zLocalInferenceDebugToolCallDto),
+    toolCallErrors: z.array(z.string()),
+    usage: z.union([
+        zLocalInferenceStreamUsageDto,
+        z.null()
+    ]).optional(),
+    elapsedMs: z.number().int().gte(0)
+});
+
+export const zLocalInferenceModelStreamTestResponse_unstable = z.object({
+    chunkCount: z.number().int().gte(0)
+});
+
 export const zLocalInferenceHuggingFaceSearchRequest_unstable = z.object({
     query: z.string(),
     limit: z.union([
         z.number().int().gte(0),
         z.null()
+    ]).optional(),
+    offset: z.union([
+        z.number().int().gte(0),
+        z.null()
     ]).optional()
 });
 
@@ -2642,6 +2899,14 @@ export const zLocalInferenceHfModelVariantDto = z.object({
     unsupportedReason: z.union([
         z.string(),
         z.null()
+    ]).optional(),
+    mmprojFilename: z.union([
+        z.string(),
+        z.null()
+    ]).optional(),
+    totalSizeBytes: z.union([
+        z.number().int().gte(0),
+        z.null()
     ]).optional()
 });
 
@@ -2805,6 +3070,14 @@ export const zGooseSessionNotification_unstable = z.object({
     update: zGooseSessionUpdate
 });
 
+/**
+ * One chunk of a local inference `stream-test` run, sent as soon as the model produces it.
+ */
+export const zLocalInferenceStreamTestChunkNotification_unstable = z.object({
+    streamId: z.string(),
+    event: zLocalInferenceStreamEventDto
+});
+
 export const zRequestRecipeParams_unstable = z.object({
     sessionId: z.string(),
     parameters: z.array(zRecipeParameterDto)
@@ -2926,12 +3199,20 @@ export const zExtRequest = z.object({
             zDictationModelSelectRequest_unstable,
             zLocalInferenceModelsListRequest_unstable,
             zLocalInferenceModelDownloadRequest_unstable,
+            zLocalInferenceModelRequantizeRequest_unstable,
+            zLocalInferenceModelValidateSpecRequest_unstable,
             zLocalInferenceModelDownloadProgressRequest_unstable,
             zLocalInferenceModelDownloadCancelRequest_unstable,
             zLocalInferenceModelDeleteRequest_unstable,
             zLocalInferenceModelEvictRequest_unstable,
+            zLocalInferenceModelsLoadedRequest_unstable,
             zLocalInferenceModelSettingsReadRequest_unstable,
+            zLocalInferenceModelSettingsRecommendedRequest_unstable,
             zLocalInferenceModelSettingsUpdateRequest_unstable,
+            zLocalInferenceModelSettingsPatchRequest_unstable,
+            zLocalInferenceModelDebugGenerateRequest_unstable,
+            zLocalInferenceModelCountTokensRequest_unstable,
+            zLocalInferenceModelStreamTestRequest_unstable,
             zLocalInferenceHuggingFaceSearchRequest_unstable,
             zLocalInferenceHuggingFaceRepoVariantsRequest_unstable,
             zLocalInferenceBuiltinChatTemplatesListRequest_unstable
@@ -3017,9 +3298,17 @@ export const zExtResponse = z.union([
                 zDictationModelDownloadProgressResponse_unstable,
                 zLocalInferenceModelsListResponse_unstable,
                 zLocalInferenceModelDownloadResponse_unstable,
+                zLocalInferenceModelRequantizeResponse_unstable,
+                zLocalInferenceModelValidateSpecResponse_unstable,
                 zLocalInferenceModelDownloadProgressResponse_unstable,
+                zLocalInferenceModelsLoadedResponse_unstable,
                 zLocalInferenceModelSettingsReadResponse_unstable,
+                zLocalInferenceModelSettingsRecommendedResponse_unstable,
                 zLocalInferenceModelSettingsUpdateResponse_unstable,
+                zLocalInferenceModelSettingsPatchResponse_unstable,
+                zLocalInferenceModelDebugGenerateResponse_unstable,
+                zLocalInferenceModelCountTokensResponse_unstable,
+                zLocalInferenceModelStreamTestResponse_unstable,
                 zLocalInferenceHuggingFaceSearchResponse_unstable,
                 zLocalInferenceHuggingFaceRepoVariantsResponse_unstable,
                 zLocalInferenceBuiltinChatTemplatesListResponse_unstable
@@ -3041,6 +3330,7 @@ export const zExtNotification = z.object({
     method: z.string(),
     params: z.union([
         zGooseSessionNotification_unstable,
+        zLocalInferenceStreamTestChunkNotification_unstable,
         z.union([
             z.record(z.unknown()),
             z.null()